@@ -0,0 +1,265 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+use iox_time::{SystemProvider, Time, TimeProvider};
+use parking_lot::Mutex;
+
+use super::{Authorizer, Error, Permission};
+
+/// A successful permissions check, valid until `expires_at`.
+#[derive(Debug)]
+struct CacheEntry {
+    requested: Vec<Permission>,
+    granted: Vec<Permission>,
+    expires_at: Time,
+}
+
+/// The cached permission checks of each token.
+#[derive(Debug)]
+struct Cache {
+    entries: HashMap<Vec<u8>, Vec<CacheEntry>>,
+    /// When the expired entries of all tokens are next removed.
+    next_sweep: Time,
+}
+
+/// A caching decorator over an [`Authorizer`] implementation.
+///
+/// Successful [`Authorizer::permissions()`] results are remembered for `ttl`,
+/// keyed by the token and the requested permissions, so that repeated
+/// requests with the same token do not each call the inner authorizer.
+/// Errors are never cached, so a rejected token is re-checked on every
+/// request.
+///
+/// Expired entries are swept from the cache at most once per `ttl`, when a
+/// new result is cached, so it only holds the tokens seen in the last two
+/// `ttl` periods or so.
+#[derive(Debug)]
+pub struct CachingAuthorizer<T, P = SystemProvider> {
+    inner: T,
+    ttl: Duration,
+    time_provider: P,
+    cache: Mutex<Cache>,
+}
+
+impl<T> CachingAuthorizer<T> {
+    /// Cache successful permission checks made through `inner` for `ttl`.
+    pub fn new(inner: T, ttl: Duration) -> Self {
+        Self::new_with_time_provider(inner, ttl, Default::default())
+    }
+}
+
+impl<T, P> CachingAuthorizer<T, P>
+where
+    P: TimeProvider,
+{
+    fn new_with_time_provider(inner: T, ttl: Duration, time_provider: P) -> Self {
+        let cache = Mutex::new(Cache {
+            entries: Default::default(),
+            next_sweep: time_provider.now() + ttl,
+        });
+        Self {
+            inner,
+            ttl,
+            time_provider,
+            cache,
+        }
+    }
+
+    /// The number of tokens with cached entries.
+    #[cfg(test)]
+    fn cached_tokens(&self) -> usize {
+        self.cache.lock().entries.len()
+    }
+}
+
+#[async_trait]
+impl<T, P> Authorizer for CachingAuthorizer<T, P>
+where
+    T: Authorizer,
+    P: TimeProvider,
+{
+    async fn permissions(
+        &self,
+        token: Option<Vec<u8>>,
+        perms: &[Permission],
+    ) -> Result<Vec<Permission>, Error> {
+        let token = match token {
+            Some(token) => token,
+            None => return self.inner.permissions(None, perms).await,
+        };
+
+        let now = self.time_provider.now();
+        let cached = self.cache.lock().entries.get(&token).and_then(|entries| {
+            entries
+                .iter()
+                .find(|e| e.expires_at > now && e.requested == perms)
+                .map(|e| e.granted.clone())
+        });
+        if let Some(granted) = cached {
+            return Ok(granted);
+        }
+
+        let granted = self.inner.permissions(Some(token.clone()), perms).await?;
+
+        let expires_at = now + self.ttl;
+        let mut cache = self.cache.lock();
+        if cache.next_sweep <= now {
+            cache.entries.retain(|_, entries| {
+                entries.retain(|e| e.expires_at > now);
+                !entries.is_empty()
+            });
+            cache.next_sweep = expires_at;
+        }
+        let entries = cache.entries.entry(token).or_default();
+        entries.retain(|e| e.expires_at > now && e.requested != perms);
+        entries.push(CacheEntry {
+            requested: perms.to_vec(),
+            granted: granted.clone(),
+            expires_at,
+        });
+
+        Ok(granted)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use assert_matches::assert_matches;
+    use iox_time::MockProvider;
+
+    use super::*;
+    use crate::{Action, Resource};
+
+    /// Grants every permission to tokens starting with "good", counting the
+    /// calls made.
+    #[derive(Debug, Default)]
+    struct MockAuthorizer {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Authorizer for MockAuthorizer {
+        async fn permissions(
+            &self,
+            token: Option<Vec<u8>>,
+            perms: &[Permission],
+        ) -> Result<Vec<Permission>, Error> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            match token.as_deref() {
+                Some(token) if token.starts_with(b"good") => Ok(perms.to_vec()),
+                Some(_) => Err(Error::Forbidden),
+                None => Err(Error::NoToken),
+            }
+        }
+    }
+
+    fn write_perm() -> Vec<Permission> {
+        vec![Permission::ResourceAction(
+            Resource::Database("bananas".to_string()),
+            Action::Write,
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_and_expiry() {
+        let inner = Arc::new(MockAuthorizer::default());
+        let time_provider = MockProvider::new(Time::from_timestamp_nanos(0));
+        let authz = CachingAuthorizer::new_with_time_provider(
+            Arc::clone(&inner) as Arc<dyn Authorizer>,
+            Duration::from_secs(10),
+            time_provider.clone(),
+        );
+        let perms = write_perm();
+
+        let got = authz.permissions(Some(b"good".to_vec()), &perms).await;
+        assert_matches!(got, Ok(p) if p == perms);
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+
+        // Served from the cache.
+        time_provider.inc(Duration::from_secs(5));
+        let got = authz.permissions(Some(b"good".to_vec()), &perms).await;
+        assert_matches!(got, Ok(p) if p == perms);
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 1);
+
+        // Different permissions are checked separately.
+        let got = authz.permissions(Some(b"good".to_vec()), &[]).await;
+        assert_matches!(got, Ok(p) if p.is_empty());
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 2);
+
+        // Expired entries are checked again.
+        time_provider.inc(Duration::from_secs(5));
+        let got = authz.permissions(Some(b"good".to_vec()), &perms).await;
+        assert_matches!(got, Ok(p) if p == perms);
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_expired_tokens_swept() {
+        let inner = Arc::new(MockAuthorizer::default());
+        let time_provider = MockProvider::new(Time::from_timestamp_nanos(0));
+        let authz = CachingAuthorizer::new_with_time_provider(
+            Arc::clone(&inner) as Arc<dyn Authorizer>,
+            Duration::from_secs(10),
+            time_provider.clone(),
+        );
+        let perms = write_perm();
+
+        authz
+            .permissions(Some(b"good1".to_vec()), &perms)
+            .await
+            .unwrap();
+        time_provider.inc(Duration::from_secs(5));
+        authz
+            .permissions(Some(b"good2".to_vec()), &perms)
+            .await
+            .unwrap();
+        assert_eq!(authz.cached_tokens(), 2);
+
+        // The first sweep is due, removing the expired "good1" only.
+        time_provider.inc(Duration::from_secs(5));
+        authz
+            .permissions(Some(b"good3".to_vec()), &perms)
+            .await
+            .unwrap();
+        assert_eq!(authz.cached_tokens(), 2);
+
+        // No sweep is due yet, so the expired "good2" is kept.
+        time_provider.inc(Duration::from_secs(5));
+        authz
+            .permissions(Some(b"good4".to_vec()), &perms)
+            .await
+            .unwrap();
+        assert_eq!(authz.cached_tokens(), 3);
+
+        // The next sweep removes "good2" and "good3".
+        time_provider.inc(Duration::from_secs(5));
+        authz
+            .permissions(Some(b"good5".to_vec()), &perms)
+            .await
+            .unwrap();
+        assert_eq!(authz.cached_tokens(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_errors_not_cached() {
+        let inner = Arc::new(MockAuthorizer::default());
+        let authz = CachingAuthorizer::new(
+            Arc::clone(&inner) as Arc<dyn Authorizer>,
+            Duration::from_secs(10),
+        );
+        let perms = write_perm();
+
+        for _ in 0..2 {
+            let got = authz.permissions(Some(b"bad".to_vec()), &perms).await;
+            assert_matches!(got, Err(Error::Forbidden));
+            let got = authz.permissions(None, &perms).await;
+            assert_matches!(got, Err(Error::NoToken));
+        }
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 4);
+    }
+}
@@ -27,6 +27,8 @@ use observability_deps::tracing::warn;
 
 mod authorizer;
 pub use authorizer::Authorizer;
+mod cache;
+pub use cache::CachingAuthorizer;
 mod iox_authorizer;
 pub use iox_authorizer::{Error, IoxAuthorizer};
 mod instrumentation;
@@ -65,10 +65,16 @@ pub struct CatalogDsnConfig {
     pub dsn: Option<String>,
 
     /// Maximum number of connections allowed to the catalog at any one time.
+    ///
+    /// This is the size of the catalog connection pool, and must be at least 1.
+    /// Services handling many concurrent requests (such as the router under a
+    /// high write load) may need to raise this to avoid contending for catalog
+    /// connections.
     #[clap(
         long = "catalog-max-connections",
         env = "INFLUXDB_IOX_CATALOG_MAX_CONNECTIONS",
         default_value = default_max_connections(),
+        value_parser = clap::value_parser!(u32).range(1..),
         action,
     )]
     pub max_catalog_connections: u32,
@@ -83,6 +89,9 @@ pub struct CatalogDsnConfig {
     pub postgres_schema_name: String,
 
     /// Set the amount of time to attempt connecting to the database.
+    ///
+    /// This also bounds how long a request waits to acquire a connection from
+    /// the catalog connection pool when all connections are in use.
     #[clap(
         long = "catalog-connect-timeout",
         env = "INFLUXDB_IOX_CATALOG_CONNECT_TIMEOUT",
@@ -126,7 +135,12 @@ impl CatalogDsnConfig {
 
         if dsn.starts_with("postgres") || dsn.starts_with("dsn-file://") {
             // do not log entire postgres dsn as it may contain credentials
-            info!(postgres_schema_name=%self.postgres_schema_name, "Catalog: Postgres");
+            info!(
+                postgres_schema_name=%self.postgres_schema_name,
+                max_connections=self.max_catalog_connections,
+                connect_timeout=?self.connect_timeout,
+                "Catalog: Postgres"
+            );
             let options = PostgresConnectionOptions {
                 app_name: app_name.to_string(),
                 schema_name: self.postgres_schema_name.clone(),
@@ -162,3 +176,38 @@ impl CatalogDsnConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use test_helpers::assert_contains;
+
+    #[test]
+    fn test_default_max_connections() {
+        let actual = CatalogDsnConfig::try_parse_from(["my_binary"]).unwrap();
+
+        assert_eq!(
+            actual.max_catalog_connections,
+            PostgresConnectionOptions::DEFAULT_MAX_CONNS
+        );
+    }
+
+    #[test]
+    fn test_max_connections() {
+        let actual =
+            CatalogDsnConfig::try_parse_from(["my_binary", "--catalog-max-connections", "42"])
+                .unwrap();
+
+        assert_eq!(actual.max_catalog_connections, 42);
+    }
+
+    #[test]
+    fn test_max_connections_zero_is_rejected() {
+        let err = CatalogDsnConfig::try_parse_from(["my_binary", "--catalog-max-connections", "0"])
+            .unwrap_err()
+            .to_string();
+
+        assert_contains!(err, "0 is not in");
+    }
+}
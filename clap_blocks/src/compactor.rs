@@ -1,11 +1,38 @@
 //! CLI config for compactor-related commands
 
-use std::num::NonZeroUsize;
+use std::{num::NonZeroUsize, path::PathBuf};
 
 use crate::{gossip::GossipConfig, memory_size::MemorySize};
 
 use super::compactor_scheduler::CompactorSchedulerConfig;
 
+/// What the compactor does with a partition that consists of a single
+/// oversize L0 file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SingleOversizeFilePolicy {
+    /// Leave the file to the regular compaction.
+    #[default]
+    Leave,
+
+    /// Split the file into L1 files of the desired size.
+    Split,
+
+    /// Rewrite the file as a single L1 file.
+    Rewrite,
+}
+
+/// What the compactor does when compacting files produces no output
+/// rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ZeroRowOutputPolicy {
+    /// Keep the input files, log a warning and permanently skip the partition.
+    #[default]
+    SkipAndWarn,
+
+    /// Delete the input files without creating any output file.
+    DeleteInputs,
+}
+
 /// CLI config for compactor
 #[derive(Debug, Clone, clap::Parser)]
 pub struct CompactorConfig {
@@ -30,6 +57,23 @@ pub struct CompactorConfig {
     )]
     pub compaction_partition_concurrency: NonZeroUsize,
 
+    /// Period, in seconds, over which the partition concurrency is
+    /// raised from 1 to `--compaction-partition-concurrency` after
+    /// startup.
+    ///
+    /// Starting all partitions at once right after boot can cause a
+    /// latency spike against a cold object store and catalog. The
+    /// effective concurrency is reported by the
+    /// `iox_compactor_effective_partition_concurrency` metric.
+    ///
+    /// If not provided, the full concurrency is used right away.
+    #[clap(
+        long = "compaction-partition-concurrency-warm-up-secs",
+        env = "INFLUXDB_IOX_COMPACTION_PARTITION_CONCURRENCY_WARM_UP_SECS",
+        action
+    )]
+    pub compaction_partition_concurrency_warm_up_secs: Option<u64>,
+
     /// Number of concurrent compaction jobs scheduled to DataFusion.
     ///
     /// This should usually be smaller than the partition concurrency
@@ -52,6 +96,39 @@ pub struct CompactorConfig {
     )]
     pub compaction_partition_scratchpad_concurrency: NonZeroUsize,
 
+    /// Number of input parquet files each compaction job fetches and
+    /// decodes concurrently.
+    ///
+    /// Higher values speed up IO-bound compactions on object stores with
+    /// high per-request latency, at the cost of holding more input data in
+    /// memory at once. The effective value is capped by how many input
+    /// files fit into the per-job memory budget.
+    #[clap(
+        long = "compaction-input-read-concurrency",
+        env = "INFLUXDB_IOX_COMPACTION_INPUT_READ_CONCURRENCY",
+        default_value = "4",
+        action
+    )]
+    pub compaction_input_read_concurrency: NonZeroUsize,
+
+    /// Target number of DataFusion partitions each compaction job is
+    /// executed with, bounding how many query exec threads a single job
+    /// keeps busy.
+    ///
+    /// Use this to tune the CPU usage per job separately from the number of
+    /// concurrent jobs (`compaction-df-concurrency`). A job's effective
+    /// partition count is also capped by
+    /// `compaction-input-read-concurrency` and by how many of its input
+    /// files fit into the per-job memory budget, so raising this value does
+    /// not increase a job's memory use beyond that budget. If not specified,
+    /// only those limits apply.
+    #[clap(
+        long = "compaction-exec-target-partitions",
+        env = "INFLUXDB_IOX_COMPACTION_EXEC_TARGET_PARTITIONS",
+        action
+    )]
+    pub compaction_exec_target_partitions: Option<NonZeroUsize>,
+
     /// Number of threads to use for the compactor query execution,
     /// compaction and persistence.
     /// If not specified, defaults to one less than the number of cores on the system
@@ -167,6 +244,30 @@ pub struct CompactorConfig {
     )]
     pub split_percentage: u16,
 
+    /// Per-table overrides of the sizing configuration, separated by `;`.
+    ///
+    /// Each override is of the form
+    /// `<table>:<key>=<value>,...` where `<table>` is either a table
+    /// name, matching the table in every namespace, or
+    /// `<namespace>/<table>`. The keys are
+    /// `max_desired_file_size_bytes`, `percentage_max_file_size` and
+    /// `split_percentage`, with the same meaning and constraints as the
+    /// corresponding global settings.
+    ///
+    /// Each setting is resolved independently, from the
+    /// `<namespace>/<table>` override, then the `<table>` override, and
+    /// finally the global setting.
+    ///
+    /// Example: "cpu:max_desired_file_size_bytes=209715200;ns/mem:split_percentage=90"
+    #[clap(
+        long = "compaction-table-sizing-override",
+        env = "INFLUXDB_IOX_COMPACTION_TABLE_SIZING_OVERRIDES",
+        value_delimiter = ';',
+        value_parser = parse_table_sizing_override,
+        action
+    )]
+    pub table_sizing_overrides: Vec<TableSizingOverride>,
+
     /// Maximum duration of the per-partition compaction task in seconds.
     #[clap(
         long = "compaction-partition-timeout-secs",
@@ -176,6 +277,116 @@ pub struct CompactorConfig {
     )]
     pub partition_timeout_secs: u64,
 
+    /// Maximum duration of a single pass over the partitions needing compaction, in seconds.
+    ///
+    /// Once the budget is exceeded, no further partitions from the current pass are started:
+    /// partitions already being compacted run to completion (bounded by
+    /// `--compaction-partition-timeout-secs`), and a fresh pass begins with the partitions
+    /// of the previous pass that were not started, followed by any others needing compaction.
+    /// A pass can therefore last up to this budget plus the partition
+    /// timeout.
+    ///
+    /// Unlimited if not set. Ignored with `--compaction-process-once`.
+    #[clap(
+        long = "compaction-run-iteration-budget-secs",
+        env = "INFLUXDB_IOX_COMPACTION_RUN_ITERATION_BUDGET_SECS",
+        action
+    )]
+    pub run_iteration_budget_secs: Option<u64>,
+
+    /// Minimum age of L0 files to compact, in seconds.
+    ///
+    /// L0 files created more recently are left untouched, letting recent writes to a
+    /// partition accumulate before they are compacted. They are picked up by a later
+    /// compaction of the partition once old enough.
+    ///
+    /// All L0 files are compacted if not set.
+    #[clap(
+        long = "compaction-min-file-age-secs",
+        env = "INFLUXDB_IOX_COMPACTION_MIN_FILE_AGE_SECS",
+        action
+    )]
+    pub min_file_age_secs: Option<u64>,
+
+    /// Maximum time in seconds to wait for a file referenced by the catalog to become visible
+    /// in the object store.
+    ///
+    /// On object stores without read-after-write consistency, a file that was just written may
+    /// not be readable yet. Reads of such a file are retried with backoff for up to this long,
+    /// after which the file is considered truly missing.
+    ///
+    /// Reads of missing files are not retried if not set. Not available in
+    /// all-in-one mode, which never retries reads of missing files.
+    #[clap(
+        long = "compaction-read-after-write-timeout-secs",
+        env = "INFLUXDB_IOX_COMPACTION_READ_AFTER_WRITE_TIMEOUT_SECS",
+        action
+    )]
+    pub read_after_write_timeout_secs: Option<u64>,
+
+    /// Rewrite files written with a Parquet writer version below this
+    /// value, even if they would not be compacted otherwise.
+    ///
+    /// Each outdated file is rewritten on its own, keeping its compaction
+    /// level, using the current Parquet writer settings, once the compaction
+    /// of its partition is done. Partitions the compactor refuses to compact,
+    /// e.g. for having too many columns, are not rewritten either. This allows files
+    /// to be migrated gradually when the encoding used by IOx changes. Must
+    /// not be greater than the writer version of this compactor.
+    ///
+    /// No files are rewritten for their format version if not set.
+    #[clap(
+        long = "compaction-rewrite-below-format-version",
+        env = "INFLUXDB_IOX_COMPACTION_REWRITE_BELOW_FORMAT_VERSION",
+        action
+    )]
+    pub rewrite_below_format_version: Option<i32>,
+
+    /// What to do with a partition that consists of a single L0 file
+    /// larger than `compaction-max-desired-file-size-bytes`.
+    ///
+    /// Such a file has nothing to be merged with. It can be split into
+    /// L1 files of the desired size, rewritten as a single L1 file, or
+    /// left to the regular compaction, which avoids rewriting it.
+    #[clap(
+        value_enum,
+        long = "compaction-single-oversize-file-policy",
+        env = "INFLUXDB_IOX_COMPACTION_SINGLE_OVERSIZE_FILE_POLICY",
+        default_value = "leave",
+        action
+    )]
+    pub single_oversize_file_policy: SingleOversizeFilePolicy,
+
+    /// Read back each compacted file before committing it to the catalog,
+    /// checking its row count and time range match the catalog record.
+    ///
+    /// A mismatch fails the compaction of the partition, leaving its input
+    /// files in place. Off by default, as every output file is read again.
+    #[clap(
+        long = "compaction-verify-output",
+        env = "INFLUXDB_IOX_COMPACTION_VERIFY_OUTPUT",
+        default_value = "false",
+        action
+    )]
+    pub verify_output: bool,
+
+    /// What to do when compacting files produces no output rows.
+    ///
+    /// Compaction only removes duplicate rows, so this indicates a bug or
+    /// broken input files. Committing such a compaction deletes the input
+    /// files without replacing them. By default the input files are kept,
+    /// a warning is logged and the partition is permanently skipped: it is
+    /// recorded as a skipped compaction and not compacted again until the
+    /// skip is removed with `influxdb_iox debug skipped-compactions delete`.
+    #[clap(
+        value_enum,
+        long = "compaction-zero-row-output-policy",
+        env = "INFLUXDB_IOX_COMPACTION_ZERO_ROW_OUTPUT_POLICY",
+        default_value = "skip-and-warn",
+        action
+    )]
+    pub zero_row_output_policy: ZeroRowOutputPolicy,
+
     /// Shadow mode.
     ///
     /// This will NOT write / commit any output to the object store or catalog.
@@ -217,6 +428,23 @@ pub struct CompactorConfig {
     )]
     pub max_num_files_per_plan: usize,
 
+    /// Minimum number of L0 files a partition must have before it is
+    /// compacted.
+    ///
+    /// Partitions with fewer L0 files are skipped until more files arrive,
+    /// which avoids spending IO compacting only a couple of small files.
+    /// Note that with a value larger than 1, a partition that stops receiving
+    /// writes may keep a few L0 files that are never compacted.
+    ///
+    /// Must be at least 1.
+    #[clap(
+        long = "compaction-min-num-l0-files-to-compact",
+        env = "INFLUXDB_IOX_COMPACTION_MIN_NUM_L0_FILES_TO_COMPACT",
+        default_value = "1",
+        action
+    )]
+    pub min_num_l0_files_to_compact: NonZeroUsize,
+
     /// Minimum number of L1 files to compact to L2.
     ///
     /// If there are more than this many L1 (by definition non
@@ -246,6 +474,19 @@ pub struct CompactorConfig {
     )]
     pub process_once: bool,
 
+    /// Path of a file that pauses compaction while it exists.
+    ///
+    /// When the file is created, the compactor finishes the partitions
+    /// it is already working on and then stops fetching new work until
+    /// the file is removed. This allows compaction to be paused during
+    /// maintenance without restarting the compactor.
+    #[clap(
+        long = "compaction-pause-file",
+        env = "INFLUXDB_IOX_COMPACTION_PAUSE_FILE",
+        action
+    )]
+    pub pause_file: Option<PathBuf>,
+
     /// Maximum number of columns in a table of a partition that
     /// will be able to considered to get compacted
     ///
@@ -269,4 +510,195 @@ pub struct CompactorConfig {
         action
     )]
     pub max_partition_fetch_queries_per_second: Option<usize>,
+
+    /// Serve the compactor status (shard, in-flight partitions, last
+    /// iteration and skip list) as JSON at `/status` on the HTTP listener.
+    ///
+    /// The endpoint is read-only, and disabled by default.
+    #[clap(
+        long = "compaction-status-endpoint",
+        env = "INFLUXDB_IOX_COMPACTION_STATUS_ENDPOINT",
+        default_value = "false",
+        action
+    )]
+    pub status_endpoint: bool,
+
+    /// The URL of a webhook a JSON summary is POSTed to once the compaction
+    /// of a partition completes.
+    ///
+    /// The summary holds the partition ID, whether the compaction succeeded
+    /// (and its error otherwise) and the number of rows read and written.
+    /// Notifications are retried with backoff; a failure to notify is logged
+    /// and counted but does not fail the compaction.
+    ///
+    /// If not provided, no notification is sent.
+    #[clap(
+        long = "compaction-completion-webhook-url",
+        env = "INFLUXDB_IOX_COMPACTION_COMPLETION_WEBHOOK_URL",
+        action
+    )]
+    pub completion_webhook_url: Option<http::Uri>,
+}
+
+/// Overrides of the sizing configuration for a table, see
+/// [`CompactorConfig::table_sizing_overrides`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableSizingOverride {
+    /// The table, as `<table>` or `<namespace>/<table>`.
+    pub table: String,
+
+    /// Overrides `--compaction-max-desired-size-bytes`.
+    pub max_desired_file_size_bytes: Option<u64>,
+
+    /// Overrides `--compaction-percentage-max-file_size`.
+    pub percentage_max_file_size: Option<u16>,
+
+    /// Overrides `--compaction-split-percentage`.
+    pub split_percentage: Option<u16>,
+}
+
+fn parse_table_sizing_override(
+    s: &str,
+) -> Result<TableSizingOverride, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let (table, settings) = s.trim().split_once(':').ok_or_else(|| {
+        format!("Invalid table sizing override - expected 'TABLE:KEY=VALUE,...' got '{s}'")
+    })?;
+    let table = table.trim();
+    if table.is_empty() {
+        return Err(format!("table sizing override '{s}' has no table").into());
+    }
+
+    let mut out = TableSizingOverride {
+        table: table.to_owned(),
+        ..Default::default()
+    };
+    for part in settings.split(',') {
+        let (key, value) = part
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid key value pair - expected 'KEY=VALUE' got '{}'",
+                    part.trim()
+                )
+            })?;
+
+        let existed = match key {
+            "max_desired_file_size_bytes" => {
+                let bytes: u64 = value.parse()?;
+                if bytes == 0 {
+                    return Err(format!("'{key}' must be greater than 0").into());
+                }
+                out.max_desired_file_size_bytes.replace(bytes).is_some()
+            }
+            "percentage_max_file_size" => out
+                .percentage_max_file_size
+                .replace(parse_percentage(key, value)?)
+                .is_some(),
+            "split_percentage" => out
+                .split_percentage
+                .replace(parse_percentage(key, value)?)
+                .is_some(),
+            _ => return Err(format!("unknown table sizing setting '{key}'").into()),
+        };
+        if existed {
+            return Err(format!("key '{key}' passed multiple times").into());
+        }
+    }
+
+    Ok(out)
+}
+
+/// Parse a percentage between (0, 100).
+fn parse_percentage(
+    key: &str,
+    value: &str,
+) -> Result<u16, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let percentage: u16 = value.parse()?;
+    if !(1..=99).contains(&percentage) {
+        return Err(format!("'{key}' must be between (0, 100), got {percentage}").into());
+    }
+    Ok(percentage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use test_helpers::assert_contains;
+
+    #[test]
+    fn test_table_sizing_overrides_default() {
+        let config = CompactorConfig::try_parse_from(["my_binary"]).unwrap();
+        assert!(config.table_sizing_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_table_sizing_overrides() {
+        let config = CompactorConfig::try_parse_from([
+            "my_binary",
+            "--compaction-table-sizing-override",
+            "cpu:max_desired_file_size_bytes=1000, split_percentage=90;ns/mem:percentage_max_file_size=30",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            config.table_sizing_overrides,
+            vec![
+                TableSizingOverride {
+                    table: "cpu".to_owned(),
+                    max_desired_file_size_bytes: Some(1000),
+                    percentage_max_file_size: None,
+                    split_percentage: Some(90),
+                },
+                TableSizingOverride {
+                    table: "ns/mem".to_owned(),
+                    max_desired_file_size_bytes: None,
+                    percentage_max_file_size: Some(30),
+                    split_percentage: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_sizing_overrides_invalid() {
+        for (arg, expected) in [
+            ("cpu", "expected 'TABLE:KEY=VALUE,...'"),
+            (":split_percentage=90", "has no table"),
+            ("cpu:split_percentage", "expected 'KEY=VALUE'"),
+            ("cpu:split_percentage=100", "must be between (0, 100)"),
+            ("cpu:percentage_max_file_size=0", "must be between (0, 100)"),
+            (
+                "cpu:max_desired_file_size_bytes=0",
+                "must be greater than 0",
+            ),
+            (
+                "cpu:split_percentage=80,split_percentage=90",
+                "passed multiple times",
+            ),
+            ("cpu:max_file_size=1", "unknown table sizing setting"),
+        ] {
+            let err = CompactorConfig::try_parse_from([
+                "my_binary",
+                "--compaction-table-sizing-override",
+                arg,
+            ])
+            .unwrap_err()
+            .to_string();
+            assert_contains!(err, expected);
+        }
+    }
+
+    #[test]
+    fn test_min_num_l0_files_to_compact_zero() {
+        let err = CompactorConfig::try_parse_from([
+            "my_binary",
+            "--compaction-min-num-l0-files-to-compact",
+            "0",
+        ])
+        .unwrap_err()
+        .to_string();
+        assert_contains!(err, "--compaction-min-num-l0-files-to-compact");
+    }
 }
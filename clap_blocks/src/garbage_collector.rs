@@ -55,6 +55,9 @@ pub struct GarbageCollectorConfig {
     /// Parquet file rows in the catalog flagged for deletion before this duration will be deleted.
     /// Parsed with <https://docs.rs/humantime/latest/humantime/fn.parse_duration.html>
     ///
+    /// Compaction only flags its input files for deletion, so this is also the grace period
+    /// during which queries planned before a compaction can still read its inputs.
+    ///
     /// If not specified, defaults to 14 days ago.
     #[clap(
         long,
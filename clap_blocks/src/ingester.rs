@@ -76,6 +76,18 @@ pub struct IngesterConfig {
     )]
     pub persist_hot_partition_cost: usize,
 
+    /// The size in bytes of a partition's in-memory buffer at which it is
+    /// queued for persistence, independently of its estimated persistence
+    /// cost and of time-based persistence.
+    ///
+    /// Must be positive. This limit is disabled by default.
+    #[clap(
+        long = "persist-memory-threshold-bytes",
+        env = "INFLUXDB_IOX_PERSIST_MEMORY_THRESHOLD_BYTES",
+        action
+    )]
+    pub persist_memory_threshold_bytes: Option<NonZeroUsize>,
+
     /// Limit the number of partitions that may be buffered in a single
     /// namespace (across all tables) at any one time.
     ///
@@ -6,12 +6,91 @@ use crate::{
     single_tenant::{
         CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
     },
+    socket_addr::SocketAddr,
 };
+use regex::Regex;
 use std::{
     num::{NonZeroUsize, ParseIntError},
+    path::PathBuf,
+    str::FromStr,
     time::Duration,
 };
 
+/// The default [`NamespaceNamePattern`], only allowing ASCII letters, digits
+/// and the `_`, `-` and `/` separators.
+pub const DEFAULT_NAMESPACE_NAME_PATTERN: &str = "^[a-zA-Z0-9_/-]+$";
+
+/// A pattern the name of a namespace must match for the router to
+/// auto-create it.
+#[derive(Debug, Clone)]
+pub struct NamespaceNamePattern(Regex);
+
+impl NamespaceNamePattern {
+    /// The compiled pattern.
+    pub fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl Default for NamespaceNamePattern {
+    fn default() -> Self {
+        DEFAULT_NAMESPACE_NAME_PATTERN
+            .parse()
+            .expect("default namespace name pattern is valid")
+    }
+}
+
+impl FromStr for NamespaceNamePattern {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Regex::new(s).map(Self)
+    }
+}
+
+/// How the router handles a tag repeated within a line protocol line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DuplicateTagPolicy {
+    /// Reject the write.
+    #[default]
+    Reject,
+
+    /// Keep the value of the last occurrence of the tag.
+    LastWins,
+
+    /// Keep the value of the first occurrence of the tag.
+    FirstWins,
+}
+
+/// How the router handles a write containing data older than the retention
+/// period of its namespace.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutsideRetentionPolicy {
+    /// Reject the write as forbidden.
+    #[default]
+    Reject,
+
+    /// Reject the write as unprocessable.
+    RejectUnprocessable,
+
+    /// Accept the write, leaving the data to expire.
+    Accept,
+}
+
+/// How the router chooses the ingester a write is sent to first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ShardAssignment {
+    /// Distribute writes uniformly across all healthy ingesters.
+    #[default]
+    RoundRobin,
+
+    /// Send all writes for a namespace to the same ingester.
+    HashByNamespace,
+
+    /// Send all writes for a partition of a namespace to the same ingester.
+    HashByPartition,
+}
+
 /// CLI config for the router using the RPC write path
 #[derive(Debug, Clone, clap::Parser)]
 #[allow(missing_copy_implementations)]
@@ -28,6 +107,19 @@ pub struct RouterConfig {
     )]
     pub authz_address: Option<String>,
 
+    /// How long a successful token check against the authz service is
+    /// cached for, avoiding a round trip to the authz service on every write
+    /// made with the same token.
+    ///
+    /// Rejected tokens are never cached. A value of "0s" disables caching.
+    #[clap(
+        long = "authz-cache-ttl",
+        env = "INFLUXDB_IOX_AUTHZ_CACHE_TTL",
+        default_value = "0s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub authz_cache_ttl: Duration,
+
     /// Differential handling based upon deployment to CST vs MT.
     ///
     /// At minimum, differs in supports of v1 endpoint. But also includes
@@ -49,6 +141,11 @@ pub struct RouterConfig {
     ///
     /// A default maximum of 200 requests, multiplied by the default 10MiB
     /// maximum for HTTP request bodies == ~2GiB.
+    ///
+    /// When `http-streaming-write-segment-bytes` is set, uncompressed write
+    /// bodies are no longer buffered in full, and only the segment size counts
+    /// towards this estimate for them. Compressed bodies are still buffered in
+    /// full.
     #[clap(
         long = "max-http-requests",
         env = "INFLUXDB_IOX_MAX_HTTP_REQUESTS",
@@ -57,6 +154,173 @@ pub struct RouterConfig {
     )]
     pub http_request_limit: usize,
 
+    /// The maximum number of simultaneously open HTTP connections.
+    ///
+    /// Connections beyond this limit are closed as soon as they are accepted,
+    /// protecting the router from exhausting its file descriptors when
+    /// serving many clients. Unlike `max-http-requests`, this also bounds idle
+    /// kept-alive connections.
+    #[clap(
+        long = "http-max-connections",
+        env = "INFLUXDB_IOX_HTTP_MAX_CONNECTIONS",
+        default_value = "10000",
+        action
+    )]
+    pub http_max_connections: usize,
+
+    /// Specify the time in seconds after which an idle kept-alive HTTP
+    /// connection (one with no request in flight) is closed.
+    #[clap(
+        long = "http-keep-alive-timeout-seconds",
+        env = "INFLUXDB_IOX_HTTP_KEEP_ALIVE_TIMEOUT_SECONDS",
+        default_value = "60",
+        value_parser = parse_duration
+    )]
+    pub http_keep_alive_timeout_seconds: Duration,
+
+    /// The number of line protocol bodies that may be parsed at once on
+    /// dedicated blocking threads.
+    ///
+    /// When set, write request bodies are parsed on the blocking thread pool
+    /// instead of the request task, preventing large writes from starving the
+    /// async runtime. When unset, bodies are parsed inline on the request
+    /// task, which is cheapest for small writes.
+    #[clap(
+        long = "http-parse-threads",
+        env = "INFLUXDB_IOX_HTTP_PARSE_THREADS",
+        action
+    )]
+    pub http_parse_threads: Option<NonZeroUsize>,
+
+    /// How a tag name appearing more than once in a single line protocol
+    /// line is handled.
+    ///
+    /// "reject" fails the write with a 422 response, "last-wins" and
+    /// "first-wins" keep the value of the last / first occurrence of the tag.
+    #[clap(
+        value_enum,
+        long = "duplicate-tag-policy",
+        env = "INFLUXDB_IOX_DUPLICATE_TAG_POLICY",
+        default_value = "reject",
+        action
+    )]
+    pub duplicate_tag_policy: DuplicateTagPolicy,
+
+    /// The maximum length of a single line protocol line, in bytes.
+    ///
+    /// Writes containing longer lines are rejected with a 422 response.
+    #[clap(
+        long = "max-line-bytes",
+        env = "INFLUXDB_IOX_MAX_LINE_BYTES",
+        default_value = "1048576", // 1 MiB
+        action
+    )]
+    pub max_line_bytes: usize,
+
+    /// The maximum number of fields in a single line protocol line.
+    ///
+    /// Writes containing lines with more fields are rejected with a 422
+    /// response.
+    #[clap(
+        long = "max-fields-per-line",
+        env = "INFLUXDB_IOX_MAX_FIELDS_PER_LINE",
+        default_value = "10000",
+        action
+    )]
+    pub max_fields_per_line: usize,
+
+    /// The maximum time in seconds to spend receiving the body of a single
+    /// write request.
+    ///
+    /// When a write body is not fully received before the deadline, the
+    /// complete lines received so far are written and the request fails with
+    /// a 408 (Request Timeout) response reporting the number of lines
+    /// accepted, allowing the client to resume the write from the following
+    /// line. Only uncompressed bodies are partially accepted. When unset,
+    /// writes have no deadline.
+    #[clap(
+        long = "http-write-deadline-seconds",
+        env = "INFLUXDB_IOX_HTTP_WRITE_DEADLINE_SECONDS",
+        value_parser = parse_duration
+    )]
+    pub http_write_deadline_seconds: Option<Duration>,
+
+    /// Parse and write uncompressed write request bodies incrementally as
+    /// they are received, in segments of complete lines of at least this many
+    /// bytes, instead of buffering the whole body first.
+    ///
+    /// This bounds the memory used by each write request to roughly the
+    /// segment size, rather than the body size. Segments are written
+    /// independently: if one fails, the earlier ones have already been written
+    /// and the error response reports the number of lines accepted, allowing
+    /// the client to resume the write from the following line. When unset,
+    /// bodies are buffered in full.
+    #[clap(
+        long = "http-streaming-write-segment-bytes",
+        env = "INFLUXDB_IOX_HTTP_STREAMING_WRITE_SEGMENT_BYTES",
+        action
+    )]
+    pub http_streaming_write_segment_bytes: Option<NonZeroUsize>,
+
+    /// Store the line protocol payloads of writes rejected with a client
+    /// error in this local directory, along with the namespace and the
+    /// rejection reason, for debugging misbehaving producers.
+    ///
+    /// Each rejected write is stored as a JSON document. Disabled when
+    /// neither this nor `http-dead-letter-object-store-prefix` is set.
+    #[clap(
+        long = "http-dead-letter-dir",
+        env = "INFLUXDB_IOX_HTTP_DEAD_LETTER_DIR",
+        conflicts_with = "http_dead_letter_object_store_prefix",
+        action
+    )]
+    pub http_dead_letter_dir: Option<PathBuf>,
+
+    /// Store the line protocol payloads of writes rejected with a client
+    /// error under this prefix of the configured object store, instead of a
+    /// local directory.
+    #[clap(
+        long = "http-dead-letter-object-store-prefix",
+        env = "INFLUXDB_IOX_HTTP_DEAD_LETTER_OBJECT_STORE_PREFIX",
+        action
+    )]
+    pub http_dead_letter_object_store_prefix: Option<String>,
+
+    /// The maximum number of bytes of each rejected payload stored in the
+    /// dead-letter sink. Longer payloads are truncated.
+    #[clap(
+        long = "http-dead-letter-max-payload-bytes",
+        env = "INFLUXDB_IOX_HTTP_DEAD_LETTER_MAX_PAYLOAD_BYTES",
+        default_value = "65536",
+        action
+    )]
+    pub http_dead_letter_max_payload_bytes: usize,
+
+    /// Redact the tag values and field values of the payloads stored in the
+    /// dead-letter sink, keeping only the structure and field types of each
+    /// line.
+    #[clap(
+        long = "http-dead-letter-redact",
+        env = "INFLUXDB_IOX_HTTP_DEAD_LETTER_REDACT",
+        default_value = "false",
+        action
+    )]
+    pub http_dead_letter_redact: bool,
+
+    /// The address on which the router serves the `/metrics` endpoint,
+    /// separately from the HTTP API.
+    ///
+    /// When set, metrics are only served on this address and no longer on the
+    /// HTTP API listener, allowing the write endpoint to be exposed publicly
+    /// while metrics stay internal. When unset, metrics are served on the HTTP
+    /// API listener.
+    #[clap(
+        long = "metrics-bind-address",
+        env = "INFLUXDB_IOX_METRICS_BIND_ADDR",
+        action
+    )]
+    pub metrics_bind_address: Option<SocketAddr>,
+
     /// gRPC address for the router to talk with the ingesters. For
     /// example:
     ///
@@ -97,6 +361,65 @@ pub struct RouterConfig {
     )]
     pub namespace_autocreation_enabled: bool,
 
+    /// Regular expression the name of a non-existent namespace must match for
+    /// the router to auto-create it. Writes to namespaces with other names
+    /// are rejected with a 422 (Unprocessable Entity).
+    ///
+    /// The default only allows ASCII letters, digits, `_`, `-` and `/`. Set
+    /// to `.*` to auto-create any otherwise valid namespace name, for example
+    /// while migrating existing data. Namespaces that already exist are not
+    /// affected.
+    #[clap(
+        long = "namespace-name-pattern",
+        env = "INFLUXDB_IOX_NAMESPACE_NAME_PATTERN",
+        default_value = DEFAULT_NAMESPACE_NAME_PATTERN,
+        action
+    )]
+    pub namespace_name_pattern: NamespaceNamePattern,
+
+    /// A regular expression the name of every measurement in a write must
+    /// match.
+    ///
+    /// Writes containing any other measurement are rejected with a 422
+    /// (Unprocessable Entity), naming the pattern. Applies to all
+    /// namespaces. All measurements are allowed if not set.
+    #[clap(
+        long = "measurement-allow-pattern",
+        env = "INFLUXDB_IOX_MEASUREMENT_ALLOW_PATTERN",
+        action
+    )]
+    pub measurement_allow_pattern: Option<Regex>,
+
+    /// A regular expression the name of no measurement in a write may match.
+    ///
+    /// Writes containing a matching measurement are rejected with a 422
+    /// (Unprocessable Entity), naming the pattern. Applies to all namespaces
+    /// and takes precedence over `--measurement-allow-pattern`. No
+    /// measurements are denied if not set.
+    #[clap(
+        long = "measurement-deny-pattern",
+        env = "INFLUXDB_IOX_MEASUREMENT_DENY_PATTERN",
+        action
+    )]
+    pub measurement_deny_pattern: Option<Regex>,
+
+    /// How a write containing data older than the retention period of its
+    /// namespace is handled.
+    ///
+    /// "reject" fails the entire write with a 403 (Forbidden), and
+    /// "reject-unprocessable" with a 422 (Unprocessable Entity), both naming
+    /// the oldest acceptable timestamp. "accept" writes the data,
+    /// which is then never returned by queries and removed with the rest of
+    /// the expired data.
+    #[clap(
+        value_enum,
+        long = "outside-retention-policy",
+        env = "INFLUXDB_IOX_OUTSIDE_RETENTION_POLICY",
+        default_value = "reject",
+        action
+    )]
+    pub outside_retention_policy: OutsideRetentionPolicy,
+
     /// Specify the timeout in seconds for a single RPC write request to an
     /// ingester.
     #[clap(
@@ -141,9 +464,201 @@ pub struct RouterConfig {
         default_value = "10"
     )]
     pub rpc_write_health_num_probes: u64,
+
+    /// Specify how the ingester a write is sent to first is chosen.
+    ///
+    /// "round-robin" distributes writes uniformly across all healthy
+    /// ingesters. "hash-by-namespace" and "hash-by-partition" consistently
+    /// send all writes for a namespace / partition to the same healthy
+    /// ingester, for as long as the set of healthy ingesters does not change.
+    ///
+    /// Writes fall through to the next healthy ingester on failure, and
+    /// replicated writes are sent to the following ingesters.
+    #[clap(
+        value_enum,
+        long = "rpc-write-shard-assignment",
+        env = "INFLUXDB_IOX_RPC_WRITE_SHARD_ASSIGNMENT",
+        default_value = "round-robin",
+        action
+    )]
+    pub rpc_write_shard_assignment: ShardAssignment,
+
+    /// FOR TESTING ONLY: allow clients to request a write is acknowledged
+    /// only once it has been persisted to object storage, by setting the
+    /// `wait=durable` query parameter on the write request.
+    ///
+    /// A durable write asks every ingester to persist all the data buffered
+    /// for the namespace through the ingester persist service, which is meant
+    /// for tests and may misbehave when used concurrently with other writes
+    /// and WAL rotations. It also causes many small parquet files. Do not
+    /// enable this in production. When disabled, such requests are rejected.
+    #[clap(
+        long = "allow-sync-writes",
+        env = "INFLUXDB_IOX_ALLOW_SYNC_WRITES",
+        default_value = "false",
+        action
+    )]
+    pub allow_sync_writes: bool,
+
+    /// Specify the timeout in seconds to wait for a durable write to be
+    /// persisted, after which it is acknowledged with a 202 (Accepted)
+    /// instead of a 200.
+    ///
+    /// Only used if `allow-sync-writes` is set.
+    #[clap(
+        long = "sync-write-timeout-seconds",
+        env = "INFLUXDB_IOX_SYNC_WRITE_TIMEOUT_SECONDS",
+        default_value = "30",
+        value_parser = parse_duration
+    )]
+    pub sync_write_timeout_seconds: Duration,
 }
 
 /// Map a string containing an integer number of seconds into a [`Duration`].
 fn parse_duration(input: &str) -> Result<Duration, ParseIntError> {
     input.parse().map(Duration::from_secs)
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_default_namespace_name_pattern() {
+        let pattern = NamespaceNamePattern::default();
+
+        for name in [
+            "bananas",
+            "Bananas_42",
+            "org_bucket",
+            "db/rp",
+            "my-namespace",
+        ] {
+            assert!(pattern.regex().is_match(name), "{name} should match");
+        }
+        for name in [
+            "bananas platanos",
+            "bananas\t",
+            "plátanos",
+            "bananas.42",
+            "",
+        ] {
+            assert!(!pattern.regex().is_match(name), "{name} should not match");
+        }
+    }
+
+    #[test]
+    fn test_namespace_name_pattern() {
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--namespace-name-pattern",
+            ".*",
+        ])
+        .unwrap();
+        assert!(config.namespace_name_pattern.regex().is_match("plátanos"));
+
+        RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--namespace-name-pattern",
+            "[",
+        ])
+        .expect_err("invalid pattern should be rejected");
+    }
+
+    #[test]
+    fn test_measurement_patterns() {
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+        ])
+        .unwrap();
+        assert!(config.measurement_allow_pattern.is_none());
+        assert!(config.measurement_deny_pattern.is_none());
+
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--measurement-allow-pattern",
+            "^prod_",
+            "--measurement-deny-pattern",
+            "_tmp$",
+        ])
+        .unwrap();
+        assert!(config
+            .measurement_allow_pattern
+            .unwrap()
+            .is_match("prod_cpu"));
+        assert!(config.measurement_deny_pattern.unwrap().is_match("cpu_tmp"));
+    }
+
+    #[test]
+    fn test_outside_retention_policy() {
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.outside_retention_policy,
+            OutsideRetentionPolicy::Reject
+        );
+
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--outside-retention-policy",
+            "accept",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.outside_retention_policy,
+            OutsideRetentionPolicy::Accept
+        );
+
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--outside-retention-policy",
+            "reject-unprocessable",
+        ])
+        .unwrap();
+        assert_eq!(
+            config.outside_retention_policy,
+            OutsideRetentionPolicy::RejectUnprocessable
+        );
+    }
+
+    #[test]
+    fn test_dead_letter_locations_conflict() {
+        let config = RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+        ])
+        .unwrap();
+        assert!(config.http_dead_letter_dir.is_none());
+        assert!(config.http_dead_letter_object_store_prefix.is_none());
+
+        RouterConfig::try_parse_from([
+            "my_binary",
+            "--ingester-addresses",
+            "http://ingester-0:8082",
+            "--http-dead-letter-dir",
+            "/tmp/dead_letters",
+            "--http-dead-letter-object-store-prefix",
+            "dead_letters",
+        ])
+        .expect_err("only one dead-letter location may be set");
+    }
+}
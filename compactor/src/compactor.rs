@@ -7,6 +7,7 @@ use futures::{
 };
 use generated_types::influxdata::iox::gossip::{v1::CompactionEvent, Topic};
 use gossip::{NopDispatcher, TopicInterests};
+use iox_catalog::interface::{Catalog, Error as CatalogError};
 use observability_deps::tracing::{info, warn};
 use tokio::task::{JoinError, JoinHandle};
 use tokio_util::sync::CancellationToken;
@@ -16,9 +17,11 @@ use crate::{
     components::{
         hardcoded::hardcoded_components,
         report::{log_components, log_config},
+        status::StatusTracker,
     },
     config::Config,
     driver::compact,
+    status::{self, StatusSnapshot},
 };
 
 /// A [`JoinHandle`] that can be cloned
@@ -34,6 +37,10 @@ fn shared_handle(handle: JoinHandle<()>) -> SharedJoinHandle {
 pub struct Compactor {
     shutdown: CancellationToken,
     worker: SharedJoinHandle,
+
+    /// The status tracker, if the status endpoint is enabled.
+    status: Option<Arc<dyn StatusTracker>>,
+    catalog: Arc<dyn Catalog>,
 }
 
 impl Compactor {
@@ -78,6 +85,11 @@ impl Compactor {
             None => None,
         };
 
+        let status = config
+            .status_endpoint
+            .then(|| Arc::clone(&components.status));
+        let catalog = Arc::clone(&config.catalog);
+
         let worker = tokio::spawn(async move {
             tokio::select! {
                 _ = shutdown_captured.cancelled() => {}
@@ -97,7 +109,12 @@ impl Compactor {
         });
         let worker = shared_handle(worker);
 
-        Self { shutdown, worker }
+        Self {
+            shutdown,
+            worker,
+            status,
+            catalog,
+        }
     }
 
     /// Trigger shutdown. You should [join](Self::join) afterwards.
@@ -110,6 +127,14 @@ impl Compactor {
     pub async fn join(&self) -> Result<(), Arc<JoinError>> {
         self.worker.clone().await
     }
+
+    /// Take a snapshot of what the compactor is currently doing.
+    ///
+    /// Returns `None` if the status endpoint is disabled.
+    pub async fn status(&self) -> Option<Result<StatusSnapshot, CatalogError>> {
+        let status = self.status.as_ref()?;
+        Some(status::snapshot(status.as_ref(), self.catalog.as_ref()).await)
+    }
 }
 
 impl Drop for Compactor {
@@ -0,0 +1,42 @@
+//! Estimation of when the compactor will have caught up with its L0 backlog.
+//!
+//! The compaction lag tells how far behind the compactor is, but not whether it is catching up.
+//! A [`CatchUpEstimator`] derives the compaction throughput and an ETA to drain the L0 backlog,
+//! so operators get "minutes until caught up" instead of raw counts.
+use std::fmt::{Debug, Display};
+
+use data_types::{ParquetFile, PartitionId};
+use serde::Serialize;
+
+pub mod noop;
+pub mod rolling_window;
+
+/// Throughput, L0 backlog and derived ETA of the compactor, as reported by the status endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CatchUpSummary {
+    /// Number of files compacted per minute over the window.
+    pub files_per_minute: u64,
+    /// Bytes of files compacted per minute over the window.
+    pub bytes_per_minute: u64,
+    /// Number of L0 files waiting to be compacted.
+    pub backlog_l0_files: usize,
+    /// Bytes of L0 files waiting to be compacted.
+    pub backlog_l0_bytes: u64,
+    /// Estimated seconds until the L0 backlog is drained, `null` if the backlog is growing or
+    /// there is not enough history yet.
+    pub eta_secs: Option<u64>,
+    /// Whether the backlog did not shrink over the window, i.e. the compactor is falling behind.
+    pub backlog_growing: bool,
+}
+
+/// Estimates when the compactor will have caught up with its L0 backlog.
+pub trait CatchUpEstimator: Debug + Display + Send + Sync {
+    /// Record the current `files` of `partition_id`, updating its share of the L0 backlog.
+    fn observe_backlog(&self, partition_id: PartitionId, files: &[ParquetFile]);
+
+    /// Record that the input `files` of a commit were compacted.
+    fn record_compacted(&self, files: &[ParquetFile]);
+
+    /// The current throughput, backlog and ETA.
+    fn summary(&self) -> CatchUpSummary;
+}
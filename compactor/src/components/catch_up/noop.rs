@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use data_types::{ParquetFile, PartitionId};
+
+use super::{CatchUpEstimator, CatchUpSummary};
+
+/// Does not track anything and always reports an empty backlog.
+#[derive(Debug, Default)]
+pub struct NoopCatchUpEstimator;
+
+impl NoopCatchUpEstimator {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopCatchUpEstimator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+impl CatchUpEstimator for NoopCatchUpEstimator {
+    fn observe_backlog(&self, _partition_id: PartitionId, _files: &[ParquetFile]) {}
+
+    fn record_compacted(&self, _files: &[ParquetFile]) {}
+
+    fn summary(&self) -> CatchUpSummary {
+        CatchUpSummary::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::CompactionLevel;
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopCatchUpEstimator::new().to_string(), "noop");
+    }
+
+    #[test]
+    fn test_summary() {
+        let estimator = NoopCatchUpEstimator::new();
+        let file = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_file_size_bytes(100)
+            .build();
+        estimator.observe_backlog(PartitionId::new(1), &[file.clone()]);
+        estimator.record_compacted(&[file]);
+        assert_eq!(estimator.summary(), CatchUpSummary::default());
+    }
+}
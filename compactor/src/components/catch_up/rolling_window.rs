@@ -0,0 +1,358 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    sync::Arc,
+    time::Duration,
+};
+
+use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use iox_time::{Time, TimeProvider};
+use metric::{Registry, U64Gauge};
+use parking_lot::Mutex;
+
+use super::{CatchUpEstimator, CatchUpSummary};
+
+const METRIC_NAME_FILES_PER_MINUTE: &str = "iox_compactor_throughput_files_per_minute";
+const METRIC_NAME_BYTES_PER_MINUTE: &str = "iox_compactor_throughput_bytes_per_minute";
+const METRIC_NAME_BACKLOG_BYTES: &str = "iox_compactor_l0_backlog_bytes";
+const METRIC_NAME_ETA: &str = "iox_compactor_catch_up_eta_seconds";
+
+/// Length of the rolling window throughput and backlog change are measured over.
+const WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// History required before the trend of the backlog is trusted.
+const MIN_HISTORY: Duration = Duration::from_secs(60);
+
+/// Value of the ETA gauge while the backlog is not shrinking.
+pub const ETA_INFINITE: u64 = u64::MAX;
+
+#[derive(Debug, Default)]
+struct State {
+    /// L0 files and bytes of each partition, as of the last fetch of its files.
+    backlog: HashMap<PartitionId, (usize, u64)>,
+    /// When files were compacted, and how many and bytes, oldest first.
+    compacted: VecDeque<(Time, u64, u64)>,
+    /// Total backlog bytes after every change, oldest first. The oldest sample is kept at or
+    /// before the start of the window so the trend covers the whole window.
+    backlog_history: VecDeque<(Time, u64)>,
+    /// When the first event was recorded.
+    first_event: Option<Time>,
+}
+
+/// Derives compaction throughput and an ETA to drain the L0 backlog from a rolling window,
+/// labelled by shard.
+///
+/// The backlog only covers partitions whose files were fetched by this compactor, like the
+/// compaction lag. The ETA is based on the net change of the backlog over the window, i.e.
+/// compaction minus ingest: a backlog that does not shrink has no ETA.
+#[derive(Debug)]
+pub struct RollingWindowCatchUpEstimator {
+    time_provider: Arc<dyn TimeProvider>,
+    state: Mutex<State>,
+    files_per_minute: U64Gauge,
+    bytes_per_minute: U64Gauge,
+    backlog_bytes: U64Gauge,
+    eta: U64Gauge,
+}
+
+impl RollingWindowCatchUpEstimator {
+    /// Create a new estimator reporting to `registry` for the given `shard` label.
+    pub fn new(time_provider: Arc<dyn TimeProvider>, registry: &Registry, shard: String) -> Self {
+        let attributes = [("shard", Cow::from(shard))];
+
+        let files_per_minute = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_FILES_PER_MINUTE,
+                "Number of files compacted per minute, over the last 10 minutes",
+            )
+            .recorder(attributes.clone());
+        let bytes_per_minute = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_BYTES_PER_MINUTE,
+                "Bytes of files compacted per minute, over the last 10 minutes",
+            )
+            .recorder(attributes.clone());
+        let backlog_bytes = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_BACKLOG_BYTES,
+                "Bytes of L0 files not yet compacted",
+            )
+            .recorder(attributes.clone());
+        let eta = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_ETA,
+                "Estimated seconds until the L0 backlog is drained at the current rate, \
+                 u64::MAX while the backlog is not shrinking",
+            )
+            .recorder(attributes);
+
+        Self {
+            time_provider,
+            state: Mutex::new(State::default()),
+            files_per_minute,
+            bytes_per_minute,
+            backlog_bytes,
+            eta,
+        }
+    }
+
+    fn update(&self, now: Time, state: &mut State) {
+        prune(now, state);
+        let summary = summarize(now, state);
+
+        self.files_per_minute.set(summary.files_per_minute);
+        self.bytes_per_minute.set(summary.bytes_per_minute);
+        self.backlog_bytes.set(summary.backlog_l0_bytes);
+        match (summary.eta_secs, summary.backlog_growing) {
+            (Some(eta), _) => self.eta.set(eta),
+            (None, true) => self.eta.set(ETA_INFINITE),
+            // not enough history, keep the previous estimate
+            (None, false) => {}
+        }
+    }
+}
+
+impl CatchUpEstimator for RollingWindowCatchUpEstimator {
+    fn observe_backlog(&self, partition_id: PartitionId, files: &[ParquetFile]) {
+        let l0 = files
+            .iter()
+            .filter(|f| f.compaction_level == CompactionLevel::Initial)
+            .fold((0, 0), |(n, bytes), f| {
+                (n + 1, bytes + f.file_size_bytes as u64)
+            });
+
+        let now = self.time_provider.now();
+        let mut state = self.state.lock();
+        state.first_event = state.first_event.or(Some(now));
+        let changed = match l0 {
+            (0, _) => state.backlog.remove(&partition_id).is_some(),
+            l0 => state.backlog.insert(partition_id, l0) != Some(l0),
+        };
+        if changed || state.backlog_history.is_empty() {
+            let total = state.backlog.values().map(|(_, bytes)| bytes).sum();
+            // only the last of several changes at the same time is a meaningful sample
+            if state
+                .backlog_history
+                .back()
+                .map_or(false, |(at, _)| *at == now)
+            {
+                state.backlog_history.pop_back();
+            }
+            state.backlog_history.push_back((now, total));
+        }
+        self.update(now, &mut state);
+    }
+
+    fn record_compacted(&self, files: &[ParquetFile]) {
+        if files.is_empty() {
+            return;
+        }
+        let bytes = files.iter().map(|f| f.file_size_bytes as u64).sum();
+
+        let now = self.time_provider.now();
+        let mut state = self.state.lock();
+        state.first_event = state.first_event.or(Some(now));
+        state.compacted.push_back((now, files.len() as u64, bytes));
+        self.update(now, &mut state);
+    }
+
+    fn summary(&self) -> CatchUpSummary {
+        let now = self.time_provider.now();
+        let mut state = self.state.lock();
+        prune(now, &mut state);
+        summarize(now, &state)
+    }
+}
+
+impl Display for RollingWindowCatchUpEstimator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catch_up(window={WINDOW:?})")
+    }
+}
+
+/// Drop everything that fell out of the window, apart from the latest backlog sample before it.
+fn prune(now: Time, state: &mut State) {
+    let Some(start) = now.checked_sub(WINDOW) else {
+        return;
+    };
+
+    while state
+        .compacted
+        .front()
+        .map_or(false, |(at, _, _)| *at < start)
+    {
+        state.compacted.pop_front();
+    }
+    while state
+        .backlog_history
+        .get(1)
+        .map_or(false, |(at, _)| *at <= start)
+    {
+        state.backlog_history.pop_front();
+    }
+}
+
+fn summarize(now: Time, state: &State) -> CatchUpSummary {
+    let (backlog_l0_files, backlog_l0_bytes) = state
+        .backlog
+        .values()
+        .fold((0, 0), |(n, bytes), (n_p, bytes_p)| {
+            (n + n_p, bytes + bytes_p)
+        });
+
+    let elapsed = state
+        .first_event
+        .and_then(|first| now.checked_duration_since(first))
+        .unwrap_or_default()
+        .min(WINDOW);
+    let (files, bytes) = state
+        .compacted
+        .iter()
+        .fold((0, 0), |(n, b), (_, n_c, b_c)| (n + n_c, b + b_c));
+    let per_minute = |count: u64| match elapsed.as_millis() {
+        0 => 0,
+        ms => (count as u128 * 60_000 / ms) as u64,
+    };
+
+    let (eta_secs, backlog_growing) = if backlog_l0_bytes == 0 {
+        (Some(0), false)
+    } else {
+        match state.backlog_history.front() {
+            Some((at, start_bytes))
+                if now.checked_duration_since(*at).unwrap_or_default() >= MIN_HISTORY =>
+            {
+                let trend = now.checked_duration_since(*at).unwrap_or_default();
+                match start_bytes.checked_sub(backlog_l0_bytes) {
+                    Some(drained) if drained > 0 => {
+                        let eta =
+                            backlog_l0_bytes as u128 * trend.as_millis() / drained as u128 / 1_000;
+                        (Some(eta.min(u64::MAX as u128) as u64), false)
+                    }
+                    _ => (None, true),
+                }
+            }
+            _ => (None, false),
+        }
+    };
+
+    CatchUpSummary {
+        files_per_minute: per_minute(files),
+        bytes_per_minute: per_minute(bytes),
+        backlog_l0_files,
+        backlog_l0_bytes,
+        eta_secs,
+        backlog_growing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use super::*;
+
+    fn l0(id: i64, size: i64) -> ParquetFile {
+        ParquetFileBuilder::new(id)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_file_size_bytes(size)
+            .build()
+    }
+
+    fn eta_gauge(registry: &Registry) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Gauge>>(METRIC_NAME_ETA)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[("shard", "1")]))
+            .expect("observer not found")
+            .fetch()
+    }
+
+    fn setup() -> (Arc<MockProvider>, Registry, RollingWindowCatchUpEstimator) {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let registry = Registry::new();
+        let estimator = RollingWindowCatchUpEstimator::new(
+            Arc::clone(&time_provider) as _,
+            &registry,
+            "1".to_owned(),
+        );
+        (time_provider, registry, estimator)
+    }
+
+    #[test]
+    fn test_display() {
+        let (_, _, estimator) = setup();
+        assert_eq!(estimator.to_string(), "catch_up(window=600s)");
+    }
+
+    #[test]
+    fn test_draining_backlog() {
+        let (time_provider, registry, estimator) = setup();
+        let p1 = PartitionId::new(1);
+        let p2 = PartitionId::new(2);
+
+        estimator.observe_backlog(p1, &[l0(1, 100), l0(2, 100)]);
+        estimator.observe_backlog(p2, &[l0(3, 200)]);
+        let summary = estimator.summary();
+        assert_eq!(summary.backlog_l0_files, 3);
+        assert_eq!(summary.backlog_l0_bytes, 400);
+        // no history yet
+        assert_eq!(summary.eta_secs, None);
+        assert!(!summary.backlog_growing);
+
+        // half of the backlog is drained in two minutes, the rest takes another two
+        time_provider.inc(Duration::from_secs(120));
+        estimator.record_compacted(&[l0(1, 100), l0(2, 100)]);
+        estimator.observe_backlog(p1, &[]);
+        let summary = estimator.summary();
+        assert_eq!(summary.files_per_minute, 1);
+        assert_eq!(summary.bytes_per_minute, 100);
+        assert_eq!(summary.backlog_l0_files, 1);
+        assert_eq!(summary.eta_secs, Some(120));
+        assert!(!summary.backlog_growing);
+        assert_eq!(eta_gauge(&registry), 120);
+
+        // caught up
+        estimator.observe_backlog(p2, &[]);
+        assert_eq!(estimator.summary().eta_secs, Some(0));
+        assert_eq!(eta_gauge(&registry), 0);
+    }
+
+    #[test]
+    fn test_growing_backlog() {
+        let (time_provider, registry, estimator) = setup();
+        let p1 = PartitionId::new(1);
+
+        estimator.observe_backlog(p1, &[l0(1, 100)]);
+        time_provider.inc(Duration::from_secs(120));
+        // compaction happens, but ingest is faster
+        estimator.record_compacted(&[l0(1, 100)]);
+        estimator.observe_backlog(p1, &[l0(2, 100), l0(3, 100)]);
+
+        let summary = estimator.summary();
+        assert_eq!(summary.files_per_minute, 0);
+        assert_eq!(summary.bytes_per_minute, 50);
+        assert_eq!(summary.eta_secs, None);
+        assert!(summary.backlog_growing);
+        assert_eq!(eta_gauge(&registry), ETA_INFINITE);
+    }
+
+    #[test]
+    fn test_window() {
+        let (time_provider, _, estimator) = setup();
+        let p1 = PartitionId::new(1);
+
+        estimator.observe_backlog(p1, &[l0(1, 1_000)]);
+        estimator.record_compacted(&[l0(1, 600)]);
+
+        // the early compaction falls out of the window, while the backlog trend reaches back to the
+        // last sample before it
+        time_provider.inc(WINDOW * 2);
+        estimator.observe_backlog(p1, &[l0(2, 500)]);
+        let summary = estimator.summary();
+        assert_eq!(summary.bytes_per_minute, 0);
+        assert_eq!(summary.eta_secs, Some(WINDOW.as_secs() * 2));
+    }
+}
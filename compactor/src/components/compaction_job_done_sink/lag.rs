@@ -0,0 +1,113 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+
+use crate::{components::partition_files_source::lag::CompactionLag, error::DynError};
+
+use super::CompactionJobDoneSink;
+
+/// Removes successfully compacted partitions from the [`CompactionLag`].
+///
+/// Their L0 files were compacted, but the last fetch of their files happened before the final
+/// commit, and they may not be fetched again for a long time.
+#[derive(Debug)]
+pub struct CompactionLagCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    lag: Arc<CompactionLag>,
+    inner: T,
+}
+
+impl<T> CompactionLagCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    pub fn new(inner: T, lag: Arc<CompactionLag>) -> Self {
+        Self { lag, inner }
+    }
+}
+
+impl<T> Display for CompactionLagCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "compaction_lag({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobDoneSink for CompactionLagCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    async fn record(&self, job: CompactionJob, res: Result<(), DynError>) -> Result<(), DynError> {
+        if res.is_ok() {
+            self.lag.evict(job.partition_id);
+        }
+        self.inner.record(job, res).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use data_types::{CompactionLevel, PartitionId};
+    use iox_tests::ParquetFileBuilder;
+    use iox_time::{MockProvider, Time};
+    use metric::Registry;
+
+    use super::{super::mock::MockCompactionJobDoneSink, *};
+
+    fn lag(time_provider: &Arc<MockProvider>) -> Arc<CompactionLag> {
+        Arc::new(CompactionLag::new(
+            &Registry::new(),
+            String::from("none"),
+            Arc::clone(time_provider) as _,
+        ))
+    }
+
+    #[test]
+    fn test_display() {
+        let sink = CompactionLagCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            lag(&Arc::new(MockProvider::new(Time::MIN))),
+        );
+        assert_eq!(sink.to_string(), "compaction_lag(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_record() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let lag = lag(&time_provider);
+        let l0 = |created_at| {
+            [ParquetFileBuilder::new(1)
+                .with_compaction_level(CompactionLevel::Initial)
+                .with_created_at(created_at)
+                .build()]
+        };
+        lag.observe(PartitionId::new(1), &l0(10_000_000_000));
+        lag.observe(PartitionId::new(2), &l0(20_000_000_000));
+        time_provider.set(Time::from_timestamp_nanos(0) + Duration::from_secs(30));
+
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let sink =
+            CompactionLagCompactionJobDoneSinkWrapper::new(Arc::clone(&inner), Arc::clone(&lag));
+
+        // failed partitions are still behind
+        sink.record(CompactionJob::new(PartitionId::new(1)), Err("msg".into()))
+            .await
+            .unwrap();
+        assert_eq!(lag.lag(), Duration::from_secs(20));
+
+        sink.record(CompactionJob::new(PartitionId::new(1)), Ok(()))
+            .await
+            .unwrap();
+        assert_eq!(lag.lag(), Duration::from_secs(10));
+
+        assert_eq!(inner.results().len(), 2);
+    }
+}
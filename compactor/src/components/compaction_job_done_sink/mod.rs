@@ -9,6 +9,7 @@ use compactor_scheduler::CompactionJob;
 use crate::DynError;
 
 pub mod error_kind;
+pub mod lag;
 pub mod logging;
 pub mod metrics;
 pub mod mock;
@@ -1,7 +1,14 @@
-use std::{collections::VecDeque, fmt::Display, sync::Arc};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Display,
+    sync::Arc,
+    time::Duration,
+};
 
 use compactor_scheduler::CompactionJob;
 use futures::{stream::BoxStream, StreamExt};
+use observability_deps::tracing::info;
+use tokio::time::Instant;
 
 use super::super::{
     compaction_jobs_source::CompactionJobsSource, partition_files_source::rate_limit::RateLimit,
@@ -15,16 +22,22 @@ where
 {
     source: Arc<T>,
     limiter: RateLimit,
+
+    /// Once this much time has passed since the first job of a batch was
+    /// yielded, a new batch is fetched. The jobs not yet yielded are carried
+    /// over to the front of the new batch.
+    iteration_budget: Option<Duration>,
 }
 
 impl<T> EndlessCompactionJobStream<T>
 where
     T: CompactionJobsSource,
 {
-    pub fn new(source: T) -> Self {
+    pub fn new(source: T, iteration_budget: Option<Duration>) -> Self {
         Self {
             source: Arc::new(source),
             limiter: RateLimit::new(1, 1), // Initial rate is irrelevant, it will be updated before first use.
+            iteration_budget,
         }
     }
 }
@@ -47,36 +60,66 @@ where
 
         // Note: we use a VecDeque as a buffer so we can preserve the order and cheaply remove the first element without
         // relocating the entire buffer content.
-        futures::stream::unfold(VecDeque::new(), move |mut buffer| {
-            let source = Arc::clone(&source);
-            async move {
-                loop {
-                    while let Some(d) = self.limiter.can_proceed() {
-                        // Throttling because either we don't need to go this fast, or we're at risk of hitting the catalog
-                        // to hard, or both.
-                        tokio::time::sleep(d).await;
-                    }
-
-                    if let Some(p_id) = buffer.pop_front() {
-                        return Some((p_id, buffer));
-                    }
-
-                    // fetch new data
-                    buffer = VecDeque::from(source.fetch().await);
-
-                    // update rate limiter so we can complete the batch in 5m, which is plenty fast.
-                    // allow a burst of 25, so after a period of inactivity, up to 25 can go quickly.
-                    let mut rate = buffer.len() / (5 * 60);
-
-                    if rate < 10 {
-                        // The purpose of this rate limiter is to keep us from hitting the catalog too hard.  There is no need to
-                        // slow it down to less than 10/s
-                        rate = 10;
+        //
+        // `started` is the time the first job of the current batch was yielded, if any.
+        futures::stream::unfold(
+            (VecDeque::new(), None::<Instant>),
+            move |(mut buffer, mut started)| {
+                let source = Arc::clone(&source);
+                async move {
+                    loop {
+                        while let Some(d) = self.limiter.can_proceed() {
+                            // Throttling because either we don't need to go this fast, or we're at risk of hitting the catalog
+                            // to hard, or both.
+                            tokio::time::sleep(d).await;
+                        }
+
+                        // Start a new iteration once the batch is over budget. Jobs already handed
+                        // out are not affected. The remaining jobs are carried over, as the source
+                        // only returns partitions that received new files since its last fetch.
+                        let mut carried_over = VecDeque::new();
+                        if let (Some(budget), Some(t)) = (self.iteration_budget, started) {
+                            if !buffer.is_empty() && t.elapsed() > budget {
+                                info!(
+                                    carried_over_jobs = buffer.len(),
+                                    budget_secs = budget.as_secs_f32(),
+                                    "compaction iteration budget exceeded, starting new iteration",
+                                );
+                                carried_over = std::mem::take(&mut buffer);
+                            }
+                        }
+
+                        if let Some(p_id) = buffer.pop_front() {
+                            let started = started.or_else(|| Some(Instant::now()));
+                            return Some((p_id, (buffer, started)));
+                        }
+
+                        // fetch new data, after the jobs carried over from the previous batch
+                        let fetched = source.fetch().await;
+                        let carried_over_partitions: HashSet<_> =
+                            carried_over.iter().map(|job| job.partition_id).collect();
+                        buffer = carried_over;
+                        buffer.extend(
+                            fetched
+                                .into_iter()
+                                .filter(|job| !carried_over_partitions.contains(&job.partition_id)),
+                        );
+                        started = None;
+
+                        // update rate limiter so we can complete the batch in 5m, which is plenty fast.
+                        // allow a burst of 25, so after a period of inactivity, up to 25 can go quickly.
+                        let mut rate = buffer.len() / (5 * 60);
+
+                        if rate < 10 {
+                            // The purpose of this rate limiter is to keep us from hitting the catalog too hard.  There is no need to
+                            // slow it down to less than 10/s
+                            rate = 10;
+                        }
+                        self.limiter.update_rps(rate, 25);
                     }
-                    self.limiter.update_rps(rate, 25);
                 }
-            }
-        })
+            },
+        )
         .boxed()
     }
 }
@@ -89,7 +132,7 @@ mod tests {
 
     #[test]
     fn test_display() {
-        let stream = EndlessCompactionJobStream::new(MockCompactionJobsSource::new(vec![]));
+        let stream = EndlessCompactionJobStream::new(MockCompactionJobsSource::new(vec![]), None);
         assert_eq!(stream.to_string(), "endless(mock)");
     }
 
@@ -100,7 +143,8 @@ mod tests {
             CompactionJob::new(PartitionId::new(3)),
             CompactionJob::new(PartitionId::new(2)),
         ];
-        let stream = EndlessCompactionJobStream::new(MockCompactionJobsSource::new(ids.clone()));
+        let stream =
+            EndlessCompactionJobStream::new(MockCompactionJobsSource::new(ids.clone()), None);
 
         // stream is stateless
         for _ in 0..2 {
@@ -111,4 +155,49 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_stream_iteration_budget() {
+        let ids = vec![
+            CompactionJob::new(PartitionId::new(1)),
+            CompactionJob::new(PartitionId::new(3)),
+            CompactionJob::new(PartitionId::new(2)),
+        ];
+        let stream = EndlessCompactionJobStream::new(
+            MockCompactionJobsSource::new(ids.clone()),
+            Some(Duration::from_nanos(1)),
+        );
+
+        // the budget is exceeded straight after the first job of each batch, the rest of the
+        // batch is carried over to the next one
+        assert_eq!(
+            stream.stream().take(4).collect::<Vec<_>>().await,
+            [&ids[..], &ids[..1]].concat(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_iteration_budget_carries_over_jobs() {
+        let ids = vec![
+            CompactionJob::new(PartitionId::new(1)),
+            CompactionJob::new(PartitionId::new(3)),
+            CompactionJob::new(PartitionId::new(2)),
+        ];
+        let stream = EndlessCompactionJobStream::new(
+            MockCompactionJobsSource::new(ids.clone()),
+            Some(Duration::from_nanos(1)),
+        );
+
+        let mut jobs = stream.stream();
+        assert_eq!(jobs.next().await.unwrap(), ids[0]);
+
+        // the partitions of the over-budget batch are not returned by the source again, but are
+        // still compacted in the following iterations
+        let other = CompactionJob::new(PartitionId::new(4));
+        stream.source.set(vec![other.clone()]);
+        assert_eq!(
+            jobs.take(3).collect::<Vec<_>>().await,
+            vec![ids[1].clone(), ids[2].clone(), other],
+        );
+    }
 }
@@ -5,8 +5,11 @@ pub mod logging;
 pub mod metrics;
 pub mod mock;
 pub mod not_empty;
+pub mod pause_file;
 pub mod randomize_order;
 pub mod scheduled;
+pub mod status;
+pub mod summary;
 
 use std::{
     fmt::{Debug, Display},
@@ -0,0 +1,167 @@
+use std::{fmt::Display, path::PathBuf, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+use iox_time::TimeProvider;
+use metric::{Registry, U64Gauge};
+use observability_deps::tracing::{info, warn};
+
+use super::CompactionJobsSource;
+
+const METRIC_NAME_PAUSED: &str = "iox_compactor_paused";
+
+/// Stops fetching compaction jobs while a pause file exists.
+///
+/// Jobs that were already fetched are not affected and run to completion.
+#[derive(Debug)]
+pub struct PauseFileCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    inner: T,
+    path: PathBuf,
+    poll_interval: Duration,
+    time_provider: Arc<dyn TimeProvider>,
+    paused: U64Gauge,
+}
+
+impl<T> PauseFileCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    pub fn new(
+        inner: T,
+        path: PathBuf,
+        poll_interval: Duration,
+        time_provider: Arc<dyn TimeProvider>,
+        registry: &Registry,
+    ) -> Self {
+        let paused = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_PAUSED,
+                "Set to 1 while compaction is paused by the pause file, 0 otherwise",
+            )
+            .recorder(&[]);
+
+        Self {
+            inner,
+            path,
+            poll_interval,
+            time_provider,
+            paused,
+        }
+    }
+
+    /// Whether the pause file exists.
+    ///
+    /// The check runs on the blocking thread pool, so a slow file system
+    /// does not stall the runtime. A file that cannot be checked does not
+    /// pause compaction.
+    async fn is_paused(&self) -> bool {
+        match tokio::fs::try_exists(&self.path).await {
+            Ok(exists) => exists,
+            Err(e) => {
+                warn!(path = %self.path.display(), %e, "cannot check for the pause file");
+                false
+            }
+        }
+    }
+}
+
+impl<T> Display for PauseFileCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "pause_file({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobsSource for PauseFileCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    async fn fetch(&self) -> Vec<CompactionJob> {
+        if self.is_paused().await {
+            info!(path = %self.path.display(), "compaction paused");
+            self.paused.set(1);
+
+            while self.is_paused().await {
+                self.time_provider.sleep(self.poll_interval).await;
+            }
+
+            info!(path = %self.path.display(), "compaction resumed");
+            self.paused.set(0);
+        }
+
+        self.inner.fetch().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use compactor_test_utils::AssertFutureExt;
+    use data_types::PartitionId;
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+
+    use super::{super::mock::MockCompactionJobsSource, *};
+
+    #[test]
+    fn test_display() {
+        let source = PauseFileCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(vec![]),
+            PathBuf::from("/does/not/exist"),
+            Duration::from_secs(1),
+            Arc::new(MockProvider::new(Time::MIN)),
+            &Registry::new(),
+        );
+        assert_eq!(source.to_string(), "pause_file(mock)",);
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let path = dir.path().join("pause");
+
+        let parts = vec![CompactionJob::new(PartitionId::new(5))];
+        let registry = Registry::new();
+        let time_provider = Arc::new(MockProvider::new(Time::MIN));
+        let source = PauseFileCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(parts.clone()),
+            path.clone(),
+            Duration::from_secs(1),
+            Arc::clone(&time_provider) as _,
+            &registry,
+        );
+
+        // not paused, so data arrives immediately
+        assert_eq!(source.fetch().poll_timeout().await, parts);
+        assert_eq!(paused(&registry), 0);
+
+        // paused while the file exists
+        std::fs::write(&path, "").unwrap();
+        let mut fut = source.fetch();
+        fut.assert_pending().await;
+        assert_eq!(paused(&registry), 1);
+
+        time_provider.inc(Duration::from_secs(10));
+        fut.assert_pending().await;
+
+        // removing the file resumes on the next poll
+        std::fs::remove_file(&path).unwrap();
+        time_provider.inc(Duration::from_secs(1));
+        assert_eq!(fut.poll_timeout().await, parts);
+        assert_eq!(paused(&registry), 0);
+    }
+
+    fn paused(registry: &Registry) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Gauge>>(METRIC_NAME_PAUSED)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[]))
+            .expect("observer not found")
+            .fetch()
+    }
+}
@@ -0,0 +1,89 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+
+use crate::components::status::StatusTracker;
+
+use super::CompactionJobsSource;
+
+/// Records every fetched batch of jobs as a new iteration in the [`StatusTracker`].
+#[derive(Debug)]
+pub struct StatusCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    inner: T,
+    status: Arc<dyn StatusTracker>,
+}
+
+impl<T> StatusCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    pub fn new(inner: T, status: Arc<dyn StatusTracker>) -> Self {
+        Self { inner, status }
+    }
+}
+
+impl<T> Display for StatusCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "status({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobsSource for StatusCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    async fn fetch(&self) -> Vec<CompactionJob> {
+        let jobs = self.inner.fetch().await;
+        self.status.iteration_started(jobs.len());
+        jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use compactor_scheduler::SchedulerConfig;
+    use data_types::PartitionId;
+    use iox_time::{MockProvider, Time};
+
+    use crate::components::status::live::LiveStatusTracker;
+
+    use super::{super::mock::MockCompactionJobsSource, *};
+
+    fn status() -> Arc<LiveStatusTracker> {
+        Arc::new(LiveStatusTracker::new(
+            &SchedulerConfig::default(),
+            Arc::new(MockProvider::new(Time::MIN)),
+        ))
+    }
+
+    #[test]
+    fn test_display() {
+        let source =
+            StatusCompactionJobsSourceWrapper::new(MockCompactionJobsSource::new(vec![]), status());
+        assert_eq!(source.to_string(), "status(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let status = status();
+        let jobs = vec![
+            CompactionJob::new(PartitionId::new(1)),
+            CompactionJob::new(PartitionId::new(2)),
+        ];
+        let source = StatusCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(jobs.clone()),
+            Arc::clone(&status) as _,
+        );
+
+        assert_eq!(source.fetch().await, jobs);
+        assert_eq!(status.snapshot(vec![]).current_iteration.unwrap().jobs, 2);
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+use observability_deps::tracing::info;
+
+use crate::components::{catch_up::CatchUpEstimator, partition_files_source::lag::CompactionLag};
+
+use super::CompactionJobsSource;
+
+/// Logs a summary of the compactor's progress whenever a new batch of jobs is fetched, i.e. once
+/// per iteration.
+#[derive(Debug)]
+pub struct SummaryCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    lag: Arc<CompactionLag>,
+    catch_up: Arc<dyn CatchUpEstimator>,
+    inner: T,
+}
+
+impl<T> SummaryCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    pub fn new(inner: T, lag: Arc<CompactionLag>, catch_up: Arc<dyn CatchUpEstimator>) -> Self {
+        Self {
+            lag,
+            catch_up,
+            inner,
+        }
+    }
+}
+
+impl<T> Display for SummaryCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "summary({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobsSource for SummaryCompactionJobsSourceWrapper<T>
+where
+    T: CompactionJobsSource,
+{
+    async fn fetch(&self) -> Vec<CompactionJob> {
+        let jobs = self.inner.fetch().await;
+        let catch_up = self.catch_up.summary();
+        info!(
+            n_jobs = jobs.len(),
+            compaction_lag_secs = self.lag.lag().as_secs(),
+            files_per_minute = catch_up.files_per_minute,
+            bytes_per_minute = catch_up.bytes_per_minute,
+            backlog_l0_files = catch_up.backlog_l0_files,
+            backlog_l0_bytes = catch_up.backlog_l0_bytes,
+            eta_secs = ?catch_up.eta_secs,
+            backlog_growing = catch_up.backlog_growing,
+            "compaction iteration summary",
+        );
+        jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use data_types::{CompactionLevel, PartitionId};
+    use iox_tests::ParquetFileBuilder;
+    use iox_time::{MockProvider, Time};
+    use metric::Registry;
+    use test_helpers::tracing::TracingCapture;
+
+    use crate::components::catch_up::noop::NoopCatchUpEstimator;
+
+    use super::{super::mock::MockCompactionJobsSource, *};
+
+    fn lag(time_provider: Arc<MockProvider>) -> Arc<CompactionLag> {
+        Arc::new(CompactionLag::new(
+            &Registry::new(),
+            String::from("none"),
+            time_provider,
+        ))
+    }
+
+    #[test]
+    fn test_display() {
+        let source = SummaryCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(vec![]),
+            lag(Arc::new(MockProvider::new(Time::MIN))),
+            Arc::new(NoopCatchUpEstimator::new()),
+        );
+        assert_eq!(source.to_string(), "summary(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let lag = lag(Arc::clone(&time_provider));
+        lag.observe(
+            PartitionId::new(1),
+            &[ParquetFileBuilder::new(1)
+                .with_compaction_level(CompactionLevel::Initial)
+                .with_created_at(0)
+                .build()],
+        );
+        time_provider.inc(Duration::from_secs(42));
+
+        let jobs = vec![CompactionJob::new(PartitionId::new(1))];
+        let source = SummaryCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(jobs.clone()),
+            lag,
+            Arc::new(NoopCatchUpEstimator::new()),
+        );
+        let capture = TracingCapture::new();
+        assert_eq!(source.fetch().await, jobs);
+        assert_eq!(
+            capture.to_string(),
+            "level = INFO; message = compaction iteration summary; n_jobs = 1; \
+            compaction_lag_secs = 42; files_per_minute = 0; bytes_per_minute = 0; \
+            backlog_l0_files = 0; backlog_l0_bytes = 0; eta_secs = None; \
+            backlog_growing = false; ",
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::{borrow::Cow, fmt::Display, sync::Arc, time::Duration};
+
+use backoff::{Backoff, BackoffConfig};
+use data_types::PartitionId;
+use hyper::{client::HttpConnector, header::CONTENT_TYPE, Body, Client, Method, Request, Uri};
+use iox_time::TimeProvider;
+use metric::{Registry, U64Counter};
+use observability_deps::tracing::{debug, warn};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+
+use crate::{
+    components::row_counts::PartitionRowCounts,
+    error::{DynError, ErrorKind, SimpleError},
+};
+
+use super::CompletionWebhook;
+
+const METRIC_NAME_NOTIFICATIONS: &str = "iox_compactor_completion_webhook_notifications";
+
+/// How long to keep retrying a notification if the backoff config has no deadline.
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(60);
+
+/// How long to wait for the webhook to respond to a single attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The JSON document POSTed to the webhook once a partition is compacted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartitionSummary {
+    /// The partition.
+    pub partition_id: i64,
+    /// Whether the compaction succeeded.
+    pub succeeded: bool,
+    /// The error the compaction failed with, if any.
+    pub error: Option<String>,
+    /// Rows of the files that were rewritten.
+    pub rows_read: u64,
+    /// Rows of the files that replaced them.
+    pub rows_written: u64,
+    /// When the compaction finished (RFC 3339).
+    pub finished_at: String,
+}
+
+/// POSTs a [`PartitionSummary`] to a webhook after each partition is compacted.
+///
+/// Notifications are sent in the background and retried with backoff; a failure to notify is
+/// logged and counted.
+#[derive(Debug)]
+pub struct HttpCompletionWebhook {
+    sender: Arc<Sender>,
+}
+
+#[derive(Debug)]
+struct Sender {
+    client: Client<HttpConnector>,
+    url: Uri,
+    backoff_config: BackoffConfig,
+    time_provider: Arc<dyn TimeProvider>,
+    sent: U64Counter,
+    failed: U64Counter,
+}
+
+impl HttpCompletionWebhook {
+    /// Create a new webhook notifier POSTing to `url`, retrying according to `backoff_config`
+    /// and reporting to `registry` for the given `shard` label.
+    pub fn new(
+        url: Uri,
+        backoff_config: BackoffConfig,
+        time_provider: Arc<dyn TimeProvider>,
+        registry: &Registry,
+        shard: String,
+    ) -> Self {
+        let notifications = registry.register_metric::<U64Counter>(
+            METRIC_NAME_NOTIFICATIONS,
+            "Number of partition completion notifications sent to the webhook, by outcome",
+        );
+        let sent = notifications.recorder([
+            ("shard", Cow::from(shard.clone())),
+            ("outcome", Cow::from("sent")),
+        ]);
+        let failed = notifications.recorder([
+            ("shard", Cow::from(shard)),
+            ("outcome", Cow::from("failed")),
+        ]);
+
+        // never retry forever, the notification would never be dropped
+        let backoff_config = BackoffConfig {
+            deadline: Some(backoff_config.deadline.unwrap_or(DEFAULT_DEADLINE)),
+            ..backoff_config
+        };
+
+        Self {
+            sender: Arc::new(Sender {
+                client: Client::new(),
+                url,
+                backoff_config,
+                time_provider,
+                sent,
+                failed,
+            }),
+        }
+    }
+
+    /// Send the notification in the background, returning the handle of the sending task.
+    fn spawn_notify(
+        &self,
+        partition_id: PartitionId,
+        res: &Result<(), DynError>,
+        row_counts: PartitionRowCounts,
+    ) -> JoinHandle<()> {
+        let summary = PartitionSummary {
+            partition_id: partition_id.get(),
+            succeeded: res.is_ok(),
+            error: res.as_ref().err().map(|e| e.to_string()),
+            rows_read: row_counts.rows_read,
+            rows_written: row_counts.rows_written,
+            finished_at: self.sender.time_provider.now().to_rfc3339(),
+        };
+
+        let sender = Arc::clone(&self.sender);
+        tokio::spawn(async move { sender.send(summary).await })
+    }
+}
+
+impl Sender {
+    async fn send(&self, summary: PartitionSummary) {
+        let body = serde_json::to_vec(&summary).expect("summary serialisation");
+
+        let res = Backoff::new(&self.backoff_config)
+            .retry_all_errors("compaction completion webhook", || {
+                let req = Request::builder()
+                    .method(Method::POST)
+                    .uri(self.url.clone())
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.clone()))
+                    .expect("valid request");
+
+                async move {
+                    let response = tokio::time::timeout(REQUEST_TIMEOUT, self.client.request(req))
+                        .await
+                        .map_err(|_| SimpleError::new(ErrorKind::Timeout, "webhook timed out"))?
+                        .map_err(|e| SimpleError::new(ErrorKind::Unknown, e.to_string()))?;
+
+                    if !response.status().is_success() {
+                        return Err(SimpleError::new(
+                            ErrorKind::Unknown,
+                            format!("webhook responded with {}", response.status()),
+                        ));
+                    }
+                    Ok(())
+                }
+            })
+            .await;
+
+        match res {
+            Ok(()) => {
+                debug!(
+                    partition_id = summary.partition_id,
+                    "notified compaction completion webhook"
+                );
+                self.sent.inc(1);
+            }
+            Err(e) => {
+                warn!(
+                    partition_id = summary.partition_id,
+                    %e,
+                    "failed to notify compaction completion webhook",
+                );
+                self.failed.inc(1);
+            }
+        }
+    }
+}
+
+impl Display for HttpCompletionWebhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "http({})", self.sender.url)
+    }
+}
+
+impl CompletionWebhook for HttpCompletionWebhook {
+    fn notify(
+        &self,
+        partition_id: PartitionId,
+        res: &Result<(), DynError>,
+        row_counts: PartitionRowCounts,
+    ) {
+        self.spawn_notify(partition_id, res, row_counts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, net::SocketAddr};
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server, StatusCode,
+    };
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// Serve a webhook failing the first `failures` requests, returning its URL and the bodies of
+    /// the requests it received.
+    fn serve(failures: usize) -> (Uri, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let received = Arc::new(Mutex::new(vec![]));
+        let received_captured = Arc::clone(&received);
+
+        let make_service = make_service_fn(move |_| {
+            let received = Arc::clone(&received_captured);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let received = Arc::clone(&received);
+                    async move {
+                        let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+                        let mut received = received.lock();
+                        received.push(serde_json::from_slice(&body).unwrap());
+                        let status = if received.len() <= failures {
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        } else {
+                            StatusCode::OK
+                        };
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(status)
+                                .body(Body::empty())
+                                .unwrap(),
+                        )
+                    }
+                }))
+            }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}/compacted", server.local_addr())
+            .parse()
+            .unwrap();
+        tokio::spawn(server);
+
+        (url, received)
+    }
+
+    fn webhook(url: Uri, registry: &Registry) -> HttpCompletionWebhook {
+        let backoff_config = BackoffConfig {
+            init_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+            base: 2.,
+            deadline: Some(Duration::from_secs(1)),
+        };
+        HttpCompletionWebhook::new(
+            url,
+            backoff_config,
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+            registry,
+            "1".to_owned(),
+        )
+    }
+
+    fn notifications(registry: &Registry, outcome: &'static str) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Counter>>(METRIC_NAME_NOTIFICATIONS)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[("shard", "1"), ("outcome", outcome)]))
+            .expect("observer not found")
+            .fetch()
+    }
+
+    #[tokio::test]
+    async fn test_notify_retries() {
+        let (url, received) = serve(2);
+        let registry = Registry::new();
+        let webhook = webhook(url, &registry);
+        assert_eq!(webhook.to_string(), format!("http({})", webhook.sender.url));
+
+        let row_counts = PartitionRowCounts {
+            rows_read: 10,
+            rows_written: 7,
+        };
+        webhook
+            .spawn_notify(PartitionId::new(1), &Ok(()), row_counts)
+            .await
+            .unwrap();
+
+        let received = received.lock();
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received[2],
+            serde_json::json!({
+                "partition_id": 1,
+                "succeeded": true,
+                "error": null,
+                "rows_read": 10,
+                "rows_written": 7,
+                "finished_at": "1970-01-01T00:00:00+00:00",
+            })
+        );
+        assert_eq!(notifications(&registry, "sent"), 1);
+        assert_eq!(notifications(&registry, "failed"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_notify_failure() {
+        // nothing listens on a port that was bound and released again
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let registry = Registry::new();
+        let webhook = webhook(format!("http://{addr}/").parse().unwrap(), &registry);
+
+        let res: Result<(), DynError> = Err(Box::new(SimpleError::new(ErrorKind::Unknown, "boom")));
+        webhook
+            .spawn_notify(PartitionId::new(1), &res, PartitionRowCounts::default())
+            .await
+            .unwrap();
+
+        assert_eq!(notifications(&registry, "sent"), 0);
+        assert_eq!(notifications(&registry, "failed"), 1);
+    }
+}
@@ -0,0 +1,26 @@
+//! Notification of an external webhook when the compaction of a partition completes.
+//!
+//! This lets external orchestration react to compaction, e.g. to invalidate caches once new files
+//! are created.
+use std::fmt::{Debug, Display};
+
+use data_types::PartitionId;
+
+use crate::{components::row_counts::PartitionRowCounts, error::DynError};
+
+pub mod http;
+pub mod noop;
+
+/// Notifies an external system once a partition was compacted.
+pub trait CompletionWebhook: Debug + Display + Send + Sync {
+    /// Notify that the compaction of `partition_id` finished with `res`.
+    ///
+    /// This must not block the compaction: notifications are sent in the background and a
+    /// failure to notify never fails the compaction.
+    fn notify(
+        &self,
+        partition_id: PartitionId,
+        res: &Result<(), DynError>,
+        row_counts: PartitionRowCounts,
+    );
+}
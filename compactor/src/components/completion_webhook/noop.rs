@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use data_types::PartitionId;
+
+use crate::{components::row_counts::PartitionRowCounts, error::DynError};
+
+use super::CompletionWebhook;
+
+/// Does not notify anyone.
+#[derive(Debug, Default)]
+pub struct NoopCompletionWebhook;
+
+impl NoopCompletionWebhook {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopCompletionWebhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+impl CompletionWebhook for NoopCompletionWebhook {
+    fn notify(
+        &self,
+        _partition_id: PartitionId,
+        _res: &Result<(), DynError>,
+        _row_counts: PartitionRowCounts,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopCompletionWebhook::new().to_string(), "noop");
+    }
+
+    #[test]
+    fn test_notify() {
+        NoopCompletionWebhook::new().notify(
+            PartitionId::new(1),
+            &Ok(()),
+            PartitionRowCounts::default(),
+        );
+    }
+}
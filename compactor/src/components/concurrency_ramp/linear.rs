@@ -0,0 +1,219 @@
+use std::{borrow::Cow, fmt::Display, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use iox_time::{Time, TimeProvider};
+use metric::{Registry, U64Gauge};
+use observability_deps::tracing::info;
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::ConcurrencyRamp;
+
+const METRIC_NAME_EFFECTIVE_CONCURRENCY: &str = "iox_compactor_effective_partition_concurrency";
+
+/// Limits the number of partitions compacted concurrently, linearly raising the limit from 1 to
+/// `max` over the warm-up period.
+#[derive(Debug)]
+pub struct LinearConcurrencyRamp {
+    max: NonZeroUsize,
+    warm_up: Duration,
+    started: Time,
+    time_provider: Arc<dyn TimeProvider>,
+    semaphore: Arc<Semaphore>,
+    /// Total number of permits added to the semaphore so far.
+    granted: Mutex<usize>,
+    effective: U64Gauge,
+}
+
+impl LinearConcurrencyRamp {
+    /// Create a ramp reaching `max` concurrent partitions `warm_up` after now.
+    pub fn new(
+        max: NonZeroUsize,
+        warm_up: Duration,
+        time_provider: Arc<dyn TimeProvider>,
+        registry: &Registry,
+        shard: String,
+    ) -> Self {
+        let effective = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_EFFECTIVE_CONCURRENCY,
+                "Number of partitions that may currently be compacted concurrently",
+            )
+            .recorder([("shard", Cow::from(shard))]);
+        effective.set(1);
+
+        Self {
+            max,
+            warm_up,
+            started: time_provider.now(),
+            time_provider,
+            semaphore: Arc::new(Semaphore::new(1)),
+            granted: Mutex::new(1),
+            effective,
+        }
+    }
+
+    /// The concurrency that should be in effect `elapsed` after the start of the ramp.
+    fn target(&self, elapsed: Duration) -> usize {
+        if elapsed >= self.warm_up {
+            return self.max.get();
+        }
+        let steps = (self.max.get() - 1) as u128;
+        1 + (steps * elapsed.as_nanos() / self.warm_up.as_nanos()) as usize
+    }
+
+    /// When the concurrency is next raised after reaching `current`, which must be below the
+    /// maximum.
+    fn next_step(&self, current: usize) -> Time {
+        let steps = (self.max.get() - 1) as u128;
+        // round up so the target is always raised once this time is reached
+        let nanos = (current as u128 * self.warm_up.as_nanos() + steps - 1) / steps;
+        self.started + Duration::from_nanos(nanos as u64)
+    }
+
+    /// Raise the concurrency to the target for now, returning the new concurrency and whether
+    /// the maximum is reached.
+    fn update(&self) -> (usize, bool) {
+        let elapsed = self
+            .time_provider
+            .now()
+            .checked_duration_since(self.started)
+            .unwrap_or_default();
+        let target = self.target(elapsed);
+
+        let mut granted = self.granted.lock();
+        if target > *granted {
+            self.semaphore.add_permits(target - *granted);
+            *granted = target;
+            self.effective.set(target as u64);
+            if target == self.max.get() {
+                info!(
+                    concurrency = target,
+                    "partition concurrency warm-up complete"
+                );
+            }
+        }
+        (*granted, *granted == self.max.get())
+    }
+
+    async fn acquire_permit(&self) -> OwnedSemaphorePermit {
+        loop {
+            let (current, done) = self.update();
+            let acquire = Arc::clone(&self.semaphore).acquire_owned();
+            if done {
+                return acquire.await.expect("semaphore not closed");
+            }
+
+            tokio::select! {
+                permit = acquire => return permit.expect("semaphore not closed"),
+                _ = self.time_provider.sleep_until(self.next_step(current)) => {}
+            }
+        }
+    }
+}
+
+impl Display for LinearConcurrencyRamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "linear(max={}, warm_up={:?})", self.max, self.warm_up)
+    }
+}
+
+#[async_trait]
+impl ConcurrencyRamp for LinearConcurrencyRamp {
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        Some(self.acquire_permit().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use super::*;
+
+    fn effective(registry: &Registry) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Gauge>>(METRIC_NAME_EFFECTIVE_CONCURRENCY)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[("shard", "1")]))
+            .expect("observer not found")
+            .fetch()
+    }
+
+    #[test]
+    fn test_display() {
+        let ramp = LinearConcurrencyRamp::new(
+            NonZeroUsize::new(5).unwrap(),
+            Duration::from_secs(60),
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+            &Registry::new(),
+            "1".to_owned(),
+        );
+        assert_eq!(ramp.to_string(), "linear(max=5, warm_up=60s)");
+    }
+
+    #[tokio::test]
+    async fn test_ramp_up() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let registry = Registry::new();
+        let ramp = Arc::new(LinearConcurrencyRamp::new(
+            NonZeroUsize::new(5).unwrap(),
+            Duration::from_secs(40),
+            Arc::clone(&time_provider) as _,
+            &registry,
+            "1".to_owned(),
+        ));
+
+        // only a single partition at startup
+        let p1 = ramp.acquire_permit().await;
+        assert!(ramp.acquire_permit().now_or_never().is_none());
+        assert_eq!(effective(&registry), 1);
+
+        // the waiting partition is admitted once the next step is reached
+        let waiting = tokio::spawn({
+            let ramp = Arc::clone(&ramp);
+            async move { ramp.acquire_permit().await }
+        });
+        tokio::task::yield_now().await;
+        time_provider.inc(Duration::from_secs(10));
+        let p2 = waiting.await.unwrap();
+        assert_eq!(effective(&registry), 2);
+        assert!(ramp.acquire_permit().now_or_never().is_none());
+
+        // the full concurrency is available after the warm-up
+        time_provider.inc(Duration::from_secs(30));
+        let _p3 = ramp.acquire_permit().await;
+        let _p4 = ramp.acquire_permit().await;
+        let _p5 = ramp.acquire_permit().await;
+        assert_eq!(effective(&registry), 5);
+        assert!(ramp.acquire_permit().now_or_never().is_none());
+
+        // and finished partitions make room for new ones
+        drop(p1);
+        drop(p2);
+        let _p6 = ramp.acquire_permit().await;
+        let _p7 = ramp.acquire_permit().await;
+        assert!(ramp.acquire_permit().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_ramp_for_single_partition() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let registry = Registry::new();
+        let ramp = LinearConcurrencyRamp::new(
+            NonZeroUsize::new(1).unwrap(),
+            Duration::from_secs(40),
+            Arc::clone(&time_provider) as _,
+            &registry,
+            "1".to_owned(),
+        );
+
+        let p1 = ramp.acquire_permit().await;
+        assert!(ramp.acquire_permit().now_or_never().is_none());
+        drop(p1);
+        let _p2 = ramp.acquire_permit().await;
+        assert_eq!(effective(&registry), 1);
+    }
+}
@@ -0,0 +1,20 @@
+//! Gradual increase of the partition concurrency after startup.
+//!
+//! Starting all partition compactions at once right after boot can cause a latency spike against
+//! a cold object store and catalog.
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use tokio::sync::OwnedSemaphorePermit;
+
+pub mod linear;
+pub mod noop;
+
+/// Limits the number of partitions compacted concurrently, on top of the configured partition
+/// concurrency.
+#[async_trait]
+pub trait ConcurrencyRamp: Debug + Display + Send + Sync {
+    /// Wait until another partition may be compacted. The returned permit, if any, must be held
+    /// while the partition is compacted.
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit>;
+}
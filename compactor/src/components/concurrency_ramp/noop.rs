@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use tokio::sync::OwnedSemaphorePermit;
+
+use super::ConcurrencyRamp;
+
+/// Does not limit the concurrency, the configured partition concurrency applies from the start.
+#[derive(Debug, Default)]
+pub struct NoopConcurrencyRamp;
+
+impl NoopConcurrencyRamp {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopConcurrencyRamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+#[async_trait]
+impl ConcurrencyRamp for NoopConcurrencyRamp {
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopConcurrencyRamp::new().to_string(), "noop");
+    }
+
+    #[tokio::test]
+    async fn test_acquire() {
+        assert!(NoopConcurrencyRamp::new().acquire().await.is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Display, sync::Arc};
+use std::{fmt::Display, num::NonZeroUsize, sync::Arc};
 
 use async_trait::async_trait;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
@@ -11,7 +11,7 @@ use parquet_file::storage::ParquetStorage;
 use crate::{
     components::df_planner::query_chunk::{to_query_chunks, QueryableParquetChunk},
     partition_info::PartitionInfo,
-    plan_ir::PlanIR,
+    plan_ir::{FileIR, PlanIR},
 };
 
 use super::DataFusionPlanner;
@@ -24,12 +24,65 @@ use super::DataFusionPlanner;
 pub struct V1DataFusionPlanner {
     store: ParquetStorage,
     exec: Arc<Executor>,
+    input_read_concurrency: NonZeroUsize,
+    max_compact_size_bytes: usize,
+    target_partitions: Option<NonZeroUsize>,
 }
 
 impl V1DataFusionPlanner {
     /// Create a new compact plan builder.
-    pub fn new(store: ParquetStorage, exec: Arc<Executor>) -> Self {
-        Self { store, exec }
+    ///
+    /// Plans read up to `input_read_concurrency` input files concurrently, but never more than the
+    /// largest input files that together fit into `max_compact_size_bytes`. Plans are executed
+    /// with at most `target_partitions` partitions, if set.
+    pub fn new(
+        store: ParquetStorage,
+        exec: Arc<Executor>,
+        input_read_concurrency: NonZeroUsize,
+        max_compact_size_bytes: usize,
+        target_partitions: Option<NonZeroUsize>,
+    ) -> Self {
+        Self {
+            store,
+            exec,
+            input_read_concurrency,
+            max_compact_size_bytes,
+            target_partitions,
+        }
+    }
+
+    /// Number of `files` to read concurrently.
+    ///
+    /// This assumes the worst case, i.e. that the largest files are read at the same time, and
+    /// limits the concurrency so that they still fit into the memory budget. At least one file is
+    /// always read.
+    fn read_concurrency(&self, files: &[FileIR]) -> NonZeroUsize {
+        let mut sizes = files
+            .iter()
+            .map(|f| f.file.file_size_bytes.max(0) as usize)
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut total = 0;
+        let fitting = sizes
+            .into_iter()
+            .take_while(|size| {
+                total += size;
+                total <= self.max_compact_size_bytes
+            })
+            .count();
+
+        NonZeroUsize::new(fitting.min(self.input_read_concurrency.get()))
+            .unwrap_or(NonZeroUsize::MIN)
+    }
+
+    /// Number of DataFusion partitions to execute the plan for `files` with.
+    fn target_partitions(&self, files: &[FileIR]) -> NonZeroUsize {
+        let read_concurrency = self.read_concurrency(files);
+        match self.target_partitions {
+            Some(target) => read_concurrency.min(target),
+            None => read_concurrency,
+        }
     }
 }
 
@@ -46,7 +99,15 @@ impl DataFusionPlanner for V1DataFusionPlanner {
         ir: &PlanIR,
         partition: Arc<PartitionInfo>,
     ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
-        let ctx = self.exec.new_context(ExecutorType::Reorg);
+        let files = match ir {
+            PlanIR::None { .. } => unreachable!("filter out None plans before calling plan"),
+            PlanIR::Compact { files, .. } | PlanIR::Split { files, .. } => files,
+        };
+        let ctx = self
+            .exec
+            .new_execution_config(ExecutorType::Reorg)
+            .with_target_partitions(self.target_partitions(files))
+            .build();
 
         let plan = match ir {
             PlanIR::None { .. } => unreachable!("filter out None plans before calling plan"),
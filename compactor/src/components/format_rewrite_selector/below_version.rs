@@ -0,0 +1,193 @@
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, ParquetFileId, PartitionId};
+use futures::{stream, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use parquet_file::{storage::ParquetStorage, ParquetFilePath};
+
+use crate::{components::partition_files_source::PartitionFilesSource, error::DynError};
+
+use super::FormatRewriteSelector;
+
+/// Number of file footers read concurrently while selecting files of a partition.
+const FOOTER_READ_CONCURRENCY: usize = 10;
+
+/// Selects files whose Parquet writer version, as recorded in their footer, is below `version`.
+///
+/// Only the footers of the files are read from the object store, and each footer only once:
+/// files are immutable, so whether a file is outdated is remembered for as long as it belongs to
+/// its partition.
+#[derive(Debug)]
+pub struct BelowVersionFormatRewriteSelector {
+    partition_files_source: Arc<dyn PartitionFilesSource>,
+    store: ParquetStorage,
+    version: i32,
+    /// Whether each file of a partition is outdated, as of the last selection for the partition.
+    outdated: Mutex<HashMap<PartitionId, HashMap<ParquetFileId, bool>>>,
+}
+
+impl BelowVersionFormatRewriteSelector {
+    pub fn new(
+        partition_files_source: Arc<dyn PartitionFilesSource>,
+        store: ParquetStorage,
+        version: i32,
+    ) -> Self {
+        Self {
+            partition_files_source,
+            store,
+            version,
+            outdated: Default::default(),
+        }
+    }
+}
+
+impl Display for BelowVersionFormatRewriteSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "below_version({})", self.version)
+    }
+}
+
+#[async_trait]
+impl FormatRewriteSelector for BelowVersionFormatRewriteSelector {
+    async fn select(&self, partition_id: PartitionId) -> Result<Vec<ParquetFile>, DynError> {
+        let files = self.partition_files_source.fetch(partition_id).await;
+        let known = self
+            .outdated
+            .lock()
+            .get(&partition_id)
+            .cloned()
+            .unwrap_or_default();
+
+        let checked: Vec<(ParquetFile, bool)> = stream::iter(files)
+            .map(|file| {
+                let known = known.get(&file.id).copied();
+                async move {
+                    let outdated = match known {
+                        Some(outdated) => outdated,
+                        None => {
+                            let path = ParquetFilePath::from(&file);
+                            let version = self
+                                .store
+                                .read_format_version(&path, file.file_size_bytes as usize)
+                                .await?;
+                            version < self.version
+                        }
+                    };
+                    Ok::<_, DynError>((file, outdated))
+                }
+            })
+            .buffer_unordered(FOOTER_READ_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        // only keep the files still in the partition, deleted ones are never seen again
+        let mut outdated = self.outdated.lock();
+        let partition_outdated = outdated.entry(partition_id).or_default();
+        partition_outdated.clear();
+        partition_outdated.extend(checked.iter().map(|(file, outdated)| (file.id, *outdated)));
+        if partition_outdated.is_empty() {
+            outdated.remove(&partition_id);
+        }
+
+        Ok(checked
+            .into_iter()
+            .filter_map(|(file, outdated)| outdated.then_some(file))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use compactor_test_utils::TestSetup;
+    use object_store::memory::InMemory;
+    use parquet_file::{serialize::PARQUET_WRITER_VERSION, storage::StorageId};
+
+    use crate::components::partition_files_source::mock::MockPartitionFilesSource;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let selector = BelowVersionFormatRewriteSelector::new(
+            Arc::new(MockPartitionFilesSource::new(
+                Default::default(),
+                Default::default(),
+            )),
+            ParquetStorage::new(Arc::new(InMemory::new()), StorageId::from("iox")),
+            2,
+        );
+        assert_eq!(selector.to_string(), "below_version(2)");
+    }
+
+    #[tokio::test]
+    async fn test_select() {
+        let setup = TestSetup::builder().await.with_files().await.build().await;
+        let partition_id = setup.partition_info.partition_id;
+        let mut files = setup.list_by_table_not_to_delete().await;
+        files.sort_by_key(|f| f.id);
+        assert!(!files.is_empty());
+
+        let files_source: Arc<dyn PartitionFilesSource> = Arc::new(MockPartitionFilesSource::new(
+            HashMap::from([(partition_id, files[0].partition_id.clone())]),
+            files.clone(),
+        ));
+        let current = PARQUET_WRITER_VERSION.as_num();
+
+        // all files were written with the current version
+        let selector = BelowVersionFormatRewriteSelector::new(
+            Arc::clone(&files_source),
+            setup.config.parquet_store_real.clone(),
+            current,
+        );
+        assert!(selector.select(partition_id).await.unwrap().is_empty());
+
+        // ...and so are all outdated once a newer version is required
+        let selector = BelowVersionFormatRewriteSelector::new(
+            files_source,
+            setup.config.parquet_store_real.clone(),
+            current + 1,
+        );
+        let mut selected = selector.select(partition_id).await.unwrap();
+        selected.sort_by_key(|f| f.id);
+        assert_eq!(selected, files);
+    }
+
+    #[tokio::test]
+    async fn test_select_reads_footers_once() {
+        let setup = TestSetup::builder().await.with_files().await.build().await;
+        let partition_id = setup.partition_info.partition_id;
+        let files = setup.list_by_table_not_to_delete().await;
+
+        let files_source: Arc<dyn PartitionFilesSource> = Arc::new(MockPartitionFilesSource::new(
+            HashMap::from([(partition_id, files[0].partition_id.clone())]),
+            files.clone(),
+        ));
+        let selector = BelowVersionFormatRewriteSelector::new(
+            files_source,
+            setup.config.parquet_store_real.clone(),
+            PARQUET_WRITER_VERSION.as_num() + 1,
+        );
+        assert_eq!(
+            selector.select(partition_id).await.unwrap().len(),
+            files.len()
+        );
+
+        // the cached results are used once the objects are gone
+        for file in &files {
+            setup
+                .config
+                .parquet_store_real
+                .object_store()
+                .delete(&ParquetFilePath::from(file).object_store_path())
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            selector.select(partition_id).await.unwrap().len(),
+            files.len()
+        );
+    }
+}
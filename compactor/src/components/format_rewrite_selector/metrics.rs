@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, PartitionId};
+use metric::{Registry, U64Counter};
+
+use crate::error::DynError;
+
+use super::FormatRewriteSelector;
+
+const METRIC_NAME_FORMAT_REWRITE: &str = "iox_compactor_format_rewrite_files";
+
+/// Counts the files selected to be rewritten because of their Parquet format.
+#[derive(Debug)]
+pub struct MetricsFormatRewriteSelectorWrapper<T>
+where
+    T: FormatRewriteSelector,
+{
+    selected_counter: U64Counter,
+    inner: T,
+}
+
+impl<T> MetricsFormatRewriteSelectorWrapper<T>
+where
+    T: FormatRewriteSelector,
+{
+    pub fn new(inner: T, registry: &Registry) -> Self {
+        let selected_counter = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_FORMAT_REWRITE,
+                "Number of files rewritten by the compactor because of an outdated parquet format version",
+            )
+            .recorder(&[]);
+
+        Self {
+            selected_counter,
+            inner,
+        }
+    }
+}
+
+impl<T> Display for MetricsFormatRewriteSelectorWrapper<T>
+where
+    T: FormatRewriteSelector,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "metrics({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> FormatRewriteSelector for MetricsFormatRewriteSelectorWrapper<T>
+where
+    T: FormatRewriteSelector,
+{
+    async fn select(&self, partition_id: PartitionId) -> Result<Vec<ParquetFile>, DynError> {
+        let files = self.inner.select(partition_id).await?;
+        self.selected_counter.inc(files.len() as u64);
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+    use metric::{assert_counter, Attributes};
+
+    use crate::components::format_rewrite_selector::mock::MockFormatRewriteSelector;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let selector = MetricsFormatRewriteSelectorWrapper::new(
+            MockFormatRewriteSelector::new(vec![]),
+            &Registry::new(),
+        );
+        assert_eq!(selector.to_string(), "metrics(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_select() {
+        let registry = Registry::new();
+        let files = vec![
+            ParquetFileBuilder::new(1).build(),
+            ParquetFileBuilder::new(2).build(),
+        ];
+        let selector = MetricsFormatRewriteSelectorWrapper::new(
+            MockFormatRewriteSelector::new(files.clone()),
+            &registry,
+        );
+
+        assert_selected_counter(&registry, 0);
+
+        assert_eq!(selector.select(PartitionId::new(1)).await.unwrap(), files);
+        assert_eq!(selector.select(PartitionId::new(2)).await.unwrap(), files);
+
+        assert_selected_counter(&registry, 4);
+    }
+
+    fn assert_selected_counter(registry: &Registry, value: u64) {
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_FORMAT_REWRITE,
+            labels = Attributes::from(&[]),
+            value = value,
+        );
+    }
+}
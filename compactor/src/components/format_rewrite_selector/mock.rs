@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, PartitionId};
+
+use crate::error::DynError;
+
+use super::FormatRewriteSelector;
+
+/// Selects the given files for every partition.
+#[derive(Debug)]
+pub struct MockFormatRewriteSelector {
+    files: Vec<ParquetFile>,
+}
+
+impl MockFormatRewriteSelector {
+    #[cfg(test)]
+    pub fn new(files: Vec<ParquetFile>) -> Self {
+        Self { files }
+    }
+}
+
+impl Display for MockFormatRewriteSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl FormatRewriteSelector for MockFormatRewriteSelector {
+    async fn select(&self, _partition_id: PartitionId) -> Result<Vec<ParquetFile>, DynError> {
+        Ok(self.files.clone())
+    }
+}
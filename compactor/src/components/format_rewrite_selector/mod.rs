@@ -0,0 +1,21 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, PartitionId};
+
+use crate::error::DynError;
+
+pub mod below_version;
+pub mod metrics;
+pub mod mock;
+pub mod noop;
+
+/// Selects files of a partition that must be rewritten because of their Parquet format, even if
+/// they would not be compacted otherwise.
+///
+/// May return an error. In this case, the partition will be marked as "skipped".
+#[async_trait]
+pub trait FormatRewriteSelector: Debug + Display + Send + Sync {
+    /// Return the undeleted files of the given partition that should be rewritten.
+    async fn select(&self, partition_id: PartitionId) -> Result<Vec<ParquetFile>, DynError>;
+}
@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, PartitionId};
+
+use crate::error::DynError;
+
+use super::FormatRewriteSelector;
+
+/// Never selects any file for rewriting.
+#[derive(Debug, Default)]
+pub struct NoopFormatRewriteSelector;
+
+impl NoopFormatRewriteSelector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopFormatRewriteSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+#[async_trait]
+impl FormatRewriteSelector for NoopFormatRewriteSelector {
+    async fn select(&self, _partition_id: PartitionId) -> Result<Vec<ParquetFile>, DynError> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopFormatRewriteSelector::new().to_string(), "noop");
+    }
+
+    #[tokio::test]
+    async fn test_select() {
+        let selector = NoopFormatRewriteSelector::new();
+        assert!(selector
+            .select(PartitionId::new(1))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
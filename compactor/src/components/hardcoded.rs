@@ -4,18 +4,25 @@
 
 use std::{sync::Arc, time::Duration};
 
-use compactor_scheduler::{create_scheduler, Scheduler};
+use compactor_scheduler::{create_scheduler, LocalSchedulerConfig, Scheduler, SchedulerConfig};
 use data_types::CompactionLevel;
 use object_store::memory::InMemory;
+use parquet_file::serialize::PARQUET_WRITER_VERSION;
 
-use crate::{config::Config, error::ErrorKind, object_store::ignore_writes::IgnoreWrites};
+use crate::{
+    config::{Config, SingleOversizeFilePolicy, SizingConfig, ZeroRowOutputPolicy},
+    error::ErrorKind,
+    object_store::ignore_writes::IgnoreWrites,
+};
 
 use super::{
+    catch_up::{rolling_window::RollingWindowCatchUpEstimator, CatchUpEstimator},
     changed_files_filter::logging::LoggingChangedFiles,
     columns_source::catalog::CatalogColumnsSource,
     commit::CommitToScheduler,
     compaction_job_done_sink::{
         error_kind::ErrorKindCompactionJobDoneSinkWrapper,
+        lag::CompactionLagCompactionJobDoneSinkWrapper,
         logging::LoggingCompactionJobDoneSinkWrapper, metrics::MetricsCompactionJobDoneSinkWrapper,
         outcome::CompactionJobDoneSinkToScheduler, CompactionJobDoneSink,
     },
@@ -25,9 +32,15 @@ use super::{
     compaction_jobs_source::{
         logging::LoggingCompactionJobsWrapper, metrics::MetricsCompactionJobsSourceWrapper,
         not_empty::NotEmptyCompactionJobsSourceWrapper,
+        pause_file::PauseFileCompactionJobsSourceWrapper,
         randomize_order::RandomizeOrderCompactionJobsSourcesWrapper,
-        scheduled::ScheduledCompactionJobsSource, CompactionJobsSource,
+        scheduled::ScheduledCompactionJobsSource, status::StatusCompactionJobsSourceWrapper,
+        summary::SummaryCompactionJobsSourceWrapper, CompactionJobsSource,
+    },
+    completion_webhook::{
+        http::HttpCompletionWebhook, noop::NoopCompletionWebhook, CompletionWebhook,
     },
+    concurrency_ramp::{linear::LinearConcurrencyRamp, noop::NoopConcurrencyRamp, ConcurrencyRamp},
     df_plan_exec::{
         dedicated::DedicatedDataFusionPlanExec, noop::NoopDataFusionPlanExec, DataFusionPlanExec,
     },
@@ -42,30 +55,44 @@ use super::{
         non_overlap_split::NonOverlapSplit, target_level_split::TargetLevelSplit,
         upgrade_split::UpgradeSplit,
     },
+    format_rewrite_selector::{
+        below_version::BelowVersionFormatRewriteSelector,
+        metrics::MetricsFormatRewriteSelectorWrapper, noop::NoopFormatRewriteSelector,
+        FormatRewriteSelector,
+    },
     ir_planner::{logging::LoggingIRPlannerWrapper, planner_v1::V1IRPlanner, IRPlanner},
     namespaces_source::catalog::CatalogNamespacesSource,
+    output_verifier::{
+        noop::NoopOutputVerifier, read_back::ReadBackOutputVerifier, OutputVerifier,
+    },
     parquet_file_sink::{
         dedicated::DedicatedExecParquetFileSinkWrapper, logging::LoggingParquetFileSinkWrapper,
         object_store::ObjectStoreParquetFileSink,
     },
-    parquet_files_sink::{dispatch::DispatchParquetFilesSink, ParquetFilesSink},
+    parquet_files_sink::{
+        dispatch::DispatchParquetFilesSink, metrics::MetricsParquetFilesSinkWrapper,
+        ParquetFilesSink,
+    },
     partition_files_source::{
         catalog::{CatalogPartitionFilesSource, QueryRateLimiter},
+        lag::{CompactionLag, CompactionLagPartitionFilesSourceWrapper},
+        min_age::MinAgePartitionFilesSourceWrapper,
         rate_limit::RateLimit,
         PartitionFilesSource,
     },
     partition_filter::{
         and::AndPartitionFilter, greater_matching_files::GreaterMatchingFilesPartitionFilter,
         greater_size_matching_files::GreaterSizeMatchingFilesPartitionFilter,
-        has_files::HasFilesPartitionFilter, has_matching_file::HasMatchingFilePartitionFilter,
-        logging::LoggingPartitionFilterWrapper, max_num_columns::MaxNumColumnsPartitionFilter,
-        metrics::MetricsPartitionFilterWrapper, or::OrPartitionFilter, PartitionFilter,
+        has_files::HasFilesPartitionFilter, logging::LoggingPartitionFilterWrapper,
+        max_num_columns::MaxNumColumnsPartitionFilter, metrics::MetricsPartitionFilterWrapper,
+        or::OrPartitionFilter, PartitionFilter,
     },
     partition_info_source::{sub_sources::SubSourcePartitionInfoSource, PartitionInfoSource},
     partition_source::{
         catalog::CatalogPartitionSource, logging::LoggingPartitionSourceWrapper,
         metrics::MetricsPartitionSourceWrapper,
     },
+    per_table_sizing::PerTableSizing,
     post_classification_partition_filter::{
         logging::LoggingPostClassificationFilterWrapper,
         metrics::MetricsPostClassificationFilterWrapper, possible_progress::PossibleProgressFilter,
@@ -73,12 +100,21 @@ use super::{
     },
     round_info_source::{LevelBasedRoundInfo, LoggingRoundInfoWrapper, RoundInfoSource},
     round_split::many_files::ManyFilesRoundSplit,
+    row_counts::metrics::MetricsRowCountTracker,
     scratchpad::{noop::NoopScratchpadGen, prod::ProdScratchpadGen, ScratchpadGen},
+    single_oversize_file::{
+        noop::NoopSingleOversizeFileSelector, policy::PolicySingleOversizeFileSelector,
+        SingleOversizeFileSelector,
+    },
     split_or_compact::{
         logging::LoggingSplitOrCompactWrapper, metrics::MetricsSplitOrCompactWrapper,
         split_compact::SplitCompact,
     },
+    status::{live::LiveStatusTracker, noop::NoopStatusTracker, StatusTracker},
     tables_source::catalog::CatalogTablesSource,
+    zero_row_output::{
+        noop::NoopZeroRowOutputCheck, policy::PolicyZeroRowOutputCheck, ZeroRowOutputCheck,
+    },
     Components,
 };
 
@@ -91,13 +127,31 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         Arc::clone(&config.metric_registry),
         config.shadow_mode,
     );
+    let catch_up: Arc<dyn CatchUpEstimator> = Arc::new(RollingWindowCatchUpEstimator::new(
+        Arc::clone(&config.time_provider),
+        &config.metric_registry,
+        shard_label(config),
+    ));
+    let status = make_status_tracker(config, Arc::clone(&catch_up));
+    let lag = Arc::new(CompactionLag::new(
+        &config.metric_registry,
+        shard_label(config),
+        Arc::clone(&config.time_provider),
+    ));
     let (compaction_jobs_source, commit, compaction_job_done_sink) =
-        make_jobs_source_commit_jobs_sink(config, Arc::clone(&scheduler));
+        make_jobs_source_commit_jobs_sink(
+            config,
+            Arc::clone(&scheduler),
+            Arc::clone(&status),
+            Arc::clone(&lag),
+            Arc::clone(&catch_up),
+        );
+    let partition_files_source = make_partition_files_source(config, Arc::clone(&lag));
 
     Arc::new(Components {
         compaction_job_stream: make_compaction_job_stream(config, compaction_jobs_source),
         partition_info_source: make_partition_info_source(config),
-        partition_files_source: make_partition_files_source(config),
+        partition_files_source: Arc::clone(&partition_files_source),
         round_info_source: make_round_info_source(config),
         partition_filter: make_partition_filter(config),
         compaction_job_done_sink,
@@ -112,12 +166,97 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         file_classifier: make_file_classifier(config),
         post_classification_partition_filter: make_post_classification_partition_filter(config),
         changed_files_filter: Arc::new(LoggingChangedFiles::new()),
+        format_rewrite_selector: make_format_rewrite_selector(config, partition_files_source),
+        single_oversize_file_selector: make_single_oversize_file_selector(config),
+        status,
+        row_counts: Arc::new(MetricsRowCountTracker::new(
+            &config.metric_registry,
+            shard_label(config),
+        )),
+        catch_up,
+        output_verifier: make_output_verifier(config),
+        zero_row_output_check: make_zero_row_output_check(config),
+        completion_webhook: make_completion_webhook(config),
+        partition_concurrency_ramp: make_partition_concurrency_ramp(config),
     })
 }
 
+fn make_status_tracker(
+    config: &Config,
+    catch_up: Arc<dyn CatchUpEstimator>,
+) -> Arc<dyn StatusTracker> {
+    // the status is only read by the status endpoint
+    if !config.status_endpoint {
+        return Arc::new(NoopStatusTracker::new());
+    }
+    Arc::new(
+        LiveStatusTracker::new(&config.scheduler_config, Arc::clone(&config.time_provider))
+            .with_catch_up(catch_up),
+    )
+}
+
+fn make_single_oversize_file_selector(config: &Config) -> Arc<dyn SingleOversizeFileSelector> {
+    match config.single_oversize_file_policy {
+        SingleOversizeFilePolicy::Leave => Arc::new(NoopSingleOversizeFileSelector::new()),
+        policy @ (SingleOversizeFilePolicy::Split | SingleOversizeFilePolicy::Rewrite) => Arc::new(
+            PolicySingleOversizeFileSelector::new(policy, config.max_desired_file_size_bytes),
+        ),
+    }
+}
+
+fn make_zero_row_output_check(config: &Config) -> Arc<dyn ZeroRowOutputCheck> {
+    match config.zero_row_output_policy {
+        ZeroRowOutputPolicy::DeleteInputs => Arc::new(NoopZeroRowOutputCheck::new()),
+        policy @ ZeroRowOutputPolicy::SkipAndWarn => {
+            Arc::new(PolicyZeroRowOutputCheck::new(policy))
+        }
+    }
+}
+
+fn make_completion_webhook(config: &Config) -> Arc<dyn CompletionWebhook> {
+    match &config.completion_webhook_url {
+        Some(url) => Arc::new(HttpCompletionWebhook::new(
+            url.clone(),
+            config.backoff_config.clone(),
+            Arc::clone(&config.time_provider),
+            &config.metric_registry,
+            shard_label(config),
+        )),
+        None => Arc::new(NoopCompletionWebhook::new()),
+    }
+}
+
+fn make_partition_concurrency_ramp(config: &Config) -> Arc<dyn ConcurrencyRamp> {
+    match config.partition_concurrency_warm_up {
+        Some(warm_up) => Arc::new(LinearConcurrencyRamp::new(
+            config.partition_concurrency,
+            warm_up,
+            Arc::clone(&config.time_provider),
+            &config.metric_registry,
+            shard_label(config),
+        )),
+        None => Arc::new(NoopConcurrencyRamp::new()),
+    }
+}
+
+fn make_output_verifier(config: &Config) -> Arc<dyn OutputVerifier> {
+    // there is nothing to read back when output is not written
+    if !config.verify_output || config.shadow_mode || config.simulate_without_object_store {
+        return Arc::new(NoopOutputVerifier::new());
+    }
+    Arc::new(ReadBackOutputVerifier::new(
+        config.parquet_store_real.clone(),
+        &config.metric_registry,
+        shard_label(config),
+    ))
+}
+
 fn make_jobs_source_commit_jobs_sink(
     config: &Config,
     scheduler: Arc<dyn Scheduler>,
+    status: Arc<dyn StatusTracker>,
+    lag: Arc<CompactionLag>,
+    catch_up: Arc<dyn CatchUpEstimator>,
 ) -> (
     Arc<dyn CompactionJobsSource>,
     Arc<CommitToScheduler>,
@@ -151,7 +290,13 @@ fn make_jobs_source_commit_jobs_sink(
         ))
     };
     let compaction_job_done_sink = Arc::new(LoggingCompactionJobDoneSinkWrapper::new(
-        MetricsCompactionJobDoneSinkWrapper::new(compaction_job_done_sink, &config.metric_registry),
+        MetricsCompactionJobDoneSinkWrapper::new(
+            CompactionLagCompactionJobDoneSinkWrapper::new(
+                compaction_job_done_sink,
+                Arc::clone(&lag),
+            ),
+            &config.metric_registry,
+        ),
     ));
 
     // Note: Place "not empty" wrapper at the very last so that the logging and metric wrapper work
@@ -161,10 +306,22 @@ fn make_jobs_source_commit_jobs_sink(
             RandomizeOrderCompactionJobsSourcesWrapper::new(compaction_jobs_source, 1234),
             &config.metric_registry,
         ));
+    // Note: Place the "pause file" wrapper inside the "not empty" wrapper so that the pause file is
+    // also checked while the compactor is idle.
+    let compaction_jobs_source: Arc<dyn CompactionJobsSource> = match &config.pause_file {
+        Some(path) => Arc::new(PauseFileCompactionJobsSourceWrapper::new(
+            compaction_jobs_source,
+            path.clone(),
+            Duration::from_secs(5),
+            Arc::clone(&config.time_provider),
+            &config.metric_registry,
+        )),
+        None => Arc::new(compaction_jobs_source),
+    };
     let compaction_jobs_source: Arc<dyn CompactionJobsSource> = if config.process_once {
         // do not wrap into the "not empty" filter because we do NOT wanna throttle in this case
         // but just exit early
-        Arc::new(compaction_jobs_source)
+        compaction_jobs_source
     } else {
         Arc::new(NotEmptyCompactionJobsSourceWrapper::new(
             compaction_jobs_source,
@@ -172,6 +329,12 @@ fn make_jobs_source_commit_jobs_sink(
             Arc::clone(&config.time_provider),
         ))
     };
+    // Note: Place the "status" wrapper outside the "not empty" wrapper so that idle polling does
+    // not end the last iteration reported by the status endpoint.
+    let compaction_jobs_source = Arc::new(StatusCompactionJobsSourceWrapper::new(
+        SummaryCompactionJobsSourceWrapper::new(compaction_jobs_source, lag, catch_up),
+        status,
+    ));
 
     (
         compaction_jobs_source,
@@ -187,7 +350,10 @@ fn make_compaction_job_stream(
     if config.process_once {
         Arc::new(OnceCompactionJobStream::new(compaction_jobs_source))
     } else {
-        Arc::new(EndlessCompactionJobStream::new(compaction_jobs_source))
+        Arc::new(EndlessCompactionJobStream::new(
+            compaction_jobs_source,
+            config.run_iteration_budget,
+        ))
     }
 }
 
@@ -203,33 +369,92 @@ fn make_partition_info_source(config: &Config) -> Arc<dyn PartitionInfoSource> {
     ))
 }
 
-fn make_partition_files_source(config: &Config) -> Arc<dyn PartitionFilesSource> {
+fn make_partition_files_source(
+    config: &Config,
+    lag: Arc<CompactionLag>,
+) -> Arc<dyn PartitionFilesSource> {
     match config.max_partition_fetch_queries_per_second {
-        Some(rps) => Arc::new(CatalogPartitionFilesSource::new(
-            config.backoff_config.clone(),
-            QueryRateLimiter::new(Arc::clone(&config.catalog), RateLimit::new(rps, 25)),
-        )),
-        None => Arc::new(CatalogPartitionFilesSource::new(
-            config.backoff_config.clone(),
-            Arc::clone(&config.catalog),
+        Some(rps) => wrap_partition_files_source(
+            config,
+            lag,
+            CatalogPartitionFilesSource::new(
+                config.backoff_config.clone(),
+                QueryRateLimiter::new(Arc::clone(&config.catalog), RateLimit::new(rps, 25)),
+            ),
+        ),
+        None => wrap_partition_files_source(
+            config,
+            lag,
+            CatalogPartitionFilesSource::new(
+                config.backoff_config.clone(),
+                Arc::clone(&config.catalog),
+            ),
+        ),
+    }
+}
+
+fn wrap_partition_files_source<T>(
+    config: &Config,
+    lag: Arc<CompactionLag>,
+    inner: T,
+) -> Arc<dyn PartitionFilesSource>
+where
+    T: PartitionFilesSource + 'static,
+{
+    // Note: Place the "compaction lag" wrapper inside the "min age" wrapper so that the lag
+    // includes files that are held back.
+    let source = CompactionLagPartitionFilesSourceWrapper::new(inner, lag);
+    match config.min_file_age {
+        Some(min_age) => Arc::new(MinAgePartitionFilesSourceWrapper::new(
+            source,
+            min_age,
+            Arc::clone(&config.time_provider),
+            &config.metric_registry,
         )),
+        None => Arc::new(source),
+    }
+}
+
+/// Label value identifying the shard this compactor is responsible for, or "none" if the
+/// compactor is not sharded.
+fn shard_label(config: &Config) -> String {
+    match &config.scheduler_config {
+        SchedulerConfig::Local(LocalSchedulerConfig {
+            shard_config: Some(shard_config),
+            ..
+        }) => shard_config.shard_id.to_string(),
+        SchedulerConfig::Local(_) => String::from("none"),
     }
 }
 
+/// Build a component depending on the sizing parameters for the global parameters and every
+/// table with overrides.
+///
+/// The single oversize file selector is not built per table: it only acts on partitions the
+/// compactor is otherwise stuck on, and thus always uses the global parameters.
+fn per_table_sizing<T>(config: &Config, make: impl Fn(SizingConfig) -> T) -> PerTableSizing<T> {
+    PerTableSizing::new(config.sizing(), &config.table_sizing_overrides, make)
+}
+
 fn make_round_info_source(config: &Config) -> Arc<dyn RoundInfoSource> {
-    Arc::new(LoggingRoundInfoWrapper::new(Arc::new(
-        LevelBasedRoundInfo::new(
-            config.max_num_files_per_plan,
-            config.max_compact_size_bytes(),
-        ),
-    )))
+    Arc::new(LoggingRoundInfoWrapper::new(Arc::new(per_table_sizing(
+        config,
+        |sizing| {
+            Arc::new(LevelBasedRoundInfo::new(
+                config.max_num_files_per_plan,
+                sizing.max_compact_size_bytes(),
+            )) as Arc<dyn RoundInfoSource>
+        },
+    ))))
 }
 
 // Conditions to compact this partition
 fn make_partition_filter(config: &Config) -> Arc<dyn PartitionFilter> {
     let mut partition_filters = exceptional_cases_partition_filters(config);
 
-    partition_filters.push(continue_condition_filter(config));
+    partition_filters.push(Arc::new(per_table_sizing(config, |sizing| {
+        continue_condition_filter(config, sizing)
+    })));
 
     let partition_continue_conditions = "continue_conditions";
     Arc::new(LoggingPartitionFilterWrapper::new(
@@ -254,13 +479,14 @@ fn exceptional_cases_partition_filters(config: &Config) -> Vec<Arc<dyn Partition
     partition_filters
 }
 
-fn continue_condition_filter(config: &Config) -> Arc<dyn PartitionFilter> {
-    // (Has-L0) OR            -- to avoid overlapped files
+fn continue_condition_filter(config: &Config, sizing: SizingConfig) -> Arc<dyn PartitionFilter> {
+    // (num(L0) >= M) OR      -- to avoid overlapped files
     // (num(L1) > N) OR       -- to avoid many files
     // (total_size(L1) > max_desired_file_size)  -- to avoid compact and than split
     Arc::new(OrPartitionFilter::new(vec![
-        Arc::new(HasMatchingFilePartitionFilter::new(
+        Arc::new(GreaterMatchingFilesPartitionFilter::new(
             LevelRangeFileFilter::new(CompactionLevel::Initial..=CompactionLevel::Initial),
+            config.min_num_l0_files_to_compact.get(),
         )),
         Arc::new(GreaterMatchingFilesPartitionFilter::new(
             LevelRangeFileFilter::new(
@@ -272,24 +498,34 @@ fn continue_condition_filter(config: &Config) -> Arc<dyn PartitionFilter> {
             LevelRangeFileFilter::new(
                 CompactionLevel::FileNonOverlapped..=CompactionLevel::FileNonOverlapped,
             ),
-            config.max_desired_file_size_bytes,
+            sizing.max_desired_file_size_bytes,
         )),
     ]))
 }
 
 fn make_ir_planner(config: &Config) -> Arc<dyn IRPlanner> {
-    Arc::new(LoggingIRPlannerWrapper::new(V1IRPlanner::new(
-        config.max_desired_file_size_bytes,
-        config.percentage_max_file_size,
-        config.split_percentage,
+    Arc::new(LoggingIRPlannerWrapper::new(per_table_sizing(
+        config,
+        |sizing| {
+            Arc::new(V1IRPlanner::new(
+                sizing.max_desired_file_size_bytes,
+                sizing.percentage_max_file_size,
+                sizing.split_percentage,
+            )) as Arc<dyn IRPlanner>
+        },
     )))
 }
 
 fn make_df_planner(config: &Config) -> Arc<dyn DataFusionPlanner> {
-    Arc::new(V1DataFusionPlanner::new(
-        config.parquet_store_scratchpad.clone(),
-        Arc::clone(&config.exec),
-    ))
+    Arc::new(per_table_sizing(config, |sizing| {
+        Arc::new(V1DataFusionPlanner::new(
+            config.parquet_store_scratchpad.clone(),
+            Arc::clone(&config.exec),
+            config.input_read_concurrency,
+            sizing.max_compact_size_bytes(),
+            config.exec_target_partitions,
+        )) as Arc<dyn DataFusionPlanner>
+    }))
 }
 
 fn make_df_plan_exec(config: &Config) -> Arc<dyn DataFusionPlanExec> {
@@ -314,7 +550,11 @@ fn make_parquet_files_sink(config: &Config) -> Arc<dyn ParquetFilesSink> {
                 Arc::clone(&config.exec),
             ),
         ));
-        Arc::new(DispatchParquetFilesSink::new(parquet_file_sink))
+        Arc::new(MetricsParquetFilesSinkWrapper::new(
+            DispatchParquetFilesSink::new(parquet_file_sink),
+            &config.metric_registry,
+            shard_label(config),
+        ))
     }
 }
 
@@ -341,20 +581,49 @@ fn make_scratchpad_gen(config: &Config) -> Arc<dyn ScratchpadGen> {
 
 fn make_file_classifier(config: &Config) -> Arc<dyn FileClassifier> {
     Arc::new(LoggingFileClassifierWrapper::new(Arc::new(
-        SplitBasedFileClassifier::new(
-            TargetLevelSplit::new(),
-            NonOverlapSplit::new(config.max_desired_file_size_bytes / 20), // rewrite non-overlapping files up to 5% of max
-            UpgradeSplit::new(config.max_desired_file_size_bytes),
-            LoggingSplitOrCompactWrapper::new(MetricsSplitOrCompactWrapper::new(
-                SplitCompact::new(
-                    config.max_num_files_per_plan,
-                    config.max_compact_size_bytes(),
-                    config.max_desired_file_size_bytes,
+        per_table_sizing(config, |sizing| {
+            Arc::new(SplitBasedFileClassifier::new(
+                TargetLevelSplit::new(),
+                NonOverlapSplit::new(sizing.max_desired_file_size_bytes / 20), // rewrite non-overlapping files up to 5% of max
+                UpgradeSplit::new(sizing.max_desired_file_size_bytes),
+                LoggingSplitOrCompactWrapper::new(MetricsSplitOrCompactWrapper::new(
+                    SplitCompact::new(
+                        config.max_num_files_per_plan,
+                        sizing.max_compact_size_bytes(),
+                        sizing.max_desired_file_size_bytes,
+                    ),
+                    &config.metric_registry,
+                )),
+            )) as Arc<dyn FileClassifier>
+        }),
+    )))
+}
+
+fn make_format_rewrite_selector(
+    config: &Config,
+    partition_files_source: Arc<dyn PartitionFilesSource>,
+) -> Arc<dyn FormatRewriteSelector> {
+    match config.rewrite_below_format_version {
+        // there are no footers to read when simulating
+        Some(version) if !config.simulate_without_object_store => {
+            let current = PARQUET_WRITER_VERSION.as_num();
+            assert!(
+                version <= current,
+                "rewrite_below_format_version ({version}) must not be greater than the parquet \
+                 writer version of this compactor ({current})",
+            );
+
+            Arc::new(MetricsFormatRewriteSelectorWrapper::new(
+                BelowVersionFormatRewriteSelector::new(
+                    partition_files_source,
+                    config.parquet_store_real.clone(),
+                    version,
                 ),
                 &config.metric_registry,
-            )),
-        ),
-    )))
+            ))
+        }
+        _ => Arc::new(NoopFormatRewriteSelector::new()),
+    }
 }
 
 fn make_post_classification_partition_filter(
@@ -364,7 +633,10 @@ fn make_post_classification_partition_filter(
 
     Arc::new(LoggingPostClassificationFilterWrapper::new(
         MetricsPostClassificationFilterWrapper::new(
-            PossibleProgressFilter::new(config.max_compact_size_bytes()),
+            per_table_sizing(config, |sizing| {
+                Arc::new(PossibleProgressFilter::new(sizing.max_compact_size_bytes()))
+                    as Arc<dyn PostClassificationPartitionFilter>
+            }),
             &config.metric_registry,
             partition_resource_limit_conditions,
         ),
@@ -197,25 +197,32 @@ impl IRPlanner for V1IRPlanner {
             self.percentage_max_file_size,
         );
 
+        let orders = chunk_orders(&files, target_level);
         let files = files
             .into_iter()
+            .zip(orders)
             .zip(object_store_ids)
             .zip(paths)
-            .map(|((file, object_store_id), path)| {
-                let order = order(file.compaction_level, target_level, file.max_l0_created_at);
-                FileIR {
-                    file: ParquetFile {
-                        object_store_id,
-                        ..file
-                    },
-                    path,
-                    order,
-                }
+            .map(|(((file, order), object_store_id), path)| FileIR {
+                file: ParquetFile {
+                    object_store_id,
+                    ..file
+                },
+                path,
+                order,
             })
             .collect::<Vec<_>>();
 
         // Build logical compact plan
-        if total_size <= small_cutoff_bytes || reason == CompactReason::ManySmallFiles {
+        // A format rewrite keeps the file as is, however large it is.
+        if total_size <= small_cutoff_bytes
+            || matches!(
+                reason,
+                CompactReason::ManySmallFiles
+                    | CompactReason::FormatRewrite
+                    | CompactReason::SingleOversizeFile
+            )
+        {
             PlanIR::Compact {
                 files,
                 target_level,
@@ -289,6 +296,31 @@ impl IRPlanner for V1IRPlanner {
     }
 }
 
+/// Order of the chunks of `files` so they can be deduplicated correctly and deterministically.
+///
+/// Files are ranked by [`order()`], with ties broken by catalog file ID (the file with the
+/// larger ID wins during deduplication). Ties happen when L0 files share the same
+/// `max_l0_created_at`, and would otherwise be broken by chunk ID, which is derived from the
+/// randomly generated object store ID of the file copy being compacted. Every chunk gets a
+/// distinct order, so compacting the same files always keeps the same rows.
+fn chunk_orders(files: &[ParquetFile], target_level: CompactionLevel) -> Vec<ChunkOrder> {
+    let mut ranked = files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let order = order(file.compaction_level, target_level, file.max_l0_created_at);
+            (order, file.id, i)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_unstable();
+
+    let mut orders = vec![ChunkOrder::MIN; files.len()];
+    for (rank, (_, _, i)) in ranked.into_iter().enumerate() {
+        orders[i] = ChunkOrder::new(rank as i64);
+    }
+    orders
+}
+
 // Order of the chunk so they can be deduplicated correctly
 fn order(
     compaction_level: CompactionLevel,
@@ -335,6 +367,7 @@ mod tests {
     use super::*;
 
     use data_types::TimestampMinMax;
+    use iox_tests::ParquetFileBuilder;
 
     #[test]
     fn test_cutoff_bytes() {
@@ -351,6 +384,39 @@ mod tests {
         assert_eq!(large, 160);
     }
 
+    #[test]
+    fn test_chunk_orders_break_ties_by_file_id() {
+        let l1 = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::FileNonOverlapped)
+            .with_max_l0_created_at(5)
+            .build();
+        let l0_a = ParquetFileBuilder::new(2)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_max_l0_created_at(10)
+            .build();
+        let l0_b = ParquetFileBuilder::new(3)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_max_l0_created_at(10)
+            .build();
+        let l0_c = ParquetFileBuilder::new(4)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_max_l0_created_at(7)
+            .build();
+
+        let files = vec![l0_b.clone(), l1.clone(), l0_a.clone(), l0_c.clone()];
+        let orders = chunk_orders(&files, CompactionLevel::FileNonOverlapped);
+        assert_eq!(
+            orders,
+            [3, 0, 2, 1].map(ChunkOrder::new),
+            "L1 first, then L0s by max_l0_created_at, then by file ID"
+        );
+
+        // The order of a file does not depend on the order the files are listed in
+        let files = vec![l0_c, l0_a, l1, l0_b];
+        let orders = chunk_orders(&files, CompactionLevel::FileNonOverlapped);
+        assert_eq!(orders, [1, 2, 0, 3].map(ChunkOrder::new));
+    }
+
     #[test]
     fn test_compute_split_time() {
         let min_time = 1;
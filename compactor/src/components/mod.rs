@@ -1,45 +1,64 @@
 use std::sync::Arc;
 
+use self::catch_up::CatchUpEstimator;
+use self::completion_webhook::CompletionWebhook;
+use self::concurrency_ramp::ConcurrencyRamp;
+use self::output_verifier::OutputVerifier;
+use self::row_counts::RowCountTracker;
+use self::single_oversize_file::SingleOversizeFileSelector;
+use self::zero_row_output::ZeroRowOutputCheck;
 use self::{
     changed_files_filter::ChangedFilesFilter, commit::CommitToScheduler,
     compaction_job_done_sink::CompactionJobDoneSink, compaction_job_stream::CompactionJobStream,
     df_plan_exec::DataFusionPlanExec, df_planner::DataFusionPlanner, divide_initial::DivideInitial,
-    file_classifier::FileClassifier, ir_planner::IRPlanner, parquet_files_sink::ParquetFilesSink,
+    file_classifier::FileClassifier, format_rewrite_selector::FormatRewriteSelector,
+    ir_planner::IRPlanner, parquet_files_sink::ParquetFilesSink,
     partition_files_source::PartitionFilesSource, partition_filter::PartitionFilter,
     partition_info_source::PartitionInfoSource,
     post_classification_partition_filter::PostClassificationPartitionFilter,
     round_info_source::RoundInfoSource, round_split::RoundSplit, scratchpad::ScratchpadGen,
+    status::StatusTracker,
 };
 
+pub mod catch_up;
 pub mod changed_files_filter;
 pub mod columns_source;
 pub(crate) mod commit;
 pub mod compaction_job_done_sink;
 pub mod compaction_job_stream;
 pub mod compaction_jobs_source;
+pub mod completion_webhook;
+pub mod concurrency_ramp;
 pub mod df_plan_exec;
 pub mod df_planner;
 pub mod divide_initial;
 pub mod file_classifier;
 pub mod file_filter;
 pub mod files_split;
+pub mod format_rewrite_selector;
 pub mod hardcoded;
 pub mod ir_planner;
 pub mod namespaces_source;
+pub mod output_verifier;
 pub mod parquet_file_sink;
 pub mod parquet_files_sink;
 pub mod partition_files_source;
 pub mod partition_filter;
 pub mod partition_info_source;
 pub mod partition_source;
+pub mod per_table_sizing;
 pub mod post_classification_partition_filter;
 pub mod report;
 pub mod round_info_source;
 pub mod round_split;
+pub mod row_counts;
 pub mod scratchpad;
+pub mod single_oversize_file;
 pub mod split_or_compact;
+pub mod status;
 pub mod tables_source;
 pub mod timeout;
+pub mod zero_row_output;
 
 /// Pluggable system to determine compactor behavior. Please see
 /// [Crate Level Documentation](crate) for more details on the
@@ -80,4 +99,22 @@ pub struct Components {
     pub file_classifier: Arc<dyn FileClassifier>,
     /// Check for other processes modifying files.
     pub changed_files_filter: Arc<dyn ChangedFilesFilter>,
+    /// Select files to rewrite because of their Parquet format once a partition is compacted.
+    pub format_rewrite_selector: Arc<dyn FormatRewriteSelector>,
+    /// Select the single oversize file of a partition to split or rewrite.
+    pub single_oversize_file_selector: Arc<dyn SingleOversizeFileSelector>,
+    /// Live state reported by the status endpoint.
+    pub status: Arc<dyn StatusTracker>,
+    /// Count the rows read and written by compaction.
+    pub row_counts: Arc<dyn RowCountTracker>,
+    /// Estimate when the compactor will have caught up with its L0 backlog.
+    pub catch_up: Arc<dyn CatchUpEstimator>,
+    /// Check the output files before they are committed.
+    pub output_verifier: Arc<dyn OutputVerifier>,
+    /// Decide whether a result without output rows may be committed.
+    pub zero_row_output_check: Arc<dyn ZeroRowOutputCheck>,
+    /// Notify an external webhook once a partition is compacted.
+    pub completion_webhook: Arc<dyn CompletionWebhook>,
+    /// Gradually raise the partition concurrency after startup.
+    pub partition_concurrency_ramp: Arc<dyn ConcurrencyRamp>,
 }
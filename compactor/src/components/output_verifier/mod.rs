@@ -0,0 +1,21 @@
+//! Verification of the Parquet files written by compaction.
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use data_types::ParquetFileParams;
+
+use crate::error::DynError;
+
+pub mod noop;
+pub mod read_back;
+
+/// Checks the files created by compaction before they are committed to the catalog, which soft
+/// deletes the input files.
+#[async_trait]
+pub trait OutputVerifier: Debug + Display + Send + Sync {
+    /// Verify the files described by `created` before they are committed.
+    ///
+    /// Returns an error for the first file that cannot be read or does not match, in which case
+    /// the compaction result must not be committed.
+    async fn verify(&self, created: &[ParquetFileParams]) -> Result<(), DynError>;
+}
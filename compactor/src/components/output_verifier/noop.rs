@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use data_types::ParquetFileParams;
+
+use crate::error::DynError;
+
+use super::OutputVerifier;
+
+/// Accepts all files without checking them.
+#[derive(Debug, Default)]
+pub struct NoopOutputVerifier;
+
+impl NoopOutputVerifier {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopOutputVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+#[async_trait]
+impl OutputVerifier for NoopOutputVerifier {
+    async fn verify(&self, _created: &[ParquetFileParams]) -> Result<(), DynError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopOutputVerifier::new().to_string(), "noop");
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let params: ParquetFileParams = ParquetFileBuilder::new(1).build().into();
+        NoopOutputVerifier::new().verify(&[params]).await.unwrap();
+    }
+}
@@ -0,0 +1,272 @@
+use std::{borrow::Cow, fmt::Display};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use data_types::ParquetFileParams;
+use datafusion::{
+    arrow::{
+        array::{Array, TimestampNanosecondArray},
+        compute::{max, min},
+    },
+    parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ProjectionMask},
+};
+use metric::{Registry, U64Counter};
+use observability_deps::tracing::warn;
+use parquet_file::{storage::ParquetStorage, ParquetFilePath};
+use schema::TIME_COLUMN_NAME;
+
+use crate::error::{DynError, ErrorKind, SimpleError};
+
+use super::OutputVerifier;
+
+const METRIC_NAME_VERIFIED: &str = "iox_compactor_output_files_verified";
+const METRIC_NAME_FAILED: &str = "iox_compactor_output_verification_failed";
+
+/// Row count and time range of a Parquet file, as read from its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStats {
+    file_size_bytes: i64,
+    row_count: i64,
+    min_time: Option<i64>,
+    max_time: Option<i64>,
+}
+
+/// Re-reads the files created by compaction and checks they match their catalog records.
+///
+/// Each output file is read back from the object store and its row count and time range are
+/// compared to the values about to be committed. This catches writer bugs while the input files
+/// are still around, at the cost of reading every output file once more.
+#[derive(Debug)]
+pub struct ReadBackOutputVerifier {
+    store: ParquetStorage,
+    verified: U64Counter,
+    failed: U64Counter,
+}
+
+impl ReadBackOutputVerifier {
+    /// Create a new verifier reading files from `store`, reporting to `registry` for the given
+    /// `shard` label.
+    pub fn new(store: ParquetStorage, registry: &Registry, shard: String) -> Self {
+        let attributes = [("shard", Cow::from(shard))];
+
+        let verified = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_VERIFIED,
+                "Number of compaction output files that were read back and matched their catalog record",
+            )
+            .recorder(attributes.clone());
+        let failed = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_FAILED,
+                "Number of compaction output files that did not match their catalog record",
+            )
+            .recorder(attributes);
+
+        Self {
+            store,
+            verified,
+            failed,
+        }
+    }
+}
+
+#[async_trait]
+impl OutputVerifier for ReadBackOutputVerifier {
+    async fn verify(&self, created: &[ParquetFileParams]) -> Result<(), DynError> {
+        for params in created {
+            let path = ParquetFilePath::from(params);
+            let data = self
+                .store
+                .object_store()
+                .get(&path.object_store_path())
+                .await?
+                .bytes()
+                .await?;
+
+            let res = tokio::task::spawn_blocking(move || read_stats(data))
+                .await
+                .map_err(|e| Box::new(e) as DynError)
+                .and_then(|res| res)
+                .and_then(|stats| check(params, stats));
+
+            if let Err(e) = res {
+                warn!(
+                    partition_id = %params.partition_id,
+                    object_store_id = %params.object_store_id,
+                    %e,
+                    "compaction output file failed verification",
+                );
+                self.failed.inc(1);
+                return Err(e);
+            }
+            self.verified.inc(1);
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ReadBackOutputVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "read_back")
+    }
+}
+
+/// Decode the time column of the Parquet file in `data`.
+fn read_stats(data: Bytes) -> Result<FileStats, DynError> {
+    let file_size_bytes = data.len() as i64;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(data)?;
+    let mask = ProjectionMask::columns(builder.parquet_schema(), [TIME_COLUMN_NAME]);
+
+    let mut stats = FileStats {
+        file_size_bytes,
+        row_count: 0,
+        min_time: None,
+        max_time: None,
+    };
+    for batch in builder.with_projection(mask).build()? {
+        let batch = batch?;
+        let time = batch
+            .column_by_name(TIME_COLUMN_NAME)
+            .and_then(|c| c.as_any().downcast_ref::<TimestampNanosecondArray>())
+            .ok_or_else(|| {
+                SimpleError::new(
+                    ErrorKind::Unknown,
+                    "compaction output file has no nanosecond time column",
+                )
+            })?;
+
+        stats.row_count += batch.num_rows() as i64;
+        stats.min_time = match (stats.min_time, min(time)) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        stats.max_time = match (stats.max_time, max(time)) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+
+    Ok(stats)
+}
+
+/// Compare the `stats` read from a file with its catalog record `params`.
+fn check(params: &ParquetFileParams, stats: FileStats) -> Result<(), DynError> {
+    let expected = FileStats {
+        file_size_bytes: params.file_size_bytes,
+        row_count: params.row_count,
+        min_time: Some(params.min_time.get()),
+        max_time: Some(params.max_time.get()),
+    };
+
+    if stats == expected {
+        return Ok(());
+    }
+
+    Err(Box::new(SimpleError::new(
+        ErrorKind::Unknown,
+        format!(
+            "compaction output file {} does not match its catalog record: \
+             read {stats:?}, expected {expected:?}",
+            params.object_store_id
+        ),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use data_types::Timestamp;
+    use datafusion::arrow::{
+        array::{ArrayRef, Int64Array},
+        record_batch::RecordBatch,
+    };
+    use datafusion::parquet::arrow::ArrowWriter;
+    use iox_tests::ParquetFileBuilder;
+    use metric::{Attributes, Metric};
+    use object_store::memory::InMemory;
+    use parquet_file::storage::StorageId;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_verify() {
+        let registry = Registry::new();
+        let store = ParquetStorage::new(Arc::new(InMemory::new()), StorageId::from("test"));
+        let verifier = ReadBackOutputVerifier::new(store.clone(), &registry, "1".to_owned());
+
+        let data = parquet_bytes(&[30, 10, 20]);
+        let params = ParquetFileParams {
+            file_size_bytes: data.len() as i64,
+            row_count: 3,
+            min_time: Timestamp::new(10),
+            max_time: Timestamp::new(30),
+            ..ParquetFileBuilder::new(1).build().into()
+        };
+        store
+            .object_store()
+            .put(
+                &ParquetFilePath::from(&params).object_store_path(),
+                data.clone(),
+            )
+            .await
+            .unwrap();
+
+        verifier.verify(&[params.clone()]).await.unwrap();
+        assert_eq!(metric_value(&registry, METRIC_NAME_VERIFIED), 1);
+        assert_eq!(metric_value(&registry, METRIC_NAME_FAILED), 0);
+
+        let wrong_row_count = ParquetFileParams {
+            row_count: 4,
+            ..params.clone()
+        };
+        let err = verifier.verify(&[wrong_row_count]).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("does not match its catalog record"),
+            "unexpected error: {err}"
+        );
+
+        let wrong_max_time = ParquetFileParams {
+            max_time: Timestamp::new(31),
+            ..params
+        };
+        verifier.verify(&[wrong_max_time]).await.unwrap_err();
+
+        assert_eq!(metric_value(&registry, METRIC_NAME_VERIFIED), 1);
+        assert_eq!(metric_value(&registry, METRIC_NAME_FAILED), 2);
+    }
+
+    #[tokio::test]
+    async fn test_verify_missing_file() {
+        let registry = Registry::new();
+        let store = ParquetStorage::new(Arc::new(InMemory::new()), StorageId::from("test"));
+        let verifier = ReadBackOutputVerifier::new(store, &registry, "1".to_owned());
+
+        let params: ParquetFileParams = ParquetFileBuilder::new(1).build().into();
+        verifier.verify(&[params]).await.unwrap_err();
+    }
+
+    fn parquet_bytes(times: &[i64]) -> Bytes {
+        let time: ArrayRef = Arc::new(TimestampNanosecondArray::from(times.to_vec()));
+        let value: ArrayRef = Arc::new(Int64Array::from(vec![1; times.len()]));
+        let batch =
+            RecordBatch::try_from_iter([(TIME_COLUMN_NAME, time), ("value", value)]).unwrap();
+
+        let mut buf = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        Bytes::from(buf)
+    }
+
+    fn metric_value(registry: &Registry, name: &'static str) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Counter>>(name)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[("shard", "1")]))
+            .expect("observer not found")
+            .fetch()
+    }
+}
@@ -0,0 +1,308 @@
+use std::{borrow::Cow, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFileParams};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use metric::{Registry, U64Counter, U64Histogram, U64HistogramOptions};
+
+use crate::{error::DynError, partition_info::PartitionInfo, plan_ir::PlanIR};
+
+use super::ParquetFilesSink;
+
+const METRIC_NAME_OUTPUT_BYTES_ESTIMATED: &str = "iox_compactor_output_bytes_estimated";
+const METRIC_NAME_OUTPUT_BYTES_ACTUAL: &str = "iox_compactor_output_bytes_actual";
+const METRIC_NAME_OUTPUT_SIZE_RATIO: &str = "iox_compactor_output_size_ratio_percent";
+
+/// Per-outcome metric recorders.
+#[derive(Debug)]
+struct OutcomeMetrics {
+    estimated_bytes: U64Counter,
+    actual_bytes: U64Counter,
+    ratio_percent: U64Histogram,
+}
+
+impl OutcomeMetrics {
+    fn new(registry: &Registry, shard: &str, outcome: &'static str) -> Self {
+        let attributes = [
+            ("shard", Cow::from(shard.to_owned())),
+            ("outcome", Cow::from(outcome)),
+        ];
+
+        let estimated_bytes = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_OUTPUT_BYTES_ESTIMATED,
+                "Estimated size of compaction output (total input file size), in bytes",
+            )
+            .recorder(attributes.clone());
+        let actual_bytes = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_OUTPUT_BYTES_ACTUAL,
+                "Actual size of the files produced by compaction, in bytes",
+            )
+            .recorder(attributes.clone());
+        let ratio_percent = registry
+            .register_metric_with_options::<U64Histogram, _>(
+                METRIC_NAME_OUTPUT_SIZE_RATIO,
+                "Actual compaction output size as a percentage of the estimate",
+                || U64HistogramOptions::new([25, 50, 75, 90, 100, 110, 125, 150, 200, u64::MAX]),
+            )
+            .recorder(attributes);
+
+        Self {
+            estimated_bytes,
+            actual_bytes,
+            ratio_percent,
+        }
+    }
+
+    fn record(&self, estimated: u64, actual: u64) {
+        self.estimated_bytes.inc(estimated);
+        self.actual_bytes.inc(actual);
+        if estimated > 0 {
+            self.ratio_percent
+                .record(actual.saturating_mul(100) / estimated);
+        }
+    }
+}
+
+/// Records how far the actual size of the compaction output deviates from
+/// the estimate (the total size of the input files) that the split logic is
+/// based on, labelled by shard and by whether the plan was a compact or a
+/// split.
+#[derive(Debug)]
+pub struct MetricsParquetFilesSinkWrapper<T>
+where
+    T: ParquetFilesSink,
+{
+    compact: OutcomeMetrics,
+    split: OutcomeMetrics,
+    inner: T,
+}
+
+impl<T> MetricsParquetFilesSinkWrapper<T>
+where
+    T: ParquetFilesSink,
+{
+    pub fn new(inner: T, registry: &Registry, shard: String) -> Self {
+        Self {
+            compact: OutcomeMetrics::new(registry, &shard, "compact"),
+            split: OutcomeMetrics::new(registry, &shard, "split"),
+            inner,
+        }
+    }
+}
+
+impl<T> Display for MetricsParquetFilesSinkWrapper<T>
+where
+    T: ParquetFilesSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "metrics({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> ParquetFilesSink for MetricsParquetFilesSinkWrapper<T>
+where
+    T: ParquetFilesSink + 'static,
+{
+    async fn stream_into_file_sink(
+        &self,
+        streams: Vec<SendableRecordBatchStream>,
+        partition_info: Arc<PartitionInfo>,
+        target_level: CompactionLevel,
+        plan_ir: &PlanIR,
+    ) -> Result<Vec<ParquetFileParams>, DynError> {
+        let created = self
+            .inner
+            .stream_into_file_sink(streams, partition_info, target_level, plan_ir)
+            .await?;
+
+        let metrics = match plan_ir {
+            PlanIR::Compact { .. } => Some(&self.compact),
+            PlanIR::Split { .. } => Some(&self.split),
+            PlanIR::None { .. } => None,
+        };
+        if let Some(metrics) = metrics {
+            let estimated = plan_ir.input_bytes().max(0) as u64;
+            let actual = created
+                .iter()
+                .map(|f| f.file_size_bytes.max(0) as u64)
+                .sum::<u64>();
+            metrics.record(estimated, actual);
+        }
+
+        Ok(created)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use data_types::ChunkOrder;
+    use iox_tests::ParquetFileBuilder;
+    use metric::{assert_counter, assert_histogram, Attributes};
+    use parquet_file::ParquetFilePath;
+
+    use crate::{
+        file_classification::{CompactReason, NoneReason, SplitReason},
+        plan_ir::FileIR,
+        test_utils::PartitionInfoBuilder,
+    };
+
+    /// Produces one output file per input file, at half the input size.
+    #[derive(Debug)]
+    struct HalvingSink;
+
+    impl Display for HalvingSink {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "halving")
+        }
+    }
+
+    #[async_trait]
+    impl ParquetFilesSink for HalvingSink {
+        async fn stream_into_file_sink(
+            &self,
+            _streams: Vec<SendableRecordBatchStream>,
+            _partition_info: Arc<PartitionInfo>,
+            _target_level: CompactionLevel,
+            plan_ir: &PlanIR,
+        ) -> Result<Vec<ParquetFileParams>, DynError> {
+            Ok(plan_ir
+                .input_parquet_files()
+                .into_iter()
+                .map(|f| ParquetFileParams {
+                    file_size_bytes: f.file_size_bytes / 2,
+                    ..f.into()
+                })
+                .collect())
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn file_ir(id: i64, file_size_bytes: i64) -> FileIR {
+        let file = ParquetFileBuilder::new(id)
+            .with_file_size_bytes(file_size_bytes)
+            .build();
+        FileIR {
+            path: ParquetFilePath::from(&file),
+            file,
+            order: ChunkOrder::new(id),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let sink = MetricsParquetFilesSinkWrapper::new(
+            HalvingSink,
+            &Registry::new(),
+            String::from("none"),
+        );
+        assert_eq!(sink.to_string(), "metrics(halving)");
+    }
+
+    #[tokio::test]
+    async fn test_records_estimated_and_actual() {
+        let registry = Registry::new();
+        let sink = MetricsParquetFilesSinkWrapper::new(HalvingSink, &registry, String::from("1"));
+        let partition_info = Arc::new(PartitionInfoBuilder::new().build());
+
+        let compact = PlanIR::Compact {
+            files: vec![file_ir(1, 100), file_ir(2, 300)],
+            target_level: CompactionLevel::FileNonOverlapped,
+            reason: CompactReason::ManySmallFiles,
+        };
+        let created = sink
+            .stream_into_file_sink(
+                vec![],
+                Arc::clone(&partition_info),
+                CompactionLevel::FileNonOverlapped,
+                &compact,
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.len(), 2);
+
+        let split = PlanIR::Split {
+            files: vec![file_ir(3, 1_000)],
+            split_times: vec![5],
+            target_level: CompactionLevel::FileNonOverlapped,
+            reason: SplitReason::ReduceOverlap,
+        };
+        sink.stream_into_file_sink(
+            vec![],
+            Arc::clone(&partition_info),
+            CompactionLevel::FileNonOverlapped,
+            &split,
+        )
+        .await
+        .unwrap();
+
+        // Plans without input files record nothing
+        let none = PlanIR::None {
+            reason: NoneReason::NoInputFiles,
+        };
+        sink.stream_into_file_sink(
+            vec![],
+            partition_info,
+            CompactionLevel::FileNonOverlapped,
+            &none,
+        )
+        .await
+        .unwrap();
+
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_OUTPUT_BYTES_ESTIMATED,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "compact")]),
+            value = 400,
+        );
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_OUTPUT_BYTES_ACTUAL,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "compact")]),
+            value = 200,
+        );
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_OUTPUT_BYTES_ESTIMATED,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "split")]),
+            value = 1_000,
+        );
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_OUTPUT_BYTES_ACTUAL,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "split")]),
+            value = 500,
+        );
+        assert_histogram!(
+            registry,
+            U64Histogram,
+            METRIC_NAME_OUTPUT_SIZE_RATIO,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "compact")]),
+            samples = 1,
+            sum = 50,
+        );
+        assert_histogram!(
+            registry,
+            U64Histogram,
+            METRIC_NAME_OUTPUT_SIZE_RATIO,
+            labels = Attributes::from(&[("shard", "1"), ("outcome", "split")]),
+            samples = 1,
+            sum = 50,
+        );
+    }
+}
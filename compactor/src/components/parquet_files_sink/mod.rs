@@ -10,6 +10,7 @@ use datafusion::physical_plan::SendableRecordBatchStream;
 use crate::{error::DynError, partition_info::PartitionInfo, plan_ir::PlanIR};
 
 pub mod dispatch;
+pub mod metrics;
 
 /// Writes streams, which corresponds to the `plan_ir.files()` to
 /// parquet files on object store, returning information about the
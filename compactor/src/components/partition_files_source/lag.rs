@@ -0,0 +1,229 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Display, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use iox_time::{Time, TimeProvider};
+use metric::{Registry, U64Gauge};
+use parking_lot::Mutex;
+
+use super::PartitionFilesSource;
+
+const METRIC_NAME_COMPACTION_LAG: &str = "iox_compactor_compaction_lag_seconds";
+
+/// Tracks the age of the oldest L0 file seen in any partition, i.e. how far behind the
+/// compactor is.
+///
+/// The oldest L0 file of each partition is updated every time the files of that partition are
+/// fetched, so partitions drop out of the calculation once a fetch finds no L0 files left, or
+/// once they were [compacted successfully](Self::evict).
+#[derive(Debug)]
+pub struct CompactionLag {
+    oldest_l0_created_at: Mutex<HashMap<PartitionId, Time>>,
+    lag_gauge: U64Gauge,
+    time_provider: Arc<dyn TimeProvider>,
+}
+
+impl CompactionLag {
+    pub fn new(registry: &Registry, shard: String, time_provider: Arc<dyn TimeProvider>) -> Self {
+        let lag_gauge = registry
+            .register_metric::<U64Gauge>(
+                METRIC_NAME_COMPACTION_LAG,
+                "Age of the oldest L0 file not yet compacted, in seconds",
+            )
+            .recorder([("shard", Cow::from(shard))]);
+
+        Self {
+            oldest_l0_created_at: Default::default(),
+            lag_gauge,
+            time_provider,
+        }
+    }
+
+    /// Record the current `files` of `partition_id`.
+    pub fn observe(&self, partition_id: PartitionId, files: &[ParquetFile]) {
+        let oldest_l0 = files
+            .iter()
+            .filter(|f| f.compaction_level == CompactionLevel::Initial)
+            .map(|f| f.created_at)
+            .min();
+
+        let mut oldest_l0_created_at = self.oldest_l0_created_at.lock();
+        match oldest_l0 {
+            Some(created_at) => {
+                oldest_l0_created_at
+                    .insert(partition_id, Time::from_timestamp_nanos(created_at.get()));
+            }
+            None => {
+                oldest_l0_created_at.remove(&partition_id);
+            }
+        }
+        self.update(&oldest_l0_created_at);
+    }
+
+    /// Forget about `partition_id`, whose L0 files were compacted.
+    pub fn evict(&self, partition_id: PartitionId) {
+        let mut oldest_l0_created_at = self.oldest_l0_created_at.lock();
+        oldest_l0_created_at.remove(&partition_id);
+        self.update(&oldest_l0_created_at);
+    }
+
+    /// The current compaction lag.
+    pub fn lag(&self) -> Duration {
+        self.calculate(&self.oldest_l0_created_at.lock())
+    }
+
+    fn calculate(&self, oldest_l0_created_at: &HashMap<PartitionId, Time>) -> Duration {
+        oldest_l0_created_at
+            .values()
+            .min()
+            .and_then(|oldest| self.time_provider.now().checked_duration_since(*oldest))
+            .unwrap_or_default()
+    }
+
+    fn update(&self, oldest_l0_created_at: &HashMap<PartitionId, Time>) {
+        self.lag_gauge
+            .set(self.calculate(oldest_l0_created_at).as_secs());
+    }
+}
+
+/// Feeds the files of every fetched partition to the [`CompactionLag`].
+#[derive(Debug)]
+pub struct CompactionLagPartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    lag: Arc<CompactionLag>,
+    inner: T,
+}
+
+impl<T> CompactionLagPartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    pub fn new(inner: T, lag: Arc<CompactionLag>) -> Self {
+        Self { lag, inner }
+    }
+}
+
+impl<T> Display for CompactionLagPartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "compaction_lag({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> PartitionFilesSource for CompactionLagPartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    async fn fetch(&self, partition_id: PartitionId) -> Vec<ParquetFile> {
+        let files = self.inner.fetch(partition_id).await;
+        self.lag.observe(partition_id, &files);
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use iox_tests::{partition_identifier, ParquetFileBuilder};
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use crate::components::partition_files_source::mock::MockPartitionFilesSource;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let source = CompactionLagPartitionFilesSourceWrapper::new(
+            MockPartitionFilesSource::new(Default::default(), Default::default()),
+            Arc::new(CompactionLag::new(
+                &Registry::new(),
+                String::from("none"),
+                Arc::new(MockProvider::new(Time::MIN)),
+            )),
+        );
+        assert_eq!(source.to_string(), "compaction_lag(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let registry = Registry::new();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+
+        let partition_id_1 = PartitionId::new(1);
+        let partition_id_2 = PartitionId::new(2);
+        let partition_identifier_1 = partition_identifier(1);
+        let partition_identifier_2 = partition_identifier(2);
+
+        // partition 1 has an L0 file created at 10s and an L1 file created at 5s
+        let f_1_1 = ParquetFileBuilder::new(1)
+            .with_partition(partition_identifier_1.clone())
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_created_at(10_000_000_000)
+            .build();
+        let f_1_2 = ParquetFileBuilder::new(2)
+            .with_partition(partition_identifier_1.clone())
+            .with_compaction_level(CompactionLevel::FileNonOverlapped)
+            .with_created_at(5_000_000_000)
+            .build();
+        // partition 2 has an L0 file created at 20s
+        let f_2_1 = ParquetFileBuilder::new(3)
+            .with_partition(partition_identifier_2.clone())
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_created_at(20_000_000_000)
+            .build();
+
+        let partition_lookup = HashMap::from([
+            (partition_id_1, partition_identifier_1),
+            (partition_id_2, partition_identifier_2),
+        ]);
+        let lag = Arc::new(CompactionLag::new(
+            &registry,
+            String::from("0"),
+            Arc::clone(&time_provider) as _,
+        ));
+        let source = CompactionLagPartitionFilesSourceWrapper::new(
+            MockPartitionFilesSource::new(partition_lookup, vec![f_1_1, f_1_2, f_2_1]),
+            Arc::clone(&lag),
+        );
+
+        assert_lag_gauge(&registry, 0);
+
+        time_provider.set(Time::from_timestamp_nanos(0) + Duration::from_secs(30));
+        source.fetch(partition_id_2).await;
+        assert_lag_gauge(&registry, 10);
+
+        // the L1 file of partition 1 does not count towards the lag
+        source.fetch(partition_id_1).await;
+        assert_lag_gauge(&registry, 20);
+        assert_eq!(lag.lag(), Duration::from_secs(20));
+
+        // unknown partition has no files and does not change the lag
+        source.fetch(PartitionId::new(3)).await;
+        assert_lag_gauge(&registry, 20);
+
+        // compacted partitions no longer count, even if their files are not fetched again
+        lag.evict(partition_id_1);
+        assert_lag_gauge(&registry, 10);
+        lag.evict(partition_id_2);
+        assert_lag_gauge(&registry, 0);
+        assert_eq!(lag.lag(), Duration::ZERO);
+    }
+
+    fn assert_lag_gauge(registry: &Registry, value: u64) {
+        let actual = registry
+            .get_instrument::<Metric<U64Gauge>>(METRIC_NAME_COMPACTION_LAG)
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("shard", "0")]))
+            .expect("failed to get observer")
+            .fetch();
+
+        assert_eq!(actual, value);
+    }
+}
@@ -0,0 +1,194 @@
+use std::{collections::HashSet, fmt::Display, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFile, ParquetFileId, PartitionId};
+use iox_time::{Time, TimeProvider};
+use metric::{Registry, U64Counter};
+use parking_lot::Mutex;
+
+use super::PartitionFilesSource;
+
+const METRIC_NAME_HELD_BACK: &str = "iox_compactor_files_held_back_by_age";
+
+/// Holds back L0 files created less than `min_age` ago, so that recent writes can settle before
+/// they are compacted.
+///
+/// Held back files are invisible to the rest of the compactor, so they are left untouched until
+/// they are old enough to be fetched for a later compaction of their partition. Files of other
+/// levels are always returned.
+///
+/// The same partition is fetched several times per compaction (e.g. again before committing), so
+/// held back files are remembered until they are returned to count each of them only once.
+#[derive(Debug)]
+pub struct MinAgePartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    inner: T,
+    min_age: Duration,
+    time_provider: Arc<dyn TimeProvider>,
+    /// Files that are held back and were already counted.
+    counted: Mutex<HashSet<ParquetFileId>>,
+    held_back: U64Counter,
+}
+
+impl<T> MinAgePartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    pub fn new(
+        inner: T,
+        min_age: Duration,
+        time_provider: Arc<dyn TimeProvider>,
+        registry: &Registry,
+    ) -> Self {
+        let held_back = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_HELD_BACK,
+                "Number of L0 files not compacted because they were younger than the minimum file age",
+            )
+            .recorder(&[]);
+
+        Self {
+            inner,
+            min_age,
+            time_provider,
+            counted: Mutex::new(HashSet::new()),
+            held_back,
+        }
+    }
+}
+
+impl<T> Display for MinAgePartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "min_age({:?}, {})", self.min_age, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> PartitionFilesSource for MinAgePartitionFilesSourceWrapper<T>
+where
+    T: PartitionFilesSource,
+{
+    async fn fetch(&self, partition_id: PartitionId) -> Vec<ParquetFile> {
+        let mut files = self.inner.fetch(partition_id).await;
+
+        let now = self.time_provider.now();
+        let mut counted = self.counted.lock();
+        let mut n_new = 0;
+        files.retain(|f| {
+            let keep = f.compaction_level != CompactionLevel::Initial
+                || now
+                    .checked_duration_since(Time::from_timestamp_nanos(f.created_at.get()))
+                    .is_some_and(|age| age >= self.min_age);
+            if keep {
+                counted.remove(&f.id);
+            } else if counted.insert(f.id) {
+                n_new += 1;
+            }
+            keep
+        });
+        self.held_back.inc(n_new);
+
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use iox_tests::{partition_identifier, ParquetFileBuilder};
+    use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
+
+    use crate::components::partition_files_source::mock::MockPartitionFilesSource;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let source = MinAgePartitionFilesSourceWrapper::new(
+            MockPartitionFilesSource::new(Default::default(), Default::default()),
+            Duration::from_secs(60),
+            Arc::new(MockProvider::new(Time::MIN)),
+            &Registry::new(),
+        );
+        assert_eq!(source.to_string(), "min_age(60s, mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let registry = Registry::new();
+        let time_provider = Arc::new(MockProvider::new(
+            Time::from_timestamp_nanos(0) + Duration::from_secs(100),
+        ));
+
+        let partition_id = PartitionId::new(1);
+        let partition = partition_identifier(1);
+
+        // an L0 file created at 10s, an L0 file created at 90s and an L1 file created at 95s
+        let old_l0 = ParquetFileBuilder::new(1)
+            .with_partition(partition.clone())
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_created_at(10_000_000_000)
+            .build();
+        let new_l0 = ParquetFileBuilder::new(2)
+            .with_partition(partition.clone())
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_created_at(90_000_000_000)
+            .build();
+        let new_l1 = ParquetFileBuilder::new(3)
+            .with_partition(partition.clone())
+            .with_compaction_level(CompactionLevel::FileNonOverlapped)
+            .with_created_at(95_000_000_000)
+            .build();
+
+        let source = MinAgePartitionFilesSourceWrapper::new(
+            MockPartitionFilesSource::new(
+                HashMap::from([(partition_id, partition)]),
+                vec![old_l0.clone(), new_l0.clone(), new_l1.clone()],
+            ),
+            Duration::from_secs(30),
+            Arc::clone(&time_provider) as _,
+            &registry,
+        );
+
+        // the recent L0 file is held back, the recent L1 file is not
+        assert_eq!(
+            source.fetch(partition_id).await,
+            vec![old_l0.clone(), new_l1.clone()]
+        );
+        assert_held_back(&registry, 1);
+
+        // fetching again while it is still too young does not count it again
+        time_provider.inc(Duration::from_secs(10));
+        assert_eq!(
+            source.fetch(partition_id).await,
+            vec![old_l0.clone(), new_l1.clone()]
+        );
+        assert_held_back(&registry, 1);
+
+        // once old enough, the L0 file is returned
+        time_provider.inc(Duration::from_secs(10));
+        assert_eq!(
+            source.fetch(partition_id).await,
+            vec![old_l0, new_l0, new_l1]
+        );
+        assert_held_back(&registry, 1);
+    }
+
+    fn assert_held_back(registry: &Registry, value: u64) {
+        let actual = registry
+            .get_instrument::<Metric<U64Counter>>(METRIC_NAME_HELD_BACK)
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch();
+
+        assert_eq!(actual, value);
+    }
+}
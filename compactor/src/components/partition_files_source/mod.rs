@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use data_types::{ParquetFile, PartitionId};
 
 pub mod catalog;
+pub mod lag;
+pub mod min_age;
 pub mod mock;
 pub mod rate_limit;
 
@@ -12,7 +14,8 @@ pub mod rate_limit;
 pub trait PartitionFilesSource: Debug + Display + Send + Sync {
     /// Get undeleted parquet files for given partition.
     ///
-    /// This MUST NOT perform any filtering (expect for the "not marked for deletion" flag).
+    /// This MUST NOT perform any filtering (expect for the "not marked for deletion" flag), other
+    /// than holding back recent files as done by [`min_age`].
     ///
     /// This method performs retries.
     async fn fetch(&self, partition: PartitionId) -> Vec<ParquetFile>;
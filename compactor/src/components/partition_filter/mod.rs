@@ -9,7 +9,6 @@ pub mod and;
 pub mod greater_matching_files;
 pub mod greater_size_matching_files;
 pub mod has_files;
-pub mod has_matching_file;
 pub mod logging;
 pub mod max_num_columns;
 pub mod metrics;
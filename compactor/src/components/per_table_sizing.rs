@@ -0,0 +1,341 @@
+//! Dispatch of the components depending on the sizing parameters to per-table instances.
+//!
+//! The sizing parameters ([`SizingConfig`]) are baked into the components using them. To honour
+//! per-table overrides, one instance of such a component is built for every table with overrides
+//! and [`PerTableSizing`] picks the instance matching the partition being compacted.
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFile};
+use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
+use parquet_file::ParquetFilePath;
+use uuid::Uuid;
+
+use crate::{
+    config::{resolve_table_sizing, SizingConfig, SizingOverrides},
+    error::DynError,
+    file_classification::{
+        CompactReason, FileClassification, FileToSplit, FilesForProgress, FilesToSplitOrCompact,
+        SplitReason,
+    },
+    partition_info::PartitionInfo,
+    plan_ir::PlanIR,
+    round_info::CompactType,
+    RoundInfo,
+};
+
+use super::{
+    df_planner::DataFusionPlanner, file_classifier::FileClassifier, ir_planner::IRPlanner,
+    partition_filter::PartitionFilter,
+    post_classification_partition_filter::PostClassificationPartitionFilter,
+    round_info_source::RoundInfoSource, Components,
+};
+
+/// Holds a component built for the global sizing parameters, and one for every table with
+/// sizing overrides.
+#[derive(Debug)]
+pub struct PerTableSizing<T> {
+    default: T,
+    /// Keyed like [`Config::table_sizing_overrides`](crate::config::Config::table_sizing_overrides).
+    overrides: HashMap<String, T>,
+}
+
+impl<T> PerTableSizing<T> {
+    /// Build the component with `make` for the `global` sizing parameters, and for the resolved
+    /// sizing parameters of every entry of `overrides`.
+    ///
+    /// For `<namespace>/<table>` keys, the table is whatever follows the last `/`.
+    pub fn new(
+        global: SizingConfig,
+        overrides: &HashMap<String, SizingOverrides>,
+        make: impl Fn(SizingConfig) -> T,
+    ) -> Self {
+        let overrides = overrides
+            .keys()
+            .map(|key| {
+                let sizing = match key.rsplit_once('/') {
+                    Some((namespace, table)) => {
+                        resolve_table_sizing(global, overrides, namespace, table)
+                    }
+                    None => overrides[key].apply(global),
+                };
+                (key.clone(), make(sizing))
+            })
+            .collect();
+
+        Self {
+            default: make(global),
+            overrides,
+        }
+    }
+
+    /// The component to use for the partition described by `partition_info`.
+    fn get(&self, partition_info: &PartitionInfo) -> &T {
+        let table = &partition_info.table.name;
+        self.overrides
+            .get(&format!("{}/{table}", partition_info.namespace_name))
+            .or_else(|| self.overrides.get(table))
+            .unwrap_or(&self.default)
+    }
+}
+
+impl<T> Display for PerTableSizing<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.overrides.is_empty() {
+            return write!(f, "{}", self.default);
+        }
+
+        let mut tables: Vec<_> = self.overrides.keys().map(String::as_str).collect();
+        tables.sort_unstable();
+        write!(
+            f,
+            "per_table_sizing({}, overrides=[{}])",
+            self.default,
+            tables.join(", ")
+        )
+    }
+}
+
+#[async_trait]
+impl RoundInfoSource for PerTableSizing<Arc<dyn RoundInfoSource>> {
+    async fn calculate(
+        &self,
+        components: Arc<Components>,
+        last_round_info: Option<Arc<RoundInfo>>,
+        partition_info: &PartitionInfo,
+        concurrency_limit: usize,
+        files: Vec<ParquetFile>,
+    ) -> Result<(Arc<RoundInfo>, bool), DynError> {
+        self.get(partition_info)
+            .calculate(
+                components,
+                last_round_info,
+                partition_info,
+                concurrency_limit,
+                files,
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl PartitionFilter for PerTableSizing<Arc<dyn PartitionFilter>> {
+    async fn apply(
+        &self,
+        partition_info: &PartitionInfo,
+        files: &[ParquetFile],
+    ) -> Result<bool, DynError> {
+        self.get(partition_info).apply(partition_info, files).await
+    }
+}
+
+impl FileClassifier for PerTableSizing<Arc<dyn FileClassifier>> {
+    fn classify(
+        &self,
+        partition_info: &PartitionInfo,
+        op: &CompactType,
+        files: Vec<ParquetFile>,
+    ) -> FileClassification {
+        self.get(partition_info).classify(partition_info, op, files)
+    }
+}
+
+#[async_trait]
+impl PostClassificationPartitionFilter
+    for PerTableSizing<Arc<dyn PostClassificationPartitionFilter>>
+{
+    async fn apply(
+        &self,
+        partition_info: &PartitionInfo,
+        files_to_make_progress_on: &FilesForProgress,
+        files_to_keep: &[ParquetFile],
+    ) -> Result<bool, DynError> {
+        self.get(partition_info)
+            .apply(partition_info, files_to_make_progress_on, files_to_keep)
+            .await
+    }
+}
+
+impl IRPlanner for PerTableSizing<Arc<dyn IRPlanner>> {
+    fn create_plans(
+        &self,
+        partition: Arc<PartitionInfo>,
+        target_level: CompactionLevel,
+        split_or_compact: FilesToSplitOrCompact,
+        object_store_ids: Vec<Uuid>,
+        object_store_paths: Vec<ParquetFilePath>,
+    ) -> Vec<PlanIR> {
+        self.get(&partition).create_plans(
+            Arc::clone(&partition),
+            target_level,
+            split_or_compact,
+            object_store_ids,
+            object_store_paths,
+        )
+    }
+
+    fn compact_plan(
+        &self,
+        files: Vec<ParquetFile>,
+        paths: Vec<ParquetFilePath>,
+        object_store_ids: Vec<Uuid>,
+        reason: CompactReason,
+        partition: Arc<PartitionInfo>,
+        target_level: CompactionLevel,
+    ) -> PlanIR {
+        self.get(&partition).compact_plan(
+            files,
+            paths,
+            object_store_ids,
+            reason,
+            Arc::clone(&partition),
+            target_level,
+        )
+    }
+
+    fn split_plan(
+        &self,
+        file_to_split: FileToSplit,
+        path: ParquetFilePath,
+        object_store_id: Uuid,
+        reason: SplitReason,
+        partition: Arc<PartitionInfo>,
+        target_level: CompactionLevel,
+    ) -> PlanIR {
+        self.get(&partition).split_plan(
+            file_to_split,
+            path,
+            object_store_id,
+            reason,
+            Arc::clone(&partition),
+            target_level,
+        )
+    }
+}
+
+#[async_trait]
+impl DataFusionPlanner for PerTableSizing<Arc<dyn DataFusionPlanner>> {
+    async fn plan(
+        &self,
+        ir: &PlanIR,
+        partition: Arc<PartitionInfo>,
+    ) -> Result<Arc<dyn ExecutionPlan>, DataFusionError> {
+        self.get(&partition).plan(ir, Arc::clone(&partition)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{
+            file_filter::level_range::LevelRangeFileFilter,
+            partition_filter::{
+                greater_size_matching_files::GreaterSizeMatchingFilesPartitionFilter,
+                has_files::HasFilesPartitionFilter,
+            },
+        },
+        test_utils::PartitionInfoBuilder,
+    };
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    const GLOBAL: SizingConfig = SizingConfig {
+        max_desired_file_size_bytes: 100,
+        percentage_max_file_size: 20,
+        split_percentage: 80,
+    };
+
+    /// A filter passing partitions with at least `max_desired_file_size_bytes` of L1 files.
+    fn filter(
+        overrides: &HashMap<String, SizingOverrides>,
+    ) -> PerTableSizing<Arc<dyn PartitionFilter>> {
+        PerTableSizing::new(GLOBAL, overrides, |sizing| {
+            Arc::new(GreaterSizeMatchingFilesPartitionFilter::new(
+                LevelRangeFileFilter::new(
+                    CompactionLevel::FileNonOverlapped..=CompactionLevel::FileNonOverlapped,
+                ),
+                sizing.max_desired_file_size_bytes,
+            )) as _
+        })
+    }
+
+    fn partition(namespace: &str, table: &str) -> PartitionInfo {
+        PartitionInfoBuilder::new()
+            .with_namespace_name(namespace)
+            .with_table_name(table)
+            .build()
+    }
+
+    #[test]
+    fn test_display() {
+        let no_overrides = PerTableSizing::new(GLOBAL, &HashMap::new(), |_| {
+            Arc::new(HasFilesPartitionFilter::new()) as Arc<dyn PartitionFilter>
+        });
+        assert_eq!(no_overrides.to_string(), "has_files");
+
+        let overrides = HashMap::from([
+            ("ns/cpu".to_owned(), SizingOverrides::default()),
+            ("mem".to_owned(), SizingOverrides::default()),
+        ]);
+        let with_overrides = PerTableSizing::new(GLOBAL, &overrides, |_| {
+            Arc::new(HasFilesPartitionFilter::new()) as Arc<dyn PartitionFilter>
+        });
+        assert_eq!(
+            with_overrides.to_string(),
+            "per_table_sizing(has_files, overrides=[mem, ns/cpu])"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_override_vs_fallback() {
+        let overrides = HashMap::from([
+            (
+                "cpu".to_owned(),
+                SizingOverrides {
+                    max_desired_file_size_bytes: Some(1_000),
+                    ..Default::default()
+                },
+            ),
+            (
+                "ns/mem".to_owned(),
+                SizingOverrides {
+                    max_desired_file_size_bytes: Some(10),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let filter = filter(&overrides);
+
+        let files = |size| {
+            [ParquetFileBuilder::new(1)
+                .with_compaction_level(CompactionLevel::FileNonOverlapped)
+                .with_file_size_bytes(size)
+                .build()]
+        };
+
+        // the global size is reached for tables without overrides ...
+        let large = files(500);
+        assert!(filter
+            .apply(&partition("ns", "disk"), &large)
+            .await
+            .unwrap());
+        // ... but not for the table with a larger desired size, in any namespace
+        assert!(!filter.apply(&partition("ns", "cpu"), &large).await.unwrap());
+        assert!(!filter
+            .apply(&partition("other", "cpu"), &large)
+            .await
+            .unwrap());
+
+        // the namespace-qualified override only applies to its namespace
+        let small = files(50);
+        assert!(filter.apply(&partition("ns", "mem"), &small).await.unwrap());
+        assert!(!filter
+            .apply(&partition("other", "mem"), &small)
+            .await
+            .unwrap());
+    }
+}
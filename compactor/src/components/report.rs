@@ -22,16 +22,28 @@ pub fn log_config(config: &Config) {
         time_provider,
         backoff_config,
         partition_concurrency,
+        partition_concurrency_warm_up,
         df_concurrency,
         partition_scratchpad_concurrency,
+        input_read_concurrency,
+        exec_target_partitions,
         max_desired_file_size_bytes,
         percentage_max_file_size,
         split_percentage,
+        table_sizing_overrides,
         partition_timeout,
+        run_iteration_budget,
+        min_file_age,
+        rewrite_below_format_version,
+        single_oversize_file_policy,
+        verify_output,
+        zero_row_output_policy,
         shadow_mode,
         enable_scratchpad,
+        min_num_l0_files_to_compact,
         min_num_l1_files_to_compact,
         process_once,
+        pause_file,
         parquet_files_sink_override,
         simulate_without_object_store,
         all_errors_are_fatal,
@@ -40,6 +52,8 @@ pub fn log_config(config: &Config) {
         max_partition_fetch_queries_per_second,
         gossip_bind_address,
         gossip_seeds,
+        status_endpoint,
+        completion_webhook_url,
     } = &config;
 
     let parquet_files_sink_override = parquet_files_sink_override
@@ -56,16 +70,28 @@ pub fn log_config(config: &Config) {
         %time_provider,
         ?backoff_config,
         partition_concurrency=partition_concurrency.get(),
+        partition_concurrency_warm_up_secs=?partition_concurrency_warm_up.map(|d| d.as_secs_f32()),
         df_concurrency=df_concurrency.get(),
         partition_scratchpad_concurrency=partition_scratchpad_concurrency.get(),
+        input_read_concurrency=input_read_concurrency.get(),
+        exec_target_partitions=?exec_target_partitions.map(|n| n.get()),
         max_desired_file_size_bytes,
         percentage_max_file_size,
         split_percentage,
+        ?table_sizing_overrides,
         partition_timeout_secs=partition_timeout.as_secs_f32(),
+        run_iteration_budget_secs=?run_iteration_budget.map(|d| d.as_secs_f32()),
+        min_file_age_secs=?min_file_age.map(|d| d.as_secs_f32()),
+        ?rewrite_below_format_version,
+        %single_oversize_file_policy,
+        verify_output,
+        %zero_row_output_policy,
         shadow_mode,
         enable_scratchpad,
+        min_num_l0_files_to_compact = min_num_l0_files_to_compact.get(),
         min_num_l1_files_to_compact,
         process_once,
+        ?pause_file,
         simulate_without_object_store,
         %parquet_files_sink_override,
         all_errors_are_fatal,
@@ -74,6 +100,8 @@ pub fn log_config(config: &Config) {
         max_partition_fetch_queries_per_second,
         ?gossip_bind_address,
         ?gossip_seeds,
+        status_endpoint,
+        ?completion_webhook_url,
         "config",
     );
 }
@@ -99,6 +127,15 @@ pub fn log_components(components: &Components) {
         scratchpad_gen,
         file_classifier,
         changed_files_filter,
+        format_rewrite_selector,
+        single_oversize_file_selector,
+        status,
+        row_counts,
+        catch_up,
+        output_verifier,
+        zero_row_output_check,
+        completion_webhook,
+        partition_concurrency_ramp,
     } = components;
 
     info!(
@@ -119,6 +156,15 @@ pub fn log_components(components: &Components) {
         %scratchpad_gen,
         %file_classifier,
         %changed_files_filter,
+        %format_rewrite_selector,
+        %single_oversize_file_selector,
+        %status,
+        %row_counts,
+        %catch_up,
+        %output_verifier,
+        %zero_row_output_check,
+        %completion_webhook,
+        %partition_concurrency_ramp,
         "component setup",
     );
 }
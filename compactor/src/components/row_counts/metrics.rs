@@ -0,0 +1,148 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Display};
+
+use data_types::{ParquetFile, ParquetFileParams, PartitionId};
+use metric::{Registry, U64Counter};
+use parking_lot::Mutex;
+
+use super::{PartitionRowCounts, RowCountTracker};
+
+const METRIC_NAME_ROWS_READ: &str = "iox_compactor_rows_read";
+const METRIC_NAME_ROWS_WRITTEN: &str = "iox_compactor_rows_written";
+const METRIC_NAME_ROWS_DROPPED: &str = "iox_compactor_rows_dropped";
+
+/// Counts the rows read and written by each commit of the compactor, labelled by shard.
+///
+/// Files that are only upgraded to another level are not rewritten and are not counted.
+#[derive(Debug)]
+pub struct MetricsRowCountTracker {
+    rows_read: U64Counter,
+    rows_written: U64Counter,
+    rows_dropped: U64Counter,
+    partitions: Mutex<HashMap<PartitionId, PartitionRowCounts>>,
+}
+
+impl MetricsRowCountTracker {
+    /// Create a new tracker reporting to `registry` for the given `shard` label.
+    pub fn new(registry: &Registry, shard: String) -> Self {
+        let attributes = [("shard", Cow::from(shard))];
+
+        let rows_read = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_ROWS_READ,
+                "Number of rows of the files rewritten by compaction",
+            )
+            .recorder(attributes.clone());
+        let rows_written = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_ROWS_WRITTEN,
+                "Number of rows of the files created by compaction",
+            )
+            .recorder(attributes.clone());
+        let rows_dropped = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_ROWS_DROPPED,
+                "Number of rows removed by compaction through deduplication",
+            )
+            .recorder(attributes);
+
+        Self {
+            rows_read,
+            rows_written,
+            rows_dropped,
+            partitions: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl RowCountTracker for MetricsRowCountTracker {
+    fn record(
+        &self,
+        partition_id: PartitionId,
+        deleted: &[ParquetFile],
+        created: &[ParquetFileParams],
+    ) {
+        let counts = PartitionRowCounts {
+            rows_read: deleted.iter().map(|f| f.row_count as u64).sum(),
+            rows_written: created.iter().map(|f| f.row_count as u64).sum(),
+        };
+
+        self.rows_read.inc(counts.rows_read);
+        self.rows_written.inc(counts.rows_written);
+        self.rows_dropped.inc(counts.rows_dropped());
+
+        let mut partitions = self.partitions.lock();
+        let total = partitions.entry(partition_id).or_default();
+        total.rows_read += counts.rows_read;
+        total.rows_written += counts.rows_written;
+    }
+
+    fn finish(&self, partition_id: PartitionId) -> PartitionRowCounts {
+        self.partitions
+            .lock()
+            .remove(&partition_id)
+            .unwrap_or_default()
+    }
+}
+
+impl Display for MetricsRowCountTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row_counts")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+    use metric::{Attributes, Metric};
+
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let registry = Registry::new();
+        let tracker = MetricsRowCountTracker::new(&registry, "1".to_owned());
+
+        let p1 = PartitionId::new(1);
+        let p2 = PartitionId::new(2);
+        let file = |id, row_count| {
+            ParquetFileBuilder::new(id)
+                .with_row_count(row_count)
+                .build()
+        };
+        let params = |row_count| ParquetFileParams {
+            row_count,
+            ..file(100, 0).into()
+        };
+
+        tracker.record(p1, &[file(1, 10), file(2, 20)], &[params(25)]);
+        tracker.record(p1, &[file(3, 25)], &[params(10), params(15)]);
+        tracker.record(p2, &[file(4, 7)], &[params(2)]);
+
+        assert_eq!(metric_value(&registry, METRIC_NAME_ROWS_READ), 62);
+        assert_eq!(metric_value(&registry, METRIC_NAME_ROWS_WRITTEN), 52);
+        assert_eq!(metric_value(&registry, METRIC_NAME_ROWS_DROPPED), 10);
+
+        let counts = tracker.finish(p1);
+        assert_eq!(
+            counts,
+            PartitionRowCounts {
+                rows_read: 55,
+                rows_written: 50,
+            }
+        );
+        assert_eq!(counts.rows_dropped(), 5);
+        assert_eq!(tracker.finish(p2).rows_dropped(), 5);
+
+        // finishing forgets the partition
+        assert_eq!(tracker.finish(p1), PartitionRowCounts::default());
+    }
+
+    fn metric_value(registry: &Registry, name: &'static str) -> u64 {
+        registry
+            .get_instrument::<Metric<U64Counter>>(name)
+            .expect("instrument not found")
+            .get_observer(&Attributes::from(&[("shard", "1")]))
+            .expect("observer not found")
+            .fetch()
+    }
+}
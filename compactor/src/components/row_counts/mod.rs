@@ -0,0 +1,41 @@
+//! Tracking of the rows read and written by compaction.
+//!
+//! Compaction writes fewer rows than it reads when it removes duplicates. The
+//! difference is exposed as metrics and summarized per partition in the logs; a sudden spike can
+//! indicate duplicate-heavy ingest.
+use std::fmt::{Debug, Display};
+
+use data_types::{ParquetFile, ParquetFileParams, PartitionId};
+
+pub mod metrics;
+pub mod noop;
+
+/// Rows read and written while compacting one partition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartitionRowCounts {
+    /// Rows of the files that were rewritten.
+    pub rows_read: u64,
+    /// Rows of the files that replaced them.
+    pub rows_written: u64,
+}
+
+impl PartitionRowCounts {
+    /// Rows removed by deduplication.
+    pub fn rows_dropped(&self) -> u64 {
+        self.rows_read.saturating_sub(self.rows_written)
+    }
+}
+
+/// Counts the rows read and written by each commit of the compactor.
+pub trait RowCountTracker: Debug + Display + Send + Sync {
+    /// Record a commit of `partition_id` replacing `deleted` with `created`.
+    fn record(
+        &self,
+        partition_id: PartitionId,
+        deleted: &[ParquetFile],
+        created: &[ParquetFileParams],
+    );
+
+    /// Return the totals recorded for `partition_id`, and forget about it.
+    fn finish(&self, partition_id: PartitionId) -> PartitionRowCounts;
+}
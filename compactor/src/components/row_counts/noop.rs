@@ -0,0 +1,57 @@
+use std::fmt::Display;
+
+use data_types::{ParquetFile, ParquetFileParams, PartitionId};
+
+use super::{PartitionRowCounts, RowCountTracker};
+
+/// Does not count any rows.
+#[derive(Debug, Default)]
+pub struct NoopRowCountTracker;
+
+impl NoopRowCountTracker {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopRowCountTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+impl RowCountTracker for NoopRowCountTracker {
+    fn record(
+        &self,
+        _partition_id: PartitionId,
+        _deleted: &[ParquetFile],
+        _created: &[ParquetFileParams],
+    ) {
+    }
+
+    fn finish(&self, _partition_id: PartitionId) -> PartitionRowCounts {
+        PartitionRowCounts::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopRowCountTracker::new().to_string(), "noop");
+    }
+
+    #[test]
+    fn test_record() {
+        let tracker = NoopRowCountTracker::new();
+        let partition_id = PartitionId::new(1);
+        let file = ParquetFileBuilder::new(1).with_row_count(10).build();
+
+        tracker.record(partition_id, &[file], &[]);
+        assert_eq!(tracker.finish(partition_id), PartitionRowCounts::default());
+    }
+}
@@ -0,0 +1,26 @@
+//! Handling of partitions that consist of a single oversize L0 file.
+use std::fmt::{Debug, Display};
+
+use data_types::ParquetFile;
+
+use crate::file_classification::FileToSplit;
+
+pub mod noop;
+pub mod policy;
+
+/// What to do with the single oversize L0 file of a partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SingleOversizeFileAction {
+    /// Split the file into non-overlapping L1 files of about the desired size.
+    Split(FileToSplit),
+    /// Rewrite the file as a single L1 file.
+    Rewrite(ParquetFile),
+}
+
+/// Selects the single oversize L0 file of a partition to be split or rewritten, instead of being
+/// compacted as usual.
+pub trait SingleOversizeFileSelector: Debug + Display + Send + Sync {
+    /// Return the action to take for a partition with the given `files`, or `None` if the
+    /// partition should be compacted as usual.
+    fn select(&self, files: &[ParquetFile]) -> Option<SingleOversizeFileAction>;
+}
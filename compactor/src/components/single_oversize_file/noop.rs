@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use data_types::ParquetFile;
+
+use super::{SingleOversizeFileAction, SingleOversizeFileSelector};
+
+/// Never selects a file, so that partitions are always compacted as usual.
+#[derive(Debug, Default)]
+pub struct NoopSingleOversizeFileSelector;
+
+impl NoopSingleOversizeFileSelector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopSingleOversizeFileSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+impl SingleOversizeFileSelector for NoopSingleOversizeFileSelector {
+    fn select(&self, _files: &[ParquetFile]) -> Option<SingleOversizeFileAction> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::CompactionLevel;
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopSingleOversizeFileSelector::new().to_string(), "noop");
+    }
+
+    #[test]
+    fn test_select() {
+        let oversize = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::Initial)
+            .with_file_size_bytes(i64::MAX)
+            .build();
+        assert_eq!(
+            NoopSingleOversizeFileSelector::new().select(&[oversize]),
+            None
+        );
+    }
+}
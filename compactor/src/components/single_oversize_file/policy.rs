@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use data_types::{CompactionLevel, ParquetFile, TimestampMinMax};
+
+use crate::{
+    components::ir_planner::planner_v1::V1IRPlanner, config::SingleOversizeFilePolicy,
+    file_classification::FileToSplit,
+};
+
+use super::{SingleOversizeFileAction, SingleOversizeFileSelector};
+
+/// Applies a [`SingleOversizeFilePolicy`] to the files of a partition.
+///
+/// Only a partition consisting of exactly one L0 file larger than the desired max file size is
+/// affected. The output is written at [`CompactionLevel::FileNonOverlapped`], so the same file is
+/// never selected again.
+#[derive(Debug)]
+pub struct PolicySingleOversizeFileSelector {
+    policy: SingleOversizeFilePolicy,
+    max_desired_file_size_bytes: u64,
+}
+
+impl PolicySingleOversizeFileSelector {
+    pub fn new(policy: SingleOversizeFilePolicy, max_desired_file_size_bytes: u64) -> Self {
+        Self {
+            policy,
+            max_desired_file_size_bytes,
+        }
+    }
+}
+
+impl SingleOversizeFileSelector for PolicySingleOversizeFileSelector {
+    fn select(&self, files: &[ParquetFile]) -> Option<SingleOversizeFileAction> {
+        let [file] = files else {
+            return None;
+        };
+        if file.compaction_level != CompactionLevel::Initial
+            || file.file_size_bytes as u64 <= self.max_desired_file_size_bytes
+        {
+            return None;
+        }
+
+        match self.policy {
+            SingleOversizeFilePolicy::Leave => None,
+            SingleOversizeFilePolicy::Rewrite => {
+                Some(SingleOversizeFileAction::Rewrite(file.clone()))
+            }
+            SingleOversizeFilePolicy::Split => {
+                let min_time = file.min_time.get();
+                let max_time = file.max_time.get();
+                let split_times = V1IRPlanner::compute_split_time(
+                    vec![TimestampMinMax {
+                        min: min_time,
+                        max: max_time,
+                    }],
+                    min_time,
+                    max_time,
+                    file.file_size_bytes as u64,
+                    self.max_desired_file_size_bytes,
+                );
+
+                // All the data may share a single timestamp, which cannot be split.
+                if split_times.iter().all(|t| *t >= max_time) {
+                    return None;
+                }
+
+                Some(SingleOversizeFileAction::Split(FileToSplit {
+                    file: file.clone(),
+                    split_times,
+                }))
+            }
+        }
+    }
+}
+
+impl Display for PolicySingleOversizeFileSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "single_oversize_file({})", self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    const MAX_SIZE: u64 = 100;
+
+    fn file(id: i64, level: CompactionLevel, size: u64) -> ParquetFile {
+        ParquetFileBuilder::new(id)
+            .with_compaction_level(level)
+            .with_time_range(0, 1_000)
+            .with_file_size_bytes(size as i64)
+            .build()
+    }
+
+    #[test]
+    fn test_display() {
+        let selector =
+            PolicySingleOversizeFileSelector::new(SingleOversizeFilePolicy::Split, MAX_SIZE);
+        assert_eq!(selector.to_string(), "single_oversize_file(split)");
+    }
+
+    #[test]
+    fn test_select() {
+        let oversize = file(1, CompactionLevel::Initial, 3 * MAX_SIZE);
+
+        let leave =
+            PolicySingleOversizeFileSelector::new(SingleOversizeFilePolicy::Leave, MAX_SIZE);
+        assert_eq!(leave.select(&[oversize.clone()]), None);
+
+        let rewrite =
+            PolicySingleOversizeFileSelector::new(SingleOversizeFilePolicy::Rewrite, MAX_SIZE);
+        assert_eq!(
+            rewrite.select(&[oversize.clone()]),
+            Some(SingleOversizeFileAction::Rewrite(oversize.clone()))
+        );
+
+        let split =
+            PolicySingleOversizeFileSelector::new(SingleOversizeFilePolicy::Split, MAX_SIZE);
+        assert_matches::assert_matches!(
+            split.select(&[oversize.clone()]),
+            Some(SingleOversizeFileAction::Split(FileToSplit { split_times, .. })) if split_times.len() == 2
+        );
+
+        // not a single file
+        let small = file(2, CompactionLevel::Initial, MAX_SIZE);
+        assert_eq!(split.select(&[oversize, small.clone()]), None);
+
+        // not oversize
+        assert_eq!(split.select(&[small]), None);
+
+        // not L0
+        let l1 = file(3, CompactionLevel::FileNonOverlapped, 3 * MAX_SIZE);
+        assert_eq!(split.select(&[l1]), None);
+    }
+}
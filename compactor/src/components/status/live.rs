@@ -0,0 +1,227 @@
+use std::{collections::BTreeMap, fmt::Display, sync::Arc};
+
+use compactor_scheduler::{LocalSchedulerConfig, SchedulerConfig};
+use data_types::PartitionId;
+use iox_time::{Time, TimeProvider};
+use parking_lot::Mutex;
+
+use crate::{
+    components::catch_up::CatchUpEstimator,
+    status::{InFlightPartition, IterationSummary, ShardStatus, SkippedPartition, StatusSnapshot},
+};
+
+use super::StatusTracker;
+
+#[derive(Debug, Clone)]
+struct Iteration {
+    started_at: Time,
+    jobs: usize,
+    succeeded: usize,
+    failed: usize,
+}
+
+impl From<&Iteration> for IterationSummary {
+    fn from(it: &Iteration) -> Self {
+        Self {
+            started_at: it.started_at.to_rfc3339(),
+            jobs: it.jobs,
+            succeeded: it.succeeded,
+            failed: it.failed,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    in_flight: BTreeMap<PartitionId, Time>,
+    current_iteration: Option<Iteration>,
+    last_iteration: Option<Iteration>,
+}
+
+/// Live state of a running compactor, reported by the status endpoint.
+#[derive(Debug)]
+pub struct LiveStatusTracker {
+    shard: Option<ShardStatus>,
+    time_provider: Arc<dyn TimeProvider>,
+    state: Mutex<State>,
+    catch_up: Option<Arc<dyn CatchUpEstimator>>,
+}
+
+impl LiveStatusTracker {
+    /// Create a new, empty status for a compactor using `scheduler_config`.
+    pub fn new(scheduler_config: &SchedulerConfig, time_provider: Arc<dyn TimeProvider>) -> Self {
+        let shard = match scheduler_config {
+            SchedulerConfig::Local(LocalSchedulerConfig {
+                shard_config: Some(shard_config),
+                ..
+            }) => Some(ShardStatus {
+                n_shards: shard_config.n_shards,
+                shard_id: shard_config.shard_id,
+            }),
+            SchedulerConfig::Local(_) => None,
+        };
+
+        Self {
+            shard,
+            time_provider,
+            state: Mutex::new(State::default()),
+            catch_up: None,
+        }
+    }
+
+    /// Report the catch-up estimate of `catch_up` along with the status.
+    pub fn with_catch_up(self, catch_up: Arc<dyn CatchUpEstimator>) -> Self {
+        Self {
+            catch_up: Some(catch_up),
+            ..self
+        }
+    }
+}
+
+impl Display for LiveStatusTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "live")
+    }
+}
+
+impl StatusTracker for LiveStatusTracker {
+    fn iteration_started(&self, jobs: usize) {
+        let now = self.time_provider.now();
+        let mut state = self.state.lock();
+        state.last_iteration = state.current_iteration.replace(Iteration {
+            started_at: now,
+            jobs,
+            succeeded: 0,
+            failed: 0,
+        });
+    }
+
+    fn partition_started(&self, partition_id: PartitionId) {
+        let now = self.time_provider.now();
+        self.state.lock().in_flight.insert(partition_id, now);
+    }
+
+    fn partition_done(&self, partition_id: PartitionId, success: bool) {
+        let mut state = self.state.lock();
+        state.in_flight.remove(&partition_id);
+        if let Some(it) = state.current_iteration.as_mut() {
+            if success {
+                it.succeeded += 1;
+            } else {
+                it.failed += 1;
+            }
+        }
+    }
+
+    fn snapshot(&self, skipped_partitions: Vec<SkippedPartition>) -> StatusSnapshot {
+        let state = self.state.lock();
+
+        StatusSnapshot {
+            shard: self.shard,
+            in_flight_partitions: state
+                .in_flight
+                .iter()
+                .map(|(partition_id, started_at)| InFlightPartition {
+                    partition_id: partition_id.get(),
+                    started_at: started_at.to_rfc3339(),
+                })
+                .collect(),
+            current_iteration: state.current_iteration.as_ref().map(Into::into),
+            last_iteration: state.last_iteration.as_ref().map(Into::into),
+            skipped_partitions,
+            catch_up: self.catch_up.as_ref().map(|c| c.summary()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use compactor_scheduler::ShardConfig;
+    use iox_time::MockProvider;
+
+    use crate::components::catch_up::rolling_window::RollingWindowCatchUpEstimator;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let status = LiveStatusTracker::new(
+            &SchedulerConfig::default(),
+            Arc::new(MockProvider::new(Time::MIN)),
+        );
+        assert_eq!(status.to_string(), "live");
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let scheduler_config = SchedulerConfig::Local(LocalSchedulerConfig {
+            shard_config: Some(ShardConfig {
+                n_shards: 4,
+                shard_id: 2,
+            }),
+            ..Default::default()
+        });
+        let status = LiveStatusTracker::new(&scheduler_config, Arc::clone(&time_provider) as _);
+
+        let snapshot = status.snapshot(vec![]);
+        assert_eq!(
+            snapshot.shard,
+            Some(ShardStatus {
+                n_shards: 4,
+                shard_id: 2
+            })
+        );
+        assert!(snapshot.in_flight_partitions.is_empty());
+        assert_eq!(snapshot.current_iteration, None);
+        assert_eq!(snapshot.last_iteration, None);
+        assert_eq!(snapshot.catch_up, None);
+
+        status.iteration_started(3);
+        status.partition_started(PartitionId::new(2));
+        status.partition_started(PartitionId::new(1));
+        status.partition_started(PartitionId::new(3));
+        status.partition_done(PartitionId::new(2), true);
+        status.partition_done(PartitionId::new(3), false);
+
+        let snapshot = status.snapshot(vec![]);
+        assert_eq!(
+            snapshot.in_flight_partitions,
+            vec![InFlightPartition {
+                partition_id: 1,
+                started_at: "1970-01-01T00:00:00+00:00".to_owned(),
+            }]
+        );
+        assert_eq!(
+            snapshot.current_iteration,
+            Some(IterationSummary {
+                started_at: "1970-01-01T00:00:00+00:00".to_owned(),
+                jobs: 3,
+                succeeded: 1,
+                failed: 1,
+            })
+        );
+
+        status.iteration_started(5);
+        let snapshot = status.snapshot(vec![]);
+        assert_eq!(snapshot.current_iteration.unwrap().jobs, 5);
+        assert_eq!(snapshot.last_iteration.unwrap().succeeded, 1);
+    }
+
+    #[test]
+    fn test_snapshot_catch_up() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let catch_up = Arc::new(RollingWindowCatchUpEstimator::new(
+            Arc::clone(&time_provider) as _,
+            &metric::Registry::new(),
+            "none".to_owned(),
+        ));
+        let status = LiveStatusTracker::new(&SchedulerConfig::default(), time_provider as _)
+            .with_catch_up(catch_up);
+
+        let catch_up = status.snapshot(vec![]).catch_up.unwrap();
+        assert_eq!(catch_up.backlog_l0_files, 0);
+        assert_eq!(catch_up.eta_secs, Some(0));
+        assert!(!catch_up.backlog_growing);
+    }
+}
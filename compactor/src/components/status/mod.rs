@@ -0,0 +1,23 @@
+use std::fmt::{Debug, Display};
+
+use data_types::PartitionId;
+
+use crate::status::{SkippedPartition, StatusSnapshot};
+
+pub mod live;
+pub mod noop;
+
+/// Tracks what the compactor is currently doing, for the status endpoint.
+pub trait StatusTracker: Debug + Display + Send + Sync {
+    /// Record that a new batch of `jobs` jobs was fetched, ending the current iteration.
+    fn iteration_started(&self, jobs: usize);
+
+    /// Record that compaction of `partition_id` started.
+    fn partition_started(&self, partition_id: PartitionId);
+
+    /// Record that compaction of `partition_id` finished.
+    fn partition_done(&self, partition_id: PartitionId, success: bool);
+
+    /// Take a snapshot of the current status, together with the given skip list.
+    fn snapshot(&self, skipped_partitions: Vec<SkippedPartition>) -> StatusSnapshot;
+}
@@ -0,0 +1,63 @@
+use std::fmt::Display;
+
+use data_types::PartitionId;
+
+use crate::status::{SkippedPartition, StatusSnapshot};
+
+use super::StatusTracker;
+
+/// Does not track anything, for compactors without a status endpoint.
+#[derive(Debug, Default)]
+pub struct NoopStatusTracker;
+
+impl NoopStatusTracker {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopStatusTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+impl StatusTracker for NoopStatusTracker {
+    fn iteration_started(&self, _jobs: usize) {}
+
+    fn partition_started(&self, _partition_id: PartitionId) {}
+
+    fn partition_done(&self, _partition_id: PartitionId, _success: bool) {}
+
+    fn snapshot(&self, skipped_partitions: Vec<SkippedPartition>) -> StatusSnapshot {
+        StatusSnapshot {
+            shard: None,
+            in_flight_partitions: vec![],
+            current_iteration: None,
+            last_iteration: None,
+            skipped_partitions,
+            catch_up: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopStatusTracker::new().to_string(), "noop");
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let status = NoopStatusTracker::new();
+        status.iteration_started(1);
+        status.partition_started(PartitionId::new(1));
+
+        let snapshot = status.snapshot(vec![]);
+        assert!(snapshot.in_flight_partitions.is_empty());
+        assert_eq!(snapshot.current_iteration, None);
+    }
+}
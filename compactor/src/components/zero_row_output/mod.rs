@@ -0,0 +1,23 @@
+//! Handling of compactions that produce no output rows.
+use std::fmt::{Debug, Display};
+
+use data_types::{ParquetFile, ParquetFileParams, PartitionId};
+
+use crate::error::DynError;
+
+pub mod noop;
+pub mod policy;
+
+/// Decides whether a compaction result without any output file may be committed.
+pub trait ZeroRowOutputCheck: Debug + Display + Send + Sync {
+    /// Check the result of compacting `files_to_delete` into `created`.
+    ///
+    /// Returns an error if the result must not be committed, in which case the input files are
+    /// kept and the partition is skipped.
+    fn check(
+        &self,
+        partition_id: PartitionId,
+        files_to_delete: &[ParquetFile],
+        created: &[ParquetFileParams],
+    ) -> Result<(), DynError>;
+}
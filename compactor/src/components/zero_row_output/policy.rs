@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use data_types::{ParquetFile, ParquetFileParams, PartitionId};
+use observability_deps::tracing::warn;
+
+use crate::{
+    config::ZeroRowOutputPolicy,
+    error::{DynError, ErrorKind, SimpleError},
+};
+
+use super::ZeroRowOutputCheck;
+
+/// Applies a [`ZeroRowOutputPolicy`] to a compaction result before it is committed.
+///
+/// A result that deletes input files without creating any output file means every input row was
+/// dropped. Compaction only removes duplicates, so this points to a bug or broken input files.
+/// Committing it removes the data of the inputs for good, so it is only done if the policy allows
+/// it. Otherwise the check fails with [`ErrorKind::Unknown`], which marks the partition as skipped
+/// for good rather than compacting the same inputs into nothing again every iteration.
+#[derive(Debug)]
+pub struct PolicyZeroRowOutputCheck {
+    policy: ZeroRowOutputPolicy,
+}
+
+impl PolicyZeroRowOutputCheck {
+    pub fn new(policy: ZeroRowOutputPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl ZeroRowOutputCheck for PolicyZeroRowOutputCheck {
+    fn check(
+        &self,
+        partition_id: PartitionId,
+        files_to_delete: &[ParquetFile],
+        created: &[ParquetFileParams],
+    ) -> Result<(), DynError> {
+        if files_to_delete.is_empty() || !created.is_empty() {
+            return Ok(());
+        }
+
+        let input_rows = files_to_delete.iter().map(|f| f.row_count).sum::<i64>();
+        match self.policy {
+            ZeroRowOutputPolicy::DeleteInputs => Ok(()),
+            ZeroRowOutputPolicy::SkipAndWarn => {
+                warn!(
+                    partition_id = partition_id.get(),
+                    n_files = files_to_delete.len(),
+                    input_rows,
+                    "compaction produced no output rows, keeping the input files",
+                );
+                Err(Box::new(SimpleError::new(
+                    ErrorKind::Unknown,
+                    format!(
+                        "compaction of {} files with {input_rows} rows produced no output rows",
+                        files_to_delete.len()
+                    ),
+                )))
+            }
+        }
+    }
+}
+
+impl Display for PolicyZeroRowOutputCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "zero_row_output({})", self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let check = PolicyZeroRowOutputCheck::new(ZeroRowOutputPolicy::SkipAndWarn);
+        assert_eq!(check.to_string(), "zero_row_output(skip-and-warn)");
+    }
+
+    #[test]
+    fn test_check() {
+        let partition_id = PartitionId::new(1);
+        let input = ParquetFileBuilder::new(1).build();
+        let output: ParquetFileParams = ParquetFileBuilder::new(2).build().into();
+
+        for policy in [
+            ZeroRowOutputPolicy::SkipAndWarn,
+            ZeroRowOutputPolicy::DeleteInputs,
+        ] {
+            let check = PolicyZeroRowOutputCheck::new(policy);
+
+            // regular output
+            check
+                .check(partition_id, &[input.clone()], &[output.clone()])
+                .unwrap();
+
+            // nothing deleted, e.g. an upgrade only
+            check.check(partition_id, &[], &[]).unwrap();
+        }
+
+        let skip = PolicyZeroRowOutputCheck::new(ZeroRowOutputPolicy::SkipAndWarn);
+        let err = skip.check(partition_id, &[input.clone()], &[]).unwrap_err();
+        assert!(
+            err.to_string().contains("produced no output rows"),
+            "unexpected error: {err}"
+        );
+
+        let delete = PolicyZeroRowOutputCheck::new(ZeroRowOutputPolicy::DeleteInputs);
+        delete.check(partition_id, &[input], &[]).unwrap();
+    }
+}
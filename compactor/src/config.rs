@@ -1,8 +1,12 @@
 //! Config-related stuff.
-use std::{net::SocketAddr, num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap, net::SocketAddr, num::NonZeroUsize, path::PathBuf, sync::Arc,
+    time::Duration,
+};
 
 use backoff::BackoffConfig;
 use compactor_scheduler::SchedulerConfig;
+use hyper::Uri;
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
 use iox_time::TimeProvider;
@@ -52,6 +56,13 @@ pub struct Config {
     /// compaction jobs.
     pub partition_concurrency: NonZeroUsize,
 
+    /// Period over which the partition concurrency is raised from 1 to
+    /// [`partition_concurrency`](Self::partition_concurrency) after startup.
+    ///
+    /// This smooths the load on a cold object store and catalog. If `None`, the full concurrency
+    /// is used right away.
+    pub partition_concurrency_warm_up: Option<Duration>,
+
     /// Number of compaction jobs concurrently scheduled to DataFusion.
     ///
     /// This should usually be smaller than the partition concurrency since one partition can spawn multiple compaction
@@ -61,6 +72,24 @@ pub struct Config {
     /// Number of jobs PER PARTITION that move files in and out of the scratchpad.
     pub partition_scratchpad_concurrency: NonZeroUsize,
 
+    /// Number of input parquet files read (fetched and decoded) concurrently by a single
+    /// compaction job.
+    ///
+    /// The effective value is further bounded by how many of the job's largest input files fit
+    /// into [`max_compact_size_bytes`](Self::max_compact_size_bytes) at once.
+    pub input_read_concurrency: NonZeroUsize,
+
+    /// Target number of DataFusion partitions a single compaction job is executed with, bounding
+    /// the number of executor threads each job keeps busy.
+    ///
+    /// This is independent of [`df_concurrency`](Self::df_concurrency), which bounds how many jobs
+    /// run at once. A job reads one input file per partition at a time, so its effective partition
+    /// count is further capped by [`input_read_concurrency`](Self::input_read_concurrency) and by
+    /// how many of its largest input files fit into the per-job memory budget
+    /// ([`max_compact_size_bytes`](Self::max_compact_size_bytes)); raising this value never
+    /// increases a job's memory use beyond that budget. Only capped by those limits if `None`.
+    pub exec_target_partitions: Option<NonZeroUsize>,
+
     /// Desired max size of compacted parquet files
     /// It is a target desired value than a guarantee
     pub max_desired_file_size_bytes: u64,
@@ -81,9 +110,56 @@ pub struct Config {
     /// This value must be between (0, 100)
     pub split_percentage: u16,
 
+    /// Per-table overrides of [`max_desired_file_size_bytes`](Self::max_desired_file_size_bytes),
+    /// [`percentage_max_file_size`](Self::percentage_max_file_size) and
+    /// [`split_percentage`](Self::split_percentage).
+    ///
+    /// Keys are either `<namespace>/<table>` or a bare `<table>` name matching the table in every
+    /// namespace. See [`Config::table_sizing`] for how overrides are resolved.
+    pub table_sizing_overrides: HashMap<String, SizingOverrides>,
+
     /// Maximum duration of the per-partition compaction task.
     pub partition_timeout: Duration,
 
+    /// Maximum duration of a single pass over the partitions needing compaction.
+    ///
+    /// Once exceeded, no new partitions from the pass are started; in-flight partitions finish
+    /// (each bounded by [`partition_timeout`](Self::partition_timeout)) and a new pass begins,
+    /// starting with the partitions of the previous pass that were not started.
+    /// Unlimited if `None`. Not used when [`process_once`](Self::process_once) is set.
+    pub run_iteration_budget: Option<Duration>,
+
+    /// Minimum age of L0 files to compact.
+    ///
+    /// More recently created L0 files are left untouched until a later compaction of their
+    /// partition. All L0 files are compacted if `None`.
+    pub min_file_age: Option<Duration>,
+
+    /// Rewrite files whose Parquet writer version is below this value, even if they would not be
+    /// compacted otherwise.
+    ///
+    /// Outdated files are rewritten one by one at their current compaction level once the
+    /// compaction of their partition is done; partitions rejected by the partition filters are
+    /// left untouched. Must not be greater than
+    /// [`PARQUET_WRITER_VERSION`](parquet_file::serialize::PARQUET_WRITER_VERSION), since rewritten
+    /// files would never catch up. No files are rewritten if `None`.
+    pub rewrite_below_format_version: Option<i32>,
+
+    /// What to do with a partition that consists of a single L0 file larger than
+    /// [`max_desired_file_size_bytes`](Self::max_desired_file_size_bytes).
+    pub single_oversize_file_policy: SingleOversizeFilePolicy,
+
+    /// Read back each output file before committing it, checking its row count and time range
+    /// match the catalog record.
+    ///
+    /// A mismatch fails the compaction of the partition without deleting its input files. Ignored
+    /// in shadow mode and when simulating without an object store.
+    pub verify_output: bool,
+
+    /// What to do with a compaction result that deletes input files without writing any output
+    /// rows.
+    pub zero_row_output_policy: ZeroRowOutputPolicy,
+
     /// Shadow mode.
     ///
     /// This will NOT write / commit any output to the object store or catalog.
@@ -98,6 +174,10 @@ pub struct Config {
     /// This is useful for disabling the scratchpad in production to evaluate the performance & memory impacts.
     pub enable_scratchpad: bool,
 
+    /// Minimum number of L0 files a partition must have to be compacted
+    /// Partitions with fewer L0 files are skipped until more files arrive
+    pub min_num_l0_files_to_compact: NonZeroUsize,
+
     /// Minimum number of L1 files to compact to L2
     /// This is to prevent too many small files
     pub min_num_l1_files_to_compact: usize,
@@ -105,6 +185,9 @@ pub struct Config {
     /// Only process all discovered partitions once.
     pub process_once: bool,
 
+    /// Pause fetching new compaction jobs while this file exists.
+    pub pause_file: Option<PathBuf>,
+
     /// Simulate compactor w/o any object store interaction. No parquet
     /// files will be read or written.
     ///
@@ -143,12 +226,242 @@ pub struct Config {
     ///
     /// Only used if `gossip_bind_address` is `Some`.
     pub gossip_seeds: Vec<String>,
+
+    /// Serve the read-only JSON status document at `/status` on the HTTP listener.
+    pub status_endpoint: bool,
+
+    /// The optional URL of a webhook a JSON summary is POSTed to once a partition is compacted.
+    ///
+    /// Notifications are retried according to [`backoff_config`](Self::backoff_config), and a
+    /// failure to notify does not fail the compaction. If `None`, no notification is sent.
+    pub completion_webhook_url: Option<Uri>,
+}
+
+/// What the compactor does with a partition that consists of a single L0 file larger than
+/// [`Config::max_desired_file_size_bytes`].
+///
+/// Such a file gains nothing from compaction since there is nothing to merge it with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SingleOversizeFilePolicy {
+    /// Compact the partition as usual, which moves the file up the compaction levels without
+    /// rewriting it.
+    #[default]
+    Leave,
+
+    /// Split the file into non-overlapping L1 files of about the desired size.
+    Split,
+
+    /// Rewrite the file as a single L1 file, without splitting it.
+    Rewrite,
+}
+
+impl std::fmt::Display for SingleOversizeFilePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Leave => write!(f, "leave"),
+            Self::Split => write!(f, "split"),
+            Self::Rewrite => write!(f, "rewrite"),
+        }
+    }
+}
+
+/// What the compactor does when compacting files produces no output rows.
+///
+/// Committing such a result deletes the input files without replacing them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ZeroRowOutputPolicy {
+    /// Keep the input files, log a warning and skip the partition.
+    ///
+    /// The partition is recorded as a skipped compaction, so it is not compacted again until the
+    /// skip is removed.
+    #[default]
+    SkipAndWarn,
+
+    /// Delete the input files without creating any output file.
+    DeleteInputs,
+}
+
+impl std::fmt::Display for ZeroRowOutputPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SkipAndWarn => write!(f, "skip-and-warn"),
+            Self::DeleteInputs => write!(f, "delete-inputs"),
+        }
+    }
 }
 
 impl Config {
     /// Maximum input bytes (from parquet files) per compaction. If there is more data, we ignore
     /// the partition (for now) as a self-protection mechanism.
+    pub fn max_compact_size_bytes(&self) -> usize {
+        self.sizing().max_compact_size_bytes()
+    }
+
+    /// The global sizing parameters, used for tables without overrides.
+    pub fn sizing(&self) -> SizingConfig {
+        SizingConfig {
+            max_desired_file_size_bytes: self.max_desired_file_size_bytes,
+            percentage_max_file_size: self.percentage_max_file_size,
+            split_percentage: self.split_percentage,
+        }
+    }
+
+    /// The sizing parameters for `table` of `namespace`, see [`resolve_table_sizing`].
+    pub fn table_sizing(&self, namespace: &str, table: &str) -> SizingConfig {
+        resolve_table_sizing(
+            self.sizing(),
+            &self.table_sizing_overrides,
+            namespace,
+            table,
+        )
+    }
+}
+
+/// Resolve the sizing parameters for `table` of `namespace`.
+///
+/// Each parameter is resolved independently, taking the first value set by:
+///
+/// 1. the `<namespace>/<table>` entry of `overrides`,
+/// 2. the `<table>` entry of `overrides`,
+/// 3. `global`.
+pub fn resolve_table_sizing(
+    global: SizingConfig,
+    overrides: &HashMap<String, SizingOverrides>,
+    namespace: &str,
+    table: &str,
+) -> SizingConfig {
+    [
+        overrides.get(&format!("{namespace}/{table}")),
+        overrides.get(table),
+    ]
+    .into_iter()
+    .flatten()
+    .rev()
+    .fold(global, |sizing, o| o.apply(sizing))
+}
+
+/// The sizing and split parameters the compactor uses for a table.
+///
+/// See [`Config::max_desired_file_size_bytes`], [`Config::percentage_max_file_size`] and
+/// [`Config::split_percentage`] for their meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizingConfig {
+    /// Desired max size of compacted parquet files.
+    pub max_desired_file_size_bytes: u64,
+    /// Percentage of the desired max file size below which output is not split.
+    pub percentage_max_file_size: u16,
+    /// Percentage of the time range placed in the first file when splitting output.
+    pub split_percentage: u16,
+}
+
+impl SizingConfig {
+    /// Maximum input bytes (from parquet files) per compaction, see
+    /// [`Config::max_compact_size_bytes`].
     pub fn max_compact_size_bytes(&self) -> usize {
         self.max_desired_file_size_bytes as usize * MIN_COMPACT_SIZE_MULTIPLE
     }
 }
+
+/// Overrides of the [`SizingConfig`] of a table. Unset values are inherited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizingOverrides {
+    /// Overrides [`SizingConfig::max_desired_file_size_bytes`].
+    pub max_desired_file_size_bytes: Option<u64>,
+    /// Overrides [`SizingConfig::percentage_max_file_size`].
+    pub percentage_max_file_size: Option<u16>,
+    /// Overrides [`SizingConfig::split_percentage`].
+    pub split_percentage: Option<u16>,
+}
+
+impl SizingOverrides {
+    /// Replace the values of `sizing` set by these overrides.
+    pub fn apply(&self, sizing: SizingConfig) -> SizingConfig {
+        SizingConfig {
+            max_desired_file_size_bytes: self
+                .max_desired_file_size_bytes
+                .unwrap_or(sizing.max_desired_file_size_bytes),
+            percentage_max_file_size: self
+                .percentage_max_file_size
+                .unwrap_or(sizing.percentage_max_file_size),
+            split_percentage: self.split_percentage.unwrap_or(sizing.split_percentage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLOBAL: SizingConfig = SizingConfig {
+        max_desired_file_size_bytes: 100,
+        percentage_max_file_size: 20,
+        split_percentage: 80,
+    };
+
+    #[test]
+    fn test_resolve_table_sizing_fallback() {
+        let overrides = HashMap::from([(
+            "other".to_owned(),
+            SizingOverrides {
+                max_desired_file_size_bytes: Some(1),
+                ..Default::default()
+            },
+        )]);
+
+        assert_eq!(
+            resolve_table_sizing(GLOBAL, &overrides, "ns", "cpu"),
+            GLOBAL
+        );
+        assert_eq!(
+            resolve_table_sizing(GLOBAL, &HashMap::new(), "ns", "cpu"),
+            GLOBAL
+        );
+    }
+
+    #[test]
+    fn test_resolve_table_sizing_override() {
+        let overrides = HashMap::from([
+            (
+                "cpu".to_owned(),
+                SizingOverrides {
+                    max_desired_file_size_bytes: Some(10),
+                    percentage_max_file_size: Some(30),
+                    split_percentage: None,
+                },
+            ),
+            (
+                "ns/cpu".to_owned(),
+                SizingOverrides {
+                    max_desired_file_size_bytes: Some(1_000),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        // the namespace-qualified entry wins, the table entry fills in, the rest is global
+        assert_eq!(
+            resolve_table_sizing(GLOBAL, &overrides, "ns", "cpu"),
+            SizingConfig {
+                max_desired_file_size_bytes: 1_000,
+                percentage_max_file_size: 30,
+                split_percentage: 80,
+            }
+        );
+
+        // only the bare table entry matches in other namespaces
+        assert_eq!(
+            resolve_table_sizing(GLOBAL, &overrides, "other_ns", "cpu"),
+            SizingConfig {
+                max_desired_file_size_bytes: 10,
+                percentage_max_file_size: 30,
+                split_percentage: 80,
+            }
+        );
+
+        // other tables are unaffected
+        assert_eq!(
+            resolve_table_sizing(GLOBAL, &overrides, "ns", "mem"),
+            GLOBAL
+        );
+    }
+}
@@ -17,11 +17,12 @@ use crate::{
     components::{
         changed_files_filter::SavedParquetFileState,
         scratchpad::Scratchpad,
+        single_oversize_file::SingleOversizeFileAction,
         timeout::{timeout_with_progress_checking, TimeoutWithProgress},
         Components,
     },
     error::{DynError, ErrorKind, ErrorKindExt, SimpleError},
-    file_classification::{FileClassification, FilesForProgress},
+    file_classification::{CompactReason, FileClassification, FilesForProgress, SplitReason},
     partition_info::PartitionInfo,
     round_info::CompactType,
     PlanIR, RoundInfo,
@@ -51,14 +52,22 @@ pub async fn compact(
                 .map(|collector| Span::root("compaction", Arc::clone(collector)));
             let span = SpanRecorder::new(root_span);
 
-            compact_partition(
-                span,
-                job,
-                partition_timeout,
-                Arc::clone(&df_semaphore),
-                components,
-                gossip_handle.clone(),
-            )
+            let df_semaphore = Arc::clone(&df_semaphore);
+            let gossip_handle = gossip_handle.clone();
+            async move {
+                // held while the partition is compacted, limiting concurrency during warm-up
+                let _permit = components.partition_concurrency_ramp.acquire().await;
+
+                compact_partition(
+                    span,
+                    job,
+                    partition_timeout,
+                    df_semaphore,
+                    components,
+                    gossip_handle,
+                )
+                .await
+            }
         })
         .buffer_unordered(partition_concurrency.get())
         .collect::<()>()
@@ -79,6 +88,7 @@ async fn compact_partition(
     let scratchpad = components.scratchpad_gen.pad();
 
     info!(partition_id = partition_id.get(), "compaction job starting");
+    components.status.partition_started(partition_id);
 
     let res = timeout_with_progress_checking(partition_timeout, |transmit_progress_signal| {
         let components = Arc::clone(&components);
@@ -116,11 +126,24 @@ async fn compact_partition(
         TimeoutWithProgress::Completed(res) => res,
     };
 
+    components.status.partition_done(partition_id, res.is_ok());
+    let row_counts = components.row_counts.finish(partition_id);
+    // sent in the background, failures are only logged
+    components
+        .completion_webhook
+        .notify(partition_id, &res, row_counts);
+
     // TODO: how handle errors detected in the CompactionJob ending actions?
     let _ = components.compaction_job_done_sink.record(job, res).await;
 
     scratchpad.clean().await;
-    info!(partition_id = partition_id.get(), "compaction job done",);
+    info!(
+        partition_id = partition_id.get(),
+        rows_read = row_counts.rows_read,
+        rows_written = row_counts.rows_written,
+        rows_dropped = row_counts.rows_dropped(),
+        "compaction job done",
+    );
 }
 
 /// Main function to compact files of a single partition.
@@ -220,6 +243,9 @@ async fn try_compact_partition(
 ) -> Result<(), DynError> {
     let partition_id = job.partition_id;
     let mut files = components.partition_files_source.fetch(partition_id).await;
+    // Only this initial fetch updates the backlog; later fetches of the same partition, e.g.
+    // before committing, still include the files that are about to be compacted.
+    components.catch_up.observe_backlog(partition_id, &files);
     let partition_info = components.partition_info_source.fetch(partition_id).await?;
     let transmit_progress_signal = Arc::new(transmit_progress_signal);
     let mut last_round_info: Option<Arc<RoundInfo>> = None;
@@ -244,6 +270,23 @@ async fn try_compact_partition(
         return Ok(());
     }
 
+    // A lone oversize L0 file has nothing to be compacted with, it is handled according to the
+    // configured policy instead.
+    if let Some(action) = components.single_oversize_file_selector.select(&files) {
+        return rewrite_single_oversize_file(
+            span,
+            job,
+            action,
+            df_semaphore,
+            components,
+            scratchpad_ctx,
+            partition_info,
+            &transmit_progress_signal,
+            gossip_handle,
+        )
+        .await;
+    }
+
     // loop for each "Round".  A round is comprised of the next thing we can do, on one or more branches within
     // one or more CompactRegions.  A round does not feed back into itself.  So when split|compaction output feeds
     // into another split|compaction, that's a new round.
@@ -264,7 +307,17 @@ async fn try_compact_partition(
         files = vec![];
 
         if done || round_info.ranges.is_empty() {
-            return Ok(());
+            return rewrite_outdated_files(
+                span,
+                job,
+                df_semaphore,
+                components,
+                scratchpad_ctx,
+                partition_info,
+                &transmit_progress_signal,
+                gossip_handle,
+            )
+            .await;
         }
 
         info!(
@@ -350,6 +403,226 @@ async fn try_compact_partition(
     }
 }
 
+/// Rewrite the files of a compacted partition selected by the
+/// [`FormatRewriteSelector`](crate::components::format_rewrite_selector::FormatRewriteSelector)
+/// because of their outdated Parquet format.
+///
+/// Each file is rewritten on its own and keeps its compaction level, so the layout of the
+/// partition does not change.
+#[allow(clippy::too_many_arguments)]
+async fn rewrite_outdated_files(
+    span: SpanRecorder,
+    job: CompactionJob,
+    df_semaphore: Arc<InstrumentedAsyncSemaphore>,
+    components: Arc<Components>,
+    scratchpad_ctx: Arc<dyn Scratchpad>,
+    partition_info: Arc<PartitionInfo>,
+    transmit_progress_signal: &Sender<bool>,
+    gossip_handle: Option<Arc<CompactionEventTx>>,
+) -> Result<(), DynError> {
+    let files = components
+        .format_rewrite_selector
+        .select(partition_info.partition_id)
+        .await?;
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    info!(
+        partition_id = partition_info.partition_id.get(),
+        n_files = files.len(),
+        "rewriting files with outdated parquet format",
+    );
+
+    let saved_parquet_file_state = SavedParquetFileState::from(&files);
+
+    for level in [
+        CompactionLevel::Initial,
+        CompactionLevel::FileNonOverlapped,
+        CompactionLevel::Final,
+    ] {
+        let level_files = files
+            .iter()
+            .filter(|f| f.compaction_level == level)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // rewrite in chunks for the same reasons as `execute_branch`
+        for chunk in level_files.chunks(df_semaphore.total_permits() * 4) {
+            let plans = chunk
+                .iter()
+                .map(|file| {
+                    let paths = vec![ParquetFilePath::from(file)];
+                    let object_store_ids = scratchpad_ctx.uuids(&paths);
+                    components.ir_planner.compact_plan(
+                        vec![file.clone()],
+                        paths,
+                        object_store_ids,
+                        CompactReason::FormatRewrite,
+                        Arc::clone(&partition_info),
+                        level,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let created_file_params = run_plans(
+                span.child("run_plans"),
+                plans,
+                &partition_info,
+                &components,
+                Arc::clone(&df_semaphore),
+                Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+            )
+            .await?;
+
+            let upload_span = span.child("upload_objects");
+            let created_file_params = upload_files_to_object_store(
+                created_file_params,
+                Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+            )
+            .await;
+            drop(upload_span);
+
+            let created_file_paths: Vec<ParquetFilePath> = created_file_params
+                .iter()
+                .map(ParquetFilePath::from)
+                .collect();
+            scratchpad_ctx
+                .clean_written_from_scratchpad(&created_file_paths)
+                .await;
+
+            let (created_files, upgraded_files) = update_catalog(
+                Arc::clone(&components),
+                job.clone(),
+                &saved_parquet_file_state,
+                chunk,
+                vec![],
+                created_file_params,
+                level,
+            )
+            .await?;
+
+            gossip_compaction_complete(
+                gossip_handle.as_deref(),
+                &created_files,
+                &upgraded_files,
+                chunk.to_vec(),
+                level,
+            );
+
+            if let Err(e) = transmit_progress_signal.send(true) {
+                return Err(Box::new(e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Split or rewrite the single oversize L0 file of a partition as selected by the
+/// [`SingleOversizeFileSelector`](crate::components::single_oversize_file::SingleOversizeFileSelector).
+///
+/// The output is written as L1 files.
+#[allow(clippy::too_many_arguments)]
+async fn rewrite_single_oversize_file(
+    span: SpanRecorder,
+    job: CompactionJob,
+    action: SingleOversizeFileAction,
+    df_semaphore: Arc<InstrumentedAsyncSemaphore>,
+    components: Arc<Components>,
+    scratchpad_ctx: Arc<dyn Scratchpad>,
+    partition_info: Arc<PartitionInfo>,
+    transmit_progress_signal: &Sender<bool>,
+    gossip_handle: Option<Arc<CompactionEventTx>>,
+) -> Result<(), DynError> {
+    let target_level = CompactionLevel::FileNonOverlapped;
+    let file = match &action {
+        SingleOversizeFileAction::Split(file_to_split) => file_to_split.file.clone(),
+        SingleOversizeFileAction::Rewrite(file) => file.clone(),
+    };
+
+    info!(
+        partition_id = partition_info.partition_id.get(),
+        file_size_bytes = file.file_size_bytes,
+        ?action,
+        "handling single oversize file",
+    );
+
+    let saved_parquet_file_state = SavedParquetFileState::from(std::slice::from_ref(&file));
+
+    let path = ParquetFilePath::from(&file);
+    let object_store_id = scratchpad_ctx.uuids(&[path.clone()])[0];
+    let plan = match action {
+        SingleOversizeFileAction::Split(file_to_split) => components.ir_planner.split_plan(
+            file_to_split,
+            path,
+            object_store_id,
+            SplitReason::SingleOversizeFile,
+            Arc::clone(&partition_info),
+            target_level,
+        ),
+        SingleOversizeFileAction::Rewrite(file) => components.ir_planner.compact_plan(
+            vec![file],
+            vec![path],
+            vec![object_store_id],
+            CompactReason::SingleOversizeFile,
+            Arc::clone(&partition_info),
+            target_level,
+        ),
+    };
+
+    let created_file_params = run_plans(
+        span.child("run_plans"),
+        vec![plan],
+        &partition_info,
+        &components,
+        df_semaphore,
+        Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+    )
+    .await?;
+
+    let upload_span = span.child("upload_objects");
+    let created_file_params = upload_files_to_object_store(
+        created_file_params,
+        Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+    )
+    .await;
+    drop(upload_span);
+
+    let created_file_paths: Vec<ParquetFilePath> = created_file_params
+        .iter()
+        .map(ParquetFilePath::from)
+        .collect();
+    scratchpad_ctx
+        .clean_written_from_scratchpad(&created_file_paths)
+        .await;
+
+    let (created_files, upgraded_files) = update_catalog(
+        components,
+        job,
+        &saved_parquet_file_state,
+        std::slice::from_ref(&file),
+        vec![],
+        created_file_params,
+        target_level,
+    )
+    .await?;
+
+    gossip_compaction_complete(
+        gossip_handle.as_deref(),
+        &created_files,
+        &upgraded_files,
+        vec![file],
+        target_level,
+    );
+
+    if let Err(e) = transmit_progress_signal.send(true) {
+        return Err(Box::new(e));
+    }
+
+    Ok(())
+}
+
 /// Compact or split given files
 #[allow(clippy::too_many_arguments)]
 async fn execute_branch(
@@ -755,6 +1028,19 @@ async fn update_catalog(
         .changed_files_filter
         .apply(saved_parquet_file_state, &current_parquet_file_state);
 
+    // Check the output before the inputs are deleted by the commit
+    components
+        .output_verifier
+        .verify(&file_params_to_create)
+        .await?;
+
+    // All input rows may have been dropped, committing that deletes the inputs without output
+    components.zero_row_output_check.check(
+        partition_id,
+        files_to_delete,
+        &file_params_to_create,
+    )?;
+
     let created_ids = components
         .commit
         .commit(
@@ -766,6 +1052,11 @@ async fn update_catalog(
         )
         .await?;
 
+    components
+        .row_counts
+        .record(partition_id, files_to_delete, &file_params_to_create);
+    components.catch_up.record_compacted(files_to_delete);
+
     // Update created ids to their corresponding file params
     let created_file_params = file_params_to_create
         .into_iter()
@@ -102,6 +102,8 @@ pub enum SplitReason {
     HighL0OverlapTotalBacklog,
     StartLevelOverlapsTooBig,
     VerticalSplit,
+    /// The only file of a partition is split because it is larger than the desired max file size.
+    SingleOversizeFile,
 }
 
 /// Reasons why there are files to compact
@@ -110,6 +112,11 @@ pub enum CompactReason {
     ManySmallFiles,
     TotalSizeLessThanMaxCompactSize,
     FoundSubsetLessThanMaxCompactSize,
+    /// A single file is rewritten because of an outdated Parquet format version.
+    FormatRewrite,
+    /// The only file of a partition is rewritten because it is larger than the desired max file
+    /// size.
+    SingleOversizeFile,
 }
 
 impl FilesToSplitOrCompact {
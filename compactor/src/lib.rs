@@ -218,6 +218,7 @@ pub mod object_store;
 mod partition_info;
 mod plan_ir;
 mod round_info;
+pub mod status;
 
 // publically expose items needed for testing
 pub use components::{
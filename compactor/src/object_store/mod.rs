@@ -2,3 +2,4 @@
 
 pub mod ignore_writes;
 pub mod metrics;
+pub mod read_after_write;
@@ -0,0 +1,219 @@
+//! Wrapper that retries reads of objects that are not visible yet.
+use std::{fmt::Display, ops::ControlFlow, ops::Range, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig, BackoffError};
+use bytes::Bytes;
+use futures::{stream::BoxStream, Future};
+use object_store::{
+    path::Path, DynObjectStore, Error, GetOptions, GetResult, ListResult, MultipartId, ObjectMeta,
+    ObjectStore, Result,
+};
+use observability_deps::tracing::{info, warn};
+use tokio::io::AsyncWrite;
+
+/// Store that retries reads failing with [`Error::NotFound`] for a limited time.
+///
+/// On stores without read-after-write consistency, a file that was just written (and committed
+/// to the catalog) may not be visible for a short while. Reads of such a file are retried with
+/// backoff until it becomes visible or `timeout` elapses, after which the file is considered
+/// truly absent and the [`Error::NotFound`] is returned. Other errors are returned as-is.
+#[derive(Debug)]
+pub struct ReadAfterWriteRetry {
+    inner: Arc<DynObjectStore>,
+    backoff_config: BackoffConfig,
+}
+
+impl ReadAfterWriteRetry {
+    /// Create new wrapper that retries reads from `inner` using `backoff_config` for at most
+    /// `timeout`.
+    pub fn new(
+        inner: Arc<DynObjectStore>,
+        backoff_config: BackoffConfig,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            backoff_config: BackoffConfig {
+                deadline: Some(timeout),
+                ..backoff_config
+            },
+        }
+    }
+
+    async fn retry_not_found<T, F, F1>(&self, location: &Path, mut read: F) -> Result<T>
+    where
+        F: (FnMut() -> F1) + Send,
+        F1: Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        let mut attempts = 0;
+        let res = Backoff::new(&self.backoff_config)
+            .retry_with_backoff("read object", || {
+                attempts += 1;
+                let read = read();
+                async move {
+                    match read.await {
+                        Err(e @ Error::NotFound { .. }) => ControlFlow::Continue(e),
+                        res => ControlFlow::Break(res),
+                    }
+                }
+            })
+            .await;
+
+        match res {
+            Ok(res) => {
+                if attempts > 1 && res.is_ok() {
+                    info!(%location, attempts, "object became visible after retrying");
+                }
+                res
+            }
+            Err(BackoffError::DeadlineExceeded { deadline, source }) => {
+                warn!(
+                    %location,
+                    attempts,
+                    ?deadline,
+                    "object still not found after retrying, treating it as absent",
+                );
+                Err(source)
+            }
+        }
+    }
+}
+
+impl Display for ReadAfterWriteRetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "read_after_write_retry({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for ReadAfterWriteRetry {
+    async fn put(&self, location: &Path, bytes: Bytes) -> Result<()> {
+        self.inner.put(location, bytes).await
+    }
+
+    async fn put_multipart(
+        &self,
+        location: &Path,
+    ) -> Result<(MultipartId, Box<dyn AsyncWrite + Unpin + Send>)> {
+        self.inner.put_multipart(location).await
+    }
+
+    async fn abort_multipart(&self, location: &Path, multipart_id: &MultipartId) -> Result<()> {
+        self.inner.abort_multipart(location, multipart_id).await
+    }
+
+    async fn get_opts(&self, location: &Path, options: GetOptions) -> Result<GetResult> {
+        self.retry_not_found(location, || self.inner.get_opts(location, options.clone()))
+            .await
+    }
+
+    async fn get_range(&self, location: &Path, range: Range<usize>) -> Result<Bytes> {
+        self.retry_not_found(location, || self.inner.get_range(location, range.clone()))
+            .await
+    }
+
+    async fn get_ranges(&self, location: &Path, ranges: &[Range<usize>]) -> Result<Vec<Bytes>> {
+        self.retry_not_found(location, || self.inner.get_ranges(location, ranges))
+            .await
+    }
+
+    async fn head(&self, location: &Path) -> Result<ObjectMeta> {
+        self.retry_not_found(location, || self.inner.head(location))
+            .await
+    }
+
+    async fn delete(&self, location: &Path) -> Result<()> {
+        self.inner.delete(location).await
+    }
+
+    async fn list(&self, prefix: Option<&Path>) -> Result<BoxStream<'_, Result<ObjectMeta>>> {
+        self.inner.list(prefix).await
+    }
+
+    async fn list_with_delimiter(&self, prefix: Option<&Path>) -> Result<ListResult> {
+        self.inner.list_with_delimiter(prefix).await
+    }
+
+    async fn copy(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy(from, to).await
+    }
+
+    async fn rename(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename(from, to).await
+    }
+
+    async fn copy_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.copy_if_not_exists(from, to).await
+    }
+
+    async fn rename_if_not_exists(&self, from: &Path, to: &Path) -> Result<()> {
+        self.inner.rename_if_not_exists(from, to).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use object_store::memory::InMemory;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let store = ReadAfterWriteRetry::new(
+            Arc::new(InMemory::new()),
+            BackoffConfig::default(),
+            Duration::from_secs(1),
+        );
+        assert_eq!(store.to_string(), "read_after_write_retry(InMemory)");
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_visible() {
+        let inner: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let store = ReadAfterWriteRetry::new(
+            Arc::clone(&inner),
+            test_backoff_config(),
+            Duration::from_secs(10),
+        );
+        let path = Path::from("foo");
+
+        // the object becomes visible a while after it is first read
+        let writer = {
+            let path = path.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                inner.put(&path, Bytes::from("bar")).await.unwrap();
+            })
+        };
+
+        let got = store.get(&path).await.unwrap().bytes().await.unwrap();
+        assert_eq!(got, Bytes::from("bar"));
+        assert_eq!(store.head(&path).await.unwrap().size, 3);
+
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_absent_after_timeout() {
+        let store = ReadAfterWriteRetry::new(
+            Arc::new(InMemory::new()),
+            test_backoff_config(),
+            Duration::from_millis(50),
+        );
+
+        let err = store.get(&Path::from("foo")).await.unwrap_err();
+        assert_matches!(err, Error::NotFound { .. });
+    }
+
+    fn test_backoff_config() -> BackoffConfig {
+        BackoffConfig {
+            init_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(10),
+            base: 1.0,
+            deadline: None,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Read-only JSON status document describing what the compactor is currently doing.
+//!
+//! The document is served at `/status` on the compactor HTTP listener when
+//! [`Config::status_endpoint`](crate::config::Config::status_endpoint) is set. It is meant for
+//! operators inspecting a single compactor ad hoc; dashboards and alerts should keep using the
+//! Prometheus metrics.
+use data_types::SkippedCompaction;
+use iox_catalog::interface::{Catalog, Error};
+use iox_time::Time;
+use serde::Serialize;
+
+use crate::components::{catch_up::CatchUpSummary, status::StatusTracker};
+
+/// The shard a compactor is responsible for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ShardStatus {
+    /// Number of shards.
+    pub n_shards: usize,
+    /// Shard ID of this compactor.
+    pub shard_id: usize,
+}
+
+/// A partition that is currently being compacted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InFlightPartition {
+    /// The partition.
+    pub partition_id: i64,
+    /// When compaction of this partition started (RFC 3339).
+    pub started_at: String,
+}
+
+/// Summary of one iteration, i.e. one batch of jobs fetched from the scheduler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IterationSummary {
+    /// When the batch was fetched (RFC 3339).
+    pub started_at: String,
+    /// Number of jobs in the batch.
+    pub jobs: usize,
+    /// Number of jobs that completed successfully so far.
+    pub succeeded: usize,
+    /// Number of jobs that failed so far.
+    pub failed: usize,
+}
+
+/// A partition the compactor skips.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedPartition {
+    /// The partition.
+    pub partition_id: i64,
+    /// Why the partition is skipped.
+    pub reason: String,
+    /// When the partition was marked as skipped (RFC 3339).
+    pub skipped_at: String,
+}
+
+impl From<SkippedCompaction> for SkippedPartition {
+    fn from(skipped: SkippedCompaction) -> Self {
+        Self {
+            partition_id: skipped.partition_id.get(),
+            reason: skipped.reason,
+            skipped_at: Time::from_timestamp_nanos(skipped.skipped_at.get()).to_rfc3339(),
+        }
+    }
+}
+
+/// The JSON document served by the status endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusSnapshot {
+    /// The shard of this compactor, or `null` if it is not sharded.
+    pub shard: Option<ShardStatus>,
+    /// Partitions currently being compacted, ordered by partition ID.
+    pub in_flight_partitions: Vec<InFlightPartition>,
+    /// The iteration currently being worked on, if any.
+    pub current_iteration: Option<IterationSummary>,
+    /// The previous iteration, if any.
+    pub last_iteration: Option<IterationSummary>,
+    /// Partitions marked as skipped in the catalog.
+    ///
+    /// The skip list is shared by all compactors, so this is not restricted to the shard.
+    pub skipped_partitions: Vec<SkippedPartition>,
+    /// Throughput, L0 backlog and the estimated time until the backlog is drained, if tracked.
+    pub catch_up: Option<CatchUpSummary>,
+}
+
+/// Take a snapshot of `status`, together with the skip list read from `catalog`.
+pub(crate) async fn snapshot(
+    status: &dyn StatusTracker,
+    catalog: &dyn Catalog,
+) -> Result<StatusSnapshot, Error> {
+    let skipped = catalog
+        .repositories()
+        .await
+        .partitions()
+        .list_skipped_compactions()
+        .await?;
+
+    Ok(status.snapshot(skipped.into_iter().map(Into::into).collect()))
+}
@@ -45,6 +45,18 @@ impl PartitionInfoBuilder {
         self
     }
 
+    pub fn with_namespace_name(mut self, name: &str) -> Self {
+        self.inner.namespace_name = name.to_string();
+        self
+    }
+
+    pub fn with_table_name(mut self, name: &str) -> Self {
+        let mut table = (*self.inner.table).clone();
+        table.name = name.to_string();
+        self.inner.table = Arc::new(table);
+        self
+    }
+
     pub fn with_num_columns(mut self, num_cols: usize) -> Self {
         let columns: Vec<_> = (0..num_cols)
             .map(|i| Column {
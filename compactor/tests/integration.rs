@@ -1,4 +1,7 @@
-use arrow_util::assert_batches_sorted_eq;
+use std::{collections::HashSet, num::NonZeroUsize};
+
+use arrow_util::{assert_batches_sorted_eq, test_util::batches_to_sorted_lines};
+use compactor::config::ZeroRowOutputPolicy;
 use compactor_test_utils::{format_files, list_object_store, TestSetup};
 use data_types::{CompactionLevel, ParquetFile, PartitionId};
 
@@ -181,6 +184,198 @@ async fn test_compact_target_level() {
     );
 }
 
+#[tokio::test]
+async fn test_input_read_concurrency_does_not_change_output() {
+    test_helpers::maybe_start_logging();
+
+    let mut outputs = vec![];
+    for concurrency in [1, 2, 8] {
+        let setup = TestSetup::builder()
+            .await
+            .with_files()
+            .await
+            .with_max_num_files_per_plan(10)
+            .with_min_num_l1_files_to_compact(2)
+            .with_input_read_concurrency(NonZeroUsize::new(concurrency).unwrap())
+            .build()
+            .await;
+
+        setup.run_compact().await;
+
+        let files = setup.list_by_table_not_to_delete().await;
+        let mut levels = files.iter().map(|f| f.compaction_level).collect::<Vec<_>>();
+        levels.sort();
+
+        let mut batches = vec![];
+        for file in files {
+            batches.extend(setup.read_parquet_file(file).await);
+        }
+
+        outputs.push((concurrency, levels, batches_to_sorted_lines(&batches)));
+    }
+
+    let (_, expected_levels, expected_lines) = &outputs[0];
+    for (concurrency, levels, lines) in &outputs[1..] {
+        assert_eq!(
+            levels, expected_levels,
+            "output levels differ with input read concurrency {concurrency}"
+        );
+        assert_eq!(
+            lines, expected_lines,
+            "output data differs with input read concurrency {concurrency}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_verify_output_does_not_change_output() {
+    test_helpers::maybe_start_logging();
+
+    let mut outputs = vec![];
+    for verify_output in [false, true] {
+        let builder = TestSetup::builder()
+            .await
+            .with_files()
+            .await
+            .with_max_num_files_per_plan(10)
+            .with_min_num_l1_files_to_compact(2);
+        let builder = if verify_output {
+            builder.with_verify_output()
+        } else {
+            builder
+        };
+        let setup = builder.build().await;
+
+        setup.run_compact().await;
+        assert_skipped_compactions(&setup, []).await;
+
+        let files = setup.list_by_table_not_to_delete().await;
+        let mut levels = files.iter().map(|f| f.compaction_level).collect::<Vec<_>>();
+        levels.sort();
+
+        let mut batches = vec![];
+        for file in files {
+            batches.extend(setup.read_parquet_file(file).await);
+        }
+
+        outputs.push((levels, batches_to_sorted_lines(&batches)));
+    }
+
+    assert_eq!(outputs[0], outputs[1]);
+}
+
+#[tokio::test]
+async fn test_zero_row_output_skip_and_warn() {
+    test_helpers::maybe_start_logging();
+
+    // the default policy keeps the inputs when all their rows are deleted
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_all_rows_deleted()
+        .build()
+        .await;
+
+    let files_before = setup.list_by_table_not_to_delete().await;
+    assert!(!files_before.is_empty());
+
+    setup.run_compact().await;
+
+    let skipped = setup
+        .catalog
+        .catalog
+        .repositories()
+        .await
+        .partitions()
+        .list_skipped_compactions()
+        .await
+        .unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].partition_id, setup.partition_info.partition_id);
+    assert!(
+        skipped[0].reason.contains("produced no output rows"),
+        "unexpected reason: {}",
+        skipped[0].reason
+    );
+
+    // files without overlaps may still have been upgraded, but none was deleted
+    let ids = |files: Vec<ParquetFile>| files.into_iter().map(|f| f.id).collect::<HashSet<_>>();
+    let files_after = setup.list_by_table_not_to_delete().await;
+    assert_eq!(ids(files_before), ids(files_after));
+}
+
+#[tokio::test]
+async fn test_zero_row_output_delete_inputs() {
+    test_helpers::maybe_start_logging();
+
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_all_rows_deleted()
+        .with_zero_row_output_policy(ZeroRowOutputPolicy::DeleteInputs)
+        .build()
+        .await;
+
+    let files_before = setup.list_by_table_not_to_delete().await;
+    assert!(!files_before.is_empty());
+
+    setup.run_compact().await;
+    assert_skipped_compactions(&setup, []).await;
+
+    // compacted inputs are gone without any output replacing them
+    let ids = |files: Vec<ParquetFile>| files.into_iter().map(|f| f.id).collect::<HashSet<_>>();
+    let ids_before = ids(files_before);
+    let ids_after = ids(setup.list_by_table_not_to_delete().await);
+    assert!(ids_after.is_subset(&ids_before));
+    assert!(ids_after.len() < ids_before.len());
+}
+
+#[tokio::test]
+async fn test_exec_target_partitions_does_not_change_output() {
+    test_helpers::maybe_start_logging();
+
+    let mut outputs = vec![];
+    for partitions in [1, 2, 8] {
+        let setup = TestSetup::builder()
+            .await
+            .with_files()
+            .await
+            .with_max_num_files_per_plan(10)
+            .with_min_num_l1_files_to_compact(2)
+            .with_input_read_concurrency(NonZeroUsize::new(8).unwrap())
+            .with_exec_target_partitions(NonZeroUsize::new(partitions).unwrap())
+            .build()
+            .await;
+
+        setup.run_compact().await;
+
+        let files = setup.list_by_table_not_to_delete().await;
+        let mut levels = files.iter().map(|f| f.compaction_level).collect::<Vec<_>>();
+        levels.sort();
+
+        let mut batches = vec![];
+        for file in files {
+            batches.extend(setup.read_parquet_file(file).await);
+        }
+
+        outputs.push((partitions, levels, batches_to_sorted_lines(&batches)));
+    }
+
+    let (_, expected_levels, expected_lines) = &outputs[0];
+    for (partitions, levels, lines) in &outputs[1..] {
+        assert_eq!(
+            levels, expected_levels,
+            "output levels differ with exec target partitions {partitions}"
+        );
+        assert_eq!(
+            lines, expected_lines,
+            "output data differs with exec target partitions {partitions}"
+        );
+    }
+}
+
 #[tokio::test]
 async fn test_compact_large_overlapes() {
     test_helpers::maybe_start_logging();
@@ -448,6 +643,43 @@ async fn test_skip_compact() {
     assert_levels(&files, expected_files_and_levels.clone());
 }
 
+#[tokio::test]
+async fn test_min_num_l0_files_to_compact() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files, 4 of them L0s
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        // Not compact the 4 L0s because the number of L0s < 5
+        .with_min_num_l0_files_to_compact(NonZeroUsize::new(5).unwrap())
+        // Not compact L1s into L2s because the number of L1s < 5
+        .with_min_num_l1_files_to_compact(5)
+        .build()
+        .await;
+
+    let expected_files_and_levels = vec![
+        (1, CompactionLevel::FileNonOverlapped),
+        (2, CompactionLevel::Initial),
+        (3, CompactionLevel::Initial),
+        (4, CompactionLevel::FileNonOverlapped),
+        (5, CompactionLevel::Initial),
+        (6, CompactionLevel::Initial),
+    ];
+
+    // verify 6 files
+    let files = setup.list_by_table_not_to_delete().await;
+    assert_levels(&files, expected_files_and_levels.clone());
+
+    // compact but nothing will be compacted because the partition is below the L0 threshold
+    setup.run_compact().await;
+
+    // verify still 6 files
+    let files = setup.list_by_table_not_to_delete().await;
+    assert_levels(&files, expected_files_and_levels);
+}
+
 #[tokio::test]
 async fn test_partition_fail() {
     test_helpers::maybe_start_logging();
@@ -2,7 +2,9 @@
 //!
 //! See [crate::layout] module for detailed documentation
 
-use data_types::CompactionLevel;
+use compactor::config::SingleOversizeFilePolicy;
+use compactor_test_utils::TestSetup;
+use data_types::{CompactionLevel, ParquetFile};
 use iox_time::Time;
 use std::time::Duration;
 
@@ -1964,3 +1966,74 @@ async fn file_over_max_size() {
     "###
     );
 }
+
+/// Compact a partition consisting of one L0 file 3x the max desired file size using `policy`,
+/// returning the input file and the resulting files.
+async fn compact_single_oversize_l0(
+    policy: SingleOversizeFilePolicy,
+) -> (ParquetFile, Vec<ParquetFile>) {
+    test_helpers::maybe_start_logging();
+
+    let setup: TestSetup = layout_setup_builder()
+        .await
+        .with_max_desired_file_size_bytes(MAX_DESIRED_FILE_SIZE)
+        .with_single_oversize_file_policy(policy)
+        .build()
+        .await;
+
+    let input = setup
+        .partition
+        .create_parquet_file(
+            parquet_builder()
+                .with_min_time(1)
+                .with_max_time(1000)
+                .with_compaction_level(CompactionLevel::Initial)
+                .with_file_size_bytes(3 * MAX_DESIRED_FILE_SIZE),
+        )
+        .await
+        .parquet_file;
+
+    run_layout_scenario(&setup).await;
+
+    (input, setup.list_by_table_not_to_delete().await)
+}
+
+#[tokio::test]
+async fn single_oversize_l0_leave() {
+    let (input, output) = compact_single_oversize_l0(SingleOversizeFilePolicy::Leave).await;
+
+    // The file is only upgraded, never rewritten
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].id, input.id);
+    assert_eq!(output[0].compaction_level, CompactionLevel::Final);
+}
+
+#[tokio::test]
+async fn single_oversize_l0_rewrite() {
+    let (input, output) = compact_single_oversize_l0(SingleOversizeFilePolicy::Rewrite).await;
+
+    // The file is rewritten as a single L1 file covering the same time range
+    assert_eq!(output.len(), 1);
+    assert_ne!(output[0].id, input.id);
+    assert_eq!(
+        output[0].compaction_level,
+        CompactionLevel::FileNonOverlapped
+    );
+    assert_eq!(output[0].min_time, input.min_time);
+    assert_eq!(output[0].max_time, input.max_time);
+}
+
+#[tokio::test]
+async fn single_oversize_l0_split() {
+    let (input, mut output) = compact_single_oversize_l0(SingleOversizeFilePolicy::Split).await;
+
+    // The file is split into three non-overlapping L1 files covering the same time range
+    assert_eq!(output.len(), 3);
+    output.sort_by_key(|f| f.min_time);
+    assert!(output
+        .iter()
+        .all(|f| f.id != input.id && f.compaction_level == CompactionLevel::FileNonOverlapped));
+    assert!(output.windows(2).all(|w| w[0].max_time < w[1].min_time));
+    assert_eq!(output[0].min_time, input.min_time);
+    assert_eq!(output[2].max_time, input.max_time);
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFileParams};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::TryStreamExt;
+
+use compactor::{DynError, ParquetFilesSink, PartitionInfo, PlanIR};
+
+/// A [`ParquetFilesSink`] that drops every row it is given, as if all rows of the input files
+/// had been deleted, and so never creates any file.
+#[derive(Debug, Default)]
+pub struct AllRowsDeletedSink;
+
+impl std::fmt::Display for AllRowsDeletedSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AllRowsDeletedSink")
+    }
+}
+
+#[async_trait]
+impl ParquetFilesSink for AllRowsDeletedSink {
+    async fn stream_into_file_sink(
+        &self,
+        streams: Vec<SendableRecordBatchStream>,
+        _partition_info: Arc<PartitionInfo>,
+        _target_level: CompactionLevel,
+        _plan_ir: &PlanIR,
+    ) -> Result<Vec<ParquetFileParams>, DynError> {
+        // still run the plan so that errors surface as they would when writing
+        for stream in streams {
+            stream.try_for_each(|_| async { Ok(()) }).await?;
+        }
+        Ok(vec![])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
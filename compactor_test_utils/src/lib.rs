@@ -17,6 +17,7 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+mod all_rows_deleted;
 mod commit_wrapper;
 mod display;
 mod simulator;
@@ -32,14 +33,16 @@ use std::{
 };
 
 use crate::{
+    all_rows_deleted::AllRowsDeletedSink,
     commit_wrapper::{CommitRecorderBuilder, InvariantCheck},
     simulator::ParquetFileSimulator,
 };
 use async_trait::async_trait;
 use backoff::BackoffConfig;
 use compactor::{
-    compact, config::Config, hardcoded_components, Components, PanicDataFusionPlanner,
-    PartitionInfo,
+    compact,
+    config::{Config, SingleOversizeFilePolicy, SizingOverrides, ZeroRowOutputPolicy},
+    hardcoded_components, Components, PanicDataFusionPlanner, PartitionInfo,
 };
 use compactor_scheduler::SchedulerConfig;
 use data_types::{
@@ -153,16 +156,28 @@ impl TestSetupBuilder<false> {
             exec: Arc::clone(&catalog.exec),
             backoff_config: BackoffConfig::default(),
             partition_concurrency: NonZeroUsize::new(1).unwrap(),
+            partition_concurrency_warm_up: None,
             df_concurrency: NonZeroUsize::new(1).unwrap(),
             partition_scratchpad_concurrency: NonZeroUsize::new(1).unwrap(),
+            input_read_concurrency: NonZeroUsize::new(1).unwrap(),
+            exec_target_partitions: None,
             max_desired_file_size_bytes: MAX_DESIRE_FILE_SIZE,
             percentage_max_file_size: PERCENTAGE_MAX_FILE_SIZE,
             split_percentage: SPLIT_PERCENTAGE,
+            table_sizing_overrides: HashMap::new(),
             partition_timeout: Duration::from_secs(3_600),
+            run_iteration_budget: None,
+            min_file_age: None,
+            rewrite_below_format_version: None,
+            single_oversize_file_policy: SingleOversizeFilePolicy::default(),
+            verify_output: false,
+            zero_row_output_policy: ZeroRowOutputPolicy::default(),
             shadow_mode: false,
             enable_scratchpad: true,
+            min_num_l0_files_to_compact: NonZeroUsize::new(1).unwrap(),
             min_num_l1_files_to_compact: MIN_NUM_L1_FILES_TO_COMPACT,
             process_once: true,
+            pause_file: None,
             simulate_without_object_store: false,
             parquet_files_sink_override: None,
             all_errors_are_fatal: true,
@@ -171,6 +186,8 @@ impl TestSetupBuilder<false> {
             max_partition_fetch_queries_per_second: None,
             gossip_bind_address: None,
             gossip_seeds: vec![],
+            status_endpoint: false,
+            completion_webhook_url: None,
         };
 
         let bytes_written = Arc::new(AtomicUsize::new(0));
@@ -554,12 +571,51 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
         self
     }
 
+    /// Read back the output files before committing them
+    pub fn with_verify_output(mut self) -> Self {
+        self.config.verify_output = true;
+        self
+    }
+
+    /// Drop all rows while compacting, as if all of them had been deleted
+    pub fn with_all_rows_deleted(mut self) -> Self {
+        self.config.parquet_files_sink_override = Some(Arc::new(AllRowsDeletedSink));
+        self
+    }
+
+    /// Set zero_row_output_policy
+    pub fn with_zero_row_output_policy(mut self, policy: ZeroRowOutputPolicy) -> Self {
+        self.config.zero_row_output_policy = policy;
+        self
+    }
+
+    /// set min_num_l0_files_to_compact
+    pub fn with_min_num_l0_files_to_compact(
+        mut self,
+        min_num_l0_files_to_compact: NonZeroUsize,
+    ) -> Self {
+        self.config.min_num_l0_files_to_compact = min_num_l0_files_to_compact;
+        self
+    }
+
     /// set min_num_l1_files_to_compact
     pub fn with_min_num_l1_files_to_compact(mut self, min_num_l1_files_to_compact: usize) -> Self {
         self.config.min_num_l1_files_to_compact = min_num_l1_files_to_compact;
         self
     }
 
+    /// Set input_read_concurrency
+    pub fn with_input_read_concurrency(mut self, input_read_concurrency: NonZeroUsize) -> Self {
+        self.config.input_read_concurrency = input_read_concurrency;
+        self
+    }
+
+    /// Set exec_target_partitions
+    pub fn with_exec_target_partitions(mut self, exec_target_partitions: NonZeroUsize) -> Self {
+        self.config.exec_target_partitions = Some(exec_target_partitions);
+        self
+    }
+
     /// Set max_num_files_per_plan;
     pub fn with_max_num_files_per_plan(mut self, max_num_files_per_plan: usize) -> Self {
         self.config.max_num_files_per_plan = max_num_files_per_plan;
@@ -601,6 +657,12 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
         self
     }
 
+    /// Set single_oversize_file_policy
+    pub fn with_single_oversize_file_policy(mut self, policy: SingleOversizeFilePolicy) -> Self {
+        self.config.single_oversize_file_policy = policy;
+        self
+    }
+
     /// Set split times required to be used
     pub fn with_required_split_times(self, required_split_times: Vec<i64>) -> Self {
         self.required_split_times
@@ -622,6 +684,18 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
         self
     }
 
+    /// Override the sizing configuration for `table`, as `<table>` or `<namespace>/<table>`
+    pub fn with_table_sizing_override(
+        mut self,
+        table: impl Into<String>,
+        overrides: SizingOverrides,
+    ) -> Self {
+        self.config
+            .table_sizing_overrides
+            .insert(table.into(), overrides);
+        self
+    }
+
     /// Set the compaction timeout
     pub fn with_partition_timeout(mut self, partition_timeout: Duration) -> Self {
         self.config.partition_timeout = partition_timeout;
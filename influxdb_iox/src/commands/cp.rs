@@ -0,0 +1,239 @@
+//! Implementation of command line option for copying a table between namespaces or servers
+
+use std::{future::Future, time::Duration};
+
+use arrow::{compute::cast, error::ArrowError, record_batch::RecordBatch};
+use data_types::ColumnType;
+use futures::TryStreamExt;
+use influxdb_iox_client::{connection::Connection, flight, write};
+use schema::{
+    builder::{Error as SchemaBuilderError, SchemaBuilder},
+    InfluxColumnType, Schema,
+};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::commands::sql_helpers::quote_identifier;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error reading the schema of the source table: {0}")]
+    Schema(#[from] influxdb_iox_client::error::Error),
+
+    #[error("Table '{0}' not found in the source namespace")]
+    TableNotFound(String),
+
+    #[error("Invalid schema for table '{0}': {1}")]
+    InvalidSchema(String, SchemaBuilderError),
+
+    #[error("Error querying the source: {0}")]
+    Query(#[from] influxdb_iox_client::flight::Error),
+
+    #[error("Error converting the queried rows: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("Error converting the queried rows to line protocol: {0}")]
+    LineProtocol(String),
+
+    #[error("Error writing to the destination: {0}")]
+    Write(influxdb_iox_client::error::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Copy the rows of a table to another namespace, possibly on another server
+///
+/// The rows are queried from the source querier and written to the
+/// destination router as line protocol, one batch at a time, preserving
+/// tags, fields and timestamps. The number of rows copied so far is printed
+/// to stderr after each batch, and the total as JSON once done.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// gRPC address of the querier to copy from
+    #[clap(long, action)]
+    from: String,
+
+    /// HTTP address of the router to copy to
+    #[clap(long, action)]
+    to: String,
+
+    /// The namespace to write to, if different from the source namespace
+    #[clap(long, action)]
+    to_namespace: Option<String>,
+
+    /// The table to copy
+    #[clap(long, action)]
+    table: String,
+
+    /// Only copy rows with a timestamp at most this long ago, e.g. `1h`
+    #[clap(long, value_parser = humantime::parse_duration)]
+    since: Option<Duration>,
+
+    /// The namespace to copy from
+    #[clap(action)]
+    namespace: String,
+}
+
+pub async fn command<C, CFut>(connection: C, config: Config) -> Result<()>
+where
+    C: Send + Fn(String) -> CFut,
+    CFut: Send + Future<Output = Connection>,
+{
+    let Config {
+        from,
+        to,
+        to_namespace,
+        table,
+        since,
+        namespace,
+    } = config;
+    let to_namespace = to_namespace.unwrap_or_else(|| namespace.clone());
+
+    let source = connection(from).await;
+    let namespace_schema = influxdb_iox_client::schema::Client::new(source.clone())
+        .get_schema(&namespace, Some(&table))
+        .await?;
+    let table_schema = namespace_schema
+        .tables
+        .get(&table)
+        .ok_or_else(|| Error::TableNotFound(table.clone()))?;
+
+    let mut columns: Vec<(String, ColumnType)> = table_schema
+        .columns
+        .iter()
+        .filter_map(|(name, column)| {
+            let column_type = ColumnType::try_from(column.column_type()).ok()?;
+            Some((name.clone(), column_type))
+        })
+        .collect();
+    columns.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    let iox_schema = iox_schema(&table, &columns)?;
+
+    let mut results = flight::Client::new(source)
+        .sql(namespace, copy_query(&table, &columns, since))
+        .await?;
+    let mut client = write::Client::new(connection(to).await);
+
+    let mut rows = 0;
+    while let Some(batch) = results.try_next().await? {
+        if batch.num_rows() == 0 {
+            continue;
+        }
+
+        let lp = to_line_protocol(&table, &iox_schema, &batch)?;
+        client
+            .write_lp(&to_namespace, lp)
+            .await
+            .map_err(Error::Write)?;
+
+        rows += batch.num_rows();
+        eprintln!("Copied {rows} rows");
+    }
+
+    println!("{:#}", json!({ "rows": rows }));
+    Ok(())
+}
+
+/// Build the IOx schema of `columns`, in the order they are selected by
+/// [`copy_query`].
+fn iox_schema(table: &str, columns: &[(String, ColumnType)]) -> Result<Schema> {
+    let mut builder = SchemaBuilder::new();
+    for (name, column_type) in columns {
+        builder.influx_column(name, InfluxColumnType::from(*column_type));
+    }
+    builder
+        .build()
+        .map_err(|e| Error::InvalidSchema(table.to_string(), e))
+}
+
+/// The query selecting the rows of `table` to copy.
+fn copy_query(table: &str, columns: &[(String, ColumnType)], since: Option<Duration>) -> String {
+    let columns = columns
+        .iter()
+        .map(|(name, _)| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut query = format!("SELECT {columns} FROM {}", quote_identifier(table));
+    if let Some(since) = since {
+        query.push_str(&format!(
+            " WHERE time >= now() - INTERVAL '{} seconds'",
+            since.as_secs()
+        ));
+    }
+    query
+}
+
+/// Convert `batch` to line protocol for `table`.
+///
+/// Query results are returned with tags as plain strings, so the columns are
+/// first cast back to the types of `iox_schema`.
+fn to_line_protocol(table: &str, iox_schema: &Schema, batch: &RecordBatch) -> Result<String> {
+    let arrow_schema = iox_schema.as_arrow();
+    let columns = arrow_schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| cast(column, field.data_type()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let batch = RecordBatch::try_new(arrow_schema, columns)?;
+
+    let lp = parquet_to_line_protocol::convert_to_lines(table, iox_schema, &batch)
+        .map_err(Error::LineProtocol)?;
+    String::from_utf8(lp).map_err(|e| Error::LineProtocol(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, StringArray, TimestampNanosecondArray};
+
+    use super::*;
+
+    fn columns() -> Vec<(String, ColumnType)> {
+        vec![
+            ("host".to_string(), ColumnType::Tag),
+            ("time".to_string(), ColumnType::Time),
+            ("usage".to_string(), ColumnType::F64),
+        ]
+    }
+
+    #[test]
+    fn test_copy_query() {
+        assert_eq!(
+            copy_query("cpu", &columns(), None),
+            r#"SELECT "host", "time", "usage" FROM "cpu""#
+        );
+        assert_eq!(
+            copy_query("cpu", &columns(), Some(Duration::from_secs(3_600))),
+            r#"SELECT "host", "time", "usage" FROM "cpu" WHERE time >= now() - INTERVAL '3600 seconds'"#
+        );
+    }
+
+    #[test]
+    fn test_to_line_protocol() {
+        let iox_schema = iox_schema("cpu", &columns()).unwrap();
+
+        // tags are returned as plain strings by the querier
+        let batch = RecordBatch::try_from_iter([
+            (
+                "host",
+                Arc::new(StringArray::from(vec![Some("a"), None])) as _,
+            ),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(vec![1, 2])) as _,
+            ),
+            (
+                "usage",
+                Arc::new(Float64Array::from(vec![Some(0.5), Some(1.0)])) as _,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            to_line_protocol("cpu", &iox_schema, &batch).unwrap(),
+            "cpu,host=a usage=0.5 1\ncpu usage=1 2\n"
+        );
+    }
+}
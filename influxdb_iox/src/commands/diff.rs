@@ -0,0 +1,237 @@
+//! Implementation of command line option for comparing query results from two servers
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    future::Future,
+};
+
+use arrow::{json::writer::record_batches_to_json_rows, record_batch::RecordBatch};
+use futures::TryStreamExt;
+use influxdb_iox_client::{connection::Connection, flight};
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error querying {0}: {1}")]
+    Query(&'static str, influxdb_iox_client::flight::Error),
+
+    #[error("Error converting {0} results: {1}")]
+    Arrow(&'static str, arrow::error::ArrowError),
+
+    #[error("Key column '{0}' is not in the {1} results")]
+    UnknownKeyColumn(String, &'static str),
+
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A result row, as a map of column name to value. Null values are omitted.
+type Row = Map<String, Value>;
+
+/// Run the same SQL query against two servers and report how the results
+/// differ
+///
+/// The diff is printed as a JSON object with three lists: rows only returned
+/// by the left server, rows only returned by the right server, and rows
+/// present on both sides with the same `--key` but different values. Pipe the
+/// output through `from json` to filter it as tables in nushell.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// gRPC address of the left server
+    #[clap(long, action)]
+    left: String,
+
+    /// gRPC address of the right server
+    #[clap(long, action)]
+    right: String,
+
+    /// Comma separated columns identifying a row.
+    ///
+    /// Rows with the same key but different values are reported as
+    /// differing. If not set, whole rows are compared, so a changed row is
+    /// reported as only in left and only in right.
+    #[clap(long, value_delimiter = ',', action)]
+    key: Vec<String>,
+
+    /// The IOx namespace to query on both servers
+    #[clap(action)]
+    namespace: String,
+
+    /// The query to run, in SQL format
+    #[clap(action)]
+    query: String,
+}
+
+pub async fn command<C, CFut>(connection: C, config: Config) -> Result<()>
+where
+    C: Send + Fn(String) -> CFut,
+    CFut: Send + Future<Output = Connection>,
+{
+    let Config {
+        left,
+        right,
+        key,
+        namespace,
+        query,
+    } = config;
+
+    let (left, right) = futures::future::join(
+        query_rows("left", connection(left), &namespace, &query, &key),
+        query_rows("right", connection(right), &namespace, &query, &key),
+    )
+    .await;
+
+    let diff = diff_rows(left?, right?, &key);
+    println!("{}", serde_json::to_string_pretty(&diff)?);
+
+    Ok(())
+}
+
+/// Run `query` against `connection`, returning the rows of the result.
+async fn query_rows(
+    side: &'static str,
+    connection: impl Future<Output = Connection> + Send,
+    namespace: &str,
+    query: &str,
+    key: &[String],
+) -> Result<Vec<Row>> {
+    let batches: Vec<RecordBatch> = flight::Client::new(connection.await)
+        .sql(namespace.to_string(), query.to_string())
+        .await
+        .map_err(|e| Error::Query(side, e))?
+        .try_collect()
+        .await
+        .map_err(|e| Error::Query(side, e))?;
+
+    if let Some(batch) = batches.first() {
+        let schema = batch.schema();
+        if let Some(column) = key.iter().find(|c| schema.column_with_name(c).is_none()) {
+            return Err(Error::UnknownKeyColumn(column.clone(), side));
+        }
+    }
+
+    record_batches_to_json_rows(&batches.iter().collect::<Vec<_>>())
+        .map_err(|e| Error::Arrow(side, e))
+}
+
+/// Compare the rows of both sides, pairing up rows by the values of the
+/// `key` columns, or by their entire content if `key` is empty.
+///
+/// Rows sharing a key are paired in the order they were returned.
+fn diff_rows(left: Vec<Row>, right: Vec<Row>, key: &[String]) -> Value {
+    // The string form of a row's key, allowing rows to be grouped by it.
+    let row_key = |row: &Row| {
+        if key.is_empty() {
+            let mut values: Vec<_> = row.iter().collect();
+            values.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            json!(values).to_string()
+        } else {
+            let values: Vec<_> = key.iter().map(|c| row.get(c)).collect();
+            json!(values).to_string()
+        }
+    };
+
+    let mut groups: BTreeMap<String, (VecDeque<Row>, VecDeque<Row>)> = BTreeMap::new();
+    for row in left {
+        groups.entry(row_key(&row)).or_default().0.push_back(row);
+    }
+    for row in right {
+        groups.entry(row_key(&row)).or_default().1.push_back(row);
+    }
+
+    let mut only_left = vec![];
+    let mut only_right = vec![];
+    let mut differing = vec![];
+    for (_, (mut left, mut right)) in groups {
+        while let (Some(l), Some(r)) = (left.front(), right.front()) {
+            if l != r {
+                let row_key: Row = key
+                    .iter()
+                    .filter_map(|c| Some((c.clone(), l.get(c)?.clone())))
+                    .collect();
+                differing.push(json!({
+                    "key": row_key,
+                    "left": l,
+                    "right": r,
+                }));
+            }
+            left.pop_front();
+            right.pop_front();
+        }
+        only_left.extend(left);
+        only_right.extend(right);
+    }
+
+    json!({
+        "only_left": only_left,
+        "only_right": only_right,
+        "differing": differing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(value: Value) -> Row {
+        match value {
+            Value::Object(row) => row,
+            _ => panic!("not an object: {value}"),
+        }
+    }
+
+    #[test]
+    fn diff_by_key() {
+        let left = vec![
+            row(json!({"host": "a", "usage": 1})),
+            row(json!({"host": "b", "usage": 2})),
+            row(json!({"host": "c", "usage": 3})),
+        ];
+        let right = vec![
+            row(json!({"host": "b", "usage": 20})),
+            row(json!({"host": "c", "usage": 3})),
+            row(json!({"host": "d", "usage": 4})),
+        ];
+
+        assert_eq!(
+            diff_rows(left, right, &["host".to_string()]),
+            json!({
+                "only_left": [{"host": "a", "usage": 1}],
+                "only_right": [{"host": "d", "usage": 4}],
+                "differing": [{
+                    "key": {"host": "b"},
+                    "left": {"host": "b", "usage": 2},
+                    "right": {"host": "b", "usage": 20},
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn diff_whole_rows() {
+        let left = vec![
+            row(json!({"host": "a", "usage": 1})),
+            row(json!({"host": "a", "usage": 1})),
+            row(json!({"host": "b", "usage": 2})),
+        ];
+        let right = vec![
+            row(json!({"usage": 1, "host": "a"})),
+            row(json!({"host": "b", "usage": 20})),
+        ];
+
+        assert_eq!(
+            diff_rows(left, right, &[]),
+            json!({
+                "only_left": [
+                    {"host": "a", "usage": 1},
+                    {"host": "b", "usage": 2},
+                ],
+                "only_right": [{"host": "b", "usage": 20}],
+                "differing": [],
+            })
+        );
+    }
+}
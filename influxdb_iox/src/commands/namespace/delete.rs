@@ -1,16 +1,30 @@
-use influxdb_iox_client::connection::Connection;
+use futures::TryStreamExt;
+use influxdb_iox_client::{connection::Connection, flight, schema};
 
-use crate::commands::namespace::Result;
+use crate::commands::{
+    namespace::Result,
+    sql_helpers::{first_row_count, quote_identifier},
+};
 
 #[derive(Debug, clap::Parser)]
 pub struct Config {
     /// The namespace to be deleted
     #[clap(action)]
     namespace: String,
+
+    /// Count the rows in each table of the namespace instead of deleting it.
+    ///
+    /// The counts are queried, so the target host must serve queries.
+    #[clap(long, action)]
+    dry_run: bool,
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
-    let Config { namespace } = config;
+    let Config { namespace, dry_run } = config;
+
+    if dry_run {
+        return preview(connection, namespace).await;
+    }
 
     let mut client = influxdb_iox_client::namespace::Client::new(connection);
 
@@ -19,3 +33,35 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
 
     Ok(())
 }
+
+/// Print how many rows deleting `namespace` would remove, without deleting it.
+async fn preview(connection: Connection, namespace: String) -> Result<()> {
+    let namespace_schema = schema::Client::new(connection.clone())
+        .get_schema(&namespace, None)
+        .await?;
+
+    let mut tables: Vec<_> = namespace_schema.tables.into_keys().collect();
+    tables.sort_unstable();
+
+    let mut client = flight::Client::new(connection);
+    let mut counts = Vec::with_capacity(tables.len());
+    for table in tables {
+        let sql = format!("SELECT count(*) FROM {}", quote_identifier(&table));
+        let batches: Vec<_> = client
+            .sql(namespace.clone(), sql)
+            .await?
+            .try_collect()
+            .await?;
+        counts.push((table, first_row_count(&batches, 0)));
+    }
+
+    let total: i64 = counts.iter().map(|(_, rows)| rows).sum();
+    println!(
+        "Dry run, nothing was deleted. Deleting namespace {namespace:?} would remove {total} rows:"
+    );
+    for (table, rows) in counts {
+        println!("  {table}: {rows}");
+    }
+
+    Ok(())
+}
@@ -16,6 +16,9 @@ pub enum Error {
 
     #[error("Client error: {0}")]
     ClientError(#[from] influxdb_iox_client::error::Error),
+
+    #[error("Query error: {0}")]
+    Query(#[from] influxdb_iox_client::flight::Error),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -1,10 +1,32 @@
-use arrow::record_batch::RecordBatch;
+use arrow::{
+    array::{make_array, timezone::Tz, Array},
+    datatypes::{DataType, Schema, SchemaRef},
+    error::ArrowError,
+    ipc::writer::StreamWriter,
+    record_batch::RecordBatch,
+};
 use clap::ValueEnum;
-use futures::TryStreamExt;
+use futures::{Stream, TryStreamExt};
 use influxdb_iox_client::format::influxql::{write_columnar, Options};
-use influxdb_iox_client::{connection::Connection, flight, format::QueryOutputFormat};
+use influxdb_iox_client::{
+    connection::Connection,
+    flight,
+    format::{batches_to_csv, CsvOptions, QueryOutputFormat},
+};
+use parquet::arrow::ArrowWriter;
+use serde_json::json;
+use std::{
+    fs::File,
+    io::Write,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
+use super::sql_helpers::{first_row_count, parse_query};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Error formatting: {0}")]
@@ -15,10 +37,48 @@ pub enum Error {
 
     #[error("Error formatting InfluxQL: {0}")]
     InfluxQlFormatting(#[from] influxdb_iox_client::format::influxql::Error),
+
+    #[error("Error writing raw query results: {0}")]
+    Raw(#[from] arrow::error::ArrowError),
+
+    #[error("The query already contains {0}; remove it or the --{1} option")]
+    DuplicateClause(&'static str, &'static str),
+
+    #[error("--count is only supported for SQL queries")]
+    CountRequiresSql,
+
+    #[error("--count is only supported for SELECT queries")]
+    CountRequiresSelect,
+
+    #[error("Error writing query results: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Error writing output file {0:?}: {1}")]
+    OutputFile(PathBuf, std::io::Error),
+
+    #[error("Error writing Parquet output file: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[error("Invalid time zone {0:?}: {1}")]
+    InvalidTimeZone(String, arrow::error::ArrowError),
+
+    #[error("The query returned no rows")]
+    AssertNonEmpty,
+
+    #[error("The query returned {0} rows, expected none")]
+    AssertEmpty(usize),
+
+    #[error(
+        "The query results exceed {0} bytes; add a LIMIT clause, use --limit or raise --limit-bytes"
+    )]
+    LimitBytes(usize),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// Environment variable providing the default for `--tz`.
+const TZ_ENV: &str = "INFLUXDB_IOX_TZ";
+
 #[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
 #[clap(rename_all = "lower")]
 enum QueryLanguage {
@@ -40,13 +100,138 @@ pub struct Config {
     query: String,
 
     /// Output format of the query results
-    #[clap(short, long, action)]
-    #[clap(value_enum, default_value_t = OutputFormat::Pretty)]
+    ///
+    /// Ignored with `--raw`, `--count` and `--measure`, so that a default set
+    /// in the environment does not conflict with them.
+    #[clap(
+        short,
+        long,
+        env = "INFLUXDB_IOX_FORMAT",
+        default_value = "pretty",
+        action,
+        value_enum
+    )]
     format: OutputFormat,
 
+    /// Whether to write a header row with the column names, with
+    /// `--format csv`.
+    #[clap(long, value_name = "BOOL", default_value = "true", action = clap::ArgAction::Set)]
+    csv_header: bool,
+
+    /// The field delimiter, with `--format csv`. Must be a single ASCII
+    /// character, e.g. `;` for tools expecting European-locale CSV.
+    #[clap(long, value_name = "CHAR", default_value = ",", value_parser = parse_csv_char)]
+    csv_delimiter: u8,
+
+    /// The character fields are quoted with when needed, with
+    /// `--format csv`. Must be a single ASCII character.
+    #[clap(long, value_name = "CHAR", default_value = "\"", value_parser = parse_csv_char)]
+    csv_quote: u8,
+
+    /// Display timestamps in this time zone, either as a fixed offset such as
+    /// `+05:30` or as an IANA name such as `Europe/London` or `UTC`.
+    ///
+    /// If not specified, the time zone is taken from the `INFLUXDB_IOX_TZ`
+    /// environment variable. If that is not set either, timestamps are
+    /// displayed as returned by the server. Only affects formatted output;
+    /// `.parquet` output files always store the timestamps as returned.
+    #[clap(long, action, conflicts_with_all = ["raw", "count", "measure"])]
+    tz: Option<String>,
+
     /// Query type used
     #[clap(short = 'l', long = "lang", default_value = "sql")]
     query_lang: QueryLanguage,
+
+    /// Write the query results exactly as returned by the server, as an
+    /// Arrow IPC stream, without any formatting or type conversion.
+    ///
+    /// This is an escape hatch for scripting and debugging.
+    #[clap(long, action)]
+    raw: bool,
+
+    /// Return at most this many rows, by appending a `LIMIT` clause to the
+    /// query.
+    ///
+    /// It is an error to use this option if the query already contains a
+    /// `LIMIT` clause.
+    #[clap(long, action)]
+    limit: Option<usize>,
+
+    /// Skip this many rows, by appending an `OFFSET` clause to the query.
+    ///
+    /// Combine with `--limit` to page through large results. It is an error
+    /// to use this option if the query already contains an `OFFSET` clause.
+    #[clap(long, action)]
+    offset: Option<usize>,
+
+    /// Print only the number of rows the query returns.
+    ///
+    /// The row count is computed by the server, by wrapping the query in
+    /// `SELECT count(*) FROM (<query>)`, so no rows are transferred. Only
+    /// SQL `SELECT` queries are supported.
+    #[clap(
+        long,
+        action,
+        conflicts_with_all = ["raw", "limit", "offset"]
+    )]
+    count: bool,
+
+    /// Benchmark the query instead of printing its results.
+    ///
+    /// The query is run once to warm up, then N more times (5 if not
+    /// specified), discarding the results. The minimum, median, maximum and
+    /// mean latency of the measured runs, and the number of rows returned, are
+    /// printed as JSON. Each run is subject to `--rpc-timeout`.
+    #[clap(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "5",
+        conflicts_with_all = ["raw", "count"]
+    )]
+    measure: Option<NonZeroUsize>,
+
+    /// Write the query results to this file instead of printing them.
+    ///
+    /// Files with a `.parquet` extension are written as Parquet, using the
+    /// Arrow schema of the results so that column types and nulls are
+    /// preserved. Other files are written in `--format`. A summary of the
+    /// written file, with its row and byte counts and schema, is printed as
+    /// JSON.
+    #[clap(long, action, conflicts_with_all = ["raw", "count", "measure"])]
+    output_file: Option<PathBuf>,
+
+    /// Fail if the query returns no rows.
+    ///
+    /// The command exits with a non-zero status without printing any results,
+    /// so it can be used as a guard in scripts, e.g. for health probes.
+    #[clap(
+        long,
+        action,
+        conflicts_with_all = ["assert_empty", "count", "measure"]
+    )]
+    assert_nonempty: bool,
+
+    /// Fail if the query returns any rows.
+    ///
+    /// The inverse of `--assert-nonempty`.
+    #[clap(long, action, conflicts_with_all = ["count", "measure"])]
+    assert_empty: bool,
+
+    /// Abort the query once its results take up more than this many bytes
+    /// in memory, to avoid accidentally pulling huge results into the
+    /// terminal. 0 disables the limit.
+    ///
+    /// The results are checked while they are streamed from the server, so
+    /// the query stops early. Does not apply to `--count` and `--measure`,
+    /// which do not keep the results.
+    #[clap(
+        long,
+        env = "INFLUXDB_IOX_QUERY_LIMIT_BYTES",
+        default_value = "1073741824",
+        action
+    )]
+    limit_bytes: usize,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -80,9 +265,76 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let Config {
         namespace,
         format,
+        csv_header,
+        csv_delimiter,
+        csv_quote,
         query,
         query_lang,
+        raw,
+        limit,
+        offset,
+        count,
+        measure,
+        output_file,
+        tz,
+        assert_nonempty,
+        assert_empty,
+        limit_bytes,
     } = config;
+    let csv = CsvOptions {
+        header: csv_header,
+        delimiter: csv_delimiter,
+        quote: csv_quote,
+    };
+
+    // Explicit flags take precedence over the environment.
+    let tz = tz.or_else(|| std::env::var(TZ_ENV).ok());
+    if let Some(tz) = &tz {
+        tz.parse::<Tz>()
+            .map_err(|e| Error::InvalidTimeZone(tz.clone(), e))?;
+    }
+
+    if count {
+        if query_lang != QueryLanguage::Sql {
+            return Err(Error::CountRequiresSql);
+        }
+
+        let batches: Vec<_> = client
+            .sql(namespace, count_query(&query)?)
+            .await?
+            .try_collect()
+            .await?;
+        let count = first_row_count(&batches, 0);
+
+        println!("{count}");
+        return Ok(());
+    }
+
+    let query = paginate(query, limit, offset)?;
+
+    if let Some(runs) = measure {
+        // Warm up
+        let rows = run_query(&mut client, &query_lang, &namespace, &query).await?;
+
+        let mut latencies = Vec::with_capacity(runs.get());
+        for _ in 0..runs.get() {
+            let start = Instant::now();
+            run_query(&mut client, &query_lang, &namespace, &query).await?;
+            latencies.push(start.elapsed());
+        }
+
+        let summary = LatencySummary::new(latencies);
+        let record = json!({
+            "runs": runs.get(),
+            "rows": rows,
+            "min_ms": as_millis(summary.min),
+            "median_ms": as_millis(summary.median),
+            "max_ms": as_millis(summary.max),
+            "mean_ms": as_millis(summary.mean),
+        });
+        println!("{record:#}");
+        return Ok(());
+    }
 
     let mut query_results = match query_lang {
         QueryLanguage::Sql => client.sql(namespace, query).await,
@@ -91,7 +343,18 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
 
     // It might be nice to do some sort of streaming write
     // rather than buffering the whole thing.
-    let mut batches: Vec<_> = (&mut query_results).try_collect().await?;
+    let mut batches = collect_limited(&mut query_results, limit_bytes).await?;
+    let num_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+    check_row_count(num_rows, assert_nonempty, assert_empty)?;
+
+    if let Some(limit) = limit {
+        if num_rows >= limit {
+            let next = offset.unwrap_or_default() + limit;
+            eprintln!(
+                "Results truncated to {limit} rows, use --offset {next} to see the next page"
+            );
+        }
+    }
 
     // read schema AFTER collection, otherwise the stream does not have the schema data yet
     let schema = query_results
@@ -100,19 +363,437 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         .cloned()
         .ok_or(influxdb_iox_client::flight::Error::NoSchema)?;
 
+    if raw {
+        let mut writer = StreamWriter::try_new(std::io::stdout(), &schema)?;
+        for batch in &batches {
+            writer.write(batch)?;
+        }
+        writer.finish()?;
+        return Ok(());
+    }
+
+    if let Some(path) = output_file {
+        let is_parquet = path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("parquet"));
+
+        if is_parquet {
+            write_parquet(&path, &schema, &batches)?;
+        } else {
+            batches.push(RecordBatch::new_empty(Arc::clone(&schema)));
+            if let Some(tz) = &tz {
+                batches = with_time_zone(batches, tz)?;
+            }
+            let file = File::create(&path).map_err(|e| Error::OutputFile(path.clone(), e))?;
+            write_formatted(file, query_lang, format, csv, &batches).map_err(|e| match e {
+                Error::Io(e) => Error::OutputFile(path.clone(), e),
+                e => e,
+            })?;
+        }
+
+        let bytes = std::fs::metadata(&path)
+            .map_err(|e| Error::OutputFile(path.clone(), e))?
+            .len();
+        let summary = json!({
+            "path": path,
+            "rows": num_rows,
+            "bytes": bytes,
+            "schema": schema_summary(&schema),
+        });
+        println!("{summary:#}");
+        return Ok(());
+    }
+
     // preserve schema so we print table headers even for empty results
     batches.push(RecordBatch::new_empty(schema));
+    if let Some(tz) = &tz {
+        batches = with_time_zone(batches, tz)?;
+    }
 
+    write_formatted(std::io::stdout(), query_lang, format, csv, &batches)
+}
+
+/// Parse a CSV option that must be a single ASCII character.
+fn parse_csv_char(s: &str) -> Result<u8, String> {
+    match s.as_bytes() {
+        [c] => Ok(*c),
+        _ => Err(format!("expected a single ASCII character, got {s:?}")),
+    }
+}
+
+/// Collect the record batches of `results`, failing as soon as they take up
+/// more than `limit_bytes` bytes in memory, unless it is 0.
+async fn collect_limited<S>(results: &mut S, limit_bytes: usize) -> Result<Vec<RecordBatch>>
+where
+    S: Stream<Item = Result<RecordBatch, flight::Error>> + Unpin,
+{
+    let mut batches = vec![];
+    let mut bytes = 0;
+    while let Some(batch) = results.try_next().await? {
+        bytes += batch.get_array_memory_size();
+        if limit_bytes > 0 && bytes > limit_bytes {
+            return Err(Error::LimitBytes(limit_bytes));
+        }
+        batches.push(batch);
+    }
+    Ok(batches)
+}
+
+/// Check the number of rows returned by the query against the `--assert-*`
+/// options.
+fn check_row_count(num_rows: usize, assert_nonempty: bool, assert_empty: bool) -> Result<()> {
+    if assert_nonempty && num_rows == 0 {
+        return Err(Error::AssertNonEmpty);
+    }
+    if assert_empty && num_rows > 0 {
+        return Err(Error::AssertEmpty(num_rows));
+    }
+    Ok(())
+}
+
+/// Mark all timestamp columns in `batches` as being in time zone `tz`, so
+/// they are displayed in that zone.
+///
+/// The timestamp values themselves are not changed, as they are UTC
+/// regardless of the time zone they are displayed in.
+fn with_time_zone(batches: Vec<RecordBatch>, tz: &str) -> Result<Vec<RecordBatch>> {
+    batches
+        .into_iter()
+        .map(|batch| {
+            let (fields, columns): (Vec<_>, Vec<_>) = batch
+                .schema()
+                .fields()
+                .iter()
+                .zip(batch.columns())
+                .map(|(field, column)| match field.data_type() {
+                    DataType::Timestamp(unit, _) => {
+                        let data_type = DataType::Timestamp(*unit, Some(tz.into()));
+                        let data = column.to_data().into_builder().data_type(data_type.clone());
+                        Ok((
+                            Arc::new(field.as_ref().clone().with_data_type(data_type)),
+                            make_array(data.build()?),
+                        ))
+                    }
+                    _ => Ok((Arc::clone(field), Arc::clone(column))),
+                })
+                .collect::<Result<Vec<_>, ArrowError>>()?
+                .into_iter()
+                .unzip();
+            let schema = Schema::new_with_metadata(fields, batch.schema().metadata().clone());
+            Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+        })
+        .collect()
+}
+
+/// Write `batches` to `out` in `format`, as appropriate for `query_lang`.
+///
+/// `csv` is only used for the CSV format.
+fn write_formatted(
+    mut out: impl Write,
+    query_lang: QueryLanguage,
+    format: OutputFormat,
+    csv: CsvOptions,
+    batches: &[RecordBatch],
+) -> Result<()> {
     match (query_lang, &format) {
         (QueryLanguage::InfluxQL, OutputFormat::Pretty) => {
-            write_columnar(std::io::stdout(), &batches, Options::default())?
+            write_columnar(out, batches, Options::default())?
+        }
+        (_, OutputFormat::Csv) => {
+            let formatted_result = batches_to_csv(batches, csv)?;
+            writeln!(out, "{formatted_result}")?;
         }
         _ => {
             let format: QueryOutputFormat = format.into();
-            let formatted_result = format.format(&batches)?;
-            println!("{formatted_result}");
+            let formatted_result = format.format(batches)?;
+            writeln!(out, "{formatted_result}")?;
         }
     }
 
     Ok(())
 }
+
+/// Write `batches` to a new Parquet file at `path`.
+fn write_parquet(path: &Path, schema: &SchemaRef, batches: &[RecordBatch]) -> Result<()> {
+    let file = File::create(path).map_err(|e| Error::OutputFile(path.to_path_buf(), e))?;
+    let mut writer = ArrowWriter::try_new(file, Arc::clone(schema), None)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+/// Describe the columns of `schema`, as included in the output file summary.
+fn schema_summary(schema: &SchemaRef) -> serde_json::Value {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            json!({
+                "name": field.name(),
+                "type": field.data_type().to_string(),
+                "nullable": field.is_nullable(),
+            })
+        })
+        .collect()
+}
+
+/// Run `query`, discarding the results, and return the number of rows it returned.
+async fn run_query(
+    client: &mut flight::Client,
+    query_lang: &QueryLanguage,
+    namespace: &str,
+    query: &str,
+) -> Result<usize> {
+    let (namespace, query) = (namespace.to_string(), query.to_string());
+    let mut results = match query_lang {
+        QueryLanguage::Sql => client.sql(namespace, query).await,
+        QueryLanguage::InfluxQL => client.influxql(namespace, query).await,
+    }?;
+
+    let mut rows = 0;
+    while let Some(batch) = results.try_next().await? {
+        rows += batch.num_rows();
+    }
+    Ok(rows)
+}
+
+/// Summary statistics of the latencies of a set of query runs.
+#[derive(Debug, PartialEq)]
+struct LatencySummary {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+    mean: Duration,
+}
+
+impl LatencySummary {
+    /// Summarise `latencies`, which must not be empty.
+    fn new(mut latencies: Vec<Duration>) -> Self {
+        latencies.sort_unstable();
+        let n = latencies.len();
+        let median = if n % 2 == 0 {
+            (latencies[n / 2 - 1] + latencies[n / 2]) / 2
+        } else {
+            latencies[n / 2]
+        };
+
+        Self {
+            min: latencies[0],
+            median,
+            max: latencies[n - 1],
+            mean: latencies.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+fn as_millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Append `LIMIT` and `OFFSET` clauses to `query` for the given options.
+fn paginate(query: String, limit: Option<usize>, offset: Option<usize>) -> Result<String> {
+    if limit.is_none() && offset.is_none() {
+        return Ok(query);
+    }
+
+    // Only the clauses of the query itself matter, not those of subqueries.
+    // Queries that do not parse are left for the server to report on.
+    if let Some(parsed) = parse_query(&query) {
+        if limit.is_some() && parsed.limit.is_some() {
+            return Err(Error::DuplicateClause("LIMIT", "limit"));
+        }
+        if offset.is_some() && parsed.offset.is_some() {
+            return Err(Error::DuplicateClause("OFFSET", "offset"));
+        }
+    }
+
+    let mut query = query.trim_end().trim_end_matches(';').to_string();
+    if let Some(limit) = limit {
+        query.push_str(&format!(" LIMIT {limit}"));
+    }
+    if let Some(offset) = offset {
+        query.push_str(&format!(" OFFSET {offset}"));
+    }
+
+    Ok(query)
+}
+
+/// Wrap the SQL `query` so that it returns its number of rows.
+fn count_query(query: &str) -> Result<String> {
+    if parse_query(query).is_none() {
+        return Err(Error::CountRequiresSelect);
+    }
+
+    let query = query.trim().trim_end_matches(';');
+
+    Ok(format!("SELECT count(*) FROM ({query})"))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int64Array;
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn measure_parses_optional_value() {
+        let measure = |args: &[&str]| {
+            let args = ["query", "ns", "SELECT 1"].iter().chain(args);
+            Config::try_parse_from(args).unwrap().measure
+        };
+
+        assert_eq!(measure(&[]), None);
+        assert_eq!(measure(&["--measure"]), NonZeroUsize::new(5));
+        assert_eq!(measure(&["--measure", "3"]), NonZeroUsize::new(3));
+        assert_eq!(measure(&["--measure=3"]), NonZeroUsize::new(3));
+        assert!(Config::try_parse_from(["query", "ns", "SELECT 1", "--measure", "0"]).is_err());
+    }
+
+    #[test]
+    fn csv_options_parse() {
+        let csv = |args: &[&str]| {
+            let args = ["query", "ns", "SELECT 1"].iter().chain(args);
+            Config::try_parse_from(args).map(|c| (c.csv_header, c.csv_delimiter, c.csv_quote))
+        };
+
+        assert_eq!(csv(&[]).unwrap(), (true, b',', b'"'));
+        assert_eq!(
+            csv(&[
+                "--csv-header",
+                "false",
+                "--csv-delimiter",
+                ";",
+                "--csv-quote",
+                "'"
+            ])
+            .unwrap(),
+            (false, b';', b'\'')
+        );
+        csv(&["--csv-delimiter", ";;"]).unwrap_err();
+        csv(&["--csv-delimiter", ""]).unwrap_err();
+        csv(&["--csv-quote", "é"]).unwrap_err();
+    }
+
+    #[test]
+    fn latency_summary() {
+        let ms = Duration::from_millis;
+
+        assert_eq!(
+            LatencySummary::new(vec![ms(30), ms(10), ms(20)]),
+            LatencySummary {
+                min: ms(10),
+                median: ms(20),
+                max: ms(30),
+                mean: ms(20),
+            }
+        );
+        assert_eq!(
+            LatencySummary::new(vec![ms(40), ms(10), ms(20), ms(10)]),
+            LatencySummary {
+                min: ms(10),
+                median: ms(15),
+                max: ms(40),
+                mean: ms(20),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_limited_stops_at_limit() {
+        let batch =
+            RecordBatch::try_from_iter([("i", Arc::new(Int64Array::from(vec![1; 100])) as _)])
+                .unwrap();
+        let size = batch.get_array_memory_size();
+        let results = || futures::stream::iter([Ok(batch.clone()), Ok(batch.clone())]);
+
+        // no limit
+        assert_eq!(collect_limited(&mut results(), 0).await.unwrap().len(), 2);
+        // all batches fit
+        assert_eq!(
+            collect_limited(&mut results(), 2 * size)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        // the second batch exceeds the limit
+        assert!(matches!(
+            collect_limited(&mut results(), 2 * size - 1).await,
+            Err(Error::LimitBytes(limit)) if limit == 2 * size - 1
+        ));
+    }
+
+    #[test]
+    fn row_count_assertions() {
+        check_row_count(0, false, false).unwrap();
+        check_row_count(1, false, false).unwrap();
+
+        check_row_count(1, true, false).unwrap();
+        assert!(matches!(
+            check_row_count(0, true, false),
+            Err(Error::AssertNonEmpty)
+        ));
+
+        check_row_count(0, false, true).unwrap();
+        assert!(matches!(
+            check_row_count(3, false, true),
+            Err(Error::AssertEmpty(3))
+        ));
+    }
+
+    #[test]
+    fn count_query_wraps_select() {
+        assert_eq!(
+            count_query("select * from cpu where host = 'a';").unwrap(),
+            "SELECT count(*) FROM (select * from cpu where host = 'a')"
+        );
+        assert_eq!(
+            count_query("WITH t AS (SELECT * FROM cpu) SELECT * FROM t").unwrap(),
+            "SELECT count(*) FROM (WITH t AS (SELECT * FROM cpu) SELECT * FROM t)"
+        );
+        assert!(matches!(
+            count_query("EXPLAIN select * from cpu"),
+            Err(Error::CountRequiresSelect)
+        ));
+    }
+
+    #[test]
+    fn paginate_appends_clauses() {
+        let query = "SELECT * FROM cpu;".to_string();
+
+        assert_eq!(paginate(query.clone(), None, None).unwrap(), query);
+        assert_eq!(
+            paginate(query.clone(), Some(10), None).unwrap(),
+            "SELECT * FROM cpu LIMIT 10"
+        );
+        assert_eq!(
+            paginate(query, Some(10), Some(20)).unwrap(),
+            "SELECT * FROM cpu LIMIT 10 OFFSET 20"
+        );
+    }
+
+    #[test]
+    fn paginate_rejects_existing_clauses() {
+        let query = "select * from cpu limit 5".to_string();
+
+        assert!(matches!(
+            paginate(query.clone(), Some(10), None),
+            Err(Error::DuplicateClause("LIMIT", "limit"))
+        ));
+        // an OFFSET may still be added to a query with its own LIMIT
+        assert_eq!(
+            paginate(query, None, Some(5)).unwrap(),
+            "select * from cpu limit 5 OFFSET 5"
+        );
+
+        // clauses of subqueries, and words in literals or identifiers, are not
+        let query = "SELECT * FROM (SELECT * FROM cpu LIMIT 5) WHERE \"limit\" = 'offset'";
+        assert_eq!(
+            paginate(query.to_string(), Some(10), Some(20)).unwrap(),
+            format!("{query} LIMIT 10 OFFSET 20")
+        );
+    }
+}
@@ -48,7 +48,13 @@ pub struct Config {
     predicate_base64: Option<String>,
 
     /// Optional format ('pretty', 'json', or 'csv')
-    #[clap(short, long, default_value = "pretty", action)]
+    #[clap(
+        short,
+        long,
+        env = "INFLUXDB_IOX_FORMAT",
+        default_value = "pretty",
+        action
+    )]
     format: String,
 }
 
@@ -13,7 +13,7 @@ use clap_blocks::{
     memory_size::MemorySize,
     object_store::{make_object_store, ObjectStoreConfig},
     querier::QuerierConfig,
-    router::RouterConfig,
+    router::{DuplicateTagPolicy, OutsideRetentionPolicy, RouterConfig, ShardAssignment},
     run_config::RunConfig,
     single_tenant::{
         CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG, CONFIG_CST_ENV_NAME, CONFIG_CST_FLAG,
@@ -483,6 +483,7 @@ impl Config {
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
+            persist_memory_threshold_bytes: None,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
             gossip_config: GossipConfig::disabled(),
             max_partitions_per_namespace: None,
@@ -490,15 +491,36 @@ impl Config {
 
         let router_config = RouterConfig {
             authz_address: authz_address.clone(),
+            authz_cache_ttl: Duration::ZERO,
             single_tenant_deployment,
             http_request_limit: 1_000,
+            http_max_connections: 10_000,
+            http_keep_alive_timeout_seconds: Duration::from_secs(60),
+            http_parse_threads: None,
+            duplicate_tag_policy: DuplicateTagPolicy::Reject,
+            max_line_bytes: 1024 * 1024,
+            max_fields_per_line: 10_000,
+            http_write_deadline_seconds: None,
+            http_streaming_write_segment_bytes: None,
+            http_dead_letter_dir: None,
+            http_dead_letter_object_store_prefix: None,
+            http_dead_letter_max_payload_bytes: 64 * 1024,
+            http_dead_letter_redact: false,
+            metrics_bind_address: None,
             ingester_addresses: ingester_addresses.clone(),
             new_namespace_retention_hours: None, // infinite retention
             namespace_autocreation_enabled: true,
+            namespace_name_pattern: Default::default(),
+            measurement_allow_pattern: None,
+            measurement_deny_pattern: None,
+            outside_retention_policy: OutsideRetentionPolicy::Reject,
             rpc_write_timeout_seconds: Duration::new(3, 0),
             rpc_write_replicas: 1.try_into().unwrap(),
             rpc_write_max_outgoing_bytes: ingester_config.rpc_write_max_incoming_bytes,
             rpc_write_health_num_probes: 10,
+            rpc_write_shard_assignment: ShardAssignment::RoundRobin,
+            allow_sync_writes: false,
+            sync_write_timeout_seconds: Duration::from_secs(30),
             gossip_config: GossipConfig::disabled(),
         };
 
@@ -508,22 +530,37 @@ impl Config {
         let compactor_config = CompactorConfig {
             compactor_scheduler_config,
             compaction_partition_concurrency: compactor_concurrency,
+            compaction_partition_concurrency_warm_up_secs: None,
             compaction_df_concurrency: compactor_concurrency,
             compaction_partition_scratchpad_concurrency: compactor_concurrency,
+            compaction_input_read_concurrency: compactor_concurrency,
+            compaction_exec_target_partitions: None,
             query_exec_thread_count: Some(num_threads),
             exec_mem_pool_bytes,
             exec_mem_pool_percent: 70,
             max_desired_file_size_bytes: 100 * 1024 * 1024, // 100 MB
             percentage_max_file_size: 30,
             split_percentage: 80,
+            table_sizing_overrides: vec![],
             partition_timeout_secs: 30 * 60, // 30 minutes
+            run_iteration_budget_secs: None,
+            min_file_age_secs: None,
+            read_after_write_timeout_secs: None,
+            rewrite_below_format_version: None,
+            single_oversize_file_policy: Default::default(),
+            verify_output: false,
+            zero_row_output_policy: Default::default(),
             shadow_mode: false,
             enable_scratchpad: true,
+            min_num_l0_files_to_compact: NonZeroUsize::new(1).unwrap(),
             min_num_l1_files_to_compact: 1,
             process_once: false,
+            pause_file: None,
             max_num_columns_per_table: 200,
             max_num_files_per_plan: 200,
             max_partition_fetch_queries_per_second: Some(500),
+            status_endpoint: false,
+            completion_webhook_url: None,
             gossip_config: GossipConfig::disabled(),
         };
 
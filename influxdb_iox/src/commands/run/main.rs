@@ -118,6 +118,8 @@ pub async fn main(
         let Service {
             http_bind_address,
             grpc_bind_address,
+            metrics_bind_address,
+            http_connection_limits,
             server_type,
         } = service;
         let server_type_name = format!("{server_type:?}");
@@ -141,11 +143,21 @@ pub async fn main(
                 }
             };
 
+            let metrics_listener = match metrics_bind_address {
+                Some(metrics_bind_address) => {
+                    info!(?metrics_bind_address, ?server_type, "Binding http metrics");
+                    Some(http_listener(metrics_bind_address.into()).await?)
+                }
+                None => None,
+            };
+
             let r = serve(
                 common_state,
                 frontend_shutdown,
                 grpc_listener,
                 http_listener,
+                metrics_listener,
+                http_connection_limits,
                 Arc::clone(&server_type),
             )
             .await;
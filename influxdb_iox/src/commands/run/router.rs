@@ -7,6 +7,7 @@ use clap_blocks::{
 };
 use iox_time::{SystemProvider, TimeProvider};
 use ioxd_common::{
+    http::connection::HttpConnectionLimits,
     server_type::{CommonServerState, CommonServerStateError},
     Service,
 };
@@ -109,6 +110,11 @@ pub async fn command(config: Config) -> Result<()> {
     .await?;
 
     info!("starting router");
-    let services = vec![Service::create(server_type, common_state.run_config())];
+    let services = vec![Service::create(server_type, common_state.run_config())
+        .with_metrics_bind_address(config.router_config.metrics_bind_address)
+        .with_http_connection_limits(HttpConnectionLimits {
+            max_connections: Some(config.router_config.http_max_connections),
+            keep_alive_timeout: Some(config.router_config.http_keep_alive_timeout_seconds),
+        })];
     Ok(main::main(common_state, services, metrics).await?)
 }
@@ -20,8 +20,14 @@ pub struct Config {
     /// Format to use for output. Can be overridden using
     /// `SET FORMAT` command
     ///
-    /// Optional format ('pretty', 'json', or 'csv')
-    #[clap(short, long, default_value = "pretty", action)]
+    /// Optional format ('pretty', 'table', 'json', or 'csv')
+    #[clap(
+        short,
+        long,
+        env = "INFLUXDB_IOX_FORMAT",
+        default_value = "pretty",
+        action
+    )]
     format: String,
 }
 
@@ -0,0 +1,96 @@
+//! Helpers for building the SQL queries run by the CLI commands
+
+use arrow::{
+    array::{Array, Int64Array},
+    record_batch::RecordBatch,
+};
+use datafusion::sql::{
+    parser::{DFParser, Statement},
+    sqlparser::ast::{Query, Statement as SqlStatement},
+};
+
+/// Quote `name` as a SQL identifier, so that it is used verbatim whatever its
+/// case and the characters it contains.
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Read the count in `column` of the first row of `batches`, the result of a
+/// query returning a single row of counts.
+///
+/// Returns 0 if there are no rows or the column is not a count.
+pub fn first_row_count(batches: &[RecordBatch], column: usize) -> i64 {
+    batches
+        .iter()
+        .find(|batch| batch.num_rows() > 0)
+        .and_then(|batch| batch.columns().get(column))
+        .and_then(|array| array.as_any().downcast_ref::<Int64Array>())
+        .map(|array| array.value(0))
+        .unwrap_or_default()
+}
+
+/// Parse `sql` with DataFusion's SQL parser, returning the query if `sql` is
+/// a single query statement, such as `SELECT ...` or `WITH ... SELECT ...`.
+pub fn parse_query(sql: &str) -> Option<Box<Query>> {
+    let mut statements = DFParser::parse_sql(sql).ok()?;
+    if statements.len() != 1 {
+        return None;
+    }
+
+    match statements.pop_front()? {
+        Statement::Statement(statement) => match *statement {
+            SqlStatement::Query(query) => Some(query),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("cpu"), r#""cpu""#);
+        assert_eq!(quote_identifier("CPU load"), r#""CPU load""#);
+        assert_eq!(quote_identifier(r#"te"mp"#), r#""te""mp""#);
+    }
+
+    #[test]
+    fn test_first_row_count() {
+        let empty = RecordBatch::try_from_iter([(
+            "count",
+            Arc::new(Int64Array::from(Vec::<i64>::new())) as _,
+        )])
+        .unwrap();
+        let counts = RecordBatch::try_from_iter([
+            ("count", Arc::new(Int64Array::from(vec![3])) as _),
+            ("other", Arc::new(Int64Array::from(vec![5])) as _),
+        ])
+        .unwrap();
+        let batches = [empty, counts];
+
+        assert_eq!(first_row_count(&batches, 0), 3);
+        assert_eq!(first_row_count(&batches, 1), 5);
+        assert_eq!(first_row_count(&batches, 2), 0);
+        assert_eq!(first_row_count(&[], 0), 0);
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query("select * from cpu limit 5;").unwrap();
+        assert!(query.limit.is_some());
+        assert!(query.offset.is_none());
+
+        // a LIMIT in a subquery is not the query's own
+        let query = parse_query("WITH t AS (SELECT * FROM cpu LIMIT 5) SELECT * FROM t").unwrap();
+        assert!(query.limit.is_none());
+
+        assert!(parse_query("EXPLAIN SELECT * FROM cpu").is_none());
+        assert!(parse_query("SELECT 1; SELECT 2").is_none());
+        assert!(parse_query("not sql").is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::commands::{
+    sql_helpers::{first_row_count, quote_identifier},
+    table::Error,
+};
+use arrow::record_batch::RecordBatch;
+use data_types::ColumnType;
+use futures::TryStreamExt;
+use influxdb_iox_client::{connection::Connection, flight, schema};
+use serde_json::json;
+
+/// Describe the columns of a table, with null and cardinality statistics
+/// computed from a sample of its rows
+#[derive(Debug, clap::Parser, Clone)]
+pub struct Config {
+    /// The database containing the table
+    #[clap(action)]
+    database: String,
+
+    /// The table to describe
+    #[clap(action)]
+    table: String,
+
+    /// The maximum number of rows to sample
+    #[clap(long, default_value = "10000", action)]
+    sample_size: usize,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<(), Error> {
+    let Config {
+        database,
+        table,
+        sample_size,
+    } = config;
+
+    let namespace_schema = schema::Client::new(connection.clone())
+        .get_schema(&database, Some(&table))
+        .await?;
+    let table_schema = namespace_schema
+        .tables
+        .get(&table)
+        .ok_or_else(|| Error::TableNotFound(table.clone()))?;
+
+    let mut columns: Vec<(String, ColumnType)> = table_schema
+        .columns
+        .iter()
+        .filter_map(|(name, column)| {
+            let column_type = ColumnType::try_from(column.column_type()).ok()?;
+            Some((name.clone(), column_type))
+        })
+        .collect();
+    columns.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    let batches: Vec<RecordBatch> = flight::Client::new(connection)
+        .sql(database, sample_query(&table, &columns, sample_size))
+        .await?
+        .try_collect()
+        .await?;
+
+    // The query returns a single row of counts
+    let count = |idx: usize| first_row_count(&batches, idx);
+
+    let sampled_rows = count(0);
+    let columns = columns
+        .into_iter()
+        .enumerate()
+        .map(|(i, (column, column_type))| {
+            let non_null = count(1 + 2 * i);
+            let null_fraction = if sampled_rows == 0 {
+                0.0
+            } else {
+                (sampled_rows - non_null) as f64 / sampled_rows as f64
+            };
+
+            // The distinct count of the sample approximates the cardinality
+            json!({
+                "column": column,
+                "type": column_type.as_str(),
+                "null_fraction": null_fraction,
+                "distinct_count": count(2 + 2 * i),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let stats = json!({
+        "table": table,
+        "sampled_rows": sampled_rows,
+        "columns": columns,
+    });
+    println!("{}", serde_json::to_string_pretty(&stats)?);
+
+    Ok(())
+}
+
+/// Build a query returning the number of sampled rows, followed by the
+/// non-null and distinct value counts of each column in `columns`.
+fn sample_query(table: &str, columns: &[(String, ColumnType)], sample_size: usize) -> String {
+    let aggregates: String = columns
+        .iter()
+        .map(|(name, _)| {
+            let name = quote_identifier(name);
+            format!(", count({name}), count(DISTINCT {name})")
+        })
+        .collect();
+
+    format!(
+        "SELECT count(*){aggregates} FROM (SELECT * FROM {} LIMIT {sample_size})",
+        quote_identifier(table)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_query() {
+        let columns = vec![
+            ("city".to_string(), ColumnType::Tag),
+            ("te\"mp".to_string(), ColumnType::F64),
+        ];
+
+        assert_eq!(
+            sample_query("h2o", &columns, 100),
+            "SELECT count(*), count(\"city\"), count(DISTINCT \"city\"), \
+            count(\"te\"\"mp\"), count(DISTINCT \"te\"\"mp\") \
+            FROM (SELECT * FROM \"h2o\" LIMIT 100)"
+        );
+    }
+}
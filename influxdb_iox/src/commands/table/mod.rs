@@ -5,6 +5,7 @@ use observability_deps::tracing::info;
 use thiserror::Error;
 
 mod create;
+mod explain_schema;
 mod list;
 
 #[allow(clippy::enum_variant_names)]
@@ -15,6 +16,12 @@ pub enum Error {
 
     #[error("Client error: {0}")]
     ClientError(#[from] influxdb_iox_client::error::Error),
+
+    #[error("Error querying: {0}")]
+    Query(#[from] influxdb_iox_client::flight::Error),
+
+    #[error("Table {0} not found")]
+    TableNotFound(String),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -33,6 +40,8 @@ enum Command {
     List(list::Config),
     /// Create a new table
     Create(create::Config),
+    /// Show the type, null fraction and cardinality of each column in a table
+    ExplainSchema(explain_schema::Config),
 }
 
 pub async fn command(connection: Connection, config: Config) -> Result<()> {
@@ -41,6 +50,9 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         Command::Create(config) => {
             info!("Creating table with config: {:?}", config);
             create::command(connection, config).await?;
+        }
+        Command::ExplainSchema(config) => {
+            explain_schema::command(connection, config).await?;
         } // Deliberately not adding _ => so the compiler will direct people here to impl new
           // commands
     }
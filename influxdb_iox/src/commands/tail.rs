@@ -0,0 +1,225 @@
+//! Implementation of command line option for watching the most recent rows written to a table
+
+use std::{str::FromStr, time::Duration};
+
+use arrow::{
+    array::{Array, BooleanArray, TimestampNanosecondArray},
+    compute::filter_record_batch,
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use futures::TryStreamExt;
+use influxdb_iox_client::{connection::Connection, flight, format::QueryOutputFormat};
+use schema::TIME_COLUMN_NAME;
+use thiserror::Error;
+
+use super::sql_helpers::quote_identifier;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Error formatting: {0}")]
+    Formatting(#[from] influxdb_iox_client::format::Error),
+
+    #[error("Error querying: {0}")]
+    Query(#[from] influxdb_iox_client::flight::Error),
+
+    #[error(
+        "Query result has no '{}' column of nanosecond timestamps",
+        TIME_COLUMN_NAME
+    )]
+    NoTimeColumn,
+
+    #[error("Error filtering already displayed rows: {0}")]
+    Filter(#[from] ArrowError),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Periodically display the most recent rows written to a table, similar to `tail -f`
+///
+/// Rows are de-duplicated by timestamp: once a row has been displayed, only
+/// rows with a strictly later timestamp are displayed in subsequent polls.
+/// Errors encountered while polling or formatting the results are printed and
+/// the tail continues.
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    /// The IOx namespace containing the table
+    #[clap(action)]
+    namespace: String,
+
+    /// The table to tail
+    #[clap(action)]
+    table: String,
+
+    /// How far back in time to look for rows on the first poll
+    #[clap(
+        long,
+        default_value = "1m",
+        value_parser = humantime::parse_duration,
+    )]
+    since: Duration,
+
+    /// How long to wait between polls
+    #[clap(
+        long,
+        default_value = "1s",
+        value_parser = humantime::parse_duration,
+    )]
+    interval: Duration,
+
+    /// Optional format ('pretty', 'json', or 'csv')
+    #[clap(
+        short,
+        long,
+        env = "INFLUXDB_IOX_FORMAT",
+        default_value = "pretty",
+        action
+    )]
+    format: String,
+}
+
+pub async fn command(connection: Connection, config: Config) -> Result<()> {
+    let mut client = flight::Client::new(connection);
+
+    let Config {
+        namespace,
+        table,
+        since,
+        interval,
+        format,
+    } = config;
+
+    let format = QueryOutputFormat::from_str(&format)?;
+
+    // Timestamp of the latest row displayed so far
+    let mut watermark: Option<i64> = None;
+
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let sql = tail_query(&table, since, watermark);
+        match poll(&mut client, &namespace, sql, &mut watermark).await {
+            Ok(batches) if batches.iter().all(|b| b.num_rows() == 0) => {}
+            Ok(batches) => match format.format(&batches) {
+                Ok(formatted) => println!("{formatted}"),
+                Err(e) => eprintln!("Error formatting rows of table {table}: {e}"),
+            },
+            Err(e) => eprintln!("Error polling table {table}: {e}"),
+        }
+    }
+}
+
+/// Build the query returning the rows of `table` that may not have been
+/// displayed yet, ordered by time.
+fn tail_query(table: &str, since: Duration, watermark: Option<i64>) -> String {
+    let predicate = match watermark {
+        // Inclusive so rows that share the watermark timestamp are never
+        // missed; they are removed again by `remove_displayed`.
+        Some(ts) => format!("{TIME_COLUMN_NAME} >= to_timestamp_nanos({ts})"),
+        None => format!(
+            "{TIME_COLUMN_NAME} >= now() - interval '{} seconds'",
+            since.as_secs()
+        ),
+    };
+
+    format!(
+        "SELECT * FROM {} WHERE {predicate} ORDER BY {TIME_COLUMN_NAME}",
+        quote_identifier(table)
+    )
+}
+
+/// Run a single poll, returning the rows that have not been displayed yet
+/// and advancing `watermark` past them.
+async fn poll(
+    client: &mut flight::Client,
+    namespace: &str,
+    sql: String,
+    watermark: &mut Option<i64>,
+) -> Result<Vec<RecordBatch>> {
+    let batches: Vec<_> = client
+        .sql(namespace.to_string(), sql)
+        .await?
+        .try_collect()
+        .await?;
+
+    let batches = batches
+        .iter()
+        .map(|batch| remove_displayed(batch, *watermark))
+        .collect::<Result<Vec<_>>>()?;
+
+    for batch in &batches {
+        let max = time_column(batch)?.iter().flatten().max();
+        *watermark = (*watermark).max(max);
+    }
+
+    Ok(batches)
+}
+
+/// Remove the rows of `batch` with a timestamp at or before `watermark`.
+fn remove_displayed(batch: &RecordBatch, watermark: Option<i64>) -> Result<RecordBatch> {
+    let Some(watermark) = watermark else {
+        return Ok(batch.clone());
+    };
+
+    let mask: BooleanArray = time_column(batch)?
+        .iter()
+        .map(|ts| Some(ts.map_or(false, |ts| ts > watermark)))
+        .collect();
+
+    Ok(filter_record_batch(batch, &mask)?)
+}
+
+fn time_column(batch: &RecordBatch) -> Result<&TimestampNanosecondArray> {
+    batch
+        .column_by_name(TIME_COLUMN_NAME)
+        .and_then(|col| col.as_any().downcast_ref::<TimestampNanosecondArray>())
+        .ok_or(Error::NoTimeColumn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array};
+    use std::sync::Arc;
+
+    fn batch(times: Vec<i64>) -> RecordBatch {
+        let values = Int64Array::from_iter_values(0..times.len() as i64);
+        RecordBatch::try_from_iter([
+            ("val", Arc::new(values) as ArrayRef),
+            (
+                TIME_COLUMN_NAME,
+                Arc::new(TimestampNanosecondArray::from(times)) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_displayed_rows() {
+        let b = batch(vec![1, 2, 3, 4]);
+
+        assert_eq!(remove_displayed(&b, None).unwrap().num_rows(), 4);
+        assert_eq!(remove_displayed(&b, Some(2)).unwrap().num_rows(), 2);
+        assert_eq!(remove_displayed(&b, Some(4)).unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn query_uses_watermark() {
+        let since = Duration::from_secs(90);
+        assert_eq!(
+            tail_query("cpu", since, None),
+            r#"SELECT * FROM "cpu" WHERE time >= now() - interval '90 seconds' ORDER BY time"#
+        );
+        assert_eq!(
+            tail_query("cpu", since, Some(42)),
+            r#"SELECT * FROM "cpu" WHERE time >= to_timestamp_nanos(42) ORDER BY time"#
+        );
+        assert_eq!(
+            tail_query(r#"my "cpu""#, since, Some(42)),
+            r#"SELECT * FROM "my ""cpu""" WHERE time >= to_timestamp_nanos(42) ORDER BY time"#
+        );
+    }
+}
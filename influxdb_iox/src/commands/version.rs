@@ -0,0 +1,139 @@
+//! Implementation of command line option for reporting client and server versions
+
+use influxdb_iox_client::connection::Builder;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::process_info::{IOX_GIT_HASH, IOX_VERSION};
+
+/// Name of the metric whose labels carry the server's version information
+const VERSION_METRIC_NAME: &str = "process_start_time_seconds";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+enum ServerVersionError {
+    #[error("Error connecting: {0}")]
+    Connection(#[from] influxdb_iox_client::connection::Error),
+
+    #[error("Error fetching metrics: {0}")]
+    Request(String),
+
+    #[error("Server metrics do not include '{}'", VERSION_METRIC_NAME)]
+    MissingMetric,
+}
+
+/// Print the versions of this client and of the server it connects to
+///
+/// The server version is read from the labels of the
+/// `process_start_time_seconds` metric published on the server's HTTP
+/// `/metrics` endpoint. If the server cannot be reached, only the client
+/// version is reported, along with a note explaining why.
+#[derive(Debug, clap::Parser)]
+pub struct Config {}
+
+/// `builder` carries the connection settings of the other commands, such as
+/// the `--header` and `--token` options. Unlike them, failing to connect is
+/// not an error.
+pub async fn command(builder: Builder, host: String, config: Config) -> Result<()> {
+    let Config {} = config;
+
+    let client = json!({
+        "version": *IOX_VERSION,
+        "git_hash": IOX_GIT_HASH,
+    });
+
+    let record = match server_version(builder, &host).await {
+        Ok(server) => json!({
+            "client": client,
+            "server": server,
+        }),
+        Err(e) => json!({
+            "client": client,
+            "server": Value::Null,
+            "note": format!("Could not determine the version of the server at {host}: {e}"),
+        }),
+    };
+
+    println!("{}", serde_json::to_string_pretty(&record)?);
+    Ok(())
+}
+
+/// Fetch the version information published by the server at `host`.
+async fn server_version(builder: Builder, host: &str) -> Result<Value, ServerVersionError> {
+    let connection = builder.build(host).await?.into_http_connection();
+    let url = format!("{}metrics", connection.uri());
+
+    let body = connection
+        .client()
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| ServerVersionError::Request(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| ServerVersionError::Request(e.to_string()))?;
+
+    let labels = version_labels(&body).ok_or(ServerVersionError::MissingMetric)?;
+    Ok(Value::Object(
+        labels
+            .into_iter()
+            .filter(|(k, _)| matches!(*k, "version" | "git_hash" | "uuid"))
+            .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+            .collect(),
+    ))
+}
+
+/// Extract the labels of the version metric from a Prometheus text exposition.
+fn version_labels(metrics: &str) -> Option<Vec<(&str, &str)>> {
+    let labels = metrics.lines().find_map(|line| {
+        line.strip_prefix(VERSION_METRIC_NAME)?
+            .strip_prefix('{')?
+            .split_once('}')
+            .map(|(labels, _)| labels)
+    })?;
+
+    Some(
+        labels
+            .split(',')
+            .filter_map(|label| {
+                let (key, value) = label.split_once('=')?;
+                Some((key.trim(), value.trim().trim_matches('"')))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_labels() {
+        let metrics = r#"# HELP process_start_time_seconds Start time of the process since unix epoch in seconds.
+# TYPE process_start_time_seconds gauge
+process_start_time_seconds{git_hash="abc123",uuid="f00",version="0.1.0"} 1696000000
+"#;
+
+        assert_eq!(
+            version_labels(metrics).unwrap(),
+            vec![
+                ("git_hash", "abc123"),
+                ("uuid", "f00"),
+                ("version", "0.1.0")
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_version_labels_missing() {
+        assert_eq!(version_labels("some_other_metric 1\n"), None);
+    }
+}
@@ -1,12 +1,14 @@
 use futures::{stream::BoxStream, StreamExt};
 use influxdb_iox_client::{connection::Connection, write};
 use observability_deps::tracing::{debug, info};
+use parking_lot::Mutex;
 use snafu::{ensure, OptionExt, ResultExt, Snafu};
 use std::{
     fs::File,
     io::{BufReader, Read},
     num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Instant,
 };
 
@@ -59,6 +61,16 @@ pub struct Config {
     #[clap(action, long, short = 'c', default_value = "10")]
     max_concurrent_uploads: usize,
 
+    /// Skip the lines that are not valid line protocol instead of failing
+    /// the write, and write the valid lines.
+    ///
+    /// The lines are checked before they are sent. The skipped lines are
+    /// reported with their file, line number and the reason, followed by the
+    /// number of lines written and skipped. Lines rejected by the server, e.g.
+    /// for a column type conflict, still fail the write.
+    #[clap(action, long)]
+    skip_errors: bool,
+
     /// The namespace into which to write, in the form <org_id>_<bucket_id>
     #[clap(action)]
     namespace: String,
@@ -77,6 +89,7 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         file_names,
         max_request_payload_size_bytes,
         max_concurrent_uploads,
+        skip_errors,
     } = config;
 
     let max_concurrent_uploads =
@@ -105,10 +118,21 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         }
     );
 
+    let report = Arc::new(Mutex::new(SkipReport::default()));
+
     // if everything looked good, go through and read the files out
     // them in parallel.
     let lp_stream = futures_util::stream::iter(file_names)
-        .map(|file_name| tokio::task::spawn(slurp_file(file_name)))
+        .map(|file_name| {
+            let report = skip_errors.then(|| Arc::clone(&report));
+            tokio::task::spawn(async move {
+                let stream = slurp_file(file_name.clone()).await?;
+                Ok::<_, Error>(match report {
+                    Some(report) => skip_invalid_lines(file_name, stream, report),
+                    None => stream,
+                })
+            })
+        })
         // Since the contents of each file are buffered into a string,
         // limit the number that are open at once to the maximum
         // possible uploads
@@ -160,9 +184,96 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let mb_per_sec = (mb / (elapsed.as_millis() as f64)) * (1000.0);
     println!("{total_bytes} Bytes OK in {elapsed:?}. {mb_per_sec:.2} MB/sec");
 
+    if skip_errors {
+        let report = report.lock();
+        for SkippedLine {
+            file_name,
+            line,
+            reason,
+        } in &report.skipped
+        {
+            eprintln!("Skipped line {line} of {file_name:?}: {reason}");
+        }
+        println!(
+            "{} lines written, {} lines skipped",
+            report.written,
+            report.skipped.len()
+        );
+    }
+
     Ok(())
 }
 
+/// A line that is not valid line protocol, skipped with `--skip-errors`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SkippedLine {
+    file_name: PathBuf,
+    line: usize,
+    reason: String,
+}
+
+/// The lines written and skipped with `--skip-errors`.
+#[derive(Debug, Default)]
+struct SkipReport {
+    written: usize,
+    skipped: Vec<SkippedLine>,
+}
+
+impl SkipReport {
+    /// Return the valid lines of `lp_data`, read from `file_name`, recording
+    /// the invalid ones as skipped.
+    ///
+    /// `line` is the line number of the start of `lp_data` in the file, and
+    /// is advanced past its end. Blank lines and comments are counted, so the
+    /// line numbers match the file.
+    fn validate(&mut self, file_name: &Path, line: &mut usize, lp_data: &str) -> String {
+        let mut valid = String::with_capacity(lp_data.len());
+        let mut number = *line;
+        for raw in influxdb_line_protocol::split_lines(lp_data) {
+            match influxdb_line_protocol::parse_lines(raw).next() {
+                // a blank line or a comment
+                None => {}
+                Some(Ok(_)) => {
+                    valid.push_str(raw);
+                    valid.push('\n');
+                    self.written += 1;
+                }
+                Some(Err(e)) => self.skipped.push(SkippedLine {
+                    file_name: file_name.to_path_buf(),
+                    line: number,
+                    reason: e.to_string(),
+                }),
+            }
+            // a line may contain quoted newlines
+            number += 1 + raw.matches('\n').count();
+        }
+        *line += lp_data.matches('\n').count();
+        valid
+    }
+}
+
+/// Remove the lines of `stream`, read from `file_name`, that are not valid
+/// line protocol, recording them in `report`.
+fn skip_invalid_lines(
+    file_name: PathBuf,
+    stream: BoxStream<'static, Result<String>>,
+    report: Arc<Mutex<SkipReport>>,
+) -> BoxStream<'static, Result<String>> {
+    let mut line = 1;
+    stream
+        .filter_map(move |lp_data| {
+            let lp_data =
+                lp_data.map(|lp_data| report.lock().validate(&file_name, &mut line, &lp_data));
+            async move {
+                match lp_data {
+                    Ok(lp_data) if lp_data.is_empty() => None,
+                    lp_data => Some(lp_data),
+                }
+            }
+        })
+        .boxed()
+}
+
 /// Reads the contents of `file_name` into one or more Strings, each
 /// containing a whole number of line protcol (lines do not span results)
 ///
@@ -251,6 +362,30 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn skip_report_validates_lines() {
+        let file_name = Path::new("data.lp");
+        let mut report = SkipReport::default();
+        let mut line = 1;
+
+        let valid = report.validate(
+            file_name,
+            &mut line,
+            "cpu,host=a usage=1 1\n\n# comment\ncpu,host=a\ncpu,host=b usage=2 2\n",
+        );
+        assert_eq!(valid, "cpu,host=a usage=1 1\ncpu,host=b usage=2 2\n");
+
+        // line numbers continue across chunks of the same file
+        let valid = report.validate(file_name, &mut line, "cpu usage=\"a\nb\" 3\nnot valid");
+        assert_eq!(valid, "cpu usage=\"a\nb\" 3\n");
+
+        assert_eq!(report.written, 3);
+        let skipped: Vec<_> = report.skipped.iter().map(|s| s.line).collect();
+        assert_eq!(skipped, [4, 8]);
+        assert_eq!(report.skipped[0].file_name, file_name);
+        assert!(!report.skipped[0].reason.is_empty());
+    }
+
     #[test]
     fn command_default_is_same_as_client_default() {
         let config = Config::try_parse_from(vec!["my_db", "file1"]).unwrap();
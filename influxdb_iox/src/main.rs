@@ -21,6 +21,7 @@ use observability_deps::tracing::{debug, warn};
 use process_info::VERSION_STRING;
 use std::{
     collections::hash_map::DefaultHasher,
+    future::Future,
     hash::{Hash, Hasher},
     str::FromStr,
 };
@@ -38,7 +39,9 @@ use trace_exporters::{
 
 mod commands {
     pub mod catalog;
+    pub mod cp;
     pub mod debug;
+    pub mod diff;
     pub mod namespace;
     pub mod partition_template;
     pub mod query;
@@ -46,9 +49,12 @@ mod commands {
     pub mod remote;
     pub mod run;
     pub mod sql;
+    pub mod sql_helpers;
     pub mod storage;
     pub mod table;
+    pub mod tail;
     pub mod tracing;
+    pub mod version;
     pub mod write;
 }
 
@@ -227,6 +233,18 @@ enum Command {
 
     /// Various commands for table manipulation
     Table(commands::table::Config),
+
+    /// Watch the most recent rows written to a table
+    Tail(commands::tail::Config),
+
+    /// Report the versions of this client and of the server
+    Version(commands::version::Config),
+
+    /// Run the same SQL query against two servers and report how the results differ
+    Diff(commands::diff::Config),
+
+    /// Copy the rows of a table to another namespace, possibly on another server
+    Cp(commands::cp::Config),
 }
 
 fn main() -> Result<(), std::io::Error> {
@@ -246,11 +264,15 @@ fn main() -> Result<(), std::io::Error> {
             .log_verbose_count;
         let rpc_timeout = global_config.rpc_timeout;
 
-        let connection = |host| async move {
-            let mut builder = headers.into_iter().fold(Builder::default(), |builder, kv| {
-                debug!(name=?kv.key, value=?kv.value, "Setting header");
-                builder.header(kv.key, kv.value)
-            });
+        // The connection settings shared by all commands
+        let builder = || {
+            let mut builder = headers
+                .iter()
+                .cloned()
+                .fold(Builder::default(), |builder, kv| {
+                    debug!(name=?kv.key, value=?kv.value, "Setting header");
+                    builder.header(kv.key, kv.value)
+                });
 
             builder = builder.timeout(rpc_timeout);
 
@@ -265,11 +287,18 @@ fn main() -> Result<(), std::io::Error> {
                 builder = builder.header(key, value);
             }
 
-            match builder.build(&host).await {
-                Ok(connection) => connection,
-                Err(e) => {
-                    eprintln!("Error connecting to {host}: {e}");
-                    std::process::exit(ReturnCode::Failure as _)
+            builder
+        };
+
+        let connection = |host: String| {
+            let builder = builder();
+            async move {
+                match builder.build(&host).await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        eprintln!("Error connecting to {host}: {e}");
+                        std::process::exit(ReturnCode::Failure as _)
+                    }
                 }
             }
         };
@@ -354,7 +383,7 @@ fn main() -> Result<(), std::io::Error> {
             Some(Command::Write(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(http_host).await;
-                if let Err(e) = commands::write::command(connection, config).await {
+                if let Err(e) = exit_on_ctrl_c(commands::write::command(connection, config)).await {
                     eprintln!("{e}");
                     std::process::exit(ReturnCode::Failure as _)
                 }
@@ -362,7 +391,7 @@ fn main() -> Result<(), std::io::Error> {
             Some(Command::Query(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(grpc_host).await;
-                if let Err(e) = commands::query::command(connection, config).await {
+                if let Err(e) = exit_on_ctrl_c(commands::query::command(connection, config)).await {
                     eprintln!("{e}");
                     std::process::exit(ReturnCode::Failure as _)
                 }
@@ -370,7 +399,9 @@ fn main() -> Result<(), std::io::Error> {
             Some(Command::QueryIngester(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 let connection = connection(grpc_host).await;
-                if let Err(e) = commands::query_ingester::command(connection, config).await {
+                if let Err(e) =
+                    exit_on_ctrl_c(commands::query_ingester::command(connection, config)).await
+                {
                     eprintln!("{e}");
                     std::process::exit(ReturnCode::Failure as _)
                 }
@@ -391,12 +422,58 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::Tail(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                let connection = connection(grpc_host).await;
+                if let Err(e) = exit_on_ctrl_c(commands::tail::command(connection, config)).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
+            Some(Command::Version(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                if let Err(e) = commands::version::command(builder(), http_host, config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
+            Some(Command::Diff(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                if let Err(e) = exit_on_ctrl_c(commands::diff::command(connection, config)).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
+            Some(Command::Cp(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                if let Err(e) = exit_on_ctrl_c(commands::cp::command(connection, config)).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
         }
     });
 
     Ok(())
 }
 
+/// Runs `fut` to completion, unless the user presses Ctrl-C first.
+///
+/// On Ctrl-C `fut` is dropped, which cancels any request it has in flight,
+/// and the process exits after reporting the cancellation.
+async fn exit_on_ctrl_c<F>(fut: F) -> F::Output
+where
+    F: Future + Send,
+{
+    tokio::select! {
+        res = fut => res,
+        _ = tokio::signal::ctrl_c() => {
+            eprintln!("Cancelled");
+            std::process::exit(ReturnCode::Failure as _)
+        }
+    }
+}
+
 /// configures tracing headers, so the remote server will sends
 /// tracing spans to Jaeger, if configured to do so.
 fn configure_tracing(mut builder: Builder, trace_id_headers: &[String]) -> Builder {
@@ -8,6 +8,7 @@ mod table;
 
 use std::{fs::read_dir, sync::Arc, time::Duration};
 
+use arrow_util::assert_batches_sorted_eq;
 use assert_cmd::Command;
 use assert_matches::assert_matches;
 use futures::FutureExt;
@@ -359,6 +360,332 @@ async fn query_error_handling() {
     .await
 }
 
+/// Test the `--raw` flag of the query CLI command
+#[tokio::test]
+async fn query_raw() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("query_raw,tag=A val=\"foo\" 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    let output = Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--raw")
+                        .arg(namespace)
+                        .arg("select * from query_raw")
+                        .assert()
+                        .success()
+                        .get_output()
+                        .stdout
+                        .clone();
+
+                    let reader = arrow::ipc::reader::StreamReader::try_new(output.as_slice(), None)
+                        .expect("raw output should be an Arrow IPC stream");
+                    let batches = reader
+                        .collect::<Result<Vec<_>, _>>()
+                        .expect("raw output should contain valid record batches");
+
+                    assert_batches_sorted_eq!(
+                        [
+                            "+-----+--------------------------------+-----+",
+                            "| tag | time                           | val |",
+                            "+-----+--------------------------------+-----+",
+                            "| A   | 1970-01-01T00:00:00.000000001Z | foo |",
+                            "+-----+--------------------------------+-----+",
+                        ],
+                        &batches
+                    );
+
+                    // a default format in the environment does not conflict with `--raw`
+                    let output = Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .env("INFLUXDB_IOX_FORMAT", "csv")
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--raw")
+                        .arg(namespace)
+                        .arg("select * from query_raw")
+                        .assert()
+                        .success()
+                        .get_output()
+                        .stdout
+                        .clone();
+                    arrow::ipc::reader::StreamReader::try_new(output.as_slice(), None)
+                        .expect("raw output should be an Arrow IPC stream");
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test the `--assert-nonempty` and `--assert-empty` flags of the query CLI command
+#[tokio::test]
+async fn query_assertions() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("query_assertions,tag=A val=1i 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    let assert_query = |flag: &str, query: &str| {
+                        Command::cargo_bin("influxdb_iox")
+                            .unwrap()
+                            .arg("-h")
+                            .arg(&querier_addr)
+                            .arg("query")
+                            .arg(flag)
+                            .arg(namespace)
+                            .arg(query)
+                            .assert()
+                    };
+                    let some_rows = "select * from query_assertions";
+                    let no_rows = "select * from query_assertions where tag = 'B'";
+
+                    assert_query("--assert-nonempty", some_rows)
+                        .success()
+                        .stdout(predicate::str::contains("| A   |"));
+                    assert_query("--assert-nonempty", no_rows)
+                        .failure()
+                        .stderr(predicate::str::contains("The query returned no rows"));
+
+                    assert_query("--assert-empty", no_rows).success();
+                    assert_query("--assert-empty", some_rows).failure().stderr(
+                        predicate::str::contains("The query returned 1 rows, expected none"),
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test the `--limit-bytes` option of the query CLI command
+#[tokio::test]
+async fn query_limit_bytes() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("query_limit_bytes,tag=A val=1i 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    let query = |limit_bytes: &str| {
+                        Command::cargo_bin("influxdb_iox")
+                            .unwrap()
+                            .arg("-h")
+                            .arg(&querier_addr)
+                            .arg("query")
+                            .arg("--limit-bytes")
+                            .arg(limit_bytes)
+                            .arg(namespace)
+                            .arg("select * from query_limit_bytes")
+                            .assert()
+                    };
+
+                    query("1")
+                        .failure()
+                        .stderr(predicate::str::contains("The query results exceed 1 bytes"));
+                    query("0")
+                        .success()
+                        .stdout(predicate::str::contains("| A   |"));
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test the `--output-file` option of the query CLI command writing Parquet
+#[tokio::test]
+async fn query_output_parquet() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(
+                "query_parquet,tag=A val=1u,other=\"foo\" 1\n\
+                 query_parquet,tag=B val=2u 2"
+                    .into(),
+            ),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+                    let dir = tempdir().unwrap();
+                    let path = dir.path().join("out.parquet");
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--output-file")
+                        .arg(&path)
+                        .arg(namespace)
+                        .arg("select * from query_parquet")
+                        .assert()
+                        .success()
+                        .stdout(
+                            predicate::str::contains(r#""rows": 2"#)
+                                .and(predicate::str::contains(r#""name": "val""#)),
+                        );
+
+                    let file = std::fs::File::open(&path).unwrap();
+                    let batches =
+                        parquet::arrow::arrow_reader::ParquetRecordBatchReader::try_new(file, 1024)
+                            .expect("output should be a Parquet file")
+                            .collect::<Result<Vec<_>, _>>()
+                            .expect("output should contain valid record batches");
+
+                    // types and nulls are preserved
+                    let schema = batches[0].schema();
+                    assert_eq!(
+                        schema.field_with_name("val").unwrap().data_type(),
+                        &arrow::datatypes::DataType::UInt64
+                    );
+                    assert_batches_sorted_eq!(
+                        [
+                            "+-------+-----+--------------------------------+-----+",
+                            "| other | tag | time                           | val |",
+                            "+-------+-----+--------------------------------+-----+",
+                            "| foo   | A   | 1970-01-01T00:00:00.000000001Z | 1   |",
+                            "|       | B   | 1970-01-01T00:00:00.000000002Z | 2   |",
+                            "+-------+-----+--------------------------------+-----+",
+                        ],
+                        &batches
+                    );
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test that the query CLI command takes its default format and time zone
+/// from the environment, and that flags override them
+#[tokio::test]
+async fn query_format_and_tz_from_env() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("query_env,tag=A val=1i 1".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .env("INFLUXDB_IOX_FORMAT", "json")
+                        .env("INFLUXDB_IOX_TZ", "+01:00")
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg(namespace)
+                        .arg("select tag, time from query_env")
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains(
+                            r#""time":"1970-01-01T01:00:00.000000001+01:00""#,
+                        ));
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .env("INFLUXDB_IOX_FORMAT", "json")
+                        .env("INFLUXDB_IOX_TZ", "+01:00")
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--format")
+                        .arg("csv")
+                        .arg("--tz")
+                        .arg("-02:00")
+                        .arg(namespace)
+                        .arg("select tag, time from query_env")
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains(
+                            "A,1969-12-31T22:00:00.000000001-02:00",
+                        ));
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .env("INFLUXDB_IOX_FORMAT", "yaml")
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg(namespace)
+                        .arg("select tag, time from query_env")
+                        .assert()
+                        .failure()
+                        .stderr(predicate::str::contains("invalid value 'yaml'"));
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .env("INFLUXDB_IOX_FORMAT", "table")
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg(namespace)
+                        .arg("select tag from query_env")
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains("| tag |"));
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 /// Test error handling for the query CLI command for InfluxQL queries
 #[tokio::test]
 async fn influxql_error_handling() {
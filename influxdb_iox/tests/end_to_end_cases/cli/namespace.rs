@@ -132,6 +132,61 @@ async fn deletion() {
     .await
 }
 
+#[tokio::test]
+async fn deletion_dry_run() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(String::from(
+                "my_awesome_table,tag1=A val=42i 123456\n\
+                 my_awesome_table,tag1=B val=43i 123457\n\
+                 other_table,tag1=A val=1i 123456",
+            )),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace().to_string();
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("namespace")
+                        .arg("delete")
+                        .arg("--dry-run")
+                        .arg(&namespace)
+                        .assert()
+                        .success()
+                        .stdout(
+                            predicate::str::contains("Dry run, nothing was deleted")
+                                .and(predicate::str::contains("would remove 3 rows"))
+                                .and(predicate::str::contains("my_awesome_table: 2"))
+                                .and(predicate::str::contains("other_table: 1")),
+                        );
+                }
+                .boxed()
+            })),
+            // the namespace is still there
+            Step::Query {
+                sql: "select count(*) from my_awesome_table".into(),
+                expected: vec![
+                    "+----------+",
+                    "| COUNT(*) |",
+                    "+----------+",
+                    "| 2        |",
+                    "+----------+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn create_service_limits() {
     test_helpers::maybe_start_logging();
@@ -2,7 +2,8 @@ use assert_cmd::Command;
 use futures::FutureExt;
 use predicates::prelude::*;
 use test_helpers_end_to_end::{
-    maybe_skip_integration, MiniCluster, Step, StepTest, StepTestState, TestConfig,
+    maybe_skip_integration, CompactorConfigOverrides, MiniCluster, Step, StepTest, StepTestState,
+    TestConfig,
 };
 
 #[tokio::test]
@@ -257,3 +258,114 @@ async fn sharded_compactor_1_never_compacts_partition_1() {
     .run()
     .await
 }
+
+#[tokio::test]
+async fn small_max_desired_file_size_splits_compaction_output() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_non_shared(database_url).await;
+
+    // Two overlapping chunks of a single partition, each persisted to its own L0 file.
+    let chunk = |value: usize| {
+        (0..1000)
+            .map(|i| {
+                format!(
+                    "my_awesome_table,tag1=A{} val={value}i {}",
+                    i % 10,
+                    i * 1_000_000
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(chunk(1)),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(chunk(2)),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            // The files are far larger than the target size, so the compacted output is split.
+            Step::CompactWithConfig(CompactorConfigOverrides {
+                max_desired_file_size_bytes: Some(5_000),
+                ..Default::default()
+            }),
+            Step::AssertMinNumParquetFiles { min: 2 },
+            Step::AssertCatalogConsistency,
+            Step::Query {
+                sql: "select count(*) from my_awesome_table".into(),
+                expected: vec![
+                    "+----------+",
+                    "| COUNT(*) |",
+                    "+----------+",
+                    "| 1000     |",
+                    "+----------+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+#[should_panic(expected = "is not known to the catalog")]
+async fn catalog_consistency_detects_orphaned_objects() {
+    test_helpers::maybe_start_logging();
+    // Tell the test to panic with the expected message if `TEST_INTEGRATION` isn't set so that
+    // this still passes
+    let database_url = maybe_skip_integration!("is not known to the catalog");
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(String::from(
+                "my_awesome_table,tag1=A,tag2=B val=42i 123456",
+            )),
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            Step::Compact,
+            // the files replaced by compaction are still known to the catalog
+            Step::AssertCatalogConsistency,
+            // store a file the catalog knows nothing about
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let namespace_id = state.cluster().namespace_id().await;
+                    let store_dir = state
+                        .cluster()
+                        .ingester()
+                        .test_config()
+                        .object_store_dir()
+                        .clone()
+                        .expect("file object store");
+                    let dir = store_dir
+                        .path()
+                        .join(namespace_id.to_string())
+                        .join("stray");
+                    std::fs::create_dir_all(&dir).unwrap();
+                    std::fs::write(
+                        dir.join("00000000-0000-0000-0000-000000000000.parquet"),
+                        b"not parquet",
+                    )
+                    .unwrap();
+                }
+                .boxed()
+            })),
+            Step::AssertCatalogConsistency,
+        ],
+    )
+    .run()
+    .await
+}
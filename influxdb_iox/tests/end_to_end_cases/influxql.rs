@@ -35,6 +35,10 @@ async fn influxql_returns_error() {
                     "Error while planning query: Error during planning: invalid SHOW TAG statement, expected KEYS or VALUES at pos 9"
                         .into(),
             },
+            Step::InfluxQLExpectError {
+                query: format!("SELECT val FROM {table_name} GROUP BY time(1m)"),
+                expected_message: "GROUP BY requires at least one aggregate function".into(),
+            },
         ],
     )
     .run()
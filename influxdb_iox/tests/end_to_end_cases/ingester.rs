@@ -26,6 +26,10 @@ async fn persist_on_demand() {
         vec![
             Step::RecordNumParquetFiles,
             Step::WriteLineProtocol(format!("{table_name},tag1=A,tag2=B val=42i 123456")),
+            // nothing is persisted until asked to
+            Step::AssertNoParquetFiles {
+                table: Some(table_name.to_string()),
+            },
             Step::Custom(Box::new(move |state: &mut StepTestState| {
                 async move {
                     // query the ingester
@@ -109,6 +113,37 @@ async fn persist_on_demand() {
     .await
 }
 
+#[tokio::test]
+async fn unpersisted_data_survives_ingester_restart() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "mytable";
+    // cannot use shared cluster because we're restarting the ingester
+    let mut cluster = MiniCluster::create_non_shared_never_persist(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            // the ingester never persists on its own, so the write is only in the WAL when the
+            // ingester restarts
+            Step::WriteThenRestartIngester(format!("{table_name},tag1=A,tag2=B val=42i 123456")),
+            Step::Query {
+                sql: format!("select * from {table_name}"),
+                expected: vec![
+                    "+------+------+--------------------------------+-----+",
+                    "| tag1 | tag2 | time                           | val |",
+                    "+------+------+--------------------------------+-----+",
+                    "| A    | B    | 1970-01-01T00:00:00.000123456Z | 42  |",
+                    "+------+------+--------------------------------+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn ingester_flight_api() {
     test_helpers::maybe_start_logging();
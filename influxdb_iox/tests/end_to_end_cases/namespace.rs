@@ -223,3 +223,100 @@ async fn soft_deletion() {
     .run()
     .await
 }
+
+/// Writes spanning three days are split into three partitions by the default daily partition
+/// template.
+#[tokio::test]
+async fn default_partition_template_partitions_by_day() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+    let day_nanos = 24 * 60 * 60 * 1_000_000_000_i64;
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A val=1i 0\n\
+                 {table_name},tag1=A val=2i {}\n\
+                 {table_name},tag1=A val=3i {}\n\
+                 {table_name},tag1=B val=4i {}",
+                day_nanos,
+                2 * day_nanos,
+                2 * day_nanos + 1,
+            )),
+            Step::ExpectPartitionCount {
+                table: table_name.to_string(),
+                count: 3,
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Queries against one namespace do not see the data written to another.
+#[tokio::test]
+async fn query_namespace_isolation() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    let table_name = "the_table";
+    let namespace_a = cluster.namespace().to_string();
+    let namespace_b = format!("{namespace_a}_other");
+    let sql = format!(
+        "SELECT table_name FROM information_schema.tables WHERE table_name = '{table_name}'"
+    );
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!("{table_name},tag1=A val=42i 123456")),
+            Step::Custom(Box::new({
+                let namespace_b = namespace_b.clone();
+                move |state: &mut StepTestState| {
+                    let namespace_b = namespace_b.clone();
+                    async move {
+                        let mut client = influxdb_iox_client::namespace::Client::new(
+                            state.cluster().router().router_grpc_connection(),
+                        );
+                        client
+                            .create_namespace(&namespace_b, None, None, None)
+                            .await
+                            .unwrap();
+                    }
+                    .boxed()
+                }
+            })),
+            Step::QueryNamespace {
+                namespace: namespace_a,
+                sql: sql.clone(),
+                expected: vec![
+                    "+------------+",
+                    "| table_name |",
+                    "+------------+",
+                    "| the_table  |",
+                    "+------------+",
+                ],
+            },
+            Step::QueryNamespace {
+                namespace: namespace_b,
+                sql,
+                expected: vec![
+                    "+------------+",
+                    "| table_name |",
+                    "+------------+",
+                    "+------------+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
@@ -1,6 +1,7 @@
 pub(crate) mod influxrpc;
 mod multi_ingester;
 
+use crate::query_tests::setups::SETUPS;
 use arrow::datatypes::{DataType, SchemaRef};
 use arrow_flight::{
     decode::{DecodedFlightData, DecodedPayload},
@@ -50,6 +51,160 @@ async fn basic_ingester() {
     .await
 }
 
+/// Assert queries return the same results while the data is buffered in the ingester and once it
+/// has been persisted, including deduplicating a row written twice.
+#[tokio::test]
+async fn query_stable_across_persistence() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared_never_persist(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::WriteLineProtocol(format!("{table_name},tag1=A,tag2=B val=44i 123456")),
+            Step::QueryStable {
+                sql: format!("select * from {table_name}"),
+                expected: vec![
+                    "+------+------+--------------------------------+-----+",
+                    "| tag1 | tag2 | time                           | val |",
+                    "+------+------+--------------------------------+-----+",
+                    "| A    | B    | 1970-01-01T00:00:00.000123456Z | 44  |",
+                    "| A    | C    | 1970-01-01T00:00:00.000123457Z | 43  |",
+                    "+------+------+--------------------------------+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+/// A field written in a later chunk fills in the NULL of a row of an earlier chunk with the same
+/// series and timestamp.
+#[tokio::test]
+async fn query_merges_nulls_across_chunks() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(
+                [
+                    "h2o,state=MA,city=Boston min_temp=70.4 50",
+                    "h2o,state=MA,city=Bedford min_temp=71.59 150",
+                    "h2o,state=MA,city=Boston max_temp=75.4 250",
+                    "h2o,state=MA,city=Andover max_temp=69.2, 250",
+                ]
+                .join("\n"),
+            ),
+            Step::Persist,
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            // Only Boston at 50 and Reading at 250 keep a NULL max_temp, the one of Bedford at
+            // 150 is updated from NULL.
+            Step::WriteLineProtocol(
+                [
+                    "h2o,state=MA,city=Bedford max_temp=78.75,area=742u 150",
+                    "h2o,state=MA,city=Boston min_temp=65.4 250",
+                    "h2o,state=MA,city=Reading min_temp=53.4, 250",
+                    "h2o,state=CA,city=SF min_temp=79.0,max_temp=87.2,area=500u 300",
+                    "h2o,state=CA,city=SJ min_temp=78.5,max_temp=88.0 300",
+                    "h2o,state=CA,city=SJ min_temp=75.5,max_temp=84.08 350",
+                ]
+                .join("\n"),
+            ),
+            Step::ExpectColumnNullFraction {
+                table: "h2o".into(),
+                column: "max_temp".into(),
+                max_null_fraction: 2.0 / 8.0,
+            },
+            Step::ExpectColumnNullFraction {
+                table: "h2o".into(),
+                column: "min_temp".into(),
+                max_null_fraction: 1.0 / 8.0,
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn query_matches_volatile_values() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared_never_persist(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            // The width of the formatted now() varies with its sub-second digits
+            Step::QueryMatches {
+                sql: format!("select tag2, val, now() as now from {table_name} order by val"),
+                pattern: vec![
+                    r"\+-+\+-+\+-+\+".into(),
+                    r"\| tag2 \| val \| now +\|".into(),
+                    r"\+-+\+-+\+-+\+".into(),
+                    r"\| B    \| 42  \| \d{4}-\d{2}-\d{2}T[\d:.]+Z +\|".into(),
+                    r"\| C    \| 43  \| \d{4}-\d{2}-\d{2}T[\d:.]+Z +\|".into(),
+                    r"\+-+\+-+\+-+\+".into(),
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn query_expect_golden_file() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared_never_persist(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A,tag2=B val=42i 123456\n\
+                 {table_name},tag1=A,tag2=C val=43i 123457"
+            )),
+            Step::QueryExpectFile {
+                sql: format!("select * from {table_name}"),
+                expected_path: "tests/end_to_end_cases/golden/query_expect_golden_file.txt".into(),
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 #[should_panic(expected = "did not get additional Parquet files in the catalog")]
 async fn never_persist_really_never_persists() {
@@ -117,6 +272,173 @@ async fn basic_on_parquet() {
     .await
 }
 
+#[tokio::test]
+#[should_panic(expected = "schema mismatch")]
+async fn query_with_schema_detects_type_change() {
+    test_helpers::maybe_start_logging();
+    // Tell the test to panic with the expected message if `TEST_INTEGRATION` isn't set so that
+    // this still passes
+    let database_url = maybe_skip_integration!("schema mismatch");
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            // Written without the `i` suffix, so `val` is a float that formats like an integer
+            Step::WriteLineProtocol(format!("{table_name},tag1=A val=42 123456")),
+            Step::QueryWithSchema {
+                sql: format!("select tag1, val from {table_name}"),
+                expected_schema: vec![("tag1", DataType::Utf8), ("val", DataType::Int64)],
+                expected: vec![
+                    "+------+------+",
+                    "| tag1 | val  |",
+                    "+------+------+",
+                    "| A    | 42.0 |",
+                    "+------+------+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn query_relative_to_captured_value() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!(
+                "{table_name},tag1=A val=1i 123456\n{table_name},tag1=B val=2i 654321"
+            )),
+            Step::QueryCapture {
+                sql: format!("select max(time) from {table_name}"),
+                name: "max_time".into(),
+            },
+            Step::Query {
+                sql: format!("select tag1, val from {table_name} where time < '${{max_time}}'"),
+                expected: vec![
+                    "+------+-----+",
+                    "| tag1 | val |",
+                    "+------+-----+",
+                    "| A    | 1   |",
+                    "+------+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+#[should_panic(expected = "undefined capture `max_time`")]
+async fn query_with_undefined_capture() {
+    test_helpers::maybe_start_logging();
+    // Tell the test to panic with the expected message if `TEST_INTEGRATION` isn't set so that
+    // this still passes
+    let database_url = maybe_skip_integration!("undefined capture `max_time`");
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(format!("{table_name},tag1=A val=1i 123456")),
+            Step::Query {
+                sql: format!("select tag1, val from {table_name} where time < '${{max_time}}'"),
+                expected: vec![],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+async fn concurrent_queries_on_parquet() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let table_name = "the_table";
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::RecordNumParquetFiles,
+            Step::WriteLineProtocol(
+                [
+                    format!("{table_name},tag1=A val=1i 100"),
+                    format!("{table_name},tag1=B val=2i 200"),
+                    format!("{table_name},tag1=C val=3i 300"),
+                ]
+                .join("\n"),
+            ),
+            // Wait for data to be persisted to parquet
+            Step::WaitForPersisted {
+                expected_increase: 1,
+            },
+            // Overlapping time ranges over the same file
+            Step::ConcurrentQueries {
+                queries: vec![
+                    (
+                        format!("select tag1, val from {table_name} where time <= 200"),
+                        vec![
+                            "+------+-----+",
+                            "| tag1 | val |",
+                            "+------+-----+",
+                            "| A    | 1   |",
+                            "| B    | 2   |",
+                            "+------+-----+",
+                        ],
+                    ),
+                    (
+                        format!("select tag1, val from {table_name} where time >= 200"),
+                        vec![
+                            "+------+-----+",
+                            "| tag1 | val |",
+                            "+------+-----+",
+                            "| B    | 2   |",
+                            "| C    | 3   |",
+                            "+------+-----+",
+                        ],
+                    ),
+                    (
+                        format!("select count(*) from {table_name}"),
+                        vec![
+                            "+----------+",
+                            "| COUNT(*) |",
+                            "+----------+",
+                            "| 3        |",
+                            "+----------+",
+                        ],
+                    ),
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
 #[tokio::test]
 async fn basic_empty() {
     test_helpers::maybe_start_logging();
@@ -870,6 +1192,30 @@ async fn iox_debug_header() {
     .await
 }
 
+/// Guard against gross performance regressions of a range scan over many overlapping parquet
+/// files, with a bound far above the expected latency.
+#[tokio::test]
+async fn query_latency_twenty_sorted_parquet_files() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    // Set up the cluster  ====================================
+    let mut cluster = MiniCluster::create_shared_never_persist(database_url).await;
+
+    let setup_steps = SETUPS
+        .get("TwentySortedParquetFiles")
+        .expect("setup should exist")
+        .iter();
+    let measure = Step::MeasureQueryLatency {
+        sql: String::from("SELECT * FROM m WHERE time >= 990 AND time <= 2001 ORDER BY time"),
+        max_ms: 5_000,
+    };
+
+    StepTest::new(&mut cluster, setup_steps.chain(std::iter::once(&measure)))
+        .run()
+        .await
+}
+
 /// Some clients, such as the golang ones, cannot decode dictionary encoded Flight data. This
 /// function asserts that all schemas received in the stream are unpacked.
 pub(crate) async fn verify_schema(stream: IOxRecordBatchStream) {
@@ -2,7 +2,8 @@ use bytes::Buf;
 use futures::FutureExt;
 use http::{HeaderValue, StatusCode};
 use test_helpers_end_to_end::{
-    maybe_skip_integration, Authorizer, MiniCluster, Step, StepTest, StepTestState, TestConfig,
+    maybe_skip_integration, Authorizer, CsvFieldType, CsvMapping, MiniCluster, Step, StepTest,
+    StepTestState, TestConfig,
 };
 use tonic::codegen::Body;
 
@@ -47,6 +48,87 @@ pub async fn test_json_errors() {
     .await;
 }
 
+#[tokio::test]
+pub async fn test_write_precision() {
+    let database_url = maybe_skip_integration!();
+
+    let test_config = TestConfig::new_all_in_one(Some(database_url));
+    let mut cluster = MiniCluster::create_all_in_one(test_config).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocolWithPrecision {
+                lp: "table_precision,tag1=s val=1i 1000".into(),
+                precision: "s".into(),
+            },
+            Step::WriteLineProtocolWithPrecision {
+                lp: "table_precision,tag1=ms val=2i 1000000".into(),
+                precision: "ms".into(),
+            },
+            Step::WriteLineProtocolWithPrecision {
+                lp: "table_precision,tag1=ns val=3i 1000000000000".into(),
+                precision: "ns".into(),
+            },
+            Step::Query {
+                sql: "select * from table_precision".into(),
+                expected: vec![
+                    "+------+----------------------+-----+",
+                    "| tag1 | time                 | val |",
+                    "+------+----------------------+-----+",
+                    "| ms   | 1970-01-01T00:16:40Z | 2   |",
+                    "| ns   | 1970-01-01T00:16:40Z | 3   |",
+                    "| s    | 1970-01-01T00:16:40Z | 1   |",
+                    "+------+----------------------+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await;
+}
+
+#[tokio::test]
+pub async fn test_write_gzip() {
+    let database_url = maybe_skip_integration!();
+
+    let test_config = TestConfig::new_all_in_one(Some(database_url));
+    let mut cluster = MiniCluster::create_all_in_one(test_config).await;
+
+    // The same data, once uncompressed and once gzipped, must produce identical results.
+    let lp = "cpu,region=west user=23.2 100\ncpu,region=east user=21.0 200";
+    let expected = vec![
+        "+--------+--------------------------------+------+",
+        "| region | time                           | user |",
+        "+--------+--------------------------------+------+",
+        "| east   | 1970-01-01T00:00:00.000000200Z | 21.0 |",
+        "| west   | 1970-01-01T00:00:00.000000100Z | 23.2 |",
+        "+--------+--------------------------------+------+",
+    ];
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol(lp.replace("cpu", "cpu_plain")),
+            Step::WriteLineProtocolGzip(lp.replace("cpu", "cpu_gzip")),
+            Step::Query {
+                sql: "select * from cpu_plain".into(),
+                expected: expected.clone(),
+            },
+            Step::Query {
+                sql: "select * from cpu_gzip".into(),
+                expected,
+            },
+            Step::WriteGzipExpectingError {
+                body: b"this is not a gzip stream".to_vec(),
+                expected_error_code: StatusCode::BAD_REQUEST,
+            },
+        ],
+    )
+    .run()
+    .await;
+}
+
 #[tokio::test]
 pub async fn test_writes_are_atomic() {
     let database_url = maybe_skip_integration!();
@@ -179,3 +261,118 @@ async fn authz() {
 
     authz.close().await;
 }
+
+/// A streamed write with an invalid last line accepts the lines before it.
+#[tokio::test]
+async fn streaming_write_partial_accept() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let ingester_config = TestConfig::new_ingester(&database_url);
+    let router_config = TestConfig::new_router(&ingester_config).with_streaming_writes(1);
+    let querier_config = TestConfig::new_querier(&ingester_config);
+
+    let mut cluster = MiniCluster::new()
+        .with_router(router_config)
+        .await
+        .with_ingester(ingester_config)
+        .await
+        .with_querier(querier_config)
+        .await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocolExpectPartialAccept {
+                line_protocol: "\
+                    bananas,tag=A val=1i 1\n\
+                    bananas,tag=B val=2i 2\n\
+                    bananas,tag=C val=nope 3"
+                    .into(),
+                expected_lines_accepted: 2,
+                expected_error_line: 3,
+            },
+            Step::Query {
+                sql: "select tag, val from bananas".into(),
+                expected: vec![
+                    "+-----+-----+",
+                    "| tag | val |",
+                    "+-----+-----+",
+                    "| A   | 1   |",
+                    "| B   | 2   |",
+                    "+-----+-----+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+fn weather_mapping() -> CsvMapping {
+    CsvMapping::new("weather", "time")
+        .with_tag("station")
+        .with_tag("city")
+        .with_field("temperature", CsvFieldType::Float)
+        .with_field("humidity", CsvFieldType::Integer)
+        .with_field("conditions", CsvFieldType::String)
+        .with_field("raining", CsvFieldType::Boolean)
+}
+
+#[tokio::test]
+async fn write_csv_fixture() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocolWithTimestampColumn {
+                csv: include_str!("data/weather.csv").into(),
+                mapping: weather_mapping(),
+            },
+            Step::Query {
+                sql: "select city, temperature, humidity, conditions, raining from weather".into(),
+                expected: vec![
+                    "+---------------+-------------+----------+-----------------+---------+",
+                    "| city          | temperature | humidity | conditions      | raining |",
+                    "+---------------+-------------+----------+-----------------+---------+",
+                    "| San Francisco | 11.5        |          | Partly \"sunny\"  | false   |",
+                    "| San Francisco | 12.25       | 71       | Clear           | false   |",
+                    "| Seattle       | 7.0         | 90       | Rain            | true    |",
+                    "| Seattle       | 7.5         | 88       | Light rain, fog | true    |",
+                    "+---------------+-------------+----------+-----------------+---------+",
+                ],
+            },
+        ],
+    )
+    .run()
+    .await
+}
+
+#[tokio::test]
+#[should_panic(expected = "invalid CSV fixture: line 3")]
+async fn write_malformed_csv_fixture() {
+    test_helpers::maybe_start_logging();
+    // Tell the test to panic with the expected message if `TEST_INTEGRATION` isn't set so that
+    // this still passes
+    let database_url = maybe_skip_integration!("invalid CSV fixture: line 3");
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![Step::WriteLineProtocolWithTimestampColumn {
+            csv: "\
+                time,station,city,temperature,humidity,conditions,raining\n\
+                1672531200000000000,KSEA,Seattle,7.5,88,Fog,true\n\
+                1672531200000000000,KSFO,San Francisco,warm,71,Clear,false\n"
+                .into(),
+            mapping: weather_mapping(),
+        }],
+    )
+    .run()
+    .await
+}
@@ -20,6 +20,24 @@ async fn list_all() {
     .await;
 }
 
+#[tokio::test]
+async fn list_after_schema_evolution() {
+    Arc::new(SchemaTest {
+        setup_name: "SchemaEvolution",
+        table_name: "h2o",
+        expected_columns: HashMap::from([
+            ("state".into(), ColumnType::Tag),
+            ("city".into(), ColumnType::Tag),
+            ("min_temp".into(), ColumnType::F64),
+            ("max_temp".into(), ColumnType::F64),
+            ("area".into(), ColumnType::U64),
+            ("time".into(), ColumnType::Time),
+        ]),
+    })
+    .run()
+    .await;
+}
+
 struct SchemaTest {
     setup_name: &'static str,
     table_name: &'static str,
@@ -5,6 +5,8 @@
 //! -- IOX_SETUP: [test name]
 //! ```
 
+use arrow::datatypes::DataType;
+use data_types::ColumnType;
 use futures_util::FutureExt;
 use influxdb_iox_client::table::generated_types::{Part, PartitionTemplate, TemplatePart};
 use iox_time::{SystemProvider, Time, TimeProvider};
@@ -242,6 +244,7 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
                 Step::WaitForPersisted {
                     expected_increase: 1,
                 },
+                Step::AssertCatalogConsistency,
             ],
         ),
         (
@@ -347,8 +350,7 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
         (
             "TwoMeasurementsManyFields",
             vec![
-                Step::RecordNumParquetFiles,
-                Step::WriteLineProtocol(
+                Step::WriteMultipleMeasurementsExpectingNFiles(
                     [
                         "h2o,state=MA,city=Boston temp=70.4 50",
                         "h2o,state=MA,city=Boston other_temp=70.4 250",
@@ -358,16 +360,13 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
                     ]
                     .join("\n"),
                 ),
-                Step::Persist,
-                Step::WaitForPersisted {
-                    expected_increase: 2,
-                },
                 Step::RecordNumParquetFiles,
                 Step::WriteLineProtocol(
                     "h2o,state=MA,city=Boston temp=70.4,moisture=43.0 100000".into(),
                 ),
                 Step::Persist,
-                Step::WaitForPersisted {
+                Step::WaitForPersistedTable {
+                    table: "h2o".into(),
                     expected_increase: 1,
                 },
             ],
@@ -507,31 +506,68 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
         ),
         (
             "AllTypes",
-            vec![Step::WriteLineProtocol(
-                [
-                    "m,tag=row1 float_field=64.0 450",
-                    "m,tag=row1 int_field=64 550",
-                    "m,tag=row1 \
-                        float_field=61.0,int_field=22,uint_field=25u,\
-                        string_field=\"foo\",bool_field=t 500",
-                    "m,tag=row1 \
-                        float_field=62.0,int_field=21,uint_field=30u,\
-                        string_field=\"ba\",bool_field=f 200",
-                    "m,tag=row1 \
-                        float_field=63.0,int_field=20,uint_field=35u,\
-                        string_field=\"baz\",bool_field=f 300",
-                    "m,tag=row1 \
-                        float_field=64.0,int_field=19,uint_field=20u,\
-                        string_field=\"bar\",bool_field=t 400",
-                    "m,tag=row1 \
-                        float_field=65.0,int_field=18,uint_field=40u,\
-                        string_field=\"fruz\",bool_field=f 100",
-                    "m,tag=row1 \
-                        float_field=66.0,int_field=17,uint_field=10u,\
-                        string_field=\"faa\",bool_field=t 600",
-                ]
-                .join("\n"),
-            )],
+            vec![
+                Step::WriteLineProtocol(
+                    [
+                        "m,tag=row1 float_field=64.0 450",
+                        "m,tag=row1 int_field=64 550",
+                        "m,tag=row1 \
+                            float_field=61.0,int_field=22,uint_field=25u,\
+                            string_field=\"foo\",bool_field=t 500",
+                        "m,tag=row1 \
+                            float_field=62.0,int_field=21,uint_field=30u,\
+                            string_field=\"ba\",bool_field=f 200",
+                        "m,tag=row1 \
+                            float_field=63.0,int_field=20,uint_field=35u,\
+                            string_field=\"baz\",bool_field=f 300",
+                        "m,tag=row1 \
+                            float_field=64.0,int_field=19,uint_field=20u,\
+                            string_field=\"bar\",bool_field=t 400",
+                        "m,tag=row1 \
+                            float_field=65.0,int_field=18,uint_field=40u,\
+                            string_field=\"fruz\",bool_field=f 100",
+                        "m,tag=row1 \
+                            float_field=66.0,int_field=17,uint_field=10u,\
+                            string_field=\"faa\",bool_field=t 600",
+                    ]
+                    .join("\n"),
+                ),
+                Step::ExpectColumnType {
+                    table: "m".into(),
+                    column: "uint_field".into(),
+                    expected_type: ColumnType::U64,
+                },
+                // Written without the `i` suffix, so inferred as a float
+                Step::ExpectColumnType {
+                    table: "m".into(),
+                    column: "int_field".into(),
+                    expected_type: ColumnType::F64,
+                },
+                Step::ExpectColumnType {
+                    table: "m".into(),
+                    column: "tag".into(),
+                    expected_type: ColumnType::Tag,
+                },
+                Step::QueryWithSchema {
+                    sql: "SELECT tag, int_field, uint_field, string_field, bool_field \
+                          FROM m WHERE time = 500"
+                        .into(),
+                    expected_schema: vec![
+                        ("tag", DataType::Utf8),
+                        ("int_field", DataType::Float64),
+                        ("uint_field", DataType::UInt64),
+                        ("string_field", DataType::Utf8),
+                        ("bool_field", DataType::Boolean),
+                    ],
+                    expected: vec![
+                        "+------+-----------+------------+--------------+------------+",
+                        "| tag  | int_field | uint_field | string_field | bool_field |",
+                        "+------+-----------+------------+--------------+------------+",
+                        "| row1 | 22.0      | 25         | foo          | true       |",
+                        "+------+-----------+------------+--------------+------------+",
+                    ],
+                },
+            ],
         ),
         (
             "ManyFieldsSeveralChunks",
@@ -1251,6 +1287,25 @@ pub static SETUPS: Lazy<HashMap<SetupName, SetupSteps>> = Lazy::new(|| {
                 },
             ],
         ),
+        (
+            // Single measurement that gains a new field (area) in a later write
+            "SchemaEvolution",
+            vec![
+                Step::WriteLineProtocol(
+                    [
+                        "h2o,state=MA,city=Boston min_temp=70.4 50",
+                        "h2o,state=MA,city=Bedford min_temp=71.59 150",
+                    ]
+                    .join("\n"),
+                ),
+                Step::WriteLineProtocolAndExpectSchemaChange {
+                    line_protocol: "h2o,state=MA,city=Bedford max_temp=78.75,area=742u 250".into(),
+                    table: "h2o".into(),
+                    column: "area".into(),
+                    expected_type: ColumnType::U64,
+                },
+            ],
+        ),
         (
             "TwoMeasurementsUnsignedType",
             vec![
@@ -15,7 +15,7 @@ pub mod influxql;
 #[derive(Debug, Error)]
 pub enum Error {
     /// Unknown formatting type
-    #[error("Unknown format type: {}. Expected one of 'pretty', 'csv' or 'json'", .0)]
+    #[error("Unknown format type: {}. Expected one of 'pretty', 'table', 'csv' or 'json'", .0)]
     Invalid(String),
 
     /// Error pretty printing
@@ -30,6 +30,10 @@ pub enum Error {
     #[error("Arrow json printing error: {}", .0)]
     JsonArrow(ArrowError),
 
+    /// Error quoting the CSV output
+    #[error("Error quoting CSV output: {}", .0)]
+    CsvQuote(csv::Error),
+
     /// Error converting CSV output to utf-8
     #[error("Error converting CSV output to UTF-8: {}", .0)]
     CsvUtf8(std::string::FromUtf8Error),
@@ -72,7 +76,8 @@ impl FromStr for QueryOutputFormat {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_str() {
-            "pretty" => Ok(Self::Pretty),
+            // `table` is accepted as an alias, like in `influxdb_iox query`
+            "pretty" | "table" => Ok(Self::Pretty),
             "csv" => Ok(Self::Csv),
             "json" => Ok(Self::Json),
             _ => Err(Error::Invalid(s.to_string())),
@@ -80,6 +85,27 @@ impl FromStr for QueryOutputFormat {
     }
 }
 
+/// Options of the CSV output format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Write a header row with the column names.
+    pub header: bool,
+    /// The field delimiter.
+    pub delimiter: u8,
+    /// The character fields are quoted with when needed.
+    pub quote: u8,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            header: true,
+            delimiter: b',',
+            quote: b'"',
+        }
+    }
+}
+
 impl QueryOutputFormat {
     /// Return the Mcontent-type of this format
     pub fn content_type(&self) -> &'static str {
@@ -122,7 +148,7 @@ impl QueryOutputFormat {
     pub fn format(&self, batches: &[RecordBatch]) -> Result<String> {
         match self {
             Self::Pretty => batches_to_pretty(batches),
-            Self::Csv => batches_to_csv(batches),
+            Self::Csv => batches_to_csv(batches, CsvOptions::default()),
             Self::Json => batches_to_json(batches),
         }
     }
@@ -132,20 +158,48 @@ fn batches_to_pretty(batches: &[RecordBatch]) -> Result<String> {
     arrow_util::display::pretty_format_batches(batches).map_err(Error::PrettyArrow)
 }
 
-fn batches_to_csv(batches: &[RecordBatch]) -> Result<String> {
+/// Format the [`RecordBatch`]es as CSV with the given `options`.
+pub fn batches_to_csv(batches: &[RecordBatch], options: CsvOptions) -> Result<String> {
     let mut bytes = vec![];
 
     {
-        let mut writer = WriterBuilder::new().has_headers(true).build(&mut bytes);
+        let mut writer = WriterBuilder::new()
+            .has_headers(options.header)
+            .with_delimiter(options.delimiter)
+            .build(&mut bytes);
 
         for batch in batches {
             writer.write(batch).map_err(Error::CsvArrow)?;
         }
     }
+
+    // The Arrow writer always quotes with `"`, so re-quote its output.
+    if options.quote != b'"' {
+        bytes = requote_csv(&bytes, options).map_err(Error::CsvQuote)?;
+    }
+
     let csv = String::from_utf8(bytes).map_err(Error::CsvUtf8)?;
     Ok(csv)
 }
 
+/// Rewrite the `"`-quoted CSV `bytes` to quote with `options.quote` instead.
+fn requote_csv(bytes: &[u8], options: CsvOptions) -> Result<Vec<u8>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(options.delimiter)
+        .from_reader(bytes);
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .from_writer(vec![]);
+
+    for record in reader.byte_records() {
+        writer.write_byte_record(&record?)?;
+    }
+
+    writer.into_inner().map_err(|e| e.into_error().into())
+}
+
 fn batches_to_json(batches: &[RecordBatch]) -> Result<String> {
     let mut bytes = vec![];
 
@@ -177,6 +231,10 @@ mod tests {
             QueryOutputFormat::from_str("pRetty").unwrap(),
             QueryOutputFormat::Pretty
         );
+        assert_eq!(
+            QueryOutputFormat::from_str("table").unwrap(),
+            QueryOutputFormat::Pretty
+        );
 
         assert_eq!(
             QueryOutputFormat::from_str("csv").unwrap(),
@@ -198,7 +256,39 @@ mod tests {
 
         assert_eq!(
             QueryOutputFormat::from_str("un").unwrap_err().to_string(),
-            "Unknown format type: un. Expected one of 'pretty', 'csv' or 'json'"
+            "Unknown format type: un. Expected one of 'pretty', 'table', 'csv' or 'json'"
+        );
+    }
+
+    #[test]
+    fn test_csv_options() {
+        use arrow::array::{Int64Array, StringArray};
+        use std::sync::Arc;
+
+        let batch = RecordBatch::try_from_iter([
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a;b", "it's", "c"])) as _,
+            ),
+            ("value", Arc::new(Int64Array::from(vec![1, 2, 3])) as _),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            batches_to_csv(&[batch.clone()], CsvOptions::default()).unwrap(),
+            "name,value\na;b,1\nit's,2\nc,3\n"
+        );
+        assert_eq!(
+            batches_to_csv(
+                &[batch],
+                CsvOptions {
+                    header: false,
+                    delimiter: b';',
+                    quote: b'\'',
+                }
+            )
+            .unwrap(),
+            "'a;b';1\n'it''s';2\nc;3\n"
         );
     }
 
@@ -553,6 +553,16 @@ pub fn parse_lines(input: &str) -> impl Iterator<Item = Result<ParsedLine<'_>>>
     })
 }
 
+/// Count the lines of `input` that [`parse_lines`] parses, skipping blank
+/// lines and comments.
+///
+/// This matches the numbering of the lines reported in parse errors.
+pub fn count_lines(input: &str) -> usize {
+    split_lines(input)
+        .filter(|line| !trim_leading(line).is_empty())
+        .count()
+}
+
 /// Split `input` into individual lines to be parsed, based on the
 /// rules of the line protocol format.
 ///
@@ -1884,6 +1894,15 @@ bar value2=2i 123"#;
         assert!(vals.is_empty());
     }
 
+    #[test]
+    fn count_lines_skips_blank_and_commented_lines() {
+        assert_eq!(super::count_lines(""), 0);
+        assert_eq!(super::count_lines("m a=1i\n"), 1);
+        assert_eq!(super::count_lines("m a=1i\n\n  \n# comment\nm a=2i"), 2);
+        // a quoted newline does not end a line
+        assert_eq!(super::count_lines("m a=\"x\ny\"\nm a=2i\n"), 2);
+    }
+
     #[test]
     fn parse_multiple_whitespace_between_elements_is_allowed() {
         let input = "  measurement  a=1i  123  ";
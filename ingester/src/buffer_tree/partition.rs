@@ -206,6 +206,12 @@ impl PartitionData {
         self.buffer.persist_cost_estimate()
     }
 
+    /// Return the approximate size in bytes of the in-memory buffer of
+    /// this [`PartitionData`], excluding any data currently persisting.
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        self.buffer.buffered_bytes()
+    }
+
     /// Returns the number of rows currently buffered in this [`PartitionData`].
     ///
     /// The returned value will always match the row count of the data returned
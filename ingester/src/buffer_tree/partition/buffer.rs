@@ -62,6 +62,12 @@ impl DataBuffer {
         }
     }
 
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        match self.0.get() {
+            FsmState::Buffering(b) => b.buffered_bytes(),
+        }
+    }
+
     /// Return all data for this buffer, ordered by the [`SequenceNumber`] from
     /// which it was buffered with.
     pub(crate) fn get_query_data(&mut self, projection: &OwnedProjection) -> Vec<RecordBatch> {
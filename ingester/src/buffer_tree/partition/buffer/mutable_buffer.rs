@@ -58,4 +58,8 @@ impl Buffer {
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.buffer().map(|v| v.size_data()).unwrap_or_default()
     }
+
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        self.buffer().map(|v| v.size()).unwrap_or_default()
+    }
 }
@@ -98,6 +98,10 @@ impl BufferState<Buffering> {
     pub(crate) fn persist_cost_estimate(&self) -> usize {
         self.state.buffer.persist_cost_estimate()
     }
+
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        self.state.buffer.buffered_bytes()
+    }
 }
 
 /// Perform an O(1) extraction of the timestamp column statistics.
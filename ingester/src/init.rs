@@ -266,6 +266,12 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// Independently of the cost estimate, if `persist_memory_threshold_bytes` is
+/// set, a partition is also enqueued for persistence once the approximate
+/// in-memory size of its buffer exceeds this many bytes. This bounds the memory
+/// used by partitions of spiky writers, complementing the time-based
+/// persistence driven by WAL rotation.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -278,6 +284,7 @@ pub async fn new<F>(
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_memory_threshold_bytes: Option<NonZeroUsize>,
     object_store: ParquetStorage,
     gossip: GossipConfig,
     max_partitions_per_namespace: NonZeroUsize,
@@ -427,6 +434,7 @@ where
     let hot_partition_persister = HotPartitionPersister::new(
         Arc::clone(&persist_handle),
         persist_hot_partition_cost,
+        persist_memory_threshold_bytes,
         &metrics,
     );
 
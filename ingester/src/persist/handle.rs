@@ -165,6 +165,10 @@ pub(crate) struct PersistHandle {
 
     /// A counter tracking the number of enqueued into the persist system.
     enqueued_jobs: U64Counter,
+
+    /// The distribution of time callers spent blocked waiting for space in a
+    /// saturated persist queue.
+    saturated_wait_duration: DurationHistogram,
 }
 
 impl PersistHandle {
@@ -304,6 +308,17 @@ impl PersistHandle {
             )
             .recorder(&[]);
 
+        // Initialise a histogram capturing the time spent waiting for a
+        // semaphore permit when the persist system is saturated, which is the
+        // time ingest is blocked by persist backpressure.
+        let saturated_wait_duration = metrics
+            .register_metric::<DurationHistogram>(
+                "ingester_persist_saturated_wait_duration",
+                "the distribution of duration an enqueue call spent waiting for \
+                space in a saturated persist queue",
+            )
+            .recorder(&[]);
+
         Self {
             sem,
             global_queue: global_tx,
@@ -311,6 +326,7 @@ impl PersistHandle {
             worker_tasks,
             persist_state,
             enqueued_jobs,
+            saturated_wait_duration,
         }
     }
 
@@ -393,10 +409,13 @@ impl PersistQueue for PersistHandle {
                 // is successful, the guard is dropped immediately when leaving
                 // this scope, after the permit has been granted.
 
-                Arc::clone(&self.sem)
+                let permit = Arc::clone(&self.sem)
                     .acquire_owned()
                     .await
-                    .expect("persist work semaphore is closed")
+                    .expect("persist work semaphore is closed");
+
+                self.saturated_wait_duration.record(enqueued_at.elapsed());
+                permit
             }
         };
 
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, num::NonZeroUsize, sync::Arc};
 
 use observability_deps::tracing::info;
 use parking_lot::{Mutex, MutexGuard};
@@ -8,14 +8,20 @@ use crate::buffer_tree::{partition::PartitionData, post_write::PostWriteObserver
 use super::queue::PersistQueue;
 
 /// A [`PostWriteObserver`] that triggers persistence of a partition when the
-/// estimated persistence cost exceeds a pre-configured limit.
+/// estimated persistence cost exceeds a pre-configured limit, or when the
+/// optional in-memory buffer size threshold is exceeded.
 #[derive(Debug)]
 pub(crate) struct HotPartitionPersister<P> {
     persist_handle: P,
     max_estimated_persist_cost: usize,
+    max_buffered_bytes: Option<NonZeroUsize>,
 
     /// A metric tracking the number of partitions persisted as "hot partitions".
     persist_count: metric::U64Counter,
+
+    /// A metric tracking the number of partitions persisted because their
+    /// buffer exceeded `max_buffered_bytes`.
+    memory_threshold_persist_count: metric::U64Counter,
 }
 
 impl<P> HotPartitionPersister<P>
@@ -25,6 +31,7 @@ where
     pub fn new(
         persist_handle: P,
         max_estimated_persist_cost: usize,
+        max_buffered_bytes: Option<NonZeroUsize>,
         metrics: &metric::Registry,
     ) -> Self {
         let persist_count = metrics
@@ -34,10 +41,19 @@ where
                 because the persist cost exceeded the pre-configured limit",
             )
             .recorder(&[]);
+        let memory_threshold_persist_count = metrics
+            .register_metric::<metric::U64Counter>(
+                "ingester_persist_memory_threshold_enqueue_count",
+                "number of times persistence of a partition has been triggered \
+                because the buffered data exceeded the pre-configured memory threshold",
+            )
+            .recorder(&[]);
         Self {
             persist_handle,
             max_estimated_persist_cost,
+            max_buffered_bytes,
             persist_count,
+            memory_threshold_persist_count,
         }
     }
 
@@ -64,8 +80,6 @@ where
             // There is no need to await on the completion handle.
             persist_handle.enqueue(partition, data).await;
         });
-        // Update any exported metrics.
-        self.persist_count.inc(1);
     }
 }
 
@@ -94,7 +108,23 @@ where
         // writes could be added to the buffer in parallel, exceeding the
         // limit before it was marked as persisting.
         if cost_estimate >= self.max_estimated_persist_cost {
-            self.persist(cost_estimate, partition, guard)
+            self.persist_count.inc(1);
+            self.persist(cost_estimate, partition, guard);
+            return;
+        }
+
+        // Independently of the persist cost, bound the memory used by the
+        // partition buffer if a threshold was configured.
+        if let Some(max_buffered_bytes) = self.max_buffered_bytes {
+            let buffered_bytes = guard.buffered_bytes();
+            if buffered_bytes >= max_buffered_bytes.get() {
+                info!(
+                    partition_id = %guard.partition_id(),
+                    buffered_bytes, "partition buffer exceeds memory threshold"
+                );
+                self.memory_threshold_persist_count.inc(1);
+                self.persist(cost_estimate, partition, guard);
+            }
         }
     }
 }
@@ -135,7 +165,7 @@ mod tests {
         let persist_handle = Arc::new(MockPersistQueue::default());
 
         let hot_partition_persister =
-            HotPartitionPersister::new(Arc::clone(&persist_handle), max_cost, &metrics);
+            HotPartitionPersister::new(Arc::clone(&persist_handle), max_cost, None, &metrics);
 
         // Observe the partition after the first write
         hot_partition_persister.observe(Arc::clone(&p), p.lock());
@@ -192,4 +222,56 @@ mod tests {
             .await;
         assert_eq!(p.lock().completed_persistence_count(), 1);
     }
+
+    #[tokio::test]
+    async fn test_memory_threshold_persist() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb = lp_to_mutable_batch(&format!(
+            r#"{},city=Hereford  people=1,crisps="good" 10"#,
+            &*ARBITRARY_TABLE_NAME
+        ))
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let max_buffered_bytes = NonZeroUsize::new(p.buffered_bytes()).unwrap();
+        let p = Arc::new(Mutex::new(p));
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+
+        // Configure a persist cost that is never reached, so only the memory
+        // threshold can trigger persistence.
+        let hot_partition_persister = HotPartitionPersister::new(
+            Arc::clone(&persist_handle),
+            usize::MAX,
+            Some(max_buffered_bytes),
+            &metrics,
+        );
+
+        hot_partition_persister.observe(Arc::clone(&p), p.lock());
+
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 1);
+
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_persist_memory_threshold_enqueue_count",
+            value = 1,
+        );
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_persist_hot_partition_enqueue_count",
+            value = 0,
+        );
+
+        drop(hot_partition_persister);
+        Arc::try_unwrap(persist_handle)
+            .expect("should be no more refs")
+            .join()
+            .await;
+        assert_eq!(p.lock().completed_persistence_count(), 1);
+    }
 }
@@ -75,6 +75,7 @@ pub struct TestContextBuilder {
 
     max_persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_memory_threshold_bytes: Option<NonZeroUsize>,
     wal_rotation_period: Duration,
 }
 
@@ -85,6 +86,7 @@ impl Default for TestContextBuilder {
             catalog: None,
             max_persist_queue_depth: DEFAULT_MAX_PERSIST_QUEUE_DEPTH,
             persist_hot_partition_cost: DEFAULT_PERSIST_HOT_PARTITION_COST,
+            persist_memory_threshold_bytes: None,
             wal_rotation_period: DEFAULT_WAL_ROTATION_PERIOD,
         }
     }
@@ -120,6 +122,13 @@ impl TestContextBuilder {
         self
     }
 
+    /// Configure the ingester to persist partitions after their in-memory
+    /// buffer exceeds this many bytes. Disabled by default.
+    pub fn with_persist_memory_threshold_bytes(mut self, bytes: NonZeroUsize) -> Self {
+        self.persist_memory_threshold_bytes = Some(bytes);
+        self
+    }
+
     /// Configure the ingester to rotate the write-ahead log at the regular
     /// interval specified by [`Duration`]. Defaults to
     /// [`DEFAULT_WAL_ROTATION_PERIOD`].
@@ -135,6 +144,7 @@ impl TestContextBuilder {
             catalog,
             max_persist_queue_depth,
             persist_hot_partition_cost,
+            persist_memory_threshold_bytes,
             wal_rotation_period,
         } = self;
 
@@ -167,6 +177,7 @@ impl TestContextBuilder {
             persist_workers,
             max_persist_queue_depth,
             persist_hot_partition_cost,
+            persist_memory_threshold_bytes,
             storage.clone(),
             GossipConfig::default(),
             NonZeroUsize::new(usize::MAX).unwrap(),
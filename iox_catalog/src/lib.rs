@@ -139,6 +139,26 @@ pub async fn validate_or_insert_schema<'a, T, U, R>(
     schema: &NamespaceSchema,
     repos: &mut R,
 ) -> Result<Option<NamespaceSchema>, TableScopedError>
+where
+    T: IntoIterator<IntoIter = U, Item = (&'a str, &'a MutableBatch)> + Send + Sync,
+    U: Iterator<Item = T::Item> + Send,
+    R: RepoCollection + ?Sized,
+{
+    validate_or_insert_schema_with_created(tables, schema, repos)
+        .await
+        .map(|(schema, _)| schema)
+}
+
+/// Identical to [`validate_or_insert_schema`], additionally returning the
+/// names of the tables inserted into the catalog by this call.
+///
+/// Tables absent from `schema` that already existed in the catalog (such as
+/// those created concurrently by another writer) are not included.
+pub async fn validate_or_insert_schema_with_created<'a, T, U, R>(
+    tables: T,
+    schema: &NamespaceSchema,
+    repos: &mut R,
+) -> Result<(Option<NamespaceSchema>, Vec<String>), TableScopedError>
 where
     T: IntoIterator<IntoIter = U, Item = (&'a str, &'a MutableBatch)> + Send + Sync,
     U: Iterator<Item = T::Item> + Send,
@@ -149,15 +169,18 @@ where
     // The (potentially updated) NamespaceSchema to return to the caller.
     let mut schema = Cow::Borrowed(schema);
 
+    // The tables inserted into the catalog by this call.
+    let mut created = Vec::new();
+
     for (table_name, batch) in tables {
-        validate_mutable_batch(batch, table_name, &mut schema, repos)
+        validate_mutable_batch(batch, table_name, &mut schema, &mut created, repos)
             .await
             .map_err(|e| TableScopedError(table_name.to_string(), e))?;
     }
 
     match schema {
-        Cow::Owned(v) => Ok(Some(v)),
-        Cow::Borrowed(_) => Ok(None),
+        Cow::Owned(v) => Ok((Some(v), created)),
+        Cow::Borrowed(_) => Ok((None, created)),
     }
 }
 
@@ -167,6 +190,7 @@ async fn validate_mutable_batch<R>(
     mb: &MutableBatch,
     table_name: &str,
     schema: &mut Cow<'_, NamespaceSchema>,
+    created: &mut Vec<String>,
     repos: &mut R,
 ) -> Result<()>
 where
@@ -183,9 +207,12 @@ where
             //
             // Attempt to load an existing table from the catalog or create a new table in the
             // catalog to populate the cache.
-            let table =
+            let (table, was_created) =
                 table_load_or_create(repos, schema.id, &schema.partition_template, table_name)
                     .await?;
+            if was_created {
+                created.push(table_name.to_string());
+            }
 
             assert!(schema
                 .to_mut()
@@ -286,16 +313,16 @@ async fn table_load_or_create<R>(
     namespace_id: NamespaceId,
     namespace_partition_template: &NamespacePartitionTemplateOverride,
     table_name: &str,
-) -> Result<TableSchema>
+) -> Result<(TableSchema, bool)>
 where
     R: RepoCollection + ?Sized,
 {
-    let table = match repos
+    let (table, created) = match repos
         .tables()
         .get_by_namespace_and_name(namespace_id, table_name)
         .await?
     {
-        Some(table) => table,
+        Some(table) => (table, false),
         None => {
             // There is a possibility of a race condition here, if another request has also
             // created this table after the `get_by_namespace_and_name` call but before
@@ -315,7 +342,7 @@ where
                 )
                 .await;
             if let Err(Error::TableNameExists { .. }) = create_result {
-                repos
+                let table = repos
                     .tables()
                     .get_by_namespace_and_name(namespace_id, table_name)
                     // Propagate any `Err` returned by the catalog
@@ -325,9 +352,10 @@ where
                     .expect(
                         "Table creation failed because the table exists, so looking up the table \
                         should return `Some(table)`, but it returned `None`",
-                    )
+                    );
+                (table, false)
             } else {
-                create_result?
+                (create_result?, true)
             }
         }
     };
@@ -342,7 +370,7 @@ where
 
     table.add_column(time_col);
 
-    Ok(table)
+    Ok((table, created))
 }
 
 /// Catalog helper functions for creation of catalog objects
@@ -734,4 +762,43 @@ mod tests {
         let table = formerly_empty_schema.tables.get("m1").unwrap();
         assert_eq!(table.columns.names(), BTreeSet::from(["t2", "f2", "time"]));
     }
+
+    #[tokio::test]
+    async fn validate_reports_only_tables_it_created() {
+        use crate::{interface::Catalog, test_helpers::arbitrary_namespace};
+        use std::ops::DerefMut;
+        const NAMESPACE_NAME: &str = "bananas";
+
+        let repo = MemCatalog::new(Default::default());
+        let mut txn = repo.repositories().await;
+        let namespace = arbitrary_namespace(&mut *txn, NAMESPACE_NAME).await;
+        let empty_schema = NamespaceSchema::new_empty_from(&namespace);
+
+        // The first write creates m1.
+        let writes = mutable_batch_lp::lines_to_batches("m1,t1=a f1=2i", 42).unwrap();
+        let (_, created) = validate_or_insert_schema_with_created(
+            writes.iter().map(|(k, v)| (k.as_str(), v)),
+            &empty_schema,
+            txn.deref_mut(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(created, ["m1"]);
+
+        // A write through a stale schema that lacks m1 loads it from the
+        // catalog, and only reports m2 as created.
+        let writes = mutable_batch_lp::lines_to_batches("m1,t1=a f1=2i\nm2 f1=1i", 43).unwrap();
+        let (schema, mut created) = validate_or_insert_schema_with_created(
+            writes.iter().map(|(k, v)| (k.as_str(), v)),
+            &empty_schema,
+            txn.deref_mut(),
+        )
+        .await
+        .unwrap();
+        created.sort_unstable();
+        assert_eq!(created, ["m2"]);
+        let schema = schema.unwrap();
+        assert!(schema.tables.contains_key("m1"));
+        assert!(schema.tables.contains_key("m2"));
+    }
 }
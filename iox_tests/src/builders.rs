@@ -90,6 +90,16 @@ impl ParquetFileBuilder {
         }
     }
 
+    /// Set created_at
+    pub fn with_created_at(self, created_at: i64) -> Self {
+        Self {
+            file: ParquetFile {
+                created_at: Timestamp::new(created_at),
+                ..self.file
+            },
+        }
+    }
+
     /// Set max_l0_created_at
     pub fn with_max_l0_created_at(self, max_l0_created_at: i64) -> Self {
         Self {
@@ -0,0 +1,399 @@
+//! Limits on the connections accepted by the HTTP server.
+
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use hyper::server::{
+    accept::Accept,
+    conn::{AddrIncoming, AddrStream},
+};
+use metric::{Registry, U64Counter, U64Gauge};
+use observability_deps::tracing::debug;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    time::{Instant, Sleep},
+};
+
+/// Limits applied to the connections of an HTTP server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpConnectionLimits {
+    /// The maximum number of simultaneously open connections.
+    ///
+    /// Connections accepted beyond this limit are closed immediately, before
+    /// any data is read from them. Unlimited if [`None`].
+    pub max_connections: Option<usize>,
+
+    /// Close kept-alive connections that have been idle (with no request in
+    /// flight and no data sent or received) for this long.
+    ///
+    /// Idle connections are kept open until the client closes them if
+    /// [`None`].
+    pub keep_alive_timeout: Option<Duration>,
+}
+
+/// An [`AddrIncoming`] wrapper enforcing [`HttpConnectionLimits`] on the
+/// accepted connections.
+#[derive(Debug)]
+pub(crate) struct LimitedIncoming {
+    inner: AddrIncoming,
+    limits: HttpConnectionLimits,
+    open: Arc<AtomicUsize>,
+    active_metric: U64Gauge,
+    rejected_metric: U64Counter,
+}
+
+impl LimitedIncoming {
+    pub(crate) fn new(
+        inner: AddrIncoming,
+        limits: HttpConnectionLimits,
+        metric_registry: &Registry,
+    ) -> Self {
+        let active_metric = metric_registry
+            .register_metric::<U64Gauge>(
+                "http_connections_active",
+                "number of open HTTP connections",
+            )
+            .recorder(&[]);
+        let rejected_metric = metric_registry
+            .register_metric::<U64Counter>(
+                "http_connections_rejected",
+                "number of HTTP connections closed for exceeding the connection limit",
+            )
+            .recorder(&[]);
+
+        Self {
+            inner,
+            limits,
+            open: Default::default(),
+            active_metric,
+            rejected_metric,
+        }
+    }
+}
+
+impl Accept for LimitedIncoming {
+    type Conn = LimitedStream;
+    type Error = io::Error;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            let stream = match ready!(Pin::new(&mut this.inner).poll_accept(cx)) {
+                Some(Ok(v)) => v,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            };
+
+            // Connections are only ever opened by this (single) acceptor, so
+            // the count cannot grow between this check and the increment
+            // below.
+            if let Some(max) = this.limits.max_connections {
+                if this.open.load(Ordering::Relaxed) >= max {
+                    debug!(
+                        remote_addr=%stream.remote_addr(),
+                        max_connections=max,
+                        "HTTP connection limit reached, closing connection"
+                    );
+                    this.rejected_metric.inc(1);
+                    continue;
+                }
+            }
+
+            let guard = OpenConnection::new(Arc::clone(&this.open), this.active_metric.clone());
+            return Poll::Ready(Some(Ok(LimitedStream::new(
+                stream,
+                guard,
+                this.limits.keep_alive_timeout,
+            ))));
+        }
+    }
+}
+
+/// Accounts for a connection while it is open.
+#[derive(Debug)]
+struct OpenConnection {
+    open: Arc<AtomicUsize>,
+    active_metric: U64Gauge,
+}
+
+impl OpenConnection {
+    fn new(open: Arc<AtomicUsize>, active_metric: U64Gauge) -> Self {
+        open.fetch_add(1, Ordering::Relaxed);
+        active_metric.inc(1);
+        Self {
+            open,
+            active_metric,
+        }
+    }
+}
+
+impl Drop for OpenConnection {
+    fn drop(&mut self) {
+        self.open.fetch_sub(1, Ordering::Relaxed);
+        self.active_metric.dec(1);
+    }
+}
+
+/// The number of requests in flight on a single connection.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionActivity {
+    in_flight: AtomicUsize,
+}
+
+impl ConnectionActivity {
+    /// Mark a request as in flight until the returned guard is dropped,
+    /// preventing the connection from being closed as idle.
+    pub(crate) fn start_request(self: &Arc<Self>) -> InFlightRequest {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightRequest(Arc::clone(self))
+    }
+
+    fn is_idle(&self) -> bool {
+        self.in_flight.load(Ordering::Relaxed) == 0
+    }
+}
+
+/// A request in flight on a connection, see
+/// [`ConnectionActivity::start_request()`].
+#[derive(Debug)]
+pub(crate) struct InFlightRequest(Arc<ConnectionActivity>);
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A connection accepted by [`LimitedIncoming`].
+///
+/// Reads from the connection report EOF once it has been idle for longer than
+/// the keep-alive timeout, causing the server to close it.
+#[derive(Debug)]
+pub(crate) struct LimitedStream {
+    inner: AddrStream,
+    activity: Arc<ConnectionActivity>,
+    keep_alive: Option<(Duration, Pin<Box<Sleep>>)>,
+    _open: OpenConnection,
+}
+
+impl LimitedStream {
+    fn new(inner: AddrStream, open: OpenConnection, keep_alive_timeout: Option<Duration>) -> Self {
+        Self {
+            inner,
+            activity: Default::default(),
+            keep_alive: keep_alive_timeout
+                .map(|timeout| (timeout, Box::pin(tokio::time::sleep(timeout)))),
+            _open: open,
+        }
+    }
+
+    /// The request activity of this connection.
+    pub(crate) fn activity(&self) -> Arc<ConnectionActivity> {
+        Arc::clone(&self.activity)
+    }
+
+    /// Restart the keep-alive timeout after data was sent or received.
+    fn reset_idle(&mut self) {
+        if let Some((timeout, sleep)) = &mut self.keep_alive {
+            sleep.as_mut().reset(Instant::now() + *timeout);
+        }
+    }
+
+    /// Returns true if the connection has been idle for longer than the
+    /// keep-alive timeout, otherwise registers `cx` to be woken once it has.
+    fn poll_idle_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        match &mut self.keep_alive {
+            Some((_, sleep)) if self.activity.is_idle() => sleep.as_mut().poll(cx).is_ready(),
+            _ => false,
+        }
+    }
+}
+
+impl AsyncRead for LimitedStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let filled = buf.filled().len();
+        match Pin::new(&mut this.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => {
+                if buf.filled().len() > filled {
+                    this.reset_idle();
+                }
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending if this.poll_idle_expired(cx) => {
+                debug!(
+                    remote_addr=%this.inner.remote_addr(),
+                    "HTTP keep-alive timeout expired, closing connection"
+                );
+                // Report EOF without reading into buf.
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl AsyncWrite for LimitedStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let res = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = res {
+            if n > 0 {
+                this.reset_idle();
+            }
+        }
+        res
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use metric::{Attributes, Metric};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        time::timeout,
+    };
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn bind(limits: HttpConnectionLimits, metrics: &Registry) -> (LimitedIncoming, SocketAddr) {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        let incoming = AddrIncoming::bind(&addr).expect("failed to bind");
+        let addr = incoming.local_addr();
+        (LimitedIncoming::new(incoming, limits, metrics), addr)
+    }
+
+    async fn accept(incoming: &mut LimitedIncoming) -> LimitedStream {
+        timeout(
+            TIMEOUT,
+            futures::future::poll_fn(|cx| Pin::new(&mut *incoming).poll_accept(cx)),
+        )
+        .await
+        .expect("timeout accepting connection")
+        .expect("listener closed")
+        .expect("accept failed")
+    }
+
+    fn metric_value(metrics: &Registry) -> (u64, u64) {
+        let active = metrics
+            .get_instrument::<Metric<U64Gauge>>("http_connections_active")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch();
+        let rejected = metrics
+            .get_instrument::<Metric<U64Counter>>("http_connections_rejected")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch();
+        (active, rejected)
+    }
+
+    #[tokio::test]
+    async fn test_max_connections() {
+        let metrics = Registry::default();
+        let (mut incoming, addr) = bind(
+            HttpConnectionLimits {
+                max_connections: Some(1),
+                keep_alive_timeout: None,
+            },
+            &metrics,
+        );
+
+        let _client_1 = TcpStream::connect(addr).await.unwrap();
+        let conn_1 = accept(&mut incoming).await;
+        assert_eq!(metric_value(&metrics), (1, 0));
+
+        // The second connection exceeds the limit, and is closed instead of
+        // being returned.
+        let mut client_2 = TcpStream::connect(addr).await.unwrap();
+        assert!(timeout(Duration::from_millis(100), accept(&mut incoming))
+            .await
+            .is_err());
+        assert_eq!(metric_value(&metrics), (1, 1));
+
+        let mut buf = [0; 1];
+        let n = timeout(TIMEOUT, client_2.read(&mut buf))
+            .await
+            .expect("timeout reading closed connection")
+            .unwrap_or(0);
+        assert_eq!(n, 0);
+
+        // Once the first connection closes, new connections are accepted.
+        drop(conn_1);
+        assert_eq!(metric_value(&metrics), (0, 1));
+        let _client_3 = TcpStream::connect(addr).await.unwrap();
+        let _conn_3 = accept(&mut incoming).await;
+        assert_eq!(metric_value(&metrics), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_keep_alive_timeout() {
+        let metrics = Registry::default();
+        let (mut incoming, addr) = bind(
+            HttpConnectionLimits {
+                max_connections: None,
+                keep_alive_timeout: Some(Duration::from_millis(50)),
+            },
+            &metrics,
+        );
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let mut conn = accept(&mut incoming).await;
+
+        // A request in flight prevents the connection from being closed as
+        // idle.
+        let request = conn.activity().start_request();
+        client.write_all(b"bananas").await.unwrap();
+        let mut buf = [0; 7];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"bananas");
+        assert!(timeout(Duration::from_millis(200), conn.read(&mut buf))
+            .await
+            .is_err());
+
+        // Once idle, the connection reports EOF after the keep-alive timeout.
+        drop(request);
+        let n = timeout(TIMEOUT, conn.read(&mut buf))
+            .await
+            .expect("timeout waiting for keep-alive timeout")
+            .unwrap();
+        assert_eq!(n, 0);
+        assert_eq!(metric_value(&metrics), (1, 0));
+    }
+}
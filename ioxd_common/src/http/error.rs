@@ -21,6 +21,7 @@ pub enum HttpApiErrorCode {
     MethodNotAllowed,
     RequestTooLarge,
     UnsupportedMediaType,
+    RequestTimeout,
 }
 
 impl HttpApiErrorCode {
@@ -40,6 +41,7 @@ impl HttpApiErrorCode {
             Self::MethodNotAllowed => "method not allowed",
             Self::RequestTooLarge => "request too large",
             Self::UnsupportedMediaType => "unsupported media type",
+            Self::RequestTimeout => "request timeout",
         }
     }
 
@@ -59,6 +61,7 @@ impl HttpApiErrorCode {
             Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
             Self::RequestTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::RequestTimeout => StatusCode::REQUEST_TIMEOUT,
         }
     }
 
@@ -84,6 +87,7 @@ impl From<StatusCode> for HttpApiErrorCode {
             StatusCode::METHOD_NOT_ALLOWED => Self::MethodNotAllowed,
             StatusCode::PAYLOAD_TOO_LARGE => Self::RequestTooLarge,
             StatusCode::UNSUPPORTED_MEDIA_TYPE => Self::UnsupportedMediaType,
+            StatusCode::REQUEST_TIMEOUT => Self::RequestTimeout,
             v => {
                 warn!(code=%v, "returning unexpected status code as internal error");
                 Self::InternalError
@@ -113,6 +117,11 @@ pub struct HttpApiError {
     /// Optional error line (for line protocol errors).
     #[serde(skip_serializing_if = "Option::is_none")]
     line: Option<usize>,
+
+    /// Optional number of lines written before the request failed (for
+    /// partially accepted writes).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lines_accepted: Option<usize>,
 }
 
 impl HttpApiError {
@@ -122,6 +131,7 @@ impl HttpApiError {
             code: code.into(),
             msg: msg.into(),
             line: None,
+            lines_accepted: None,
         }
     }
 
@@ -130,6 +140,14 @@ impl HttpApiError {
         Self { line, ..self }
     }
 
+    /// Add the number of lines accepted before the error to the error.
+    pub fn with_lines_accepted(self, lines_accepted: Option<usize>) -> Self {
+        Self {
+            lines_accepted,
+            ..self
+        }
+    }
+
     /// Generate response body for this error.
     fn body(&self) -> Body {
         Body::from(serde_json::to_string(&self).expect("must serialise to json"))
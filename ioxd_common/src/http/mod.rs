@@ -4,8 +4,9 @@ use authz::http::AuthorizationHeaderExtension;
 use hyper::{
     http::HeaderValue,
     server::conn::{AddrIncoming, AddrStream},
-    Body, Method, Request, Response,
+    Body, Method, Request, Response, StatusCode,
 };
+use metric::Registry;
 use observability_deps::tracing::{debug, error};
 use serde::Deserialize;
 use snafu::Snafu;
@@ -14,7 +15,10 @@ use tower::Layer;
 use trace_http::{ctx::TraceHeaderParser, tower::TraceLayer};
 
 use crate::{
-    http::error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource},
+    http::{
+        connection::{HttpConnectionLimits, LimitedIncoming, LimitedStream},
+        error::{HttpApiError, HttpApiErrorExt, HttpApiErrorSource},
+    },
     server_type::ServerType,
 };
 
@@ -24,6 +28,7 @@ mod heappy;
 #[cfg(feature = "pprof")]
 mod pprof;
 
+pub mod connection;
 pub mod error;
 pub mod metrics;
 pub mod utils;
@@ -88,14 +93,21 @@ impl HttpApiErrorSource for ApplicationError {
     }
 }
 
+/// Serve the HTTP API of `server_type` on `addr`, applying `connection_limits` to the accepted
+/// connections.
+///
+/// The `/metrics` endpoint is only served if `serve_metrics` is true.
 pub async fn serve(
     addr: AddrIncoming,
     server_type: Arc<dyn ServerType>,
     shutdown: CancellationToken,
     trace_header_parser: TraceHeaderParser,
+    serve_metrics: bool,
+    connection_limits: HttpConnectionLimits,
 ) -> Result<(), hyper::Error> {
     let metric_registry = server_type.metric_registry();
     let trace_collector = server_type.trace_collector();
+    let addr = LimitedIncoming::new(addr, connection_limits, &metric_registry);
 
     let trace_layer = TraceLayer::new(
         trace_header_parser,
@@ -106,10 +118,17 @@ pub async fn serve(
     );
 
     hyper::Server::builder(addr)
-        .serve(hyper::service::make_service_fn(|_conn: &AddrStream| {
+        .serve(hyper::service::make_service_fn(|conn: &LimitedStream| {
             let server_type = Arc::clone(&server_type);
+            let activity = conn.activity();
             let service = hyper::service::service_fn(move |request: Request<_>| {
-                route_request(Arc::clone(&server_type), request)
+                let in_flight = activity.start_request();
+                let response = route_request(Arc::clone(&server_type), request, serve_metrics);
+                async move {
+                    let response = response.await;
+                    drop(in_flight);
+                    response
+                }
             });
 
             let service = trace_layer.layer(service);
@@ -119,9 +138,36 @@ pub async fn serve(
         .await
 }
 
+/// Serve only the `/metrics` endpoint for `metric_registry` on `addr`.
+pub async fn serve_metrics(
+    addr: AddrIncoming,
+    metric_registry: Arc<Registry>,
+    shutdown: CancellationToken,
+) -> Result<(), hyper::Error> {
+    hyper::Server::builder(addr)
+        .serve(hyper::service::make_service_fn(|_conn: &AddrStream| {
+            let metric_registry = Arc::clone(&metric_registry);
+            let service = hyper::service::service_fn(move |request: Request<Body>| {
+                let response = match (request.method(), request.uri().path()) {
+                    (&Method::GET, "/metrics") => Response::new(metrics_body(&metric_registry)),
+                    _ => {
+                        let mut response = Response::new(Body::empty());
+                        *response.status_mut() = StatusCode::NOT_FOUND;
+                        response
+                    }
+                };
+                futures::future::ready(Ok::<_, Infallible>(response))
+            });
+            futures::future::ready(Ok::<_, Infallible>(service))
+        }))
+        .with_graceful_shutdown(shutdown.cancelled())
+        .await
+}
+
 async fn route_request(
     server_type: Arc<dyn ServerType>,
     mut req: Request<Body>,
+    serve_metrics: bool,
 ) -> Result<Response<Body>, Infallible> {
     let auth = { req.headers().get(hyper::header::AUTHORIZATION).cloned() };
     req.extensions_mut()
@@ -137,7 +183,7 @@ async fn route_request(
 
     let response = match (method.clone(), uri.path()) {
         (Method::GET, "/health") => health(),
-        (Method::GET, "/metrics") => handle_metrics(server_type.as_ref()),
+        (Method::GET, "/metrics") if serve_metrics => handle_metrics(server_type.as_ref()),
         (Method::GET, "/debug/pprof") => pprof_home(req).await,
         (Method::GET, "/debug/pprof/profile") => pprof_profile(req).await,
         (Method::GET, "/debug/pprof/allocs") => pprof_heappy_profile(req).await,
@@ -171,11 +217,15 @@ fn health() -> Result<Response<Body>, ApplicationError> {
 }
 
 fn handle_metrics(server_type: &dyn ServerType) -> Result<Response<Body>, ApplicationError> {
+    Ok(Response::new(metrics_body(&server_type.metric_registry())))
+}
+
+fn metrics_body(metric_registry: &Registry) -> Body {
     let mut body: Vec<u8> = Default::default();
     let mut reporter = metric_exporters::PrometheusTextEncoder::new(&mut body);
-    server_type.metric_registry().report(&mut reporter);
+    metric_registry.report(&mut reporter);
 
-    Ok(Response::new(Body::from(body)))
+    Body::from(body)
 }
 
 async fn pprof_home(req: Request<Body>) -> Result<Response<Body>, ApplicationError> {
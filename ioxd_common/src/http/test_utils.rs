@@ -113,6 +113,8 @@ where
                 server_type_captured,
                 CancellationToken::new(),
                 trace_header_parser,
+                true,
+                Default::default(),
             )
             .await
             .unwrap();
@@ -118,11 +118,16 @@ pub async fn http_listener(addr: SocketAddr) -> Result<AddrIncoming> {
 
 /// Instantiates the gRPC and optional HTTP listeners and returns a `Future` that completes when
 /// the listeners have all exited or the `frontend_shutdown` token is called.
+///
+/// If a `metrics_listener` is provided, the `/metrics` endpoint is served on it instead of on
+/// the `http_listener`.
 pub async fn serve(
     common_state: CommonServerState,
     frontend_shutdown: CancellationToken,
     grpc_listener: tokio::net::TcpListener,
     http_listener: Option<AddrIncoming>,
+    metrics_listener: Option<AddrIncoming>,
+    http_connection_limits: http::connection::HttpConnectionLimits,
     server_type: Arc<dyn ServerType>,
 ) -> Result<()> {
     let trace_header_parser = TraceHeaderParser::new()
@@ -152,19 +157,41 @@ pub async fn serve(
     let captured_server_type = Arc::clone(&server_type);
     let captured_shutdown = frontend_shutdown.clone();
     let http_server = async move {
-        if let Some(http_listener) = http_listener {
-            info!(server_type=?captured_server_type, "HTTP server listening");
-            http::serve(
-                http_listener,
-                captured_server_type,
-                captured_shutdown,
-                trace_header_parser,
-            )
-            .await?
-        } else {
-            // don't resolve otherwise will cause server to shutdown
-            captured_shutdown.cancelled().await
-        }
+        let serve_metrics = metrics_listener.is_none();
+
+        let metrics_server = async {
+            if let Some(metrics_listener) = metrics_listener {
+                info!(server_type=?captured_server_type, "HTTP metrics server listening");
+                http::serve_metrics(
+                    metrics_listener,
+                    captured_server_type.metric_registry(),
+                    captured_shutdown.clone(),
+                )
+                .await?
+            }
+            Ok::<_, hyper::Error>(())
+        };
+
+        let api_server = async {
+            if let Some(http_listener) = http_listener {
+                info!(server_type=?captured_server_type, "HTTP server listening");
+                http::serve(
+                    http_listener,
+                    Arc::clone(&captured_server_type),
+                    captured_shutdown.clone(),
+                    trace_header_parser,
+                    serve_metrics,
+                    http_connection_limits,
+                )
+                .await?
+            } else {
+                // don't resolve otherwise will cause server to shutdown
+                captured_shutdown.cancelled().await
+            }
+            Ok::<_, hyper::Error>(())
+        };
+
+        futures::try_join!(api_server, metrics_server)?;
         Ok(())
     }
     .fuse();
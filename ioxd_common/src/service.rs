@@ -2,13 +2,18 @@ use std::sync::Arc;
 
 use clap_blocks::{run_config::RunConfig, socket_addr::SocketAddr};
 
-use crate::server_type::ServerType;
+use crate::{http::connection::HttpConnectionLimits, server_type::ServerType};
 
 /// A service that will start on the specified addresses
 #[derive(Debug)]
 pub struct Service {
     pub http_bind_address: Option<SocketAddr>,
     pub grpc_bind_address: SocketAddr,
+    /// Optional separate address to serve the `/metrics` endpoint on, instead of the HTTP
+    /// API address.
+    pub metrics_bind_address: Option<SocketAddr>,
+    /// Limits on the connections accepted by the HTTP API listener.
+    pub http_connection_limits: HttpConnectionLimits,
     pub server_type: Arc<dyn ServerType>,
 }
 
@@ -17,6 +22,8 @@ impl Service {
         Self {
             http_bind_address: Some(run_config.http_bind_address),
             grpc_bind_address: run_config.grpc_bind_address,
+            metrics_bind_address: None,
+            http_connection_limits: Default::default(),
             server_type,
         }
     }
@@ -25,7 +32,26 @@ impl Service {
         Self {
             http_bind_address: None,
             grpc_bind_address: run_config.grpc_bind_address,
+            metrics_bind_address: None,
+            http_connection_limits: Default::default(),
             server_type,
         }
     }
+
+    /// Serve the `/metrics` endpoint on `metrics_bind_address`, if specified, rather than on
+    /// the HTTP API address.
+    pub fn with_metrics_bind_address(self, metrics_bind_address: Option<SocketAddr>) -> Self {
+        Self {
+            metrics_bind_address,
+            ..self
+        }
+    }
+
+    /// Apply `http_connection_limits` to the connections accepted by the HTTP API listener.
+    pub fn with_http_connection_limits(self, http_connection_limits: HttpConnectionLimits) -> Self {
+        Self {
+            http_connection_limits,
+            ..self
+        }
+    }
 }
@@ -17,9 +17,13 @@ use workspace_hack as _;
 
 use async_trait::async_trait;
 use backoff::BackoffConfig;
-use clap_blocks::compactor::CompactorConfig;
-use compactor::{compactor::Compactor, config::Config};
-use hyper::{Body, Request, Response};
+use clap_blocks::compactor::{CompactorConfig, SingleOversizeFilePolicy, ZeroRowOutputPolicy};
+use compactor::{
+    compactor::Compactor,
+    config::{Config, SizingOverrides},
+    object_store::read_after_write::ReadAfterWriteRetry,
+};
+use hyper::{header::CONTENT_TYPE, Body, Method, Request, Response};
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
 use iox_time::TimeProvider;
@@ -88,12 +92,26 @@ impl ServerType for CompactorServerType {
         self.trace_collector.as_ref().map(Arc::clone)
     }
 
-    /// Just return "not found".
+    /// Serve the compactor status at `/status` if enabled, otherwise return "not found".
     async fn route_http_request(
         &self,
-        _req: Request<Body>,
+        req: Request<Body>,
     ) -> Result<Response<Body>, Box<dyn HttpApiErrorSource>> {
-        Err(Box::new(IoxHttpError::NotFound))
+        if req.method() != Method::GET || req.uri().path() != "/status" {
+            return Err(Box::new(IoxHttpError::NotFound));
+        }
+
+        let snapshot = match self.compactor.status().await {
+            Some(Ok(snapshot)) => snapshot,
+            Some(Err(e)) => return Err(Box::new(IoxHttpError::Status(e.to_string()))),
+            None => return Err(Box::new(IoxHttpError::NotFound)),
+        };
+        let body = serde_json::to_vec(&snapshot).expect("status serialisation");
+
+        Ok(Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("valid response"))
     }
 
     /// Configure the gRPC services.
@@ -118,23 +136,28 @@ impl ServerType for CompactorServerType {
     }
 }
 
-/// Simple error struct, we're not really providing an HTTP interface for the compactor.
+/// Simple error struct, the compactor only serves its status over HTTP.
 #[derive(Debug)]
 pub enum IoxHttpError {
     NotFound,
+    Status(String),
 }
 
 impl IoxHttpError {
     fn status_code(&self) -> HttpApiErrorCode {
         match self {
             Self::NotFound => HttpApiErrorCode::NotFound,
+            Self::Status(_) => HttpApiErrorCode::InternalError,
         }
     }
 }
 
 impl Display for IoxHttpError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Self::NotFound => write!(f, "{self:?}"),
+            Self::Status(e) => write!(f, "failed to read the compactor status: {e}"),
+        }
     }
 }
 
@@ -202,6 +225,20 @@ pub async fn create_compactor_server_type(
 ) -> Arc<dyn ServerType> {
     let backoff_config = BackoffConfig::default();
 
+    // Retry reads of files that are not visible yet, on stores without read-after-write
+    // consistency.
+    let parquet_store_real = match compactor_config.read_after_write_timeout_secs {
+        Some(secs) => ParquetStorage::new(
+            Arc::new(ReadAfterWriteRetry::new(
+                Arc::clone(parquet_store_real.object_store()),
+                backoff_config.clone(),
+                Duration::from_secs(secs),
+            )),
+            parquet_store_real.id(),
+        ),
+        None => parquet_store_real,
+    };
+
     let compactor = Compactor::start(Config {
         metric_registry: Arc::clone(&metric_registry),
         trace_collector: common_state.trace_collector(),
@@ -215,17 +252,57 @@ pub async fn create_compactor_server_type(
         time_provider,
         backoff_config,
         partition_concurrency: compactor_config.compaction_partition_concurrency,
+        partition_concurrency_warm_up: compactor_config
+            .compaction_partition_concurrency_warm_up_secs
+            .map(Duration::from_secs),
         df_concurrency: compactor_config.compaction_df_concurrency,
         partition_scratchpad_concurrency: compactor_config
             .compaction_partition_scratchpad_concurrency,
+        input_read_concurrency: compactor_config.compaction_input_read_concurrency,
+        exec_target_partitions: compactor_config.compaction_exec_target_partitions,
         max_desired_file_size_bytes: compactor_config.max_desired_file_size_bytes,
         percentage_max_file_size: compactor_config.percentage_max_file_size,
         split_percentage: compactor_config.split_percentage,
+        table_sizing_overrides: compactor_config
+            .table_sizing_overrides
+            .iter()
+            .map(|o| {
+                (
+                    o.table.clone(),
+                    SizingOverrides {
+                        max_desired_file_size_bytes: o.max_desired_file_size_bytes,
+                        percentage_max_file_size: o.percentage_max_file_size,
+                        split_percentage: o.split_percentage,
+                    },
+                )
+            })
+            .collect(),
         partition_timeout: Duration::from_secs(compactor_config.partition_timeout_secs),
+        run_iteration_budget: compactor_config
+            .run_iteration_budget_secs
+            .map(Duration::from_secs),
+        min_file_age: compactor_config.min_file_age_secs.map(Duration::from_secs),
+        rewrite_below_format_version: compactor_config.rewrite_below_format_version,
+        single_oversize_file_policy: match compactor_config.single_oversize_file_policy {
+            SingleOversizeFilePolicy::Leave => compactor::config::SingleOversizeFilePolicy::Leave,
+            SingleOversizeFilePolicy::Split => compactor::config::SingleOversizeFilePolicy::Split,
+            SingleOversizeFilePolicy::Rewrite => {
+                compactor::config::SingleOversizeFilePolicy::Rewrite
+            }
+        },
+        verify_output: compactor_config.verify_output,
+        zero_row_output_policy: match compactor_config.zero_row_output_policy {
+            ZeroRowOutputPolicy::SkipAndWarn => compactor::config::ZeroRowOutputPolicy::SkipAndWarn,
+            ZeroRowOutputPolicy::DeleteInputs => {
+                compactor::config::ZeroRowOutputPolicy::DeleteInputs
+            }
+        },
         shadow_mode: compactor_config.shadow_mode,
         enable_scratchpad: compactor_config.enable_scratchpad,
+        min_num_l0_files_to_compact: compactor_config.min_num_l0_files_to_compact,
         min_num_l1_files_to_compact: compactor_config.min_num_l1_files_to_compact,
         process_once: compactor_config.process_once,
+        pause_file: compactor_config.pause_file,
         simulate_without_object_store: false,
         parquet_files_sink_override: None,
         all_errors_are_fatal: false,
@@ -238,6 +315,8 @@ pub async fn create_compactor_server_type(
             .gossip_config
             .gossip_bind_address
             .map(Into::into),
+        status_endpoint: compactor_config.status_endpoint,
+        completion_webhook_url: compactor_config.completion_webhook_url,
     })
     .await;
 
@@ -229,6 +229,7 @@ pub async fn create_ingester_server_type(
         ingester_config.persist_max_parallelism,
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
+        ingester_config.persist_memory_threshold_bytes,
         object_store,
         gossip,
         ingester_config
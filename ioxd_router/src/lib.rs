@@ -14,7 +14,7 @@
 
 use gossip::{Bytes, Identity, TopicInterests};
 use gossip_schema::{dispatcher::SchemaRx, handle::SchemaTx};
-use observability_deps::tracing::info;
+use observability_deps::tracing::{info, warn};
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
@@ -25,8 +25,11 @@ use std::{
 };
 
 use async_trait::async_trait;
-use authz::{Authorizer, AuthorizerInstrumentation, IoxAuthorizer};
-use clap_blocks::{gossip::GossipConfig, router::RouterConfig};
+use authz::{Authorizer, AuthorizerInstrumentation, CachingAuthorizer, IoxAuthorizer};
+use clap_blocks::{
+    gossip::GossipConfig,
+    router::{DuplicateTagPolicy, OutsideRetentionPolicy, RouterConfig, ShardAssignment},
+};
 use data_types::NamespaceName;
 use hashbrown::HashMap;
 use hyper::{Body, Request, Response};
@@ -52,11 +55,11 @@ use ioxd_common::{
 };
 use metric::Registry;
 use mutable_batch::MutableBatch;
-use object_store::DynObjectStore;
+use object_store::{local::LocalFileSystem, path::Path, DynObjectStore};
 use router::{
     dml_handlers::{
         lazy_connector::LazyConnector, DmlHandler, DmlHandlerChainExt, FanOutAdaptor,
-        InstrumentationDecorator, Partitioner, RetentionValidator, RpcWrite,
+        InstrumentationDecorator, MeasurementFilter, Partitioner, RetentionValidator, RpcWrite,
     },
     gossip::{
         anti_entropy::{
@@ -82,6 +85,8 @@ use router::{
     server::{
         grpc::RpcWriteGrpcDelegate,
         http::{
+            dead_letter::DeadLetterSink,
+            durable::IngesterPersistWaiter,
             write::{
                 multi_tenant::MultiTenantRequestUnifier, single_tenant::SingleTenantRequestUnifier,
                 WriteRequestUnifier,
@@ -112,6 +117,13 @@ pub enum Error {
     /// An error binding the UDP socket for gossip communication.
     #[error("failed to bind udp gossip socket: {0}")]
     GossipBind(std::io::Error),
+
+    /// An error opening the local directory rejected writes are stored in.
+    #[error("failed to open dead-letter directory '{}': {source}", path.display())]
+    DeadLetterDir {
+        source: Box<dyn std::error::Error>,
+        path: std::path::PathBuf,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -244,6 +256,7 @@ impl HttpApiErrorSource for IoxHttpErrorAdaptor {
     fn to_http_api_error(&self) -> HttpApiError {
         HttpApiError::new(self.0.as_status_code(), self.to_string())
             .with_line(self.0.get_parse_error_line_index())
+            .with_lines_accepted(self.0.lines_accepted())
     }
 }
 
@@ -280,7 +293,12 @@ pub async fn create_router_server_type(
         router_config.rpc_write_replicas,
         &metrics,
         router_config.rpc_write_health_num_probes,
-    );
+    )
+    .with_shard_assignment(match router_config.rpc_write_shard_assignment {
+        ShardAssignment::RoundRobin => router::dml_handlers::ShardAssignment::RoundRobin,
+        ShardAssignment::HashByNamespace => router::dml_handlers::ShardAssignment::HashByNamespace,
+        ShardAssignment::HashByPartition => router::dml_handlers::ShardAssignment::HashByPartition,
+    });
     let rpc_writer = InstrumentationDecorator::new("rpc_writer", &metrics, rpc_writer);
 
     // # Namespace cache
@@ -350,10 +368,32 @@ pub async fn create_router_server_type(
     // # Retention validator
     //
     // Add a retention validator into handler stack to reject data outside the retention period
-    let retention_validator = RetentionValidator::new();
+    // (unless configured to accept it)
+    let retention_validator = RetentionValidator::new(
+        match router_config.outside_retention_policy {
+            OutsideRetentionPolicy::Reject => router::dml_handlers::OutsideRetentionPolicy::Reject,
+            OutsideRetentionPolicy::RejectUnprocessable => {
+                router::dml_handlers::OutsideRetentionPolicy::RejectUnprocessable
+            }
+            OutsideRetentionPolicy::Accept => router::dml_handlers::OutsideRetentionPolicy::Accept,
+        },
+        &metrics,
+    );
     let retention_validator =
         InstrumentationDecorator::new("retention_validator", &metrics, retention_validator);
 
+    // # Measurement filter
+    //
+    // Reject writes containing measurements not accepted by the configured
+    // allow / deny patterns (accepts all measurements by default)
+    let measurement_filter = MeasurementFilter::new(
+        router_config.measurement_allow_pattern.clone(),
+        router_config.measurement_deny_pattern.clone(),
+        &metrics,
+    );
+    let measurement_filter =
+        InstrumentationDecorator::new("measurement_filter", &metrics, measurement_filter);
+
     // # Write partitioner
     //
     // Add a write partitioner into the handler stack that splits by the date
@@ -381,7 +421,8 @@ pub async fn create_router_server_type(
                 MissingNamespaceAction::Reject
             }
         },
-    );
+    )
+    .with_name_pattern(router_config.namespace_name_pattern.regex().clone());
     //
     ////////////////////////////////////////////////////////////////////////////
 
@@ -391,7 +432,8 @@ pub async fn create_router_server_type(
     // # Handler stack
     //
     // Build the chain of DML handlers that forms the request processing pipeline
-    let handler_stack = retention_validator
+    let handler_stack = measurement_filter
+        .and_then(retention_validator)
         .and_then(schema_validator)
         .and_then(partitioner)
         // Once writes have been partitioned, they are processed in parallel.
@@ -417,7 +459,13 @@ pub async fn create_router_server_type(
         (true, Some(addr)) => {
             let authz = IoxAuthorizer::connect_lazy(addr.clone())
                 .map(|c| {
-                    Arc::new(AuthorizerInstrumentation::new(&metrics, c)) as Arc<dyn Authorizer>
+                    let authz = AuthorizerInstrumentation::new(&metrics, c);
+                    let ttl = router_config.authz_cache_ttl;
+                    if ttl.is_zero() {
+                        Arc::new(authz) as Arc<dyn Authorizer>
+                    } else {
+                        Arc::new(CachingAuthorizer::new(authz, ttl))
+                    }
                 })
                 .map_err(|source| Error::AuthzConfig {
                     source,
@@ -441,7 +489,7 @@ pub async fn create_router_server_type(
             unreachable!("INFLUXDB_IOX_AUTHZ_ADDR is set, but authz only exists for single_tenancy. Check the INFLUXDB_IOX_SINGLE_TENANCY")
         }
     };
-    let http = HttpDelegate::new(
+    let mut http = HttpDelegate::new(
         common_state.run_config().max_http_request_size,
         router_config.http_request_limit,
         namespace_resolver,
@@ -449,6 +497,62 @@ pub async fn create_router_server_type(
         &metrics,
         write_request_unifier?,
     );
+    if let Some(threads) = router_config.http_parse_threads {
+        http = http.with_parse_workers(threads);
+    }
+    http = http.with_duplicate_tag_policy(match router_config.duplicate_tag_policy {
+        DuplicateTagPolicy::Reject => mutable_batch_lp::DuplicateTagPolicy::Reject,
+        DuplicateTagPolicy::LastWins => mutable_batch_lp::DuplicateTagPolicy::LastWins,
+        DuplicateTagPolicy::FirstWins => mutable_batch_lp::DuplicateTagPolicy::FirstWins,
+    });
+    http = http.with_line_limits(
+        router_config.max_line_bytes,
+        router_config.max_fields_per_line,
+    );
+    if let Some(deadline) = router_config.http_write_deadline_seconds {
+        http = http.with_write_deadline(deadline);
+    }
+    if let Some(segment_bytes) = router_config.http_streaming_write_segment_bytes {
+        http = http.with_streaming_writes(segment_bytes);
+    }
+    let dead_letter_location = match (
+        &router_config.http_dead_letter_dir,
+        &router_config.http_dead_letter_object_store_prefix,
+    ) {
+        (Some(dir), _) => {
+            let dead_letter_dir_err = |e: Box<dyn std::error::Error>| Error::DeadLetterDir {
+                source: e,
+                path: dir.clone(),
+            };
+            std::fs::create_dir_all(dir).map_err(|e| dead_letter_dir_err(e.into()))?;
+            let store =
+                LocalFileSystem::new_with_prefix(dir).map_err(|e| dead_letter_dir_err(e.into()))?;
+            Some((Arc::new(store) as Arc<DynObjectStore>, Path::default()))
+        }
+        (None, Some(prefix)) => Some((Arc::clone(&object_store), Path::from(prefix.as_str()))),
+        (None, None) => None,
+    };
+    if let Some((store, prefix)) = dead_letter_location {
+        http = http.with_dead_letter_sink(DeadLetterSink::new(
+            store,
+            prefix,
+            router_config.http_dead_letter_max_payload_bytes,
+            router_config.http_dead_letter_redact,
+            catalog.time_provider(),
+            &metrics,
+        ));
+    }
+    if router_config.allow_sync_writes {
+        warn!("durable writes are enabled, they are meant for testing only");
+        let ingesters = router_config.ingester_addresses.iter().map(|addr| {
+            Endpoint::from_shared(hyper::body::Bytes::from(addr.to_string()))
+                .expect("invalid ingester connection address")
+        });
+        http = http.with_durable_writes(
+            Arc::new(IngesterPersistWaiter::new(ingesters)),
+            router_config.sync_write_timeout_seconds,
+        );
+    }
 
     // Initialize the gRPC API delegate that creates the services relevant to the RPC
     // write router path and use it to create the relevant `RpcWriteRouterServer` and
@@ -21,7 +21,8 @@ use criterion as _;
 use workspace_hack as _;
 
 use hashbrown::{hash_map::Entry, HashMap, HashSet};
-use influxdb_line_protocol::{parse_lines, FieldValue, ParsedLine};
+use influxdb_line_protocol::{parse_lines, split_lines, FieldValue, ParsedLine};
+use itertools::Either;
 use mutable_batch::writer::Writer;
 use mutable_batch::MutableBatch;
 use snafu::{ResultExt, Snafu};
@@ -57,6 +58,39 @@ pub enum LineError {
 
     #[snafu(display("timestamp overflows i64 on line {} (1-based)", line))]
     TimestampOverflow { line: usize },
+
+    #[snafu(display(
+        "line {} (1-based) is {} bytes long, exceeding the maximum of {} bytes",
+        line,
+        len,
+        max
+    ))]
+    LineTooLong { line: usize, len: usize, max: usize },
+
+    #[snafu(display(
+        "line {} (1-based) has {} fields, exceeding the maximum of {} fields",
+        line,
+        fields,
+        max
+    ))]
+    TooManyFields {
+        line: usize,
+        fields: usize,
+        max: usize,
+    },
+}
+
+impl LineError {
+    /// The 1-based number of the line this error occurred on.
+    pub fn line(&self) -> usize {
+        match self {
+            Self::LineProtocol { line, .. }
+            | Self::Write { line, .. }
+            | Self::TimestampOverflow { line }
+            | Self::LineTooLong { line, .. }
+            | Self::TooManyFields { line, .. } => *line,
+        }
+    }
 }
 
 /// Result type for line protocol conversion
@@ -71,6 +105,18 @@ pub struct PayloadStatistics {
     pub num_lines: usize,
 }
 
+/// How a tag name appearing more than once in a single line is handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateTagPolicy {
+    /// Reject the line with [`LineWriteError::DuplicateTag`].
+    #[default]
+    Reject,
+    /// Keep the value of the last occurrence of the tag.
+    LastWins,
+    /// Keep the value of the first occurrence of the tag.
+    FirstWins,
+}
+
 /// Converts line protocol to a set of [`MutableBatch`]
 #[derive(Debug)]
 pub struct LinesConverter {
@@ -78,6 +124,12 @@ pub struct LinesConverter {
     default_time: i64,
     /// The multiplier to convert input timestamps to nanoseconds
     timestamp_base: i64,
+    /// How tags repeated within a line are handled
+    duplicate_tag_policy: DuplicateTagPolicy,
+    /// The maximum length of a line in bytes, if limited
+    max_line_bytes: Option<usize>,
+    /// The maximum number of fields in a line, if limited
+    max_fields_per_line: Option<usize>,
     /// The statistics
     stats: PayloadStatistics,
     /// The current batches
@@ -90,6 +142,9 @@ impl LinesConverter {
         Self {
             default_time,
             timestamp_base: 1,
+            duplicate_tag_policy: Default::default(),
+            max_line_bytes: None,
+            max_fields_per_line: None,
             stats: Default::default(),
             batches: Default::default(),
         }
@@ -100,6 +155,27 @@ impl LinesConverter {
         self.timestamp_base = timestamp_base
     }
 
+    /// Sets how tags that appear more than once in a single line are handled,
+    /// [`DuplicateTagPolicy::Reject`] by default.
+    pub fn set_duplicate_tag_policy(&mut self, policy: DuplicateTagPolicy) {
+        self.duplicate_tag_policy = policy
+    }
+
+    /// Rejects lines longer than `max` bytes with [`LineError::LineTooLong`].
+    ///
+    /// Lines are not limited by default.
+    pub fn set_max_line_bytes(&mut self, max: usize) {
+        self.max_line_bytes = Some(max)
+    }
+
+    /// Rejects lines with more than `max` fields with
+    /// [`LineError::TooManyFields`].
+    ///
+    /// Lines are not limited by default.
+    pub fn set_max_fields_per_line(&mut self, max: usize) {
+        self.max_fields_per_line = Some(max)
+    }
+
     /// Write some line protocol data.
     ///
     /// If a field / tag name appears more than once in a single line, the
@@ -109,22 +185,29 @@ impl LinesConverter {
     ///   * duplicate fields, different value: last occurrence wins
     ///   * duplicate fields, different types: return
     ///     [`LineWriteError::ConflictedFieldTypes`]
-    ///   * duplicate tags, same value: return [`LineWriteError::DuplicateTag`]
-    ///   * duplicate tags, different value: return
-    ///     [`LineWriteError::DuplicateTag`]
-    ///   * duplicate tags, different types: return
-    ///     [`LineWriteError::DuplicateTag`]
+    ///   * duplicate tags: return [`LineWriteError::DuplicateTag`], or keep
+    ///     the last / first occurrence, as set by
+    ///     [`set_duplicate_tag_policy()`](Self::set_duplicate_tag_policy)
     ///   * same name for tag and field: return
     ///     [`mutable_batch::writer::Error::TypeMismatch`]
     ///   * same name for tag and field, different type :
     ///     [`mutable_batch::writer::Error::TypeMismatch`]
     ///
     pub fn write_lp(&mut self, lines: &str) -> Result<()> {
-        let errors = parse_lines(lines)
+        // Only split out the raw lines to measure them when they are limited.
+        let lines = match self.max_line_bytes {
+            None => Either::Left(parse_lines(lines).map(|line| (0, line))),
+            Some(_) => Either::Right(
+                split_lines(lines).filter_map(|raw| Some((raw.len(), parse_lines(raw).next()?))),
+            ),
+        };
+
+        let errors = lines
             .enumerate()
-            .filter_map(|(line_idx, maybe_line)| {
-                maybe_line
-                    .context(LineProtocolSnafu { line: line_idx + 1 })
+            .filter_map(|(line_idx, (len, maybe_line))| {
+                self.check_line_bytes(len, line_idx)
+                    .and_then(|_| maybe_line.context(LineProtocolSnafu { line: line_idx + 1 }))
+                    .and_then(|line| self.check_fields(line, line_idx))
                     .and_then(|line| self.rebase_timestamp(line, line_idx))
                     .and_then(|line| self.add_line_to_batch(line, line_idx))
                     .err()
@@ -138,6 +221,33 @@ impl LinesConverter {
         Ok(())
     }
 
+    fn check_line_bytes(&self, len: usize, line_idx: usize) -> Result<(), LineError> {
+        match self.max_line_bytes {
+            Some(max) if len > max => Err(LineError::LineTooLong {
+                line: line_idx + 1,
+                len,
+                max,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_fields<'a>(
+        &self,
+        line: ParsedLine<'a>,
+        line_idx: usize,
+    ) -> Result<ParsedLine<'a>, LineError> {
+        let fields = line.field_set.len();
+        match self.max_fields_per_line {
+            Some(max) if fields > max => Err(LineError::TooManyFields {
+                line: line_idx + 1,
+                fields,
+                max,
+            }),
+            _ => Ok(line),
+        }
+    }
+
     fn rebase_timestamp<'a>(
         &self,
         mut line: ParsedLine<'a>,
@@ -171,8 +281,13 @@ impl LinesConverter {
 
         // TODO: Reuse writer
         let mut writer = Writer::new(batch, 1);
-        match write_line(&mut writer, &line, self.default_time)
-            .context(WriteSnafu { line: line_idx + 1 })
+        match write_line(
+            &mut writer,
+            &line,
+            self.default_time,
+            self.duplicate_tag_policy,
+        )
+        .context(WriteSnafu { line: line_idx + 1 })
         {
             Ok(_) => writer.commit(),
             Err(e) => return Err(e),
@@ -219,8 +334,8 @@ pub enum LineWriteError {
         source: mutable_batch::writer::Error,
     },
 
-    /// The specified tag name appears twice in one LP line, with conflicting
-    /// values.
+    /// The specified tag name appears twice in one LP line, and duplicate tags
+    /// are rejected.
     #[snafu(display(
         "the tag '{}' is specified more than once with conflicting values",
         name
@@ -248,19 +363,32 @@ pub fn write_line(
     writer: &mut Writer<'_>,
     line: &ParsedLine<'_>,
     default_time: i64,
+    duplicate_tag_policy: DuplicateTagPolicy,
 ) -> Result<(), LineWriteError> {
     // Only allocate the seen tags hashset if there are tags.
     if let Some(tags) = &line.series.tag_set {
         let mut seen = HashSet::with_capacity(tags.len());
+
+        // Visit the occurrence of each tag that is kept first.
+        let tags = match duplicate_tag_policy {
+            DuplicateTagPolicy::LastWins => Either::Left(tags.iter().rev()),
+            DuplicateTagPolicy::Reject | DuplicateTagPolicy::FirstWins => {
+                Either::Right(tags.iter())
+            }
+        };
+
         for (tag_key, tag_value) in tags {
-            // Check if a field with this name has been observed previously.
+            // Check if a tag with this name has been observed previously.
             if !seen.insert(tag_key) {
-                // This tag_key appears more than once, with differing values.
-                //
-                // This is always an error.
-                return Err(LineWriteError::DuplicateTag {
-                    name: tag_key.to_string(),
-                });
+                // This tag_key appears more than once.
+                match duplicate_tag_policy {
+                    DuplicateTagPolicy::Reject => {
+                        return Err(LineWriteError::DuplicateTag {
+                            name: tag_key.to_string(),
+                        })
+                    }
+                    DuplicateTagPolicy::LastWins | DuplicateTagPolicy::FirstWins => continue,
+                }
             }
             writer
                 .write_tag(tag_key.as_str(), None, std::iter::once(tag_value.as_str()))
@@ -472,6 +600,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_line_limits() {
+        let lp = "cpu,tag1=v1 val=2i 0\n\
+                  \n\
+                  cpu,tag1=a_long_tag_value val=2i 0\n\
+                  cpu,tag1=v1 val=2i,other=3i 0\n\
+                  cpu,tag1=v2 val=2i 1";
+
+        let mut converter = LinesConverter::new(5);
+        converter.set_max_line_bytes(30);
+        converter.set_max_fields_per_line(1);
+        let result = converter.write_lp(lp);
+        assert_matches!(
+            result,
+            Err(Error::PerLine { lines }) if matches!(
+                &lines[..],
+                [
+                    LineError::LineTooLong { line: 2, len: 34, max: 30 },
+                    LineError::TooManyFields { line: 3, fields: 2, max: 1 },
+                ]
+            ),
+            "expected line limit errors from write_lp(), but found {:?}", result
+        );
+
+        let (batches, stats) = converter.finish().unwrap();
+        assert_eq!(stats.num_lines, 2);
+        assert_batches_eq!(
+            &[
+                "+------+--------------------------------+-----+",
+                "| tag1 | time                           | val |",
+                "+------+--------------------------------+-----+",
+                "| v1   | 1970-01-01T00:00:00Z           | 2   |",
+                "| v2   | 1970-01-01T00:00:00.000000001Z | 2   |",
+                "+------+--------------------------------+-----+",
+            ],
+            &[batches["cpu"].to_arrow(Projection::All).unwrap()]
+        );
+    }
+
     #[test]
     fn test_nulls_string_and_float() {
         let lp = r#"m f0="cat" 1639612800000000000
@@ -635,6 +802,46 @@ m b=t 1639612800000000000
             ));
         }
 
+        fn duplicate_tags_with_policy(policy: DuplicateTagPolicy) -> MutableBatch {
+            let mut converter = LinesConverter::new(5);
+            converter.set_duplicate_tag_policy(policy);
+            converter
+                .write_lp("m1,tag=1,other=A,tag=2 val=1i 0")
+                .unwrap();
+            let (mut batches, _) = converter.finish().unwrap();
+            batches.remove("m1").unwrap()
+        }
+
+        #[test]
+        fn test_duplicate_tags_last_wins() {
+            let batch = duplicate_tags_with_policy(DuplicateTagPolicy::LastWins);
+            assert_batches_eq!(
+                &[
+                    "+-------+-----+----------------------+-----+",
+                    "| other | tag | time                 | val |",
+                    "+-------+-----+----------------------+-----+",
+                    "| A     | 2   | 1970-01-01T00:00:00Z | 1   |",
+                    "+-------+-----+----------------------+-----+",
+                ],
+                &[batch.to_arrow(Projection::All).unwrap()]
+            );
+        }
+
+        #[test]
+        fn test_duplicate_tags_first_wins() {
+            let batch = duplicate_tags_with_policy(DuplicateTagPolicy::FirstWins);
+            assert_batches_eq!(
+                &[
+                    "+-------+-----+----------------------+-----+",
+                    "| other | tag | time                 | val |",
+                    "+-------+-----+----------------------+-----+",
+                    "| A     | 1   | 1970-01-01T00:00:00Z | 1   |",
+                    "+-------+-----+----------------------+-----+",
+                ],
+                &[batch.to_arrow(Projection::All).unwrap()]
+            );
+        }
+
         // NOTE: All tags are strings, so this should never be a type conflict.
         #[test]
         fn test_duplicate_tags_different_type() {
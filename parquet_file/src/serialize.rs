@@ -14,7 +14,10 @@ use observability_deps::tracing::{debug, trace, warn};
 use parquet::{
     basic::Compression,
     errors::ParquetError,
-    file::{metadata::KeyValue, properties::WriterProperties},
+    file::{
+        metadata::KeyValue,
+        properties::{WriterProperties, WriterVersion},
+    },
 };
 use thiserror::Error;
 
@@ -31,6 +34,13 @@ pub const ROW_GROUP_WRITE_SIZE: usize = 1024 * 1024;
 #[allow(clippy::assertions_on_constants)]
 const _: () = assert!(ROW_GROUP_WRITE_SIZE % BATCH_SIZE == 0);
 
+/// Parquet writer version used for all files written by IOx.
+///
+/// The numeric value of this version is recorded in the footer of every file
+/// (see [`WriterVersion::as_num`]) and allows files written with an older
+/// version to be identified and rewritten.
+pub const PARQUET_WRITER_VERSION: WriterVersion = WriterVersion::PARQUET_1_0;
+
 /// [`RecordBatch`] to Parquet serialisation errors.
 ///
 /// [`RecordBatch`]: arrow::record_batch::RecordBatch
@@ -199,7 +209,8 @@ fn writer_props(meta: &IoxMetadata) -> Result<WriterProperties, prost::EncodeErr
             value: Some(meta.to_base64()?),
         }]))
         .set_compression(Compression::ZSTD(Default::default()))
-        .set_max_row_group_size(ROW_GROUP_WRITE_SIZE);
+        .set_max_row_group_size(ROW_GROUP_WRITE_SIZE)
+        .set_writer_version(PARQUET_WRITER_VERSION);
 
     Ok(builder.build())
 }
@@ -26,6 +26,10 @@ use datafusion::{
 use datafusion_util::config::{iox_session_config, register_iox_object_store};
 use object_store::{DynObjectStore, ObjectMeta};
 use observability_deps::tracing::*;
+use parquet::file::{
+    footer::{decode_footer, decode_metadata},
+    FOOTER_SIZE,
+};
 use schema::Projection;
 use std::{
     fmt::Display,
@@ -67,6 +71,23 @@ impl From<UploadError> for DataFusionError {
     }
 }
 
+/// Errors returned when reading the Parquet format version of a file with
+/// [`ParquetStorage::read_format_version`].
+#[derive(Debug, Error)]
+pub enum FormatVersionError {
+    /// The file is smaller than the footer it claims to have.
+    #[error("file of {0} bytes is too small to contain a parquet footer")]
+    TooSmall(usize),
+
+    /// Reading the footer from the object store failed.
+    #[error("failed to read parquet footer from object storage: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// The footer is not valid Parquet metadata.
+    #[error("failed to decode parquet footer: {0}")]
+    Decode(#[from] parquet::errors::ParquetError),
+}
+
 /// ID for an object store hooked up into DataFusion.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct StorageId(&'static str);
@@ -283,6 +304,42 @@ impl ParquetStorage {
         Ok((parquet_meta, file_size))
     }
 
+    /// Read the Parquet writer version recorded in the footer of the
+    /// `file_size` bytes long file at `path`.
+    ///
+    /// Only the footer is fetched from the object store, not the file data.
+    pub async fn read_format_version(
+        &self,
+        path: &ParquetFilePath,
+        file_size: usize,
+    ) -> Result<i32, FormatVersionError> {
+        if file_size < FOOTER_SIZE {
+            return Err(FormatVersionError::TooSmall(file_size));
+        }
+        let location = path.object_store_path();
+
+        let footer = self
+            .object_store
+            .get_range(&location, file_size - FOOTER_SIZE..file_size)
+            .await?;
+        let footer: &[u8; FOOTER_SIZE] = footer
+            .as_ref()
+            .try_into()
+            .expect("object store returned the requested range");
+        let metadata_len = decode_footer(footer)?;
+
+        let metadata_end = file_size - FOOTER_SIZE;
+        let metadata_start = metadata_end
+            .checked_sub(metadata_len)
+            .ok_or(FormatVersionError::TooSmall(file_size))?;
+        let metadata = self
+            .object_store
+            .get_range(&location, metadata_start..metadata_end)
+            .await?;
+
+        Ok(decode_metadata(&metadata)?.file_metadata().version())
+    }
+
     /// Inputs for [`ParquetExec`].
     ///
     /// See [`ParquetExecInput`] for more information.
@@ -358,6 +415,29 @@ mod tests {
         assert_eq!(got_iox_meta, meta);
     }
 
+    #[tokio::test]
+    async fn test_read_format_version() {
+        let object_store: Arc<DynObjectStore> = Arc::new(object_store::memory::InMemory::default());
+
+        let store = ParquetStorage::new(object_store, StorageId::from("iox"));
+
+        let (partition_id, meta) = meta();
+        let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
+        let (_file_meta, file_size) = upload(&store, &partition_id, &meta, batch).await;
+
+        let path: ParquetFilePath = (&partition_id, &meta).into();
+        let version = store
+            .read_format_version(&path, file_size)
+            .await
+            .expect("should read format version");
+        assert_eq!(version, serialize::PARQUET_WRITER_VERSION.as_num());
+
+        assert!(matches!(
+            store.read_format_version(&path, 4).await,
+            Err(FormatVersionError::TooSmall(4))
+        ));
+    }
+
     #[tokio::test]
     async fn test_simple_roundtrip() {
         let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
@@ -0,0 +1,45 @@
+//! Structured audit events for catalog objects created by the router.
+//!
+//! Events are emitted at [`AUDIT_LOG_TARGET`] so that they can be filtered
+//! into a dedicated audit sink, independently of the router's other logs.
+
+use data_types::{NamespaceName, TableId};
+use iox_time::{SystemProvider, TimeProvider};
+use observability_deps::tracing::*;
+use trace::ctx::SpanContext;
+
+/// The tracing target all audit events are emitted at.
+pub const AUDIT_LOG_TARGET: &str = "iox_router::audit";
+
+/// Record the auto-creation of `namespace` with the given retention period.
+pub(crate) fn namespace_created(namespace: &NamespaceName<'_>, retention_period_ns: Option<i64>) {
+    info!(
+        target: AUDIT_LOG_TARGET,
+        %namespace,
+        ?retention_period_ns,
+        created_at = %SystemProvider::new().now(),
+        "namespace created"
+    );
+}
+
+/// Record the creation of `table` in `namespace` by a write.
+///
+/// The trace ID of the originating request is included when the request is
+/// traced, allowing the event to be correlated with the request logs.
+pub(crate) fn table_created(
+    namespace: &NamespaceName<'_>,
+    table: &str,
+    table_id: TableId,
+    span_ctx: Option<&SpanContext>,
+) {
+    let trace_id = span_ctx.map(|ctx| format!("{:x}", ctx.trace_id.get()));
+    info!(
+        target: AUDIT_LOG_TARGET,
+        %namespace,
+        table_name = %table,
+        %table_id,
+        ?trace_id,
+        created_at = %SystemProvider::new().now(),
+        "table created"
+    );
+}
@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use data_types::{NamespaceName, NamespaceSchema};
+use hashbrown::HashMap;
+use metric::U64Counter;
+use mutable_batch::MutableBatch;
+use regex::Regex;
+use std::sync::Arc;
+use thiserror::Error;
+use trace::ctx::SpanContext;
+
+use super::DmlHandler;
+
+/// Errors emitted when a write contains a measurement that is not accepted.
+#[derive(Debug, Error)]
+pub enum MeasurementFilterError {
+    /// The measurement does not match the allow pattern.
+    #[error(
+        "measurement {measurement} is not accepted: it does not match the allow \
+        pattern {pattern}"
+    )]
+    NotAllowed {
+        /// The rejected measurement.
+        measurement: String,
+        /// The allow pattern the measurement does not match.
+        pattern: String,
+    },
+
+    /// The measurement matches the deny pattern.
+    #[error("measurement {measurement} is not accepted: it matches the deny pattern {pattern}")]
+    Denied {
+        /// The rejected measurement.
+        measurement: String,
+        /// The deny pattern the measurement matches.
+        pattern: String,
+    },
+}
+
+/// A [`DmlHandler`] implementation that rejects writes containing a
+/// measurement not accepted by the configured patterns.
+///
+/// A measurement is accepted if it matches the allow pattern (if any) and does
+/// not match the deny pattern (if any). The patterns apply to all namespaces.
+/// If any measurement of a write is not accepted, the entire write is
+/// rejected.
+#[derive(Debug)]
+pub struct MeasurementFilter {
+    allow: Option<Regex>,
+    deny: Option<Regex>,
+
+    rejected_not_allowed: U64Counter,
+    rejected_denied: U64Counter,
+}
+
+impl MeasurementFilter {
+    /// Initialise a new [`MeasurementFilter`] accepting the measurements that
+    /// match `allow` and do not match `deny`.
+    ///
+    /// All measurements are accepted if neither pattern is set.
+    pub fn new(allow: Option<Regex>, deny: Option<Regex>, metrics: &metric::Registry) -> Self {
+        let rejected = metrics.register_metric::<U64Counter>(
+            "router_measurement_rejected",
+            "number of writes rejected because of a measurement not accepted by the named rule",
+        );
+
+        Self {
+            allow,
+            deny,
+            rejected_not_allowed: rejected.recorder(&[("rule", "allow")]),
+            rejected_denied: rejected.recorder(&[("rule", "deny")]),
+        }
+    }
+
+    fn check(&self, measurement: &str) -> Result<(), MeasurementFilterError> {
+        if let Some(allow) = &self.allow {
+            if !allow.is_match(measurement) {
+                self.rejected_not_allowed.inc(1);
+                return Err(MeasurementFilterError::NotAllowed {
+                    measurement: measurement.to_string(),
+                    pattern: allow.to_string(),
+                });
+            }
+        }
+        if let Some(deny) = &self.deny {
+            if deny.is_match(measurement) {
+                self.rejected_denied.inc(1);
+                return Err(MeasurementFilterError::Denied {
+                    measurement: measurement.to_string(),
+                    pattern: deny.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DmlHandler for MeasurementFilter {
+    type WriteError = MeasurementFilterError;
+
+    type WriteInput = HashMap<String, MutableBatch>;
+    type WriteOutput = Self::WriteInput;
+
+    /// Reject the write if any of its measurements is not accepted.
+    async fn write(
+        &self,
+        _namespace: &NamespaceName<'static>,
+        _namespace_schema: Arc<NamespaceSchema>,
+        batch: Self::WriteInput,
+        _span_ctx: Option<SpanContext>,
+    ) -> Result<Self::WriteOutput, Self::WriteError> {
+        for table_name in batch.keys() {
+            self.check(table_name)?;
+        }
+
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use iox_tests::{TestCatalog, TestNamespace};
+    use metric::{Attributes, Metric};
+    use once_cell::sync::Lazy;
+
+    use super::*;
+
+    static NAMESPACE: Lazy<NamespaceName<'static>> = Lazy::new(|| "bananas".try_into().unwrap());
+
+    #[tokio::test]
+    async fn test_accept_all_by_default() {
+        let namespace = test_setup().await;
+        let metrics = metric::Registry::default();
+        let handler = MeasurementFilter::new(None, None, &metrics);
+
+        let writes = lp_to_writes("bananas val=42i 1\nplatanos val=42i 1");
+        let got = handler
+            .write(&NAMESPACE, namespace.schema().await.into(), writes, None)
+            .await
+            .expect("write should be accepted");
+        assert_eq!(got.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_allow_pattern() {
+        let namespace = test_setup().await;
+        let metrics = metric::Registry::default();
+        let handler = MeasurementFilter::new(Some(Regex::new("^prod_").unwrap()), None, &metrics);
+
+        handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes("prod_cpu val=42i 1"),
+                None,
+            )
+            .await
+            .expect("write should be accepted");
+
+        let got = handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes("prod_cpu val=42i 1\njunk val=42i 1"),
+                None,
+            )
+            .await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(
+                e.to_string(),
+                "measurement junk is not accepted: it does not match the allow pattern ^prod_"
+            );
+        });
+
+        assert_eq!(rejected(&metrics, "allow"), 1);
+        assert_eq!(rejected(&metrics, "deny"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_deny_pattern() {
+        let namespace = test_setup().await;
+        let metrics = metric::Registry::default();
+        let handler = MeasurementFilter::new(
+            Some(Regex::new("^prod_").unwrap()),
+            Some(Regex::new("_tmp$").unwrap()),
+            &metrics,
+        );
+
+        let got = handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes("prod_cpu_tmp val=42i 1"),
+                None,
+            )
+            .await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(
+                e.to_string(),
+                "measurement prod_cpu_tmp is not accepted: it matches the deny pattern _tmp$"
+            );
+        });
+
+        assert_eq!(rejected(&metrics, "allow"), 0);
+        assert_eq!(rejected(&metrics, "deny"), 1);
+    }
+
+    fn rejected(metrics: &metric::Registry, rule: &'static str) -> u64 {
+        metrics
+            .get_instrument::<Metric<U64Counter>>("router_measurement_rejected")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("rule", rule)]))
+            .expect("failed to get observer")
+            .fetch()
+    }
+
+    // Parse `lp` into a table-keyed MutableBatch map.
+    fn lp_to_writes(lp: &str) -> HashMap<String, MutableBatch> {
+        let (writes, _) = mutable_batch_lp::lines_to_batches_stats(lp, 42)
+            .expect("failed to build test writes from LP");
+        writes
+    }
+
+    /// Initialise an in-memory [`MemCatalog`] and create a single namespace
+    /// named [`NAMESPACE`].
+    async fn test_setup() -> Arc<TestNamespace> {
+        let catalog = TestCatalog::new();
+
+        catalog.create_namespace_1hr_retention(&NAMESPACE).await
+    }
+}
@@ -53,6 +53,9 @@ pub mod nop;
 mod retention_validation;
 pub use retention_validation::*;
 
+mod measurement_filter;
+pub use measurement_filter::*;
+
 mod partitioner;
 pub use partitioner::*;
 
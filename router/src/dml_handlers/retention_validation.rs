@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use data_types::{NamespaceName, NamespaceSchema};
 use hashbrown::HashMap;
 use iox_time::{SystemProvider, TimeProvider};
+use metric::U64Counter;
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
 use std::sync::Arc;
@@ -28,6 +29,41 @@ pub enum RetentionError {
         /// The table name in which the observed timestamp was found.
         table_name: String,
     },
+
+    /// Time is outside the retention period, and the write is rejected as
+    /// unprocessable by [`OutsideRetentionPolicy::RejectUnprocessable`].
+    #[error(
+        "data in table {table_name} is outside of the retention period: minimum \
+        acceptable timestamp is {min_acceptable_ts}, but observed timestamp \
+        {observed_ts} is older."
+    )]
+    OutsideRetentionUnprocessable {
+        /// The minimum row timestamp that will be considered within the
+        /// retention period.
+        min_acceptable_ts: iox_time::Time,
+        /// The timestamp in the write that exceeds the retention minimum.
+        observed_ts: iox_time::Time,
+        /// The table name in which the observed timestamp was found.
+        table_name: String,
+    },
+}
+
+/// What the [`RetentionValidator`] does with a write containing data older
+/// than the retention period of its namespace.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutsideRetentionPolicy {
+    /// Reject the entire write with [`RetentionError::OutsideRetention`].
+    #[default]
+    Reject,
+
+    /// Reject the entire write with
+    /// [`RetentionError::OutsideRetentionUnprocessable`], which is reported as
+    /// unprocessable rather than forbidden.
+    RejectUnprocessable,
+
+    /// Accept the write. The data outside the retention period is not
+    /// queryable and is removed with the rest of the expired data.
+    Accept,
 }
 
 /// A [`DmlHandler`] implementation that validates that the write is within the
@@ -35,16 +71,42 @@ pub enum RetentionError {
 ///
 /// Each row of data being wrote is inspected, and if any "time" column
 /// timestamp lays outside of the configured namespace retention period, the
-/// entire write is rejected.
-#[derive(Debug, Default)]
+/// entire write is rejected, unless configured to
+/// [accept](OutsideRetentionPolicy::Accept) such writes.
+#[derive(Debug)]
 pub struct RetentionValidator<P = SystemProvider> {
     time_provider: P,
+    policy: OutsideRetentionPolicy,
+
+    rejected: U64Counter,
 }
 
 impl RetentionValidator {
-    /// Initialise a new [`RetentionValidator`], rejecting time outside retention period
-    pub fn new() -> Self {
-        Self::default()
+    /// Initialise a new [`RetentionValidator`], handling time outside
+    /// retention period according to `policy`.
+    pub fn new(policy: OutsideRetentionPolicy, metrics: &metric::Registry) -> Self {
+        Self::new_with_time_provider(policy, metrics, SystemProvider::default())
+    }
+}
+
+impl<P> RetentionValidator<P> {
+    fn new_with_time_provider(
+        policy: OutsideRetentionPolicy,
+        metrics: &metric::Registry,
+        time_provider: P,
+    ) -> Self {
+        let rejected = metrics
+            .register_metric::<U64Counter>(
+                "router_retention_rejected",
+                "number of writes rejected because of data outside the retention period",
+            )
+            .recorder(&[]);
+
+        Self {
+            time_provider,
+            policy,
+            rejected,
+        }
     }
 }
 
@@ -61,7 +123,7 @@ where
     /// Partition the per-table [`MutableBatch`].
     async fn write(
         &self,
-        _namespace: &NamespaceName<'static>,
+        namespace: &NamespaceName<'static>,
         namespace_schema: Arc<NamespaceSchema>,
         batch: Self::WriteInput,
         _span_ctx: Option<SpanContext>,
@@ -73,11 +135,35 @@ where
             for (table_name, batch) in &batch {
                 if let Some(min) = batch.timestamp_summary().and_then(|v| v.stats.min) {
                     if min < min_retention {
-                        return Err(RetentionError::OutsideRetention {
-                            table_name: table_name.clone(),
-                            min_acceptable_ts: iox_time::Time::from_timestamp_nanos(min_retention),
-                            observed_ts: iox_time::Time::from_timestamp_nanos(min),
-                        });
+                        let table_name = table_name.clone();
+                        let min_acceptable_ts = iox_time::Time::from_timestamp_nanos(min_retention);
+                        let observed_ts = iox_time::Time::from_timestamp_nanos(min);
+                        match self.policy {
+                            OutsideRetentionPolicy::Reject => {
+                                self.rejected.inc(1);
+                                return Err(RetentionError::OutsideRetention {
+                                    table_name,
+                                    min_acceptable_ts,
+                                    observed_ts,
+                                });
+                            }
+                            OutsideRetentionPolicy::RejectUnprocessable => {
+                                self.rejected.inc(1);
+                                return Err(RetentionError::OutsideRetentionUnprocessable {
+                                    table_name,
+                                    min_acceptable_ts,
+                                    observed_ts,
+                                });
+                            }
+                            OutsideRetentionPolicy::Accept => {
+                                debug!(
+                                    %namespace,
+                                    %table_name,
+                                    "accepting data outside of the retention period"
+                                );
+                                break;
+                            }
+                        }
                     }
                 }
             }
@@ -94,6 +180,7 @@ mod tests {
     use assert_matches::assert_matches;
     use iox_tests::{TestCatalog, TestNamespace};
     use iox_time::MockProvider;
+    use metric::{Attributes, Metric};
     use once_cell::sync::Lazy;
 
     use super::*;
@@ -108,7 +195,8 @@ mod tests {
         let _want_id = namespace.create_table("bananas").await.table.id;
 
         // Create the validator whose retention period is 1 hour
-        let handler = RetentionValidator::new();
+        let handler =
+            RetentionValidator::new(OutsideRetentionPolicy::Reject, &metric::Registry::default());
 
         // Make time now to be inside the retention period
         let now = SystemProvider::default()
@@ -135,9 +223,11 @@ mod tests {
         let mock_time = MockProvider::new(mock_now);
 
         // Create the validator whse retention period is 1 hour
-        let handler = RetentionValidator {
-            time_provider: mock_time.clone(),
-        };
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::Reject,
+            &metric::Registry::default(),
+            mock_time.clone(),
+        );
 
         // Make time outside the retention period
         let two_hours_ago = (mock_now.timestamp_nanos() - 2 * 3_600 * 1_000_000_000).to_string();
@@ -170,9 +260,11 @@ mod tests {
         let mock_time = MockProvider::new(mock_now);
 
         // Create the validator whse retention period is 1 hour
-        let handler = RetentionValidator {
-            time_provider: mock_time.clone(),
-        };
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::Reject,
+            &metric::Registry::default(),
+            mock_time.clone(),
+        );
 
         // Make time now to be inside the retention period
         let now = mock_now.timestamp_nanos().to_string();
@@ -210,9 +302,11 @@ mod tests {
         let mock_time = MockProvider::new(mock_now);
 
         // Create the validator whse retention period is 1 hour
-        let handler = RetentionValidator {
-            time_provider: mock_time.clone(),
-        };
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::Reject,
+            &metric::Registry::default(),
+            mock_time.clone(),
+        );
 
         // Make time now to be inside the retention period
         let now = mock_now.timestamp_nanos().to_string();
@@ -238,6 +332,86 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_outside_retention_period_policy() {
+        let namespace = test_setup().await;
+
+        let mock_now = iox_time::Time::from_rfc3339("2023-05-23T09:59:06+00:00").unwrap();
+        let mock_time = MockProvider::new(mock_now);
+
+        // Make time outside the retention period
+        let two_hours_ago = (mock_now.timestamp_nanos() - 2 * 3_600 * 1_000_000_000).to_string();
+        let line = "bananas,tag1=A,tag2=B val=42i ".to_string() + &two_hours_ago;
+
+        // Rejected writes are counted
+        let metrics = metric::Registry::default();
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::Reject,
+            &metrics,
+            mock_time.clone(),
+        );
+        let result = handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes(&line),
+                None,
+            )
+            .await;
+        assert_matches!(result, Err(RetentionError::OutsideRetention { .. }));
+        assert_eq!(rejected(&metrics), 1);
+
+        // Unprocessable rejections are distinguished, and counted too
+        let metrics = metric::Registry::default();
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::RejectUnprocessable,
+            &metrics,
+            mock_time.clone(),
+        );
+        let result = handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes(&line),
+                None,
+            )
+            .await;
+        assert_matches!(
+            result,
+            Err(RetentionError::OutsideRetentionUnprocessable { .. })
+        );
+        assert_eq!(rejected(&metrics), 1);
+
+        // Accepted writes are passed through unchanged
+        let metrics = metric::Registry::default();
+        let handler = RetentionValidator::new_with_time_provider(
+            OutsideRetentionPolicy::Accept,
+            &metrics,
+            mock_time,
+        );
+        let result = handler
+            .write(
+                &NAMESPACE,
+                namespace.schema().await.into(),
+                lp_to_writes(&line),
+                None,
+            )
+            .await
+            .expect("write outside retention period should be accepted");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result["bananas"].rows(), 1);
+        assert_eq!(rejected(&metrics), 0);
+    }
+
+    fn rejected(metrics: &metric::Registry) -> u64 {
+        metrics
+            .get_instrument::<Metric<U64Counter>>("router_retention_rejected")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch()
+    }
+
     // Parse `lp` into a table-keyed MutableBatch map.
     fn lp_to_writes(lp: &str) -> HashMap<String, MutableBatch> {
         let (writes, _) = mutable_batch_lp::lines_to_batches_stats(lp, 42)
@@ -73,12 +73,36 @@ pub enum RpcWriteError {
     },
 }
 
+/// The strategy used by [`RpcWrite`] to choose the upstream ingester a write
+/// is sent to first.
+///
+/// Regardless of the strategy, a write is only ever sent to a healthy
+/// ingester (or one being probed), and falls through to the next healthy
+/// ingester should the write fail.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShardAssignment {
+    /// Distribute writes uniformly across all healthy ingesters.
+    #[default]
+    RoundRobin,
+
+    /// Consistently send all writes for a namespace to the same ingester.
+    HashByNamespace,
+
+    /// Consistently send all writes for a (namespace, partition key) pair to
+    /// the same ingester.
+    ///
+    /// A single write request contains all the series within a partition, so
+    /// this is the finest granularity at which writes can be assigned.
+    HashByPartition,
+}
+
 /// An [`RpcWrite`] handler submits a write directly to an Ingester via the
 /// [gRPC write service].
 ///
-/// Requests are sent to an arbitrary downstream Ingester, and request load is
-/// distributed approximately uniformly across all downstream Ingesters. There
-/// is no effort made to enforce or attempt data locality.
+/// By default requests are sent to an arbitrary downstream Ingester, and
+/// request load is distributed approximately uniformly across all downstream
+/// Ingesters. There is no effort made to enforce or attempt data locality
+/// unless a hashing [`ShardAssignment`] is configured.
 ///
 /// # Replication
 ///
@@ -112,6 +136,9 @@ pub struct RpcWrite<T, C = CircuitBreaker> {
     /// may NACK a write, having already buffered the data. When this request is
     /// retried, the data will be duplicated.
     n_copies: usize,
+
+    /// The strategy used to choose the first upstream of a write.
+    assignment: ShardAssignment,
 }
 
 impl<T> RpcWrite<T> {
@@ -162,8 +189,16 @@ impl<T> RpcWrite<T> {
         Self {
             endpoints,
             n_copies,
+            assignment: ShardAssignment::default(),
         }
     }
+
+    /// Choose the first upstream of each write using `assignment`, instead of
+    /// the default [`ShardAssignment::RoundRobin`].
+    pub fn with_shard_assignment(self, assignment: ShardAssignment) -> Self {
+        debug!(?assignment, "write shard assignment");
+        Self { assignment, ..self }
+    }
 }
 
 #[async_trait]
@@ -211,10 +246,14 @@ where
 
         // Obtain a snapshot of currently-healthy upstreams (and potentially
         // some that need probing).
-        let snap = self
-            .endpoints
-            .endpoints()
-            .ok_or(RpcWriteError::NoHealthyUpstreams)?;
+        let snap = match self.assignment {
+            ShardAssignment::RoundRobin => self.endpoints.endpoints(),
+            ShardAssignment::HashByNamespace => self.endpoints.endpoints_for(namespace.as_str()),
+            ShardAssignment::HashByPartition => self
+                .endpoints
+                .endpoints_for((namespace.as_str(), &partition_key)),
+        }
+        .ok_or(RpcWriteError::NoHealthyUpstreams)?;
 
         // It's possible the set of endpoints may contain fewer upstreams than
         // necessary for the write request to succeed (N < replication factor).
@@ -418,6 +457,7 @@ mod tests {
         let handler = RpcWrite {
             endpoints: Balancer::new(endpoints, None),
             n_copies,
+            assignment: ShardAssignment::default(),
         };
 
         assert!(
@@ -509,6 +549,47 @@ mod tests {
         assert_eq!(got_tables, want_tables);
     }
 
+    /// Ensure writes for the same namespace are all sent to the same upstream
+    /// when hashing by namespace, regardless of their partition key.
+    #[tokio::test]
+    async fn test_write_hash_by_namespace() {
+        let clients = (0..3)
+            .map(|_| Arc::new(MockWriteClient::default()))
+            .collect::<Vec<_>>();
+
+        let handler = RpcWrite::new(
+            clients
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (Arc::clone(c), format!("client-{i}"))),
+            1.try_into().unwrap(),
+            &metric::Registry::default(),
+            ARBITRARY_TEST_NUM_PROBES,
+        )
+        .with_shard_assignment(ShardAssignment::HashByNamespace);
+
+        for day in 1..=9 {
+            let input = Partitioned::new(
+                PartitionKey::from(format!("2022-01-0{day}")),
+                lp_to_writes("bananas,tag1=A,tag2=B val=42i 1"),
+            );
+
+            handler
+                .write(
+                    &NamespaceName::new(NAMESPACE_NAME).unwrap(),
+                    Arc::new(new_empty_namespace_schema(NAMESPACE_ID.get())),
+                    input,
+                    None,
+                )
+                .await
+                .expect("write should succeed");
+        }
+
+        let mut calls = clients.iter().map(|c| c.calls().len()).collect::<Vec<_>>();
+        calls.sort_unstable();
+        assert_eq!(calls, [0, 0, 9]);
+    }
+
     /// Ensure all candidates returned by the balancer are tried, aborting after
     /// the first successful request.
     #[tokio::test]
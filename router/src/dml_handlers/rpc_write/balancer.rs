@@ -1,8 +1,11 @@
-use std::{borrow::Cow, cell::RefCell, cmp::max, fmt::Debug, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow, cell::RefCell, cmp::max, fmt::Debug, hash::Hash, sync::Arc, time::Duration,
+};
 
 use futures::Future;
 use metric::U64Gauge;
 use observability_deps::tracing::warn;
+use sharder::JumpHash;
 use tokio::task::JoinHandle;
 
 use super::{
@@ -82,6 +85,37 @@ where
             new_value
         });
 
+        // Reduce it to the range of [0, N) where N is the number of healthy
+        // clients in this balancer, ensuring not to calculate the remainder of
+        // a division by 0.
+        self.snapshot(|n_healthy| counter % max(n_healthy, 1))
+    }
+
+    /// Like [`Balancer::endpoints()`], but the iterator starts at the healthy
+    /// client `key` consistently hashes to, rather than the next one in
+    /// round-robin order.
+    ///
+    /// The same `key` maps to the same client for as long as the set of
+    /// healthy clients does not change.
+    pub(super) fn endpoints_for<H>(
+        &self,
+        key: H,
+    ) -> Option<UpstreamSnapshot<Arc<CircuitBreakingClient<T, C>>>>
+    where
+        H: Hash,
+    {
+        self.snapshot(|n_healthy| match n_healthy {
+            0 => 0,
+            n => *JumpHash::new(0..n).hash(key),
+        })
+    }
+
+    /// Take a snapshot of the healthy clients, starting the returned iterator
+    /// at the index `start` returns for the number of healthy clients.
+    fn snapshot(
+        &self,
+        start: impl FnOnce(usize) -> usize,
+    ) -> Option<UpstreamSnapshot<Arc<CircuitBreakingClient<T, C>>>> {
         // Build a set of only healthy nodes, and at most one node needing a
         // health probe.
         //
@@ -112,12 +146,7 @@ where
         // (otherwise it might not get a request sent to it).
         let idx = match probe.is_some() {
             true => 0, // Run the probe first
-            false => {
-                // Reduce it to the range of [0, N) where N is the number of
-                // healthy clients in this balancer, ensuring not to calculate
-                // the remainder of a division by 0.
-                counter % max(healthy.len(), 1)
-            }
+            false => start(healthy.len()),
         };
 
         let contains_probe = probe.is_some();
@@ -512,6 +541,49 @@ mod tests {
         assert_eq!(circuit_ok_2.err_count(), 0);
     }
 
+    // Ensure the balancer consistently starts at the same healthy client for
+    // the same key.
+    #[tokio::test]
+    async fn test_endpoints_for_key() {
+        const N: usize = 10;
+
+        let circuits = (0..3)
+            .map(|_| {
+                let c = Arc::new(MockCircuitBreaker::default());
+                c.set_healthy(true);
+                c
+            })
+            .collect::<Vec<_>>();
+
+        let balancer = Balancer::new(
+            circuits.iter().map(|c| {
+                CircuitBreakingClient::new(
+                    Arc::new(MockWriteClient::default()),
+                    "bananas",
+                    ARBITRARY_TEST_NUM_PROBES,
+                )
+                .with_circuit_breaker(Arc::clone(c))
+            }),
+            None,
+        );
+
+        for _ in 0..N {
+            balancer
+                .endpoints_for("platanos")
+                .unwrap()
+                .next()
+                .expect("should yield healthy client")
+                .write(WriteRequest::default(), None)
+                .await
+                .expect("should succeed");
+        }
+
+        // All the writes for the key were sent to a single client.
+        let mut ok_counts = circuits.iter().map(|c| c.ok_count()).collect::<Vec<_>>();
+        ok_counts.sort_unstable();
+        assert_eq!(ok_counts, [0, 0, N]);
+    }
+
     // Ensure the metric task exports the correct "healthy" values.
     #[tokio::test]
     async fn test_metric_exporter() {
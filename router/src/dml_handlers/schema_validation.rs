@@ -5,13 +5,14 @@ use data_types::{
     partition_template::TablePartitionTemplateOverride, NamespaceName, NamespaceSchema, TableId,
 };
 use hashbrown::HashMap;
-use iox_catalog::{interface::Error as CatalogError, validate_or_insert_schema};
+use iox_catalog::{interface::Error as CatalogError, validate_or_insert_schema_with_created};
 use mutable_batch::MutableBatch;
 use observability_deps::tracing::*;
 use trace::ctx::SpanContext;
 
 use super::DmlHandler;
 use crate::{
+    audit,
     namespace_cache::NamespaceCache,
     schema_validator::{SchemaError, SchemaValidator},
 };
@@ -45,7 +46,7 @@ where
         namespace: &NamespaceName<'static>,
         namespace_schema: Arc<NamespaceSchema>,
         batches: Self::WriteInput,
-        _span_ctx: Option<SpanContext>,
+        span_ctx: Option<SpanContext>,
     ) -> Result<Self::WriteOutput, Self::WriteError> {
         let namespace_id = namespace_schema.id;
 
@@ -57,7 +58,7 @@ where
 
         let mut repos = self.catalog.repositories().await;
 
-        let maybe_new_schema = validate_or_insert_schema(
+        let (maybe_new_schema, created_tables) = validate_or_insert_schema_with_created(
             batches.iter().map(|(k, v)| (k.as_str(), v)),
             &namespace_schema,
             repos.deref_mut(),
@@ -125,6 +126,15 @@ where
         // complete.
         let latest_schema = match maybe_new_schema {
             Some(v) => {
+                // Audit the tables this write inserted into the catalog.
+                //
+                // Tables missing from a stale cached schema but already in
+                // the catalog are loaded, not created, and are not audited.
+                for name in &created_tables {
+                    let table_id = v.tables[name.as_str()].id;
+                    audit::table_created(namespace, name, table_id, span_ctx.as_ref());
+                }
+
                 let (new_schema, _) = self.cache.put_schema(namespace.clone(), v);
                 trace!(%namespace, "schema cache updated");
                 new_schema
@@ -160,6 +170,7 @@ mod tests {
     use data_types::{ColumnType, MaxColumnsPerTable, MaxTables};
     use iox_tests::{TestCatalog, TestNamespace};
     use once_cell::sync::Lazy;
+    use test_helpers::{assert_contains, tracing::TracingCapture};
 
     use super::*;
     use crate::namespace_cache::{MemoryNamespaceCache, ReadThroughCache};
@@ -330,6 +341,50 @@ mod tests {
         assert_eq!(name, "bananas");
     }
 
+    #[tokio::test]
+    async fn test_write_new_table_audited() {
+        let (catalog, _namespace) = test_setup().await;
+        let metrics = Arc::new(metric::Registry::default());
+        let cache = Arc::new(setup_test_cache(&catalog));
+        let handler = SchemaValidator::new(catalog.catalog(), Arc::clone(&cache), &metrics);
+
+        let capture = TracingCapture::new();
+
+        // The first write to the table creates it, and must be audited.
+        for _ in 0..2 {
+            handler
+                .write(
+                    &NAMESPACE,
+                    cache.get_schema(&NAMESPACE).await.unwrap(),
+                    lp_to_writes("platanos val=42i 123456"),
+                    None,
+                )
+                .await
+                .expect("request should succeed");
+        }
+
+        // A write through a stale schema that lacks the table loads it from
+        // the catalog, and must not be audited.
+        let stale_schema = Arc::new(NamespaceSchema {
+            tables: Default::default(),
+            ..(*cache.get_schema(&NAMESPACE).await.unwrap()).clone()
+        });
+        handler
+            .write(
+                &NAMESPACE,
+                stale_schema,
+                lp_to_writes("platanos val=42i 123456"),
+                None,
+            )
+            .await
+            .expect("request should succeed");
+
+        let logs = capture.to_string();
+        assert_eq!(logs.matches("message = table created;").count(), 1);
+        assert_contains!(logs, "namespace = bananas;");
+        assert_contains!(logs, "table_name = platanos;");
+    }
+
     #[tokio::test]
     async fn test_write_validation_failure() {
         let (catalog, namespace) = test_setup().await;
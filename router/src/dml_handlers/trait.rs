@@ -1,4 +1,7 @@
-use super::{partitioner::PartitionError, retention_validation::RetentionError, RpcWriteError};
+use super::{
+    measurement_filter::MeasurementFilterError, partitioner::PartitionError,
+    retention_validation::RetentionError, RpcWriteError,
+};
 use crate::schema_validator::SchemaError;
 use async_trait::async_trait;
 use data_types::{NamespaceName, NamespaceSchema};
@@ -31,6 +34,10 @@ pub enum DmlError {
     #[error(transparent)]
     Retention(#[from] RetentionError),
 
+    /// A measurement of the write is not accepted.
+    #[error(transparent)]
+    MeasurementFilter(#[from] MeasurementFilterError),
+
     /// An unknown error occured while processing the DML request.
     #[error("internal dml handler error: {0}")]
     Internal(Box<dyn Error + Send + Sync>),
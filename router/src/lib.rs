@@ -136,6 +136,7 @@
 use criterion as _;
 use workspace_hack as _;
 
+pub mod audit;
 pub mod dml_handlers;
 pub mod gossip;
 pub mod namespace_cache;
@@ -4,10 +4,11 @@ use async_trait::async_trait;
 use data_types::{NamespaceName, NamespaceSchema};
 use iox_catalog::interface::Catalog;
 use observability_deps::tracing::*;
+use regex::Regex;
 use thiserror::Error;
 
 use super::NamespaceResolver;
-use crate::namespace_cache::NamespaceCache;
+use crate::{audit, namespace_cache::NamespaceCache};
 
 /// What to do when the namespace doesn't exist
 #[derive(Debug, Copy, Clone)]
@@ -30,6 +31,16 @@ pub enum NamespaceCreationError {
     /// disabled.
     #[error("rejecting write due to non-existing namespace: {0}")]
     Reject(String),
+
+    /// The write is to be rejected because the namespace doesn't exist and its
+    /// name does not match the pattern required to auto-create it.
+    #[error("rejecting write to namespace '{name}': name does not match '{pattern}'")]
+    InvalidName {
+        /// The name of the namespace.
+        name: String,
+        /// The pattern auto-created namespace names must match.
+        pattern: String,
+    },
 }
 
 /// A layer to populate the [`Catalog`] with all the namespaces the router
@@ -44,6 +55,7 @@ pub struct NamespaceAutocreation<C, T> {
     catalog: Arc<dyn Catalog>,
 
     action: MissingNamespaceAction,
+    name_pattern: Option<Regex>,
 }
 
 impl<C, T> NamespaceAutocreation<C, T> {
@@ -66,8 +78,18 @@ impl<C, T> NamespaceAutocreation<C, T> {
             cache,
             catalog,
             action,
+            name_pattern: None,
         }
     }
+
+    /// Only auto-create namespaces whose name matches `pattern`, rejecting
+    /// writes to any other missing namespace.
+    ///
+    /// Namespaces that already exist are not affected.
+    pub fn with_name_pattern(mut self, pattern: Regex) -> Self {
+        self.name_pattern = Some(pattern);
+        self
+    }
 }
 
 #[async_trait]
@@ -102,6 +124,23 @@ where
                     }
                 }
                 MissingNamespaceAction::AutoCreate(retention_period_ns) => {
+                    if let Some(pattern) = self
+                        .name_pattern
+                        .as_ref()
+                        .filter(|p| !p.is_match(namespace.as_str()))
+                    {
+                        warn!(
+                            %namespace,
+                            %pattern,
+                            "namespace name does not match pattern and auto-creation rejected"
+                        );
+                        return Err(NamespaceCreationError::InvalidName {
+                            name: namespace.into(),
+                            pattern: pattern.to_string(),
+                        }
+                        .into());
+                    }
+
                     match self
                         .catalog
                         .repositories()
@@ -112,6 +151,7 @@ where
                     {
                         Ok(_) => {
                             debug!(%namespace, "created namespace");
+                            audit::namespace_created(namespace, retention_period_ns);
                         }
                         Err(iox_catalog::interface::Error::NameExists { .. }) => {
                             // Either the cache has not yet converged to include this
@@ -310,4 +350,62 @@ mod tests {
             .expect("should allow existing namespace from catalog");
         assert_eq!(created_id, id);
     }
+
+    #[tokio::test]
+    async fn test_name_pattern() {
+        let metrics = Arc::new(metric::Registry::new());
+        let catalog: Arc<dyn Catalog> = Arc::new(MemCatalog::new(metrics));
+        let cache = Arc::new(ReadThroughCache::new(
+            MemoryNamespaceCache::default(),
+            Arc::clone(&catalog),
+        ));
+
+        let creator = NamespaceAutocreation::new(
+            NamespaceSchemaResolver::new(Arc::clone(&cache)),
+            Arc::clone(&cache),
+            Arc::clone(&catalog),
+            MissingNamespaceAction::AutoCreate(TEST_RETENTION_PERIOD_NS),
+        )
+        .with_name_pattern(Regex::new("^[a-z_]+$").unwrap());
+
+        // A matching name is created
+        let ns = NamespaceName::try_from("platanos_bananas").unwrap();
+        creator
+            .get_namespace_schema(&ns)
+            .await
+            .expect("matching name should be created");
+
+        // A name that does not match is rejected, and not created
+        let ns = NamespaceName::try_from("Bananas").unwrap();
+        assert_matches!(
+            creator.get_namespace_schema(&ns).await,
+            Err(crate::namespace_resolver::Error::Create(
+                NamespaceCreationError::InvalidName { name, pattern }
+            )) => {
+                assert_eq!(name, "Bananas");
+                assert_eq!(pattern, "^[a-z_]+$");
+            }
+        );
+        {
+            let mut repos = catalog.repositories().await;
+            assert_matches!(
+                repos
+                    .namespaces()
+                    .get_by_name(ns.as_str(), SoftDeletedRows::ExcludeDeleted)
+                    .await,
+                Ok(None)
+            );
+
+            // ...unless it already exists.
+            repos
+                .namespaces()
+                .create(&ns, None, None, None)
+                .await
+                .expect("create should succeed");
+        }
+        creator
+            .get_namespace_schema(&ns)
+            .await
+            .expect("existing namespace should be allowed");
+    }
 }
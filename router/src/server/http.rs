@@ -1,25 +1,45 @@
 //! HTTP service implementations for `router`.
 
+pub mod dead_letter;
+pub mod durable;
 pub mod write;
 
-use std::{str::Utf8Error, time::Instant};
+use std::{
+    borrow::Cow,
+    num::NonZeroUsize,
+    str::Utf8Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use bytes::{Bytes, BytesMut};
+use data_types::NamespaceName;
 use futures::StreamExt;
 use hashbrown::HashMap;
 use hyper::{header::CONTENT_ENCODING, Body, Method, Request, Response, StatusCode};
 use iox_time::{SystemProvider, TimeProvider};
-use metric::{DurationHistogram, U64Counter};
+use metric::{
+    DurationHistogram, DurationHistogramOptions, Metric, U64Counter, U64Gauge, DURATION_MAX,
+};
 use mutable_batch::MutableBatch;
-use mutable_batch_lp::{LineError, LinesConverter};
+use mutable_batch_lp::{
+    DuplicateTagPolicy, LineError, LineWriteError, LinesConverter, PayloadStatistics,
+};
 use observability_deps::tracing::*;
 use thiserror::Error;
 use tokio::sync::{Semaphore, TryAcquireError};
 use trace::ctx::SpanContext;
 
-use self::write::{
-    multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError, WriteParams,
-    WriteRequestUnifier,
+use self::{
+    dead_letter::DeadLetterSink,
+    durable::{PersistWaiter, WaitFor, WaitParam},
+    write::{
+        multi_tenant::MultiTenantExtractError, single_tenant::SingleTenantExtractError,
+        WriteParams, WriteRequestUnifier,
+    },
 };
 use crate::{
     dml_handlers::{
@@ -30,6 +50,10 @@ use crate::{
     schema_validator::SchemaError,
 };
 
+/// The `namespace` label of the `http_write_duration` metric for writes whose
+/// namespace was not resolved.
+const UNKNOWN_NAMESPACE: &str = "unknown";
+
 /// Errors returned by the `router` HTTP request handler.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -100,6 +124,44 @@ pub enum Error {
     /// The provided authorization is not sufficient to perform the request.
     #[error("access denied")]
     Forbidden,
+
+    /// The `wait` query parameter of a write request is invalid.
+    #[error("invalid wait parameter: {0}")]
+    InvalidWaitParam(serde::de::value::Error),
+
+    /// A durable write acknowledgement was requested, but this router is not
+    /// configured to allow them.
+    #[error("durable writes (wait=durable) are not enabled on this router")]
+    DurableWritesDisabled,
+
+    /// The request body was not received before the configured write
+    /// deadline.
+    ///
+    /// The first `lines_accepted` lines of the body were written, and the
+    /// client may resume the write from the following line. As in parse
+    /// errors, blank lines and comments are not counted.
+    #[error(
+        "write deadline of {deadline:?} exceeded, the first {lines_accepted} lines were accepted"
+    )]
+    WriteDeadlineExceeded {
+        /// The configured write deadline.
+        deadline: Duration,
+        /// The number of lines at the start of the body that were written.
+        lines_accepted: usize,
+    },
+
+    /// A streamed write failed after some of its lines were written.
+    ///
+    /// The first `lines_accepted` lines of the body were written, and the
+    /// client may resume the write from the following line. As in parse
+    /// errors, blank lines and comments are not counted.
+    #[error("{source}, the first {lines_accepted} lines were accepted")]
+    StreamedWritePartiallyAccepted {
+        /// The number of lines at the start of the body that were written.
+        lines_accepted: usize,
+        /// The error that stopped the write.
+        source: Box<Error>,
+    },
 }
 
 impl Error {
@@ -113,6 +175,11 @@ impl Error {
             Error::InvalidGzip(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8ContentHeader(_) => StatusCode::BAD_REQUEST,
             Error::NonUtf8Body(_) => StatusCode::BAD_REQUEST,
+            Error::ParseLineProtocol(mutable_batch_lp::Error::PerLine { lines })
+                if lines.iter().any(is_unprocessable_line_error) =>
+            {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
             Error::ParseLineProtocol(_) => StatusCode::BAD_REQUEST,
             Error::RequestSizeExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
             Error::InvalidContentEncoding(_) => {
@@ -124,10 +191,19 @@ impl Error {
             Error::NamespaceResolver(crate::namespace_resolver::Error::Create(
                 crate::namespace_resolver::ns_autocreation::NamespaceCreationError::Reject(_),
             )) => StatusCode::BAD_REQUEST,
+            Error::NamespaceResolver(crate::namespace_resolver::Error::Create(
+                crate::namespace_resolver::ns_autocreation::NamespaceCreationError::InvalidName {
+                    ..
+                },
+            )) => StatusCode::UNPROCESSABLE_ENTITY,
             Error::NamespaceResolver(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::RequestLimit => StatusCode::SERVICE_UNAVAILABLE,
             Error::Unauthenticated => StatusCode::UNAUTHORIZED,
             Error::Forbidden => StatusCode::FORBIDDEN,
+            Error::InvalidWaitParam(_) => StatusCode::BAD_REQUEST,
+            Error::DurableWritesDisabled => StatusCode::BAD_REQUEST,
+            Error::WriteDeadlineExceeded { .. } => StatusCode::REQUEST_TIMEOUT,
+            Error::StreamedWritePartiallyAccepted { source, .. } => source.as_status_code(),
             Error::SingleTenantError(e) => StatusCode::from(e),
             Error::MultiTenantError(e) => StatusCode::from(e),
         }
@@ -140,21 +216,48 @@ impl Error {
     pub fn get_parse_error_line_index(&self) -> Option<usize> {
         match self {
             Self::ParseLineProtocol(mutable_batch_lp::Error::PerLine { lines }) => {
-                let line = match lines
+                let line = lines
                     .get(0)
                     .expect("PerLine error must have at least one line")
-                {
-                    LineError::LineProtocol { source: _, line }
-                    | LineError::TimestampOverflow { line }
-                    | LineError::Write { source: _, line } => *line,
-                };
+                    .line();
                 Some(line)
             }
+            // Lines are numbered from the start of the segment that failed.
+            Self::StreamedWritePartiallyAccepted {
+                lines_accepted,
+                source,
+            } => source
+                .get_parse_error_line_index()
+                .map(|line| line + lines_accepted),
+            _ => None,
+        }
+    }
+
+    /// Return the number of lines at the start of the request body that were
+    /// written, if the write was partially accepted.
+    pub fn lines_accepted(&self) -> Option<usize> {
+        match self {
+            Self::WriteDeadlineExceeded { lines_accepted, .. }
+            | Self::StreamedWritePartiallyAccepted { lines_accepted, .. } => Some(*lines_accepted),
             _ => None,
         }
     }
 }
 
+/// Returns true if `e` rejects a well-formed line, for exceeding the configured
+/// line limits or repeating a tag, rather than for being invalid.
+fn is_unprocessable_line_error(e: &LineError) -> bool {
+    matches!(
+        e,
+        LineError::LineTooLong { .. }
+            | LineError::TooManyFields { .. }
+            | LineError::Write {
+                source: LineWriteError::DuplicateTag { .. },
+                ..
+            }
+    )
+}
+
 impl From<&DmlError> for StatusCode {
     fn from(e: &DmlError) -> Self {
         match e {
@@ -175,6 +278,10 @@ impl From<&DmlError> for StatusCode {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             DmlError::Retention(RetentionError::OutsideRetention { .. }) => StatusCode::FORBIDDEN,
+            DmlError::Retention(RetentionError::OutsideRetentionUnprocessable { .. }) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            DmlError::MeasurementFilter(_) => StatusCode::UNPROCESSABLE_ENTITY,
             DmlError::RpcWrite(RpcWriteError::Client(RpcWriteClientError::Upstream(_))) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -220,12 +327,46 @@ pub struct HttpDelegate<D, N, T = SystemProvider> {
     // overall system availability, instead of OOMing or otherwise failing.
     request_sem: Semaphore,
 
+    // When set, line protocol bodies are parsed on the blocking thread pool
+    // instead of the request task, with at most as many bodies being parsed at
+    // once as there are permits.
+    parse_workers: Option<Arc<Semaphore>>,
+
+    // How tags repeated within a single line protocol line are handled.
+    duplicate_tag_policy: DuplicateTagPolicy,
+
+    // The maximum length in bytes / number of fields of a single line protocol
+    // line, if limited.
+    max_line_bytes: Option<usize>,
+    max_fields_per_line: Option<usize>,
+
+    // When set, clients may request a write is only acknowledged once durable
+    // with `wait=durable`, waiting at most for the given duration.
+    durable_writes: Option<(Arc<dyn PersistWaiter>, Duration)>,
+
+    // The maximum time to spend receiving a write request body, after which
+    // the complete lines received so far are written.
+    write_deadline: Option<Duration>,
+
+    // When set, uncompressed write bodies are parsed and written in segments
+    // of at least this many bytes as they are received, instead of being
+    // buffered in full.
+    streaming_segment_bytes: Option<usize>,
+
+    // When set, the payloads of writes rejected with a client error are
+    // stored for debugging.
+    dead_letters: Option<DeadLetterSink>,
+
     write_metric_lines: U64Counter,
+    write_duration: Metric<DurationHistogram>,
     http_line_protocol_parse_duration: DurationHistogram,
+    http_line_protocol_parse_queue_depth: U64Gauge,
     write_metric_fields: U64Counter,
     write_metric_tables: U64Counter,
     write_metric_body_size: U64Counter,
     request_limit_rejected: U64Counter,
+    write_rejected_line_too_long: U64Counter,
+    write_rejected_too_many_fields: U64Counter,
 }
 
 impl<D, N> HttpDelegate<D, N, SystemProvider> {
@@ -272,12 +413,51 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
                 "number of HTTP requests rejected due to exceeding parallel request limit",
             )
             .recorder(&[]);
+        let write_rejected_lines = metrics.register_metric::<U64Counter>(
+            "http_write_lines_rejected",
+            "number of line protocol lines rejected for exceeding the configured line limits",
+        );
+        let write_rejected_line_too_long =
+            write_rejected_lines.recorder(&[("reason", "line_too_long")]);
+        let write_rejected_too_many_fields =
+            write_rejected_lines.recorder(&[("reason", "too_many_fields")]);
         let http_line_protocol_parse_duration = metrics
             .register_metric::<DurationHistogram>(
                 "http_line_protocol_parse_duration",
                 "write latency of line protocol parsing",
             )
             .recorder(&[]);
+        let write_duration = metrics.register_metric_with_options::<DurationHistogram, _>(
+            "http_write_duration",
+            "end-to-end latency of write requests, by namespace and outcome",
+            || {
+                DurationHistogramOptions::new([
+                    Duration::from_micros(100),
+                    Duration::from_micros(250),
+                    Duration::from_micros(500),
+                    Duration::from_millis(1),
+                    Duration::from_micros(2_500),
+                    Duration::from_millis(5),
+                    Duration::from_millis(10),
+                    Duration::from_millis(25),
+                    Duration::from_millis(50),
+                    Duration::from_millis(100),
+                    Duration::from_millis(250),
+                    Duration::from_millis(500),
+                    Duration::from_secs(1),
+                    Duration::from_millis(2_500),
+                    Duration::from_secs(5),
+                    Duration::from_secs(10),
+                    DURATION_MAX,
+                ])
+            },
+        );
+        let http_line_protocol_parse_queue_depth = metrics
+            .register_metric::<U64Gauge>(
+                "http_line_protocol_parse_queue_depth",
+                "number of line protocol bodies waiting for or being parsed by a parse worker",
+            )
+            .recorder(&[]);
 
         Self {
             max_request_bytes,
@@ -286,16 +466,128 @@ impl<D, N> HttpDelegate<D, N, SystemProvider> {
             write_request_mode_handler,
             dml_handler,
             request_sem: Semaphore::new(max_requests),
+            parse_workers: None,
+            duplicate_tag_policy: DuplicateTagPolicy::default(),
+            max_line_bytes: None,
+            max_fields_per_line: None,
+            durable_writes: None,
+            write_deadline: None,
+            streaming_segment_bytes: None,
+            dead_letters: None,
             write_metric_lines,
+            write_duration,
             http_line_protocol_parse_duration,
+            http_line_protocol_parse_queue_depth,
             write_metric_fields,
             write_metric_tables,
             write_metric_body_size,
             request_limit_rejected,
+            write_rejected_line_too_long,
+            write_rejected_too_many_fields,
         }
     }
 }
 
+impl<D, N, T> HttpDelegate<D, N, T> {
+    /// Parse line protocol bodies on the blocking thread pool, with at most
+    /// `workers` bodies parsed at once, instead of inline on the request task.
+    ///
+    /// This keeps large writes from stalling the async runtime, at the cost of
+    /// a thread hand-off for every write.
+    pub fn with_parse_workers(mut self, workers: NonZeroUsize) -> Self {
+        self.parse_workers = Some(Arc::new(Semaphore::new(workers.get())));
+        self
+    }
+
+    /// Handle tags that appear more than once in a line protocol line
+    /// according to `policy`, instead of rejecting the write.
+    pub fn with_duplicate_tag_policy(mut self, policy: DuplicateTagPolicy) -> Self {
+        self.duplicate_tag_policy = policy;
+        self
+    }
+
+    /// Reject line protocol lines longer than `max_line_bytes`, or with more
+    /// than `max_fields_per_line` fields.
+    pub fn with_line_limits(mut self, max_line_bytes: usize, max_fields_per_line: usize) -> Self {
+        self.max_line_bytes = Some(max_line_bytes);
+        self.max_fields_per_line = Some(max_fields_per_line);
+        self
+    }
+
+    /// Allow clients to request a write is acknowledged only once it is
+    /// persisted, by setting the `wait=durable` query parameter. For testing
+    /// only, see [`durable`].
+    ///
+    /// Such writes are acknowledged with a 200 once `persist_waiter` reports
+    /// the namespace persisted, or with a 202 if this takes longer than
+    /// `timeout` (the write remains buffered and will be persisted later).
+    pub fn with_durable_writes(
+        mut self,
+        persist_waiter: Arc<dyn PersistWaiter>,
+        timeout: Duration,
+    ) -> Self {
+        self.durable_writes = Some((persist_waiter, timeout));
+        self
+    }
+
+    /// Limit the time spent receiving the body of a write request to
+    /// `deadline`.
+    ///
+    /// If an uncompressed body is not fully received in time, the complete
+    /// lines received so far are written and the request fails with
+    /// [`Error::WriteDeadlineExceeded`], reporting how many lines were
+    /// accepted so the client can resume the write. Compressed bodies cannot
+    /// be split and are rejected entirely.
+    pub fn with_write_deadline(mut self, deadline: Duration) -> Self {
+        self.write_deadline = Some(deadline);
+        self
+    }
+
+    /// Parse and write uncompressed write bodies incrementally as they are
+    /// received, in segments of complete lines of at least `segment_bytes`
+    /// bytes, instead of buffering the whole body before parsing it.
+    ///
+    /// This bounds the memory used by a write request to roughly
+    /// `segment_bytes` (plus the longest line), rather than the size of the
+    /// body. The overall body size remains limited to `max_request_bytes`.
+    ///
+    /// Segments are written independently: if a segment fails, the earlier
+    /// ones have already been written and the request fails with
+    /// [`Error::StreamedWritePartiallyAccepted`], reporting how many lines were
+    /// accepted so the client can resume the write. Compressed bodies are
+    /// still buffered in full.
+    pub fn with_streaming_writes(mut self, segment_bytes: NonZeroUsize) -> Self {
+        self.streaming_segment_bytes = Some(segment_bytes.get());
+        self
+    }
+
+    /// Store the payloads of writes rejected with a client error (4xx) in
+    /// `sink`, along with the namespace and rejection reason.
+    ///
+    /// For streamed writes, only the rejected segment is stored. Writes
+    /// rejected before their body is read (e.g. for exceeding the request
+    /// size limit) are not stored.
+    pub fn with_dead_letter_sink(mut self, sink: DeadLetterSink) -> Self {
+        self.dead_letters = Some(sink);
+        self
+    }
+
+    /// Initialise a [`LinesConverter`] applying the configured line protocol
+    /// handling and limits.
+    fn lines_converter(&self, default_time: i64, timestamp_base: i64) -> LinesConverter {
+        let mut converter = LinesConverter::new(default_time);
+        converter.set_timestamp_base(timestamp_base);
+        converter.set_duplicate_tag_policy(self.duplicate_tag_policy);
+        if let Some(max) = self.max_line_bytes {
+            converter.set_max_line_bytes(max);
+        }
+        if let Some(max) = self.max_fields_per_line {
+            converter.set_max_fields_per_line(max);
+        }
+        converter
+    }
+}
+
 impl<D, N, T> HttpDelegate<D, N, T>
 where
     D: DmlHandler<WriteInput = HashMap<String, MutableBatch>, WriteOutput = ()>,
@@ -326,28 +618,114 @@ where
         match (req.method(), req.uri().path()) {
             (&Method::POST, "/write") => {
                 let dml_info = self.write_request_mode_handler.parse_v1(&req).await?;
-                self.write_handler(req, dml_info).await
+                let wait = self.parse_wait(&req)?;
+                self.write_handler(req, dml_info, wait).await
             }
             (&Method::POST, "/api/v2/write") => {
                 let dml_info = self.write_request_mode_handler.parse_v2(&req).await?;
-                self.write_handler(req, dml_info).await
+                let wait = self.parse_wait(&req)?;
+                self.write_handler(req, dml_info, wait).await
             }
             (&Method::POST, "/api/v2/delete") => return Err(Error::DeletesUnsupported),
             _ => return Err(Error::NoHandler),
         }
-        .map(|_summary| {
+        .map(|status| {
             Response::builder()
-                .status(StatusCode::NO_CONTENT)
+                .status(status)
                 .body(Body::empty())
                 .unwrap()
         })
     }
 
+    /// Read the write acknowledgement requested by the `wait` query parameter
+    /// of `req`, rejecting durable acknowledgements unless they are enabled.
+    fn parse_wait(&self, req: &Request<Body>) -> Result<WaitFor, Error> {
+        let wait = match req.uri().query() {
+            Some(query) => {
+                serde_urlencoded::from_str::<WaitParam>(query)
+                    .map_err(Error::InvalidWaitParam)?
+                    .wait
+            }
+            None => WaitFor::default(),
+        };
+
+        if wait == WaitFor::Durable && self.durable_writes.is_none() {
+            return Err(Error::DurableWritesDisabled);
+        }
+
+        Ok(wait)
+    }
+
+    /// Wait for the data written to `namespace` to be persisted, returning the
+    /// status code to acknowledge the write with.
+    async fn wait_durable(&self, namespace: &NamespaceName<'_>) -> StatusCode {
+        let (persist_waiter, timeout) = self
+            .durable_writes
+            .as_ref()
+            .expect("durable write requested when not enabled");
+
+        match tokio::time::timeout(*timeout, persist_waiter.wait_persisted(namespace)).await {
+            Ok(Ok(())) => StatusCode::OK,
+            Ok(Err(e)) => {
+                warn!(error=%e, %namespace, "failed to wait for durable write");
+                StatusCode::ACCEPTED
+            }
+            Err(_) => {
+                debug!(%namespace, ?timeout, "timeout waiting for durable write");
+                StatusCode::ACCEPTED
+            }
+        }
+    }
+
+    /// Handle a write request, recording its latency from the start of reading
+    /// the body until the write is acknowledged, labelled by namespace and
+    /// outcome.
+    ///
+    /// The namespace label is only recorded once the namespace was resolved,
+    /// so that requests for arbitrary namespaces that do not exist cannot
+    /// create an unbounded number of metric series. All other requests are
+    /// recorded as [`UNKNOWN_NAMESPACE`].
     async fn write_handler(
         &self,
         req: Request<Body>,
         write_info: WriteParams,
-    ) -> Result<(), Error> {
+        wait: WaitFor,
+    ) -> Result<StatusCode, Error> {
+        let start_instant = Instant::now();
+        let namespace = write_info.namespace.to_string();
+        let namespace_resolved = AtomicBool::new(false);
+
+        let res = self
+            .write_request(req, write_info, wait, &namespace_resolved)
+            .await;
+
+        let outcome = match &res {
+            Ok(_) => "accepted",
+            Err(e) if e.as_status_code().is_client_error() => "rejected",
+            Err(_) => "error",
+        };
+        let namespace = if namespace_resolved.load(Ordering::Relaxed) {
+            Cow::Owned(namespace)
+        } else {
+            Cow::Borrowed(UNKNOWN_NAMESPACE)
+        };
+        self.write_duration
+            .recorder([
+                ("namespace", namespace),
+                ("outcome", Cow::Borrowed(outcome)),
+            ])
+            .record(start_instant.elapsed());
+
+        res
+    }
+
+    async fn write_request(
+        &self,
+        req: Request<Body>,
+        write_info: WriteParams,
+        wait: WaitFor,
+        namespace_resolved: &AtomicBool,
+    ) -> Result<StatusCode, Error> {
         let span_ctx: Option<SpanContext> = req.extensions().get().cloned();
 
         trace!(
@@ -355,24 +733,126 @@ where
             "processing write request"
         );
 
-        // Read the HTTP body and convert it to a str.
-        let body = self.read_body(req).await?;
-        let body = std::str::from_utf8(&body).map_err(Error::NonUtf8Body)?;
-
         // The time, in nanoseconds since the epoch, to assign to any points that don't
         // contain a timestamp
         let default_time = self.time_provider.now().timestamp_nanos();
+
+        let wrote = match self.streaming_segment_bytes {
+            Some(segment_bytes) if !is_gzip(&req)? => {
+                self.write_streaming(
+                    req,
+                    &write_info,
+                    span_ctx,
+                    default_time,
+                    segment_bytes,
+                    namespace_resolved,
+                )
+                .await?
+            }
+            _ => {
+                // Read the HTTP body.
+                let (body, truncated) = self.read_body(req).await?;
+
+                let wrote = self
+                    .write_body(
+                        &body,
+                        &write_info,
+                        span_ctx,
+                        default_time,
+                        namespace_resolved,
+                    )
+                    .await?;
+                if truncated {
+                    return Err(self.write_deadline_exceeded(count_lines(&body)));
+                }
+                wrote
+            }
+        };
+
+        if !wrote {
+            debug!("nothing to write");
+            return Ok(StatusCode::NO_CONTENT);
+        }
+
+        match wait {
+            WaitFor::Buffered => Ok(StatusCode::NO_CONTENT),
+            WaitFor::Durable => Ok(self.wait_durable(&write_info.namespace).await),
+        }
+    }
+
+    /// Parse the line protocol in `body` and write it to the DML handler,
+    /// returning false if it contains no lines to write.
+    ///
+    /// If the write is rejected, `body` is stored in the dead-letter sink, if
+    /// any.
+    async fn write_body(
+        &self,
+        body: &Bytes,
+        write_info: &WriteParams,
+        span_ctx: Option<SpanContext>,
+        default_time: i64,
+        namespace_resolved: &AtomicBool,
+    ) -> Result<bool, Error> {
+        let res = self
+            .parse_and_write(body, write_info, span_ctx, default_time, namespace_resolved)
+            .await;
+
+        if let (Err(e), Some(sink)) = (&res, &self.dead_letters) {
+            if e.as_status_code().is_client_error() {
+                sink.store(&write_info.namespace, &e.to_string(), body)
+                    .await;
+            }
+        }
+
+        res
+    }
+
+    /// Parse the line protocol in `body` and write it to the DML handler,
+    /// returning false if it contains no lines to write.
+    ///
+    /// `namespace_resolved` is set once the namespace schema was resolved.
+    async fn parse_and_write(
+        &self,
+        body: &Bytes,
+        write_info: &WriteParams,
+        span_ctx: Option<SpanContext>,
+        default_time: i64,
+        namespace_resolved: &AtomicBool,
+    ) -> Result<bool, Error> {
+        let converter = self.lines_converter(default_time, write_info.precision.timestamp_base());
         let start_instant = Instant::now();
 
-        let mut converter = LinesConverter::new(default_time);
-        converter.set_timestamp_base(write_info.precision.timestamp_base());
-        let (batches, stats) = match converter.write_lp(body).and_then(|_| converter.finish()) {
-            Ok(v) => v,
-            Err(mutable_batch_lp::Error::EmptyPayload) => {
-                debug!("nothing to write");
-                return Ok(());
+        let parsed = match &self.parse_workers {
+            None => parse_lp(body, converter),
+            Some(workers) => {
+                self.http_line_protocol_parse_queue_depth.inc(1);
+                let _queued = QueueDepthGuard(&self.http_line_protocol_parse_queue_depth);
+
+                let permit = Arc::clone(workers)
+                    .acquire_owned()
+                    .await
+                    .expect("parse worker semaphore closed");
+                let body = body.clone();
+                tokio::task::spawn_blocking(move || {
+                    let _permit = permit;
+                    parse_lp(&body, converter)
+                })
+                .await
+                .expect("line protocol parse task failed")
             }
-            Err(line_errors) => return Err(Error::ParseLineProtocol(line_errors)),
+        };
+        if let Err(Error::ParseLineProtocol(mutable_batch_lp::Error::PerLine { lines })) = &parsed {
+            for e in lines {
+                match e {
+                    LineError::LineTooLong { .. } => self.write_rejected_line_too_long.inc(1),
+                    LineError::TooManyFields { .. } => self.write_rejected_too_many_fields.inc(1),
+                    _ => {}
+                }
+            }
+        }
+        let (batches, stats) = match parsed? {
+            Some(v) => v,
+            None => return Ok(false),
         };
 
         let num_tables = batches.len();
@@ -394,6 +874,7 @@ where
             .namespace_resolver
             .get_namespace_schema(&write_info.namespace)
             .await?;
+        namespace_resolved.store(true, Ordering::Relaxed);
 
         self.dml_handler
             .write(&write_info.namespace, namespace_schema, batches, span_ctx)
@@ -405,39 +886,161 @@ where
         self.write_metric_tables.inc(num_tables as _);
         self.write_metric_body_size.inc(body.len() as _);
 
-        Ok(())
+        Ok(true)
+    }
+
+    /// Write the uncompressed body of `req` in segments of complete lines as
+    /// it is received, as described in [`Self::with_streaming_writes`],
+    /// returning false if it contains no lines to write.
+    ///
+    /// Each segment is written with [`Self::write_body`], so the namespace
+    /// schema is resolved again for every segment and observes the columns
+    /// added by the previous ones.
+    async fn write_streaming(
+        &self,
+        req: Request<Body>,
+        write_info: &WriteParams,
+        span_ctx: Option<SpanContext>,
+        default_time: i64,
+        segment_bytes: usize,
+        namespace_resolved: &AtomicBool,
+    ) -> Result<bool, Error> {
+        let mut payload = req.into_body();
+        let deadline = self.write_deadline.map(|d| tokio::time::Instant::now() + d);
+
+        let mut buf = BytesMut::new();
+        let mut body_len = 0;
+        let mut lines_accepted = 0;
+        let mut wrote = false;
+        loop {
+            let (chunk, truncated) = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, payload.next()).await {
+                    Ok(v) => (v, false),
+                    Err(_) => (None, true),
+                },
+                None => (payload.next().await, false),
+            };
+            let done = match chunk {
+                Some(chunk) => {
+                    let chunk = chunk
+                        .map_err(Error::ClientHangup)
+                        .map_err(|e| partially_accepted(e, lines_accepted))?;
+                    // limit the overall size of the body
+                    body_len += chunk.len();
+                    if body_len > self.max_request_bytes {
+                        return Err(partially_accepted(
+                            Error::RequestSizeExceeded(self.max_request_bytes),
+                            lines_accepted,
+                        ));
+                    }
+                    buf.extend_from_slice(&chunk);
+                    false
+                }
+                None => true,
+            };
+
+            // Write the complete lines buffered so far once they fill a
+            // segment, and everything that remains once the body is complete.
+            // The partially received last line of a truncated body is
+            // discarded.
+            let segment_len = if done && !truncated {
+                buf.len()
+            } else if done || buf.len() >= segment_bytes {
+                buf.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1)
+            } else {
+                0
+            };
+            if segment_len > 0 {
+                let segment = buf.split_to(segment_len).freeze();
+                wrote |= self
+                    .write_body(
+                        &segment,
+                        write_info,
+                        span_ctx.clone(),
+                        default_time,
+                        namespace_resolved,
+                    )
+                    .await
+                    .map_err(|e| partially_accepted(e, lines_accepted))?;
+                lines_accepted += count_lines(&segment);
+            }
+
+            if truncated {
+                return Err(self.write_deadline_exceeded(lines_accepted));
+            }
+            if done {
+                return Ok(wrote);
+            }
+        }
+    }
+
+    /// Build the error returned after writing the first `lines_accepted` lines
+    /// of a request that exceeded the write deadline.
+    fn write_deadline_exceeded(&self, lines_accepted: usize) -> Error {
+        let deadline = self
+            .write_deadline
+            .expect("body truncated without a write deadline");
+
+        warn!(?deadline, lines_accepted, "write deadline exceeded");
+        Error::WriteDeadlineExceeded {
+            deadline,
+            lines_accepted,
+        }
     }
 
     /// Parse the request's body into raw bytes, applying the configured size
     /// limits and decoding any content encoding.
-    async fn read_body(&self, req: hyper::Request<Body>) -> Result<Bytes, Error> {
-        let encoding = req
-            .headers()
-            .get(&CONTENT_ENCODING)
-            .map(|v| v.to_str().map_err(Error::NonUtf8ContentHeader))
-            .transpose()?;
-        let ungzip = match encoding {
-            None | Some("identity") => false,
-            Some("gzip") => true,
-            Some(v) => return Err(Error::InvalidContentEncoding(v.to_string())),
-        };
+    ///
+    /// If the write deadline expires before the body is fully received, the
+    /// complete lines received so far are returned, and the returned flag is
+    /// true.
+    async fn read_body(&self, req: hyper::Request<Body>) -> Result<(Bytes, bool), Error> {
+        let ungzip = is_gzip(&req)?;
 
         let mut payload = req.into_body();
+        let deadline = self.write_deadline.map(|d| tokio::time::Instant::now() + d);
 
         let mut body = BytesMut::new();
-        while let Some(chunk) = payload.next().await {
-            let chunk = chunk.map_err(Error::ClientHangup)?;
+        let mut truncated = false;
+        loop {
+            let chunk = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, payload.next()).await {
+                    Ok(v) => v,
+                    Err(_) => {
+                        truncated = true;
+                        break;
+                    }
+                },
+                None => payload.next().await,
+            };
+            let chunk = match chunk {
+                Some(v) => v.map_err(Error::ClientHangup)?,
+                None => break,
+            };
             // limit max size of in-memory payload
             if (body.len() + chunk.len()) > self.max_request_bytes {
                 return Err(Error::RequestSizeExceeded(self.max_request_bytes));
             }
             body.extend_from_slice(&chunk);
         }
+
+        if truncated {
+            // A truncated gzip stream cannot be split into complete lines, so
+            // nothing is accepted.
+            if ungzip {
+                return Err(self.write_deadline_exceeded(0));
+            }
+
+            // Discard the partially received last line.
+            let complete = body.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            body.truncate(complete);
+            return Ok((body.freeze(), true));
+        }
         let body = body.freeze();
 
         // If the body is not compressed, return early.
         if !ungzip {
-            return Ok(body);
+            return Ok((body, false));
         }
 
         // Unzip the gzip-encoded content
@@ -462,7 +1065,67 @@ where
             return Err(Error::RequestSizeExceeded(self.max_request_bytes));
         }
 
-        Ok(decoded_data.into())
+        Ok((decoded_data.into(), false))
+    }
+}
+
+/// Returns true if the body of `req` is gzip-compressed, according to its
+/// `Content-Encoding` header.
+fn is_gzip(req: &Request<Body>) -> Result<bool, Error> {
+    let encoding = req
+        .headers()
+        .get(&CONTENT_ENCODING)
+        .map(|v| v.to_str().map_err(Error::NonUtf8ContentHeader))
+        .transpose()?;
+    match encoding {
+        None | Some("identity") => Ok(false),
+        Some("gzip") => Ok(true),
+        Some(v) => Err(Error::InvalidContentEncoding(v.to_string())),
+    }
+}
+
+/// Count the lines of the written `body` the line protocol parser consumed.
+///
+/// Blank lines and comments are not counted, matching the line numbers
+/// reported in parse errors.
+fn count_lines(body: &[u8]) -> usize {
+    std::str::from_utf8(body).map_or(0, influxdb_line_protocol::count_lines)
+}
+
+/// Report `e` as stopping a streamed write after its first `lines_accepted`
+/// lines were written, if any.
+fn partially_accepted(e: Error, lines_accepted: usize) -> Error {
+    if lines_accepted == 0 {
+        return e;
+    }
+    Error::StreamedWritePartiallyAccepted {
+        lines_accepted,
+        source: Box::new(e),
+    }
+}
+
+/// Parse `body` as line protocol with `converter`, returning [`None`] if it
+/// contains no lines.
+fn parse_lp(
+    body: &[u8],
+    mut converter: LinesConverter,
+) -> Result<Option<(HashMap<String, MutableBatch>, PayloadStatistics)>, Error> {
+    let body = std::str::from_utf8(body).map_err(Error::NonUtf8Body)?;
+
+    match converter.write_lp(body).and_then(|_| converter.finish()) {
+        Ok(v) => Ok(Some(v)),
+        Err(mutable_batch_lp::Error::EmptyPayload) => Ok(None),
+        Err(line_errors) => Err(Error::ParseLineProtocol(line_errors)),
+    }
+}
+
+/// Decrements the parse queue depth when dropped, including when the request
+/// is abandoned while waiting for a parse worker.
+struct QueueDepthGuard<'a>(&'a U64Gauge);
+
+impl Drop for QueueDepthGuard<'_> {
+    fn drop(&mut self) {
+        self.0.dec(1);
     }
 }
 
@@ -476,6 +1139,7 @@ mod tests {
         OrgBucketMappingError, TableId,
     };
     use flate2::{write::GzEncoder, Compression};
+    use futures::TryStreamExt;
     use hyper::header::HeaderValue;
     use metric::{Attributes, Metric};
     use mutable_batch::column::ColumnData;
@@ -488,6 +1152,7 @@ mod tests {
         dml_handlers::mock::{MockDmlHandler, MockDmlHandlerCall},
         namespace_resolver::{mock::MockNamespaceResolver, NamespaceCreationError},
         schema_validator::CachedServiceProtectionLimit,
+        server::http::durable::mock::MockPersistWaiter,
         server::http::write::{
             mock::{MockUnifyingParseCall, MockWriteRequestUnifier},
             multi_tenant::MultiTenantRequestUnifier,
@@ -1318,6 +1983,654 @@ mod tests {
         );
     }
 
+    /// Assert writes are parsed and routed when parsing is offloaded to parse
+    /// workers, and that the parse queue is drained afterwards.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_write_with_parse_workers() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_parse_workers(NonZeroUsize::new(1).unwrap());
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("platanos,tag1=A,tag2=B val=42i 123456"))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Ok(_));
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                assert!(write_input.contains_key("platanos"));
+            }
+        );
+        assert_metric_hit(&metrics, "http_write_lines", Some(1));
+
+        let queue_depth = metrics
+            .get_instrument::<Metric<U64Gauge>>("http_line_protocol_parse_queue_depth")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(queue_depth, 0);
+    }
+
+    /// Assert repeated tags are accepted when a non-rejecting duplicate tag
+    /// policy is configured, keeping the configured occurrence.
+    #[tokio::test]
+    async fn test_write_duplicate_tag_policy_last_wins() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_duplicate_tag_policy(DuplicateTagPolicy::LastWins);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("whydo,InputPower=300i,InputPower=42i field=42i"))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Ok(_));
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                let batch = write_input.get("whydo").expect("table not found");
+                let col = batch.column("InputPower").expect("tag column not found");
+                assert_matches!(col.data(), ColumnData::Tag(_, _, stats) => {
+                    assert_eq!(stats.min.as_deref(), Some("42i"));
+                    assert_eq!(stats.max.as_deref(), Some("42i"));
+                });
+            }
+        );
+    }
+
+    /// Assert repeated tags are rejected with a 422 by the default duplicate tag
+    /// policy.
+    #[tokio::test]
+    async fn test_write_duplicate_tag_rejected() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from("whydo,InputPower=300i,InputPower=42i field=42i"))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(e.as_status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(e.get_parse_error_line_index(), Some(1));
+        });
+        assert_matches!(dml_handler.calls().as_slice(), []);
+    }
+
+    /// Assert lines exceeding the configured line limits are rejected with a
+    /// 422, and the rejections are counted by reason.
+    #[tokio::test]
+    async fn test_write_line_limits() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_line_limits(40, 1);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::from(
+                "platanos,tag1=A val=42i 123456\n\
+                 platanos,tag1=a_long_tag_value val=42i 123456\n\
+                 platanos,tag1=A val=42i,other=1i 123456",
+            ))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(e.as_status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(e.get_parse_error_line_index(), Some(2));
+        });
+        assert_matches!(dml_handler.calls().as_slice(), []);
+
+        for reason in ["line_too_long", "too_many_fields"] {
+            let rejected = metrics
+                .get_instrument::<Metric<U64Counter>>("http_write_lines_rejected")
+                .expect("failed to read metric")
+                .get_observer(&Attributes::from(&[("reason", reason)]))
+                .expect("failed to get observer")
+                .fetch();
+            assert_eq!(rejected, 1, "unexpected rejections for {reason}");
+        }
+    }
+
+    /// Assert a write body that is not fully received before the write deadline
+    /// has its complete lines written, and reports how many were accepted.
+    #[tokio::test]
+    async fn test_write_deadline_partial_accept() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_write_deadline(Duration::from_millis(50));
+
+        // Send two complete lines and the start of a third, then hold the
+        // body open (by retaining tx) to simulate a slow client. The blank
+        // line is not counted as accepted, as it is not numbered by the
+        // parser.
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<&'static str, MockError>>(2);
+        tx.send(Ok("platanos val=1i 1\n\nplatanos val=2i 2\n"))
+            .await
+            .unwrap();
+        tx.send(Ok("platanos val=3i")).await.unwrap();
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::wrap_stream(ReceiverStream::new(rx)))
+            .unwrap();
+        let got = delegate
+            .route(request)
+            .with_timeout_panic(Duration::from_secs(5))
+            .await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(e.as_status_code(), StatusCode::REQUEST_TIMEOUT);
+            assert_eq!(e.lines_accepted(), Some(2));
+        });
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                let table = write_input.get("platanos").expect("table not found");
+                assert_eq!(table.rows(), 2);
+            }
+        );
+
+        drop(tx);
+    }
+
+    /// Assert a large streamed body is written in bounded segments of complete
+    /// lines as it is received, rather than buffered in full.
+    #[tokio::test]
+    async fn test_streaming_write_bounded_segments() {
+        const SEGMENT_BYTES: usize = 1024;
+        const N_LINES: usize = 10_000;
+
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(
+            MockDmlHandler::default().with_write_return(
+                iter::repeat_with(|| Ok(()))
+                    .take(N_LINES)
+                    .collect::<Vec<_>>(),
+            ),
+        );
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            1024 * 1024,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_streaming_writes(NonZeroUsize::new(SEGMENT_BYTES).unwrap());
+
+        // A body of N_LINES lines, sent in chunks that do not align with line
+        // boundaries.
+        let body = (0..N_LINES)
+            .map(|i| format!("platanos,tag1=A val={i}i {i}\n"))
+            .collect::<String>();
+        assert!(body.len() > 100 * SEGMENT_BYTES);
+        let chunks = body
+            .as_bytes()
+            .chunks(100)
+            .map(|c| Ok::<_, MockError>(Bytes::copy_from_slice(c)))
+            .collect::<Vec<_>>();
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::wrap_stream(ReceiverStream::new(rx)))
+            .unwrap();
+
+        let send = {
+            let dml_handler = Arc::clone(&dml_handler);
+            async move {
+                let mut chunks = chunks.into_iter();
+
+                // Send enough data to fill a segment, and wait for it to be
+                // written while the rest of the body is still being sent.
+                for chunk in chunks.by_ref().take(SEGMENT_BYTES / 100 + 1) {
+                    tx.send(chunk).await.unwrap();
+                }
+                async {
+                    while dml_handler.calls().is_empty() {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                    }
+                }
+                .with_timeout_panic(Duration::from_secs(5))
+                .await;
+
+                for chunk in chunks {
+                    tx.send(chunk).await.unwrap();
+                }
+            }
+        };
+        let (got, _) = tokio::join!(
+            delegate
+                .route(request)
+                .with_timeout_panic(Duration::from_secs(5)),
+            send,
+        );
+        assert_matches!(got, Ok(_));
+
+        // Every line is written exactly once, in segments no larger than a
+        // segment plus one chunk.
+        let calls = dml_handler.calls();
+        assert!(
+            calls.len() > 100,
+            "expected many segments, got {}",
+            calls.len()
+        );
+        let mut rows = 0;
+        for call in calls {
+            assert_matches!(call, MockDmlHandlerCall::Write { write_input, .. } => {
+                let table = write_input.get("platanos").expect("table not found");
+                assert!(table.rows() * "platanos,tag1=A val=0i 0\n".len() <= SEGMENT_BYTES + 100);
+                rows += table.rows();
+            });
+        }
+        assert_eq!(rows, N_LINES);
+        assert_metric_hit(&metrics, "http_write_lines", Some(N_LINES as _));
+    }
+
+    /// Assert a streamed write failing after some of its segments were written
+    /// reports how many lines were accepted, and the failing line relative to
+    /// the start of the body.
+    #[tokio::test]
+    async fn test_streaming_write_partial_accept() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_streaming_writes(NonZeroUsize::new(16).unwrap());
+
+        // The first chunk fills a segment, the fourth line is invalid.
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<&'static str, MockError>>(2);
+        tx.send(Ok("platanos val=1i 1\nplatanos val=2i 2\n"))
+            .await
+            .unwrap();
+        tx.send(Ok("platanos val=3i 3\nplatanos 4\n"))
+            .await
+            .unwrap();
+        drop(tx);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::wrap_stream(ReceiverStream::new(rx)))
+            .unwrap();
+        let got = delegate
+            .route(request)
+            .with_timeout_panic(Duration::from_secs(5))
+            .await;
+        assert_matches!(got, Err(e) => {
+            assert_matches!(e, Error::StreamedWritePartiallyAccepted { .. });
+            assert_eq!(e.as_status_code(), StatusCode::BAD_REQUEST);
+            assert_eq!(e.lines_accepted(), Some(2));
+            assert_eq!(e.get_parse_error_line_index(), Some(4));
+        });
+
+        assert_matches!(
+            dml_handler.calls().as_slice(),
+            [MockDmlHandlerCall::Write { write_input, .. }] => {
+                let table = write_input.get("platanos").expect("table not found");
+                assert_eq!(table.rows(), 2);
+            }
+        );
+    }
+
+    /// Assert blank lines are not counted in the lines accepted by a streamed
+    /// write, nor in the failing line, consistent with the parser.
+    #[tokio::test]
+    async fn test_streaming_write_partial_accept_blank_lines() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_streaming_writes(NonZeroUsize::new(16).unwrap());
+
+        // The first chunk fills a segment of two lines, the fourth line is
+        // invalid.
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<&'static str, MockError>>(2);
+        tx.send(Ok("platanos val=1i 1\n\nplatanos val=2i 2\n"))
+            .await
+            .unwrap();
+        tx.send(Ok("\nplatanos val=3i 3\n\nplatanos 4\n"))
+            .await
+            .unwrap();
+        drop(tx);
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+            .method("POST")
+            .body(Body::wrap_stream(ReceiverStream::new(rx)))
+            .unwrap();
+        let got = delegate
+            .route(request)
+            .with_timeout_panic(Duration::from_secs(5))
+            .await;
+        assert_matches!(got, Err(e) => {
+            assert_eq!(e.lines_accepted(), Some(2));
+            assert_eq!(e.get_parse_error_line_index(), Some(4));
+        });
+    }
+
+    /// Write latency is recorded by namespace and outcome, with the namespace
+    /// only recorded once it was resolved.
+    #[tokio::test]
+    async fn test_write_duration_metric() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(
+            MockDmlHandler::default()
+                .with_write_return([Ok(()), Err(DmlError::Internal("💣".into()))]),
+        );
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let write = |body: &'static str| {
+            Request::builder()
+                .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+                .method("POST")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Accepted, rejected for invalid line protocol before the namespace
+        // is resolved, failed by the DML handler, then failed to resolve an
+        // unknown namespace.
+        assert_matches!(delegate.route(write("platanos val=1i 1")).await, Ok(_));
+        assert_matches!(delegate.route(write("platanos 1")).await, Err(_));
+        assert_matches!(delegate.route(write("platanos val=2i 2")).await, Err(_));
+        let unknown = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=missing")
+            .method("POST")
+            .body(Body::from("platanos val=3i 3"))
+            .unwrap();
+        assert_matches!(delegate.route(unknown).await, Err(_));
+
+        let histogram = metrics
+            .get_instrument::<Metric<DurationHistogram>>("http_write_duration")
+            .expect("failed to read metric");
+        let count = |namespace, outcome| {
+            histogram
+                .get_observer(&Attributes::from(&[
+                    ("namespace", namespace),
+                    ("outcome", outcome),
+                ]))
+                .map(|o| o.fetch().sample_count())
+                .unwrap_or_default()
+        };
+        assert_eq!(count(NAMESPACE_NAME, "accepted"), 1);
+        assert_eq!(count(NAMESPACE_NAME, "error"), 1);
+        assert_eq!(count(NAMESPACE_NAME, "rejected"), 0);
+        assert_eq!(count(UNKNOWN_NAMESPACE, "rejected"), 1);
+        assert_eq!(count(UNKNOWN_NAMESPACE, "error"), 1);
+        assert_eq!(count("bananas_missing", "error"), 0);
+    }
+
+    /// Only the payloads of rejected writes are stored in the dead-letter
+    /// sink.
+    #[tokio::test]
+    async fn test_write_dead_letters() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(
+            MockDmlHandler::default()
+                .with_write_return([Ok(()), Err(DmlError::Internal("💣".into()))]),
+        );
+        let metrics = Arc::new(metric::Registry::default());
+        let store = Arc::new(object_store::memory::InMemory::new());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        )
+        .with_dead_letter_sink(DeadLetterSink::new(
+            Arc::clone(&store) as _,
+            object_store::path::Path::from("dead_letters"),
+            1024,
+            false,
+            Arc::new(iox_time::SystemProvider::new()),
+            &metrics,
+        ));
+
+        let write = |body: &'static str| {
+            Request::builder()
+                .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test")
+                .method("POST")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Accepted, rejected for invalid line protocol, then failed by the
+        // DML handler.
+        assert_matches!(delegate.route(write("platanos val=1i 1")).await, Ok(_));
+        assert_matches!(delegate.route(write("platanos 1")).await, Err(_));
+        assert_matches!(delegate.route(write("platanos val=2i 2")).await, Err(_));
+
+        let stored = object_store::ObjectStore::list(store.as_ref(), None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        let [meta] = stored.as_slice() else {
+            panic!("expected one dead letter, got {stored:?}");
+        };
+        let data = object_store::ObjectStore::get(store.as_ref(), &meta.location)
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let letter: serde_json::Value = serde_json::from_slice(&data).unwrap();
+        assert_eq!(letter["namespace"], NAMESPACE_NAME);
+        assert_eq!(letter["payload"], "platanos 1");
+        assert!(
+            letter["reason"]
+                .as_str()
+                .unwrap()
+                .starts_with("failed to parse line protocol"),
+            "unexpected reason: {}",
+            letter["reason"]
+        );
+    }
+
+    /// Writes auto-creating a namespace with an invalid name are rejected with
+    /// a 422, rather than a 500 like other namespace creation failures.
+    #[test]
+    fn test_invalid_namespace_name_status_code() {
+        let err = Error::NamespaceResolver(crate::namespace_resolver::Error::Create(
+            NamespaceCreationError::InvalidName {
+                name: "bananas platanos".to_string(),
+                pattern: "^[a-z]+$".to_string(),
+            },
+        ));
+        assert_eq!(err.as_status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    /// Assert writes requesting a durable acknowledgement are rejected unless
+    /// durable writes are enabled.
+    #[tokio::test]
+    async fn test_write_durable_disabled() {
+        let mock_namespace_resolver =
+            MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+        let dml_handler = Arc::new(MockDmlHandler::default());
+        let metrics = Arc::new(metric::Registry::default());
+        let delegate = HttpDelegate::new(
+            MAX_BYTES,
+            100,
+            mock_namespace_resolver,
+            Arc::clone(&dml_handler),
+            &metrics,
+            Box::<MultiTenantRequestUnifier>::default(),
+        );
+
+        let request = Request::builder()
+            .uri("https://bananas.example/api/v2/write?org=bananas&bucket=test&wait=durable")
+            .method("POST")
+            .body(Body::from("platanos,tag1=A,tag2=B val=42i 123456"))
+            .unwrap();
+        let got = delegate.route(request).await;
+        assert_matches!(got, Err(Error::DurableWritesDisabled));
+        assert_matches!(dml_handler.calls().as_slice(), []);
+    }
+
+    /// Assert the status code of writes with each acknowledgement mode when
+    /// durable writes are enabled.
+    #[tokio::test]
+    async fn test_write_durable() {
+        for (query, delay, want_status, want_waits) in [
+            ("", None, StatusCode::NO_CONTENT, 0),
+            ("&wait=buffered", None, StatusCode::NO_CONTENT, 0),
+            ("&wait=durable", None, StatusCode::OK, 1),
+            (
+                "&wait=durable",
+                Some(Duration::from_secs(60)),
+                StatusCode::ACCEPTED,
+                1,
+            ),
+        ] {
+            let mock_namespace_resolver =
+                MockNamespaceResolver::default().with_mapping(NAMESPACE_NAME, NAMESPACE_ID);
+
+            let dml_handler = Arc::new(MockDmlHandler::default().with_write_return([Ok(())]));
+            let mut persist_waiter = MockPersistWaiter::default();
+            if let Some(delay) = delay {
+                persist_waiter = persist_waiter.with_delay(delay);
+            }
+            let persist_waiter = Arc::new(persist_waiter);
+            let metrics = Arc::new(metric::Registry::default());
+            let delegate = HttpDelegate::new(
+                MAX_BYTES,
+                100,
+                mock_namespace_resolver,
+                Arc::clone(&dml_handler),
+                &metrics,
+                Box::<MultiTenantRequestUnifier>::default(),
+            )
+            .with_durable_writes(Arc::clone(&persist_waiter) as _, Duration::from_millis(10));
+
+            let request = Request::builder()
+                .uri(format!(
+                    "https://bananas.example/api/v2/write?org=bananas&bucket=test{query}"
+                ))
+                .method("POST")
+                .body(Body::from("platanos,tag1=A,tag2=B val=42i 123456"))
+                .unwrap();
+            let got = delegate
+                .route(request)
+                .with_timeout_panic(Duration::from_secs(5))
+                .await;
+            assert_matches!(got, Ok(resp) => {
+                assert_eq!(resp.status(), want_status, "query {query}");
+            });
+
+            let waits = persist_waiter.calls();
+            assert_eq!(waits.len(), want_waits, "query {query}");
+            assert!(waits.iter().all(|ns| ns == NAMESPACE_NAME));
+            assert_matches!(
+                dml_handler.calls().as_slice(),
+                [MockDmlHandlerCall::Write { .. }]
+            );
+        }
+    }
+
     // The display text of Error gets passed through `ioxd_router::IoxHttpErrorAdaptor` then
     // `ioxd_common::http::error::HttpApiError` as the JSON "message" value in error response
     // bodies. These are fixture tests to document error messages that users might see when
@@ -1454,6 +2767,27 @@ mod tests {
             \nerror writing line 42 (1-based): the field 'bananas' is specified more than once with conflicting types",
         ),
 
+        (
+            ParseLineProtocol(mutable_batch_lp::Error::PerLine {
+                lines: vec![
+                    mutable_batch_lp::LineError::LineTooLong {
+                        line: 42,
+                        len: 2048,
+                        max: 1024,
+                    },
+                    mutable_batch_lp::LineError::TooManyFields {
+                        line: 43,
+                        fields: 3,
+                        max: 2,
+                    },
+                ]
+            }),
+            "failed to parse line protocol: \
+            errors encountered on line(s):\
+            \nline 42 (1-based) is 2048 bytes long, exceeding the maximum of 1024 bytes\
+            \nline 43 (1-based) has 3 fields, exceeding the maximum of 2 fields",
+        ),
+
         (
             ParseLineProtocol(mutable_batch_lp::Error::EmptyPayload),
             "failed to parse line protocol: empty write payload",
@@ -1512,6 +2846,16 @@ mod tests {
             "rejecting write due to non-existing namespace: bananas",
         ),
 
+        (
+            NamespaceResolver(
+                crate::namespace_resolver::Error::Create(NamespaceCreationError::InvalidName {
+                    name: "bananas platanos".to_string(),
+                    pattern: "^[a-z]+$".to_string(),
+                })
+            ),
+            "rejecting write to namespace 'bananas platanos': name does not match '^[a-z]+$'",
+        ),
+
         (
             RequestLimit,
             "this service is overloaded, please try again later",
@@ -1522,6 +2866,16 @@ mod tests {
             "authentication required",
         ),
 
+        (
+            InvalidWaitParam(serde_urlencoded::from_str::<durable::WaitParam>("wait=forever").unwrap_err()),
+            "invalid wait parameter: unknown variant `forever`, expected `buffered` or `durable`",
+        ),
+
+        (
+            DurableWritesDisabled,
+            "durable writes (wait=durable) are not enabled on this router",
+        ),
+
         (
             Forbidden,
             "access denied",
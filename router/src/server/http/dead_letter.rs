@@ -0,0 +1,301 @@
+//! Capture of rejected write requests for debugging misbehaving producers.
+//!
+//! When enabled, the line protocol payload of every write rejected with a
+//! client error is stored in a dead-letter [`ObjectStore`] (which may be a
+//! local directory), along with the namespace and the rejection reason. This
+//! provides a forensic trail without logging full request bodies inline.
+//!
+//! [`ObjectStore`]: object_store::ObjectStore
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use data_types::NamespaceName;
+use influxdb_line_protocol::{parse_lines, EscapedStr, FieldValue};
+use iox_time::TimeProvider;
+use metric::U64Counter;
+use object_store::{path::Path, DynObjectStore};
+use observability_deps::tracing::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// The value tag values are replaced with when redacting payloads.
+const REDACTED: &str = "redacted";
+
+/// A rejected write, as stored in the dead-letter object store.
+#[derive(Debug, Serialize)]
+struct DeadLetter<'a> {
+    namespace: &'a str,
+    reason: &'a str,
+    received_at: String,
+    /// The size of the original payload, before redaction and truncation.
+    payload_bytes: usize,
+    truncated: bool,
+    redacted: bool,
+    payload: String,
+}
+
+/// Stores the payloads of rejected writes as JSON documents under a prefix
+/// of an object store.
+///
+/// Each rejected write is stored as its own object at
+/// `<prefix>/<namespace>/<received at, in nanoseconds>-<uuid>.json`.
+#[derive(Debug)]
+pub struct DeadLetterSink {
+    store: Arc<DynObjectStore>,
+    prefix: Path,
+    max_payload_bytes: usize,
+    redact: bool,
+    time_provider: Arc<dyn TimeProvider>,
+
+    bytes_written: U64Counter,
+    store_errors: U64Counter,
+}
+
+impl DeadLetterSink {
+    /// Store rejected writes in `store` under `prefix`, keeping at most the
+    /// first `max_payload_bytes` bytes of each payload.
+    ///
+    /// If `redact` is set, tag values and field values are replaced before
+    /// the payload is stored, preserving the structure and field types of
+    /// each line; lines that cannot be parsed are replaced with a placeholder.
+    pub fn new(
+        store: Arc<DynObjectStore>,
+        prefix: Path,
+        max_payload_bytes: usize,
+        redact: bool,
+        time_provider: Arc<dyn TimeProvider>,
+        metrics: &metric::Registry,
+    ) -> Self {
+        let bytes_written = metrics
+            .register_metric::<U64Counter>(
+                "http_write_dead_letter_bytes",
+                "cumulative number of bytes of rejected writes stored in the dead-letter sink",
+            )
+            .recorder(&[]);
+        let store_errors = metrics
+            .register_metric::<U64Counter>(
+                "http_write_dead_letter_errors",
+                "number of rejected writes that could not be stored in the dead-letter sink",
+            )
+            .recorder(&[]);
+
+        Self {
+            store,
+            prefix,
+            max_payload_bytes,
+            redact,
+            time_provider,
+            bytes_written,
+            store_errors,
+        }
+    }
+
+    /// Store the `payload` of a write to `namespace` rejected for `reason`.
+    ///
+    /// Failures are logged and counted, but never fail the request.
+    pub(crate) async fn store(&self, namespace: &NamespaceName<'_>, reason: &str, payload: &[u8]) {
+        let received_at = self.time_provider.now();
+
+        let mut stored = if self.redact {
+            redact(&String::from_utf8_lossy(payload))
+        } else {
+            String::from_utf8_lossy(payload).into_owned()
+        };
+        let truncated = stored.len() > self.max_payload_bytes;
+        if truncated {
+            let mut len = self.max_payload_bytes;
+            while !stored.is_char_boundary(len) {
+                len -= 1;
+            }
+            stored.truncate(len);
+        }
+
+        let letter = DeadLetter {
+            namespace: namespace.as_str(),
+            reason,
+            received_at: received_at.to_rfc3339(),
+            payload_bytes: payload.len(),
+            truncated,
+            redacted: self.redact,
+            payload: stored,
+        };
+        let data = Bytes::from(serde_json::to_vec(&letter).expect("dead letter serialisation"));
+        let len = data.len();
+
+        let path = self.prefix.child(namespace.as_str()).child(format!(
+            "{}-{}.json",
+            received_at.timestamp_nanos(),
+            Uuid::new_v4()
+        ));
+        match self.store.put(&path, data).await {
+            Ok(_) => {
+                debug!(%namespace, %path, bytes = len, "stored rejected write");
+                self.bytes_written.inc(len as _);
+            }
+            Err(e) => {
+                warn!(error=%e, %namespace, %path, "failed to store rejected write");
+                self.store_errors.inc(1);
+            }
+        }
+    }
+}
+
+/// Replace the tag values and field values of the line protocol `payload`,
+/// keeping its measurements, tag and field keys, field types and timestamps.
+///
+/// Lines that fail to parse cannot be redacted reliably and are replaced with
+/// a placeholder.
+fn redact(payload: &str) -> String {
+    parse_lines(payload)
+        .map(|line| match line {
+            Ok(mut line) => {
+                for (_, value) in line.series.tag_set.iter_mut().flatten() {
+                    *value = EscapedStr::from(REDACTED);
+                }
+                for (_, value) in line.field_set.iter_mut() {
+                    *value = match value {
+                        FieldValue::I64(_) => FieldValue::I64(0),
+                        FieldValue::U64(_) => FieldValue::U64(0),
+                        FieldValue::F64(_) => FieldValue::F64(0.0),
+                        FieldValue::String(_) => FieldValue::String(EscapedStr::from(REDACTED)),
+                        FieldValue::Boolean(_) => FieldValue::Boolean(false),
+                    };
+                }
+                line.to_string()
+            }
+            Err(_) => "# <unparseable line redacted>".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use iox_time::{MockProvider, Time};
+    use metric::{Attributes, Metric};
+    use object_store::{memory::InMemory, ObjectStore};
+
+    use super::*;
+
+    async fn stored(store: &Arc<InMemory>) -> Vec<(Path, serde_json::Value)> {
+        let metas = store
+            .list(None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+
+        let mut letters = vec![];
+        for meta in metas {
+            let data = store
+                .get(&meta.location)
+                .await
+                .unwrap()
+                .bytes()
+                .await
+                .unwrap();
+            letters.push((meta.location, serde_json::from_slice(&data).unwrap()));
+        }
+        letters
+    }
+
+    fn sink(store: &Arc<InMemory>, max_payload_bytes: usize, redact: bool) -> DeadLetterSink {
+        DeadLetterSink::new(
+            Arc::clone(store) as _,
+            Path::from("dead_letters"),
+            max_payload_bytes,
+            redact,
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(42))),
+            &metric::Registry::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_store() {
+        let store = Arc::new(InMemory::new());
+        let metrics = metric::Registry::default();
+        let sink = DeadLetterSink::new(
+            Arc::clone(&store) as _,
+            Path::from("dead_letters"),
+            1024,
+            false,
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(42))),
+            &metrics,
+        );
+
+        let namespace = NamespaceName::try_from("db/rp").unwrap();
+        sink.store(&namespace, "bad line", b"cpu,host=a val=1 42")
+            .await;
+
+        let letters = stored(&store).await;
+        let [(path, letter)] = letters.as_slice() else {
+            panic!("expected one dead letter, got {letters:?}");
+        };
+        assert!(
+            path.as_ref().starts_with("dead_letters/db%2Frp/42-"),
+            "unexpected path {path}"
+        );
+        assert_eq!(letter["namespace"], "db/rp");
+        assert_eq!(letter["reason"], "bad line");
+        assert_eq!(letter["payload"], "cpu,host=a val=1 42");
+        assert_eq!(letter["payload_bytes"], 19);
+        assert_eq!(letter["truncated"], false);
+        assert_eq!(letter["redacted"], false);
+
+        let bytes = metrics
+            .get_instrument::<Metric<U64Counter>>("http_write_dead_letter_bytes")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(
+            bytes,
+            store.head(path).await.unwrap().size as u64,
+            "metered bytes must match the stored object"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_truncated() {
+        let store = Arc::new(InMemory::new());
+        let sink = sink(&store, 10, false);
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        sink.store(&namespace, "bad line", b"cpu,host=a val=1 42")
+            .await;
+
+        let letters = stored(&store).await;
+        assert_eq!(letters.len(), 1);
+        let letter = &letters[0].1;
+        assert_eq!(letter["payload"], "cpu,host=a");
+        assert_eq!(letter["payload_bytes"], 19);
+        assert_eq!(letter["truncated"], true);
+    }
+
+    #[tokio::test]
+    async fn test_store_redacted() {
+        let store = Arc::new(InMemory::new());
+        let sink = sink(&store, 1024, true);
+
+        let namespace = NamespaceName::try_from("bananas").unwrap();
+        sink.store(
+            &namespace,
+            "bad line",
+            b"cpu,host=secret a=1.5,b=2i,c=3u,d=\"secret\",e=t 42\nnot line protocol",
+        )
+        .await;
+
+        let letters = stored(&store).await;
+        assert_eq!(letters.len(), 1);
+        let letter = &letters[0].1;
+        assert_eq!(
+            letter["payload"],
+            "cpu,host=redacted a=0,b=0i,c=0u,d=\"redacted\",e=false 42\n\
+             # <unparseable line redacted>"
+        );
+        assert_eq!(letter["redacted"], true);
+    }
+}
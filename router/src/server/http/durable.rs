@@ -0,0 +1,149 @@
+//! Support for durable write acknowledgements, requested by setting the
+//! `wait=durable` query parameter on a write request.
+//!
+//! By default the router acknowledges a write once the ingesters have buffered
+//! it (and written it to their WAL). A durable write additionally waits until
+//! the data has been persisted to object storage before acknowledging it.
+//!
+//! Durable writes are meant for tests only. The ingesters cannot report when
+//! the data of a single write has been persisted, so the whole namespace is
+//! persisted on request instead, see [`IngesterPersistWaiter`].
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use data_types::NamespaceName;
+use futures::future::try_join_all;
+use generated_types::influxdata::iox::ingester::v1::{
+    persist_service_client::PersistServiceClient, PersistRequest,
+};
+use serde::Deserialize;
+use thiserror::Error;
+use tonic::transport::{Channel, Endpoint};
+
+/// The write acknowledgement requested by a client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WaitFor {
+    /// Acknowledge the write once it has been buffered by the ingesters.
+    #[default]
+    Buffered,
+    /// Acknowledge the write once it has been persisted to object storage.
+    Durable,
+}
+
+/// The `wait` query parameter of a write request, if any.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct WaitParam {
+    #[serde(default)]
+    pub(crate) wait: WaitFor,
+}
+
+/// An error waiting for a write to become durable.
+#[derive(Debug, Error)]
+#[error("failed to persist namespace on ingester {addr}: {source}")]
+pub struct PersistWaitError {
+    addr: String,
+    source: tonic::Status,
+}
+
+/// An abstract mechanism to wait until all data previously written to a
+/// namespace has been persisted.
+#[async_trait]
+pub trait PersistWaiter: Debug + Send + Sync {
+    /// Return once all data buffered for `namespace` at the time of the call
+    /// has been persisted to object storage.
+    async fn wait_persisted(&self, namespace: &NamespaceName<'_>) -> Result<(), PersistWaitError>;
+}
+
+/// A [`PersistWaiter`] that asks every ingester to persist all the data it
+/// buffers for the namespace, using the ingester persist service.
+///
+/// Persisting a namespace flushes ALL its buffered data, not only the data of
+/// the write being acknowledged, so durable writes to a namespace receiving a
+/// high write volume cause many small parquet files. The persist service is
+/// also meant for tests, and may behave in unexpected ways if used
+/// concurrently with writes and ingester WAL rotations, so this must not be
+/// used in production.
+#[derive(Debug)]
+pub struct IngesterPersistWaiter {
+    ingesters: Vec<(Channel, String)>,
+}
+
+impl IngesterPersistWaiter {
+    /// Initialise a new [`IngesterPersistWaiter`] that lazily connects to each
+    /// of the ingester `endpoints`.
+    pub fn new(endpoints: impl IntoIterator<Item = Endpoint>) -> Self {
+        let ingesters = endpoints
+            .into_iter()
+            .map(|e| {
+                let addr = e.uri().to_string();
+                (e.connect_lazy(), addr)
+            })
+            .collect();
+
+        Self { ingesters }
+    }
+}
+
+#[async_trait]
+impl PersistWaiter for IngesterPersistWaiter {
+    async fn wait_persisted(&self, namespace: &NamespaceName<'_>) -> Result<(), PersistWaitError> {
+        try_join_all(self.ingesters.iter().map(|(conn, addr)| async move {
+            PersistServiceClient::new(conn.clone())
+                .persist(PersistRequest {
+                    namespace: namespace.to_string(),
+                })
+                .await
+                .map_err(|source| PersistWaitError {
+                    addr: addr.clone(),
+                    source,
+                })
+        }))
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::time::Duration;
+
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// A mock [`PersistWaiter`] recording the namespaces it was asked to wait
+    /// for, that completes after an optional delay.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockPersistWaiter {
+        delay: Option<Duration>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl MockPersistWaiter {
+        pub(crate) fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = Some(delay);
+            self
+        }
+
+        pub(crate) fn calls(&self) -> Vec<String> {
+            self.calls.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl PersistWaiter for MockPersistWaiter {
+        async fn wait_persisted(
+            &self,
+            namespace: &NamespaceName<'_>,
+        ) -> Result<(), PersistWaitError> {
+            self.calls.lock().push(namespace.to_string());
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            Ok(())
+        }
+    }
+}
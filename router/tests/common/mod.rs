@@ -13,7 +13,8 @@ use object_store::memory::InMemory;
 use router::{
     dml_handlers::{
         client::mock::MockWriteClient, Chain, DmlHandlerChainExt, FanOutAdaptor,
-        InstrumentationDecorator, Partitioned, Partitioner, RetentionValidator, RpcWrite,
+        InstrumentationDecorator, OutsideRetentionPolicy, Partitioned, Partitioner,
+        RetentionValidator, RpcWrite,
     },
     gossip::anti_entropy::{mst::actor::AntiEntropyActor, sync::rpc_server::AntiEntropyService},
     namespace_cache::{MemoryNamespaceCache, ReadThroughCache, ShardedCache},
@@ -162,7 +163,8 @@ impl TestContext {
         let schema_validator =
             SchemaValidator::new(Arc::clone(&catalog), Arc::clone(&ns_cache), &metrics);
 
-        let retention_validator = RetentionValidator::new();
+        let retention_validator =
+            RetentionValidator::new(OutsideRetentionPolicy::default(), &metrics);
 
         let partitioner = Partitioner::default();
 
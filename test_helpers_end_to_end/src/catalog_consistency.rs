@@ -0,0 +1,206 @@
+//! Consistency checks between the catalog and the object store of a mini cluster, for
+//! [`Step::AssertCatalogConsistency`].
+//!
+//! [`Step::AssertCatalogConsistency`]: crate::Step::AssertCatalogConsistency
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use data_types::NamespaceId;
+use iox_catalog::{
+    interface::Catalog,
+    postgres::{PostgresCatalog, PostgresConnectionOptions},
+    sqlite::{SqliteCatalog, SqliteConnectionOptions},
+};
+
+use crate::TestConfig;
+
+/// Check that the catalog entries of the namespace `namespace_id` agree with the contents of the
+/// object store, both as configured by `config`, returning a description of every inconsistency
+/// found.
+///
+/// The following is checked:
+///
+/// - every Parquet file references a table of the namespace and a partition of that table,
+/// - every Parquet file not marked for deletion exists in the object store, under the
+///   directories of its namespace and table, with the size recorded in the catalog,
+/// - every Parquet file stored for the namespace is known to the catalog (files marked for
+///   deletion are known until they are removed).
+pub(crate) async fn check_catalog_consistency(
+    config: &TestConfig,
+    namespace_id: NamespaceId,
+) -> Vec<String> {
+    let catalog = connect_catalog(config).await;
+    let mut repos = catalog.repositories().await;
+
+    let tables: HashSet<_> = repos
+        .tables()
+        .list_by_namespace_id(namespace_id)
+        .await
+        .expect("listing tables")
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+
+    let mut partitions = HashMap::new();
+    for table_id in &tables {
+        for partition in repos
+            .partitions()
+            .list_by_table_id(*table_id)
+            .await
+            .expect("listing partitions")
+        {
+            partitions.insert(partition.transition_partition_id(), partition.table_id);
+        }
+    }
+
+    let files: Vec<_> = repos
+        .parquet_files()
+        .list_all()
+        .await
+        .expect("listing Parquet files")
+        .into_iter()
+        .filter(|f| f.namespace_id == namespace_id)
+        .collect();
+
+    let store_dir = config
+        .object_store_dir()
+        .as_ref()
+        .expect("checking catalog consistency requires a file object store");
+    let namespace_dir = store_dir.path().join(namespace_id.to_string());
+    let mut objects = HashMap::new();
+    list_parquet_objects(&namespace_dir, &mut objects);
+
+    let mut problems = vec![];
+    for file in &files {
+        let desc = format!(
+            "Parquet file {} (object store id {})",
+            file.id, file.object_store_id
+        );
+
+        if !tables.contains(&file.table_id) {
+            problems.push(format!(
+                "{desc} references table {} which is not in namespace {namespace_id}",
+                file.table_id
+            ));
+        }
+        match partitions.get(&file.partition_id) {
+            None => problems.push(format!(
+                "{desc} references unknown partition {}",
+                file.partition_id
+            )),
+            Some(table_id) if *table_id != file.table_id => problems.push(format!(
+                "{desc} of table {} references partition {} of table {table_id}",
+                file.table_id, file.partition_id
+            )),
+            Some(_) => {}
+        }
+
+        if file.to_delete.is_some() {
+            continue;
+        }
+        match objects.get(&file.object_store_id.to_string()) {
+            None => problems.push(format!("{desc} is missing from the object store")),
+            Some((path, size)) => {
+                let table_dir = namespace_dir.join(file.table_id.to_string());
+                if !path.starts_with(&table_dir) {
+                    problems.push(format!(
+                        "{desc} of table {} is stored outside of its table at {}",
+                        file.table_id,
+                        path.display()
+                    ));
+                }
+                if *size != file.file_size_bytes as u64 {
+                    problems.push(format!(
+                        "{desc} has {} bytes in the catalog but {size} bytes in the object store",
+                        file.file_size_bytes
+                    ));
+                }
+            }
+        }
+    }
+
+    let known: HashSet<_> = files
+        .iter()
+        .map(|f| f.object_store_id.to_string())
+        .collect();
+    let mut orphans: Vec<_> = objects
+        .iter()
+        .filter(|(id, _)| !known.contains(*id))
+        .map(|(_, (path, _))| path)
+        .collect();
+    orphans.sort();
+    for path in orphans {
+        problems.push(format!(
+            "orphaned object {} is not known to the catalog",
+            path.display()
+        ));
+    }
+
+    problems
+}
+
+/// Connect to the catalog the servers started with `config` use.
+async fn connect_catalog(config: &TestConfig) -> Arc<dyn Catalog> {
+    let dsn = config
+        .dsn()
+        .as_deref()
+        .expect("checking catalog consistency requires a catalog DSN");
+    let metrics = Arc::new(metric::Registry::default());
+
+    match dsn.strip_prefix("sqlite://") {
+        Some(file_path) => Arc::new(
+            SqliteCatalog::connect(
+                SqliteConnectionOptions {
+                    file_path: file_path.to_string(),
+                },
+                metrics,
+            )
+            .await
+            .expect("connecting to sqlite catalog"),
+        ),
+        None => Arc::new(
+            PostgresCatalog::connect(
+                PostgresConnectionOptions {
+                    app_name: "test_helpers_end_to_end".to_string(),
+                    schema_name: config.catalog_schema_name().to_string(),
+                    dsn: iox_catalog::postgres::parse_dsn(dsn).expect("invalid catalog DSN"),
+                    ..Default::default()
+                },
+                metrics,
+            )
+            .await
+            .expect("connecting to postgres catalog"),
+        ),
+    }
+}
+
+/// Collect the Parquet files below `dir`, keyed by their object store ID, with their path and
+/// size.
+fn list_parquet_objects(dir: &Path, objects: &mut HashMap<String, (PathBuf, u64)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // nothing was persisted for the namespace yet
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => panic!("could not list object store directory {dir:?}: {e}"),
+    };
+
+    for entry in entries {
+        let entry = entry.expect("reading object store directory entry");
+        let path = entry.path();
+        let metadata = entry.metadata().expect("reading object store metadata");
+        if metadata.is_dir() {
+            list_parquet_objects(&path, objects);
+        } else if path.extension().map_or(false, |ext| ext == "parquet") {
+            let id = path
+                .file_stem()
+                .expect("file name")
+                .to_string_lossy()
+                .into_owned();
+            objects.insert(id, (path, metadata.len()));
+        }
+    }
+}
@@ -22,7 +22,6 @@ pub async fn write_to_router(
     write_base: impl AsRef<str>,
     authorization: Option<&str>,
 ) -> Response<Body> {
-    let client = Client::new();
     let url = format!(
         "{}/api/v2/write?org={}&bucket={}",
         write_base.as_ref(),
@@ -30,12 +29,73 @@ pub async fn write_to_router(
         bucket.as_ref()
     );
 
+    post_line_protocol(line_protocol, url, authorization).await
+}
+
+/// Writes the line protocol to the write_base/api/v2/write endpoint (typically on the router),
+/// declaring the timestamps to be in the given `precision` (one of `s`, `ms`, `us` or `ns`)
+pub async fn write_to_router_with_precision(
+    line_protocol: impl Into<String>,
+    org: impl AsRef<str>,
+    bucket: impl AsRef<str>,
+    write_base: impl AsRef<str>,
+    precision: impl AsRef<str>,
+) -> Response<Body> {
+    let url = format!(
+        "{}/api/v2/write?org={}&bucket={}&precision={}",
+        write_base.as_ref(),
+        org.as_ref(),
+        bucket.as_ref(),
+        precision.as_ref()
+    );
+
+    post_line_protocol(line_protocol, url, None).await
+}
+
+/// Writes a gzip-compressed `body` to the write_base/api/v2/write endpoint (typically on the
+/// router), setting the `Content-Encoding: gzip` header.
+///
+/// The body is sent as is, so it may be deliberately corrupt.
+pub async fn write_gzip_to_router(
+    body: impl Into<Vec<u8>>,
+    org: impl AsRef<str>,
+    bucket: impl AsRef<str>,
+    write_base: impl AsRef<str>,
+) -> Response<Body> {
+    let url = format!(
+        "{}/api/v2/write?org={}&bucket={}",
+        write_base.as_ref(),
+        org.as_ref(),
+        bucket.as_ref()
+    );
+
+    post_write_body(Body::from(body.into()), url, None, Some("gzip")).await
+}
+
+async fn post_line_protocol(
+    line_protocol: impl Into<String>,
+    url: String,
+    authorization: Option<&str>,
+) -> Response<Body> {
+    post_write_body(Body::from(line_protocol.into()), url, authorization, None).await
+}
+
+async fn post_write_body(
+    body: Body,
+    url: String,
+    authorization: Option<&str>,
+    content_encoding: Option<&str>,
+) -> Response<Body> {
+    let client = Client::new();
     let mut builder = Request::builder().uri(url).method("POST");
     if let Some(authorization) = authorization {
         builder = builder.header(hyper::header::AUTHORIZATION, authorization);
     };
+    if let Some(content_encoding) = content_encoding {
+        builder = builder.header(hyper::header::CONTENT_ENCODING, content_encoding);
+    };
     let request = builder
-        .body(Body::from(line_protocol.into()))
+        .body(body)
         .expect("failed to construct HTTP request");
 
     client
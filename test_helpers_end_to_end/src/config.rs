@@ -214,6 +214,15 @@ impl TestConfig {
         )
     }
 
+    /// Configure the router to write uncompressed request bodies in segments of at least
+    /// `segment_bytes` as they are received, accepting the segments before an invalid line.
+    pub fn with_streaming_writes(self, segment_bytes: usize) -> Self {
+        self.with_env(
+            "INFLUXDB_IOX_HTTP_STREAMING_WRITE_SEGMENT_BYTES",
+            segment_bytes.to_string(),
+        )
+    }
+
     pub fn with_ingester_never_persist(self) -> Self {
         self.with_env("INFLUXDB_IOX_WAL_ROTATION_PERIOD_SECONDS", "86400")
     }
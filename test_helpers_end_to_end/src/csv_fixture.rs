@@ -0,0 +1,242 @@
+//! Conversion of CSV fixtures to line protocol, for [`Step::WriteLineProtocolWithTimestampColumn`].
+//!
+//! [`Step::WriteLineProtocolWithTimestampColumn`]: crate::Step::WriteLineProtocolWithTimestampColumn
+use snafu::{ensure, OptionExt, Snafu};
+use std::collections::HashMap;
+
+/// The line protocol type a CSV column is written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvFieldType {
+    Float,
+    Integer,
+    UInteger,
+    String,
+    Boolean,
+}
+
+/// Declares how the columns of a CSV fixture map to line protocol.
+///
+/// The first line of the fixture is a header naming its columns. Columns that are not mentioned
+/// in the mapping are ignored. Empty tag and field cells are omitted from the written line, but
+/// every row must have a timestamp and at least one field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvMapping {
+    /// The measurement every row is written to.
+    pub measurement: String,
+    /// The columns written as tags.
+    pub tags: Vec<String>,
+    /// The columns written as fields, and their types.
+    pub fields: Vec<(String, CsvFieldType)>,
+    /// The column holding the timestamp of each row, in nanoseconds since the epoch.
+    pub timestamp: String,
+}
+
+impl CsvMapping {
+    /// Map the CSV to `measurement`, with the timestamp in the `timestamp` column and no tags or
+    /// fields.
+    pub fn new(measurement: impl Into<String>, timestamp: impl Into<String>) -> Self {
+        Self {
+            measurement: measurement.into(),
+            tags: vec![],
+            fields: vec![],
+            timestamp: timestamp.into(),
+        }
+    }
+
+    /// Write the `column` as a tag.
+    pub fn with_tag(mut self, column: impl Into<String>) -> Self {
+        self.tags.push(column.into());
+        self
+    }
+
+    /// Write the `column` as a field of type `field_type`.
+    pub fn with_field(mut self, column: impl Into<String>, field_type: CsvFieldType) -> Self {
+        self.fields.push((column.into(), field_type));
+        self
+    }
+}
+
+/// Errors converting a CSV fixture to line protocol.
+#[derive(Debug, Snafu, PartialEq, Eq)]
+pub enum CsvError {
+    #[snafu(display("CSV fixture has no header line"))]
+    MissingHeader,
+
+    #[snafu(display("CSV fixture header has no column named {column:?}"))]
+    MissingColumn { column: String },
+
+    #[snafu(display("line {line}: {reason}"))]
+    Malformed { line: usize, reason: String },
+}
+
+/// Convert the rows of the `csv` fixture to line protocol according to `mapping`, one line per
+/// row.
+///
+/// Fields may be quoted with `"`, in which case they may contain commas and `""` stands for a
+/// literal quote. Quoted fields cannot span lines. Blank lines are skipped.
+pub(crate) fn csv_to_line_protocol(csv: &str, mapping: &CsvMapping) -> Result<String, CsvError> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_line, header) = lines.next().context(MissingHeaderSnafu)?;
+    let header = split_row(header).map_err(|reason| CsvError::Malformed {
+        line: header_line,
+        reason,
+    })?;
+    let columns: HashMap<_, _> = header
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| (name.trim(), idx))
+        .collect();
+    let position = |column: &str| {
+        columns
+            .get(column)
+            .copied()
+            .context(MissingColumnSnafu { column })
+    };
+
+    let timestamp = position(&mapping.timestamp)?;
+    let tags = mapping
+        .tags
+        .iter()
+        .map(|column| Ok((column.as_str(), position(column)?)))
+        .collect::<Result<Vec<_>, CsvError>>()?;
+    let fields = mapping
+        .fields
+        .iter()
+        .map(|(column, field_type)| Ok((column.as_str(), *field_type, position(column)?)))
+        .collect::<Result<Vec<_>, CsvError>>()?;
+
+    let mut lp = vec![];
+    for (line, row) in lines {
+        let malformed = |reason: String| CsvError::Malformed { line, reason };
+
+        let row = split_row(row).map_err(malformed)?;
+        ensure!(
+            row.len() == header.len(),
+            MalformedSnafu {
+                line,
+                reason: format!("expected {} columns, found {}", header.len(), row.len()),
+            }
+        );
+
+        let mut out = escape(&mapping.measurement, &[',', ' ']);
+        for (name, idx) in &tags {
+            let value = row[*idx].as_str();
+            if !value.is_empty() {
+                out.push(',');
+                out.push_str(&escape(name, &[',', '=', ' ']));
+                out.push('=');
+                out.push_str(&escape(value, &[',', '=', ' ']));
+            }
+        }
+
+        let mut field_set = vec![];
+        for (name, field_type, idx) in &fields {
+            let value = row[*idx].as_str();
+            if value.is_empty() {
+                continue;
+            }
+            let value = format_field(value, *field_type)
+                .map_err(|reason| malformed(format!("column {name:?}: {reason}")))?;
+            field_set.push(format!("{}={value}", escape(name, &[',', '=', ' '])));
+        }
+        ensure!(
+            !field_set.is_empty(),
+            MalformedSnafu {
+                line,
+                reason: "row has no field values",
+            }
+        );
+
+        let ts = row[timestamp].trim();
+        let ts: i64 = ts.parse().map_err(|_| {
+            malformed(format!(
+                "column {:?}: invalid timestamp {ts:?}, expected nanoseconds since the epoch",
+                mapping.timestamp
+            ))
+        })?;
+
+        out.push(' ');
+        out.push_str(&field_set.join(","));
+        out.push(' ');
+        out.push_str(&ts.to_string());
+        lp.push(out);
+    }
+
+    Ok(lp.join("\n"))
+}
+
+/// Split a CSV `row` into its (unquoted) cells.
+fn split_row(row: &str) -> Result<Vec<String>, String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = row.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            (true, '"') => {
+                quoted = false;
+                if !matches!(chars.peek(), None | Some(',')) {
+                    return Err(format!(
+                        "unexpected character after closing quote in {row:?}"
+                    ));
+                }
+            }
+            (false, '"') if cell.is_empty() => quoted = true,
+            (false, ',') => cells.push(std::mem::take(&mut cell)),
+            (_, c) => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err(format!("unterminated quoted field in {row:?}"));
+    }
+    cells.push(cell);
+
+    Ok(cells)
+}
+
+/// Render the CSV cell `value` as a line protocol field value of type `field_type`.
+fn format_field(value: &str, field_type: CsvFieldType) -> Result<String, String> {
+    let trimmed = value.trim();
+    match field_type {
+        CsvFieldType::Float => trimmed
+            .parse::<f64>()
+            .map(|v| v.to_string())
+            .map_err(|_| format!("invalid float {value:?}")),
+        CsvFieldType::Integer => trimmed
+            .parse::<i64>()
+            .map(|v| format!("{v}i"))
+            .map_err(|_| format!("invalid integer {value:?}")),
+        CsvFieldType::UInteger => trimmed
+            .parse::<u64>()
+            .map(|v| format!("{v}u"))
+            .map_err(|_| format!("invalid unsigned integer {value:?}")),
+        CsvFieldType::Boolean => match trimmed.to_ascii_lowercase().as_str() {
+            "t" | "true" => Ok("true".to_string()),
+            "f" | "false" => Ok("false".to_string()),
+            _ => Err(format!("invalid boolean {value:?}")),
+        },
+        CsvFieldType::String => Ok(format!("\"{}\"", escape(value, &['"', '\\']))),
+    }
+}
+
+/// Backslash-escape the `special` characters of `s`.
+fn escape(s: &str, special: &[char]) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if special.contains(&c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
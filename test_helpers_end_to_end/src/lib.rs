@@ -10,8 +10,10 @@ use rand::{
 
 mod addrs;
 mod authz;
+mod catalog_consistency;
 mod client;
 mod config;
+mod csv_fixture;
 mod data_generator;
 mod database;
 mod error;
@@ -27,13 +29,16 @@ pub use addrs::BindAddresses;
 pub use authz::Authorizer;
 pub use client::*;
 pub use config::TestConfig;
+pub use csv_fixture::{CsvError, CsvFieldType, CsvMapping};
 pub use data_generator::DataGenerator;
 pub use error::{check_flight_error, check_tonic_status};
 pub use grpc::GrpcRequestBuilder;
 pub use mini_cluster::MiniCluster;
 pub use server_fixture::{ServerFixture, TestServer};
 pub use server_type::{AddAddrEnv, ServerType};
-pub use steps::{FCustom, Step, StepTest, StepTestState};
+pub use steps::{
+    CompactorConfigOverrides, FCustom, Step, StepTest, StepTestState, UPDATE_GOLDEN_ENV,
+};
 pub use udp_listener::UdpCapture;
 
 /// Return a random string suitable for use as a namespace name
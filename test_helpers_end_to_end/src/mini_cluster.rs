@@ -1,6 +1,7 @@
 use crate::{
-    dump_log_to_stdout, log_command, rand_id, server_type::AddAddrEnv, write_to_ingester,
-    write_to_router, ServerFixture, TestConfig, TestServer,
+    dump_log_to_stdout, log_command, rand_id, server_type::AddAddrEnv, write_gzip_to_router,
+    write_to_ingester, write_to_router, write_to_router_with_precision, ServerFixture, TestConfig,
+    TestServer,
 };
 use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
 use arrow_flight::{
@@ -498,6 +499,34 @@ impl MiniCluster {
         .await
     }
 
+    /// Write to the router using the HTTP interface, with timestamps in the given `precision`
+    /// (one of `s`, `ms`, `us` or `ns`).
+    pub async fn write_to_router_with_precision(
+        &self,
+        line_protocol: impl Into<String>,
+        precision: &str,
+    ) -> Response<Body> {
+        write_to_router_with_precision(
+            line_protocol,
+            &self.org_id,
+            &self.bucket_id,
+            self.router().router_http_base(),
+            precision,
+        )
+        .await
+    }
+
+    /// Write a gzip-compressed `body` to the router using the HTTP interface.
+    pub async fn write_gzip_to_router(&self, body: impl Into<Vec<u8>>) -> Response<Body> {
+        write_gzip_to_router(
+            body,
+            &self.org_id,
+            &self.bucket_id,
+            self.router().router_http_base(),
+        )
+        .await
+    }
+
     /// Write to the ingester using the gRPC interface directly, rather than through a router.
     pub async fn write_to_ingester(&self, line_protocol: impl Into<String>, table_name: &str) {
         write_to_ingester(
@@ -584,6 +613,17 @@ impl MiniCluster {
     }
 
     pub fn run_compaction(&self) -> Result<(), String> {
+        self.run_compaction_with_env(std::iter::empty::<(String, String)>())
+    }
+
+    /// Run one compaction pass like [`Self::run_compaction`], setting the additional environment
+    /// variables in `env` to override the compactor config.
+    pub fn run_compaction_with_env<I, K, V>(&self, env: I) -> Result<(), String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<std::ffi::OsStr>,
+        V: AsRef<std::ffi::OsStr>,
+    {
         let (log_file, log_path) = NamedTempFile::new()
             .expect("opening log file")
             .keep()
@@ -621,6 +661,7 @@ impl MiniCluster {
                 self.compactor_config().catalog_schema_name(),
             )
             .envs(self.compactor_config().env())
+            .envs(env)
             .add_addr_env(
                 self.compactor_config().server_type(),
                 self.compactor_config().addrs(),
@@ -77,6 +77,11 @@ impl ServerFixture {
         }
     }
 
+    /// Return the configuration the server was started with
+    pub fn test_config(&self) -> &TestConfig {
+        &self.server.test_config
+    }
+
     pub fn connections(&self) -> &Connections {
         &self.connections
     }
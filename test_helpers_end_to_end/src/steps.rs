@@ -1,18 +1,90 @@
+use crate::catalog_consistency::check_catalog_consistency;
+use crate::csv_fixture::{csv_to_line_protocol, CsvMapping};
 use crate::snapshot_comparison::Language;
 use crate::{
     check_flight_error, run_influxql, run_sql, snapshot_comparison, try_run_influxql, try_run_sql,
     MiniCluster,
 };
-use arrow::record_batch::RecordBatch;
-use arrow_util::assert_batches_sorted_eq;
+use arrow::{
+    array::Int64Array,
+    datatypes::{DataType, Schema},
+    record_batch::RecordBatch,
+    util::display::array_value_to_string,
+};
+use arrow_util::{
+    assert_batches_sorted_eq,
+    test_util::{batches_to_lines, batches_to_sorted_lines, sort_lines},
+};
+use data_types::ColumnType;
+use flate2::{write::GzEncoder, Compression};
 use futures::future::BoxFuture;
 use http::StatusCode;
+use influxdb_iox_client::catalog::generated_types::ParquetFile;
 use observability_deps::tracing::info;
-use std::{path::PathBuf, time::Duration};
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use test_helpers::assert_contains;
 
 const MAX_QUERY_RETRY_TIME_SEC: u64 = 20;
 
+/// Number of timed runs of a query by [`Step::MeasureQueryLatency`], after the warmup run.
+const QUERY_LATENCY_SAMPLES: usize = 5;
+
+/// Compactor config values overriding those of the mini cluster's compactor for a single
+/// [`Step::CompactWithConfig`] pass. Unset values keep the cluster's config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactorConfigOverrides {
+    /// Desired max size of compacted Parquet files.
+    pub max_desired_file_size_bytes: Option<u64>,
+    /// Percentage of the desired max file size above which compaction output is split.
+    pub percentage_max_file_size: Option<u16>,
+    /// Percentage of the data to place in the first file when splitting compaction output.
+    pub split_percentage: Option<u16>,
+    /// Maximum number of files compacted in one plan.
+    pub max_num_files_per_plan: Option<usize>,
+}
+
+impl CompactorConfigOverrides {
+    /// The environment variables setting the overridden values.
+    fn env(&self) -> Vec<(&'static str, String)> {
+        let Self {
+            max_desired_file_size_bytes,
+            percentage_max_file_size,
+            split_percentage,
+            max_num_files_per_plan,
+        } = self;
+
+        [
+            (
+                "INFLUXDB_IOX_COMPACTION_MAX_DESIRED_FILE_SIZE_BYTES",
+                max_desired_file_size_bytes.map(|v| v.to_string()),
+            ),
+            (
+                "INFLUXDB_IOX_COMPACTION_PERCENTAGE_MAX_FILE_SIZE",
+                percentage_max_file_size.map(|v| v.to_string()),
+            ),
+            (
+                "INFLUXDB_IOX_COMPACTION_SPLIT_PERCENTAGE",
+                split_percentage.map(|v| v.to_string()),
+            ),
+            (
+                "INFLUXDB_IOX_COMPACTION_MAX_NUM_FILES_PER_PLAN",
+                max_num_files_per_plan.map(|v| v.to_string()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, value?)))
+        .collect()
+    }
+}
+
 /// Test harness for end to end tests that are comprised of several steps
 pub struct StepTest<'a, S> {
     cluster: &'a mut MiniCluster,
@@ -30,6 +102,13 @@ pub struct StepTestState<'a> {
     /// for tracking when persistence has happened. If this is `None`, we haven't ever checked with
     /// the catalog service.
     num_parquet_files: Option<usize>,
+
+    /// How many Parquet files the catalog service knew about for each table (by ID) of the mini
+    /// cluster's namespace, the last time the number of Parquet files was recorded.
+    num_parquet_files_by_table: HashMap<i64, usize>,
+
+    /// Values captured by [`Step::QueryCapture`], by name.
+    captures: HashMap<String, String>,
 }
 
 impl<'a> StepTestState<'a> {
@@ -45,45 +124,121 @@ impl<'a> StepTestState<'a> {
         &mut self.cluster
     }
 
-    /// Store the number of Parquet files the catalog has for the mini cluster's namespace.
+    /// Replace every `${name}` in `sql` with the value captured under `name` by
+    /// [`Step::QueryCapture`], panicking if there is no such capture.
+    fn substitute_captures(&self, sql: &str) -> String {
+        let mut substituted = String::with_capacity(sql.len());
+        let mut rest = sql;
+        while let Some(start) = rest.find("${") {
+            substituted.push_str(&rest[..start]);
+            let (name, after) = rest[start + 2..]
+                .split_once('}')
+                .unwrap_or_else(|| panic!("unterminated capture reference in query: {sql}"));
+            let value = self.captures.get(name).unwrap_or_else(|| {
+                let mut defined: Vec<_> = self.captures.keys().collect();
+                defined.sort_unstable();
+                panic!(
+                    "undefined capture `{name}` referenced in query: {sql}\n\n\
+                     defined captures: {defined:?}"
+                )
+            });
+            substituted.push_str(value);
+            rest = after;
+        }
+        substituted.push_str(rest);
+        substituted
+    }
+
+    /// Store the number of Parquet files the catalog has for the mini cluster's namespace, in
+    /// total and for each table.
     /// Call this before a write to be able to tell when a write has been persisted by checking for
     /// a change in this count.
     pub async fn record_num_parquet_files(&mut self) {
-        let num_parquet_files = self.get_num_parquet_files().await;
+        let connection = self.cluster.router().router_grpc_connection();
+        let files = influxdb_iox_client::catalog::Client::new(connection)
+            .get_parquet_files_by_namespace(self.cluster.namespace())
+            .await
+            .unwrap_or_default();
 
         info!(
-            "Recorded count of Parquet files for namespace {}: {num_parquet_files}",
-            self.cluster.namespace()
+            "Recorded count of Parquet files for namespace {}: {}",
+            self.cluster.namespace(),
+            files.len()
         );
-        self.num_parquet_files = Some(num_parquet_files);
+        self.num_parquet_files = Some(files.len());
+        self.num_parquet_files_by_table = files.iter().fold(HashMap::new(), |mut acc, f| {
+            *acc.entry(f.table_id).or_default() += 1;
+            acc
+        });
     }
 
     /// Wait for a change (up to a timeout) in the number of Parquet files the catalog has for the
     /// mini cluster's namespacee since the last time the number of Parquet files was recorded,
     /// which indicates persistence has taken place.
     pub async fn wait_for_num_parquet_file_change(&mut self, expected_increase: usize) {
-        let retry_duration = Duration::from_secs(MAX_QUERY_RETRY_TIME_SEC);
         let num_parquet_files = self.num_parquet_files.expect(
             "No previous number of Parquet files recorded! \
                 Use `Step::RecordNumParquetFiles` before `Step::WaitForPersisted`.",
         );
-        let expected_count = num_parquet_files + expected_increase;
 
-        tokio::time::timeout(retry_duration, async move {
+        self.wait_for_num_parquet_files(None, num_parquet_files + expected_increase)
+            .await;
+
+        // Reset the saved value to require recording before waiting again
+        self.num_parquet_files = None;
+    }
+
+    /// Wait for a change (up to a timeout) in the number of Parquet files the catalog has for
+    /// `table` since the last time the number of Parquet files was recorded.
+    ///
+    /// Unlike [`Self::wait_for_num_parquet_file_change`], the recorded counts are kept, so that
+    /// each table persisted after a single recording can be waited on in turn.
+    pub async fn wait_for_num_parquet_file_change_in_table(
+        &mut self,
+        table: &str,
+        expected_increase: usize,
+    ) {
+        assert!(
+            self.num_parquet_files.is_some(),
+            "No previous number of Parquet files recorded! \
+                Use `Step::RecordNumParquetFiles` before `Step::WaitForPersistedTable`."
+        );
+        let table_id = self.cluster.table_id(table).await;
+        let num_parquet_files = self
+            .num_parquet_files_by_table
+            .get(&table_id.get())
+            .copied()
+            .unwrap_or_default();
+
+        self.wait_for_num_parquet_files(Some(table), num_parquet_files + expected_increase)
+            .await;
+    }
+
+    /// Poll (up to a timeout) until the catalog has at least `expected_count` Parquet files for
+    /// the mini cluster's namespace, or only for `table` within it if specified.
+    async fn wait_for_num_parquet_files(&self, table: Option<&str>, expected_count: usize) {
+        let retry_duration = Duration::from_secs(MAX_QUERY_RETRY_TIME_SEC);
+        let scope = table.map_or_else(
+            || format!("namespace {}", self.cluster.namespace()),
+            |table| format!("table {table}"),
+        );
+
+        tokio::time::timeout(retry_duration, async {
             let mut interval = tokio::time::interval(Duration::from_millis(1000));
             loop {
-                let current_count = self.get_num_parquet_files().await;
+                let current_count = match table {
+                    Some(table) => self.get_num_parquet_files_in_table(table).await,
+                    None => self.get_num_parquet_files().await,
+                };
                 if current_count >= expected_count {
                     info!(
-                        "Success; Parquet file count is now {current_count} \
+                        "Success; Parquet file count for {scope} is now {current_count} \
                         which is at least {expected_count}"
                     );
-                    // Reset the saved value to require recording before waiting again
-                    self.num_parquet_files = None;
                     return;
                 }
                 info!(
-                    "Retrying; Parquet file count is still {current_count} \
+                    "Retrying; Parquet file count for {scope} is still {current_count} \
                     which is less than {expected_count}"
                 );
 
@@ -91,7 +246,9 @@ impl<'a> StepTestState<'a> {
             }
         })
         .await
-        .expect("did not get additional Parquet files in the catalog");
+        .unwrap_or_else(|_| {
+            panic!("did not get additional Parquet files in the catalog for {scope}")
+        });
     }
 
     /// Ask the catalog service how many Parquet files it has for the mini cluster's namespace.
@@ -106,6 +263,111 @@ impl<'a> StepTestState<'a> {
             .unwrap_or_default()
     }
 
+    /// Ask the catalog service how many Parquet files it has for `table` in the mini cluster's
+    /// namespace.
+    async fn get_num_parquet_files_in_table(&self, table: &str) -> usize {
+        let connection = self.cluster.router().router_grpc_connection();
+        let mut catalog_client = influxdb_iox_client::catalog::Client::new(connection);
+
+        catalog_client
+            .get_parquet_files_by_namespace_table(self.cluster.namespace(), table)
+            .await
+            .map(|parquet_files| parquet_files.len())
+            .unwrap_or_default()
+    }
+
+    /// Ask the catalog service for the Parquet files of the mini cluster's namespace, or only of
+    /// `table` within it if specified.
+    async fn get_parquet_files(&self, table: Option<&str>) -> Vec<ParquetFile> {
+        let connection = self.cluster.router().router_grpc_connection();
+        let mut catalog_client = influxdb_iox_client::catalog::Client::new(connection);
+        let namespace = self.cluster.namespace();
+
+        match table {
+            Some(table) => catalog_client
+                .get_parquet_files_by_namespace_table(namespace, table)
+                .await
+                .expect("listing Parquet files for table"),
+            None => catalog_client
+                .get_parquet_files_by_namespace(namespace)
+                .await
+                .expect("listing Parquet files for namespace"),
+        }
+    }
+
+    /// Get the type of `column` in `table` from the router's schema service, or [`None`] if the
+    /// table has no such column.
+    async fn get_column_type(&self, table: &str, column: &str) -> Option<ColumnType> {
+        let connection = self.cluster.router().router_grpc_connection();
+        let schema = influxdb_iox_client::schema::Client::new(connection)
+            .get_schema(self.cluster.namespace(), Some(table))
+            .await
+            .expect("getting table schema");
+
+        let column = schema.tables.get(table)?.columns.get(column)?;
+        Some(ColumnType::try_from(column.column_type()).expect("invalid column type"))
+    }
+
+    /// Count the rows of `table` and those of them with a non-NULL `column` using the querier.
+    async fn count_non_null(&self, table: &str, column: &str) -> (i64, i64) {
+        let sql =
+            format!(r#"SELECT count(*) AS total, count("{column}") AS non_null FROM "{table}""#);
+        let (batches, _schema) = run_sql(
+            sql,
+            self.cluster.namespace(),
+            self.cluster.querier().querier_grpc_connection(),
+            None,
+            false,
+        )
+        .await;
+
+        let batch = batches
+            .iter()
+            .find(|b| b.num_rows() > 0)
+            .expect("count query returned no rows");
+        let count = |name: &str| {
+            batch
+                .column_by_name(name)
+                .and_then(|c| c.as_any().downcast_ref::<Int64Array>())
+                .unwrap_or_else(|| panic!("count query returned no {name} column"))
+                .value(0)
+        };
+        (count("total"), count("non_null"))
+    }
+
+    /// Write `line_protocol` to the router's `/api/v2/write` endpoint, asserting the write
+    /// succeeded.
+    async fn write_line_protocol(&self, line_protocol: &str) {
+        let response = self.cluster.write_to_router(line_protocol, None).await;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("reading response body");
+        assert!(
+            status == StatusCode::NO_CONTENT,
+            "Invalid response code while writing line protocol:\n\nLine Protocol:\n{}\n\nExpected Status: {}\nActual Status: {}\n\nBody:\n{:?}",
+            line_protocol,
+            StatusCode::NO_CONTENT,
+            status,
+            body,
+        );
+    }
+
+    /// Run `sql` against `namespace` using the FlightSQL interface and verify that the results
+    /// match `expected` using the `assert_batches_sorted_eq!` macro.
+    async fn assert_sql_query(&self, namespace: &str, sql: &str, expected: &[&str]) {
+        let (mut batches, schema) = run_sql(
+            sql,
+            namespace,
+            self.cluster.querier().querier_grpc_connection(),
+            None,
+            false,
+        )
+        .await;
+        batches.push(RecordBatch::new_empty(schema));
+        assert_batches_sorted_eq!(expected, &batches);
+    }
+
     /// waits for `MAX_QUERY_RETRY_TIME_SEC` for the database to
     /// report exactly `expected` for its partition keys
     async fn wait_for_partition_keys(
@@ -134,6 +396,32 @@ impl<'a> StepTestState<'a> {
 
         assert_eq!(partition_keys, *expected);
     }
+
+    /// waits for `MAX_QUERY_RETRY_TIME_SEC` for the catalog to hold
+    /// exactly `expected` partitions for `table_name`
+    async fn wait_for_partition_count(&self, table_name: &str, expected: usize) {
+        let retry_duration = Duration::from_secs(MAX_QUERY_RETRY_TIME_SEC);
+        let mut partition_keys = vec![];
+        let res = tokio::time::timeout(retry_duration, async {
+            loop {
+                partition_keys = self.cluster().partition_keys(table_name, None).await;
+                partition_keys.sort();
+                info!("====Read partition keys: {partition_keys:?}");
+
+                if partition_keys.len() == expected {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        })
+        .await;
+
+        assert!(
+            res.is_ok(),
+            "expected {expected} partitions for table {table_name} but got {}: {partition_keys:?}",
+            partition_keys.len(),
+        );
+    }
 }
 
 /// Function used for custom [`Step`]s.
@@ -165,6 +453,50 @@ pub enum Step {
     /// endpoint, assert the data was written successfully
     WriteLineProtocol(String),
 
+    /// Writes the specified line protocol to the `/api/v2/write` endpoint, assert the data was
+    /// written successfully, then restart only the ingester(s) of the cluster so that any
+    /// unpersisted data must be recovered by replaying the WAL.
+    ///
+    /// The step completes once the restarted ingesters serve gRPC requests again, which they
+    /// only do after WAL replay has finished, so a following `Step::Query` observes the replayed
+    /// data.
+    ///
+    /// Only supported by non-shared clusters with a dedicated ingester process, such as those
+    /// from [`MiniCluster::create_non_shared`] or [`MiniCluster::create_non_shared_never_persist`]
+    /// (the latter ensures the write is still unpersisted at restart). Shared clusters can not be
+    /// restarted, and restarting the ingester of an all-in-one cluster restarts every component.
+    WriteThenRestartIngester(String),
+
+    /// Writes `count` lines of line protocol generated from `template` to the `/api/v2/write`
+    /// endpoint in a single request, assert the data was written successfully.
+    ///
+    /// Each line is produced by substituting the following placeholders in `template`:
+    ///
+    /// * `{i}`: the index of the line, counting up from 0
+    /// * `{timestamp}`: `timestamp_start + i * timestamp_step`
+    ///
+    /// For example, the template `m,tag=t{i} f={i} {timestamp}` with a `count` of 3, a
+    /// `timestamp_start` of 100 and a `timestamp_step` of 10 writes:
+    ///
+    /// ```text
+    /// m,tag=t0 f=0 100
+    /// m,tag=t1 f=1 110
+    /// m,tag=t2 f=2 120
+    /// ```
+    WriteLineProtocolRepeated {
+        template: String,
+        count: usize,
+        timestamp_start: i64,
+        timestamp_step: i64,
+    },
+
+    /// Writes the specified line protocol to the `/api/v2/write` endpoint, persists it, and
+    /// asserts that exactly one Parquet file was created for each distinct measurement in the
+    /// line protocol.
+    ///
+    /// All the lines of a measurement must fall within a single partition.
+    WriteMultipleMeasurementsExpectingNFiles(String),
+
     /// Writes the specified line protocol to the `/api/v2/write` endpoint; assert the request
     /// returned an error with the given code
     WriteLineProtocolExpectingError {
@@ -174,6 +506,35 @@ pub enum Step {
         expected_line_number: Option<usize>,
     },
 
+    /// Writes the specified line protocol to the `/api/v2/write` endpoint of a router configured
+    /// with [`TestConfig::with_streaming_writes`], asserting the write was rejected with a 400
+    /// reporting that the first `expected_lines_accepted` lines were written and that the line
+    /// numbered `expected_error_line` (1-based) is invalid.
+    ///
+    /// The router stops at the first invalid line, and only lines of segments preceding it are
+    /// accepted. Ending the valid lines with a newline and leaving the invalid line last and
+    /// unterminated ensures the valid lines are written regardless of how the body is received.
+    ///
+    /// [`TestConfig::with_streaming_writes`]: crate::TestConfig::with_streaming_writes
+    WriteLineProtocolExpectPartialAccept {
+        line_protocol: String,
+        expected_lines_accepted: usize,
+        expected_error_line: usize,
+    },
+
+    /// Gzips the specified line protocol and writes it to the `/api/v2/write` endpoint with
+    /// `Content-Encoding: gzip`, assert the data was written successfully.
+    WriteLineProtocolGzip(String),
+
+    /// Writes the specified bytes to the `/api/v2/write` endpoint with `Content-Encoding: gzip`
+    /// and assert the write is rejected with `expected_error_code`.
+    ///
+    /// The body is sent as is, so this can be used to send a corrupt gzip stream.
+    WriteGzipExpectingError {
+        body: Vec<u8>,
+        expected_error_code: StatusCode,
+    },
+
     /// Writes the specified line protocol to the `/api/v2/write` endpoint
     /// using the specified authorization header, assert the data was
     /// written successfully.
@@ -182,6 +543,17 @@ pub enum Step {
         authorization: String,
     },
 
+    /// Writes the specified line protocol to the `/api/v2/write` endpoint, declaring its
+    /// timestamps to be in `precision` (one of `s`, `ms`, `us` or `ns`), and assert the data was
+    /// written successfully.
+    WriteLineProtocolWithPrecision { lp: String, precision: String },
+
+    /// Converts the specified CSV fixture to line protocol according to `mapping`, writes it to
+    /// the `/api/v2/write` endpoint and assert the data was written successfully.
+    ///
+    /// Panics, naming the offending line, if a row of the fixture is malformed.
+    WriteLineProtocolWithTimestampColumn { csv: String, mapping: CsvMapping },
+
     /// Ask the catalog service how many Parquet files it has for this cluster's namespace. Do this
     /// before a write where you're interested in when the write has been persisted to Parquet;
     /// then after the write use `WaitForPersisted` to observe the change in the number of Parquet
@@ -192,6 +564,54 @@ pub enum Step {
     /// cluster's namespace, asserting the value matches expected.
     AssertNumParquetFiles { expected: usize },
 
+    /// Query the catalog service for how many Parquet files it has for this cluster's namespace,
+    /// asserting there are at least `min`.
+    AssertMinNumParquetFiles { min: usize },
+
+    /// Query the catalog service for the parquet files of this cluster's
+    /// namespace, or only of `table` if specified, asserting there are none.
+    ///
+    /// Useful straight after a write to ensure nothing was persisted eagerly.
+    AssertNoParquetFiles { table: Option<String> },
+
+    /// Check that the catalog and the object store of this cluster's namespace agree, and fail
+    /// listing every inconsistency found otherwise: every Parquet file must reference a table and
+    /// partition of the namespace, every live Parquet file must exist in the object store with
+    /// the recorded size, and every Parquet file in the object store must be known to the
+    /// catalog.
+    ///
+    /// Requires a cluster with a file object store and a catalog DSN, such as the shared ones.
+    AssertCatalogConsistency,
+
+    /// Query the schema service for the type of `column` in `table` of this cluster's namespace,
+    /// asserting it was inferred as `expected_type`.
+    ExpectColumnType {
+        table: String,
+        column: String,
+        expected_type: ColumnType,
+    },
+
+    /// Query the fraction of the rows of `table` in this cluster's namespace that have a NULL
+    /// `column`, asserting it is at most `max_null_fraction`.
+    ///
+    /// Useful to check that a field written in a later write fills in the NULL of an earlier
+    /// row with the same series and timestamp once deduplicated.
+    ExpectColumnNullFraction {
+        table: String,
+        column: String,
+        max_null_fraction: f64,
+    },
+
+    /// Write `line_protocol` adding `column` to the existing `table` of this cluster's namespace,
+    /// asserting the column is absent from the schema before the write and present as
+    /// `expected_type` after it.
+    WriteLineProtocolAndExpectSchemaChange {
+        line_protocol: String,
+        table: String,
+        column: String,
+        expected_type: ColumnType,
+    },
+
     /// Ask the ingester to persist immediately through the persist service gRPC API
     Persist,
 
@@ -199,6 +619,17 @@ pub enum Step {
     /// of Parquet files in the catalog as specified for this cluster's namespace.
     WaitForPersisted { expected_increase: usize },
 
+    /// Wait for all previously written data of `table` to be persisted by observing an increase
+    /// in the number of Parquet files in the catalog for that table, compared to the count recorded
+    /// by `RecordNumParquetFiles`.
+    ///
+    /// Unlike `WaitForPersisted`, this does not reset the recorded counts, so setups writing
+    /// several tables can record once and then wait on each table independently.
+    WaitForPersistedTable {
+        table: String,
+        expected_increase: usize,
+    },
+
     /// Set the namespace retention interval to a retention period,
     /// specified in ns relative to `now()`.  `None` represents infinite retention
     /// (i.e. never drop data).
@@ -211,6 +642,10 @@ pub enum Step {
     /// the specified message.
     CompactExpectingError { expected_message: String },
 
+    /// Run one compaction operation with the compactor config values in the given overrides and
+    /// wait for it to finish, expecting success.
+    CompactWithConfig(CompactorConfigOverrides),
+
     /// Run a SQL query using the FlightSQL interface and verify that the
     /// results match the expected results using the
     /// `assert_batches_eq!` macro
@@ -219,6 +654,63 @@ pub enum Step {
         expected: Vec<&'static str>,
     },
 
+    /// Run a SQL query using the FlightSQL interface and verify both that the columns of the
+    /// result have the names and Arrow types in `expected_schema`, in order, and that the
+    /// results match `expected` (ignoring row order).
+    ///
+    /// This catches changes to the inferred types of values that format the same, such as an
+    /// integer column becoming a float column. Schema and value mismatches are reported
+    /// separately.
+    QueryWithSchema {
+        sql: String,
+        expected_schema: Vec<(&'static str, DataType)>,
+        expected: Vec<&'static str>,
+    },
+
+    /// Run a SQL query using the FlightSQL interface against data buffered by the ingesters,
+    /// persist all buffered data, and run it again against the persisted Parquet files, verifying
+    /// that the results of both stages match `expected` (ignoring row order).
+    ///
+    /// Requires unpersisted data in the ingesters, and ingesters that persist on request.
+    QueryStable {
+        sql: String,
+        expected: Vec<&'static str>,
+    },
+
+    /// Run a SQL query using the FlightSQL interface and verify that each line of the formatted
+    /// results matches the corresponding regex in `pattern`.
+    ///
+    /// Each pattern must match its whole line, so table delimiters such as `|` and `+` need
+    /// escaping. This allows asserting the shape of results containing volatile values, such as
+    /// generated IDs or timings, that cannot be matched exactly.
+    QueryMatches { sql: String, pattern: Vec<String> },
+
+    /// Run a SQL query using the FlightSQL interface and verify that the formatted results
+    /// (ignoring row order) match the contents of the golden file at `expected_path`.
+    ///
+    /// Trailing whitespace is ignored. Relative paths are resolved against the working directory
+    /// of the test, usually the directory of the crate's `Cargo.toml`. Set the
+    /// [`UPDATE_GOLDEN_ENV`] environment variable to `1` to (re)write the file with the actual
+    /// results instead of failing on a mismatch.
+    QueryExpectFile { sql: String, expected_path: PathBuf },
+
+    /// Run a SQL query against the specified namespace, rather than the mini cluster's
+    /// namespace, using the FlightSQL interface and verify that the results match the expected
+    /// results using the `assert_batches_eq!` macro
+    QueryNamespace {
+        namespace: String,
+        sql: String,
+        expected: Vec<&'static str>,
+    },
+
+    /// Run all the SQL queries concurrently using the FlightSQL interface and verify that the
+    /// results of each match its expected results, as with `Step::Query`. Every query is run to
+    /// completion before the results are checked, and all the queries that returned unexpected
+    /// results are reported.
+    ConcurrentQueries {
+        queries: Vec<(String, Vec<&'static str>)>,
+    },
+
     /// Read the SQL queries in the specified file and verify that the results match the expected
     /// results in the corresponding expected file
     QueryAndCompare {
@@ -254,6 +746,26 @@ pub enum Step {
         expected: Vec<&'static str>,
     },
 
+    /// Run a SQL query using the FlightSQL interface once to warm up, then several more times,
+    /// and verify that the median latency of these runs does not exceed `max_ms` milliseconds.
+    ///
+    /// The measured latencies are logged whether or not the bound holds. The bound should be
+    /// generous, so that noisy CI machines do not cause failures but gross performance
+    /// regressions are still caught.
+    MeasureQueryLatency { sql: String, max_ms: u64 },
+
+    /// Run a SQL query using the FlightSQL interface and capture the value of the first column of
+    /// the first row of the results under `name`, formatted as it would be in a results table.
+    ///
+    /// Later steps running a single SQL query (all `Query*` steps apart from
+    /// `Step::QueryAndCompare`, as well as `Step::ConcurrentQueries`,
+    /// `Step::MeasureQueryLatency` and `Step::VerifiedQuery`) substitute `${name}` in their SQL
+    /// with the captured value, verbatim, so string values need quoting in the SQL, e.g.
+    /// `WHERE time > '${max_time}'`. Capturing under an existing name replaces its value.
+    /// Referencing a name that has not been captured fails the test, as does a query returning
+    /// no rows.
+    QueryCapture { sql: String, name: String },
+
     /// Run a SQL query using the FlightSQL interface, and then verifies
     /// the results using the provided validation function on the
     /// results.
@@ -289,6 +801,16 @@ pub enum Step {
         expected_message: String,
     },
 
+    /// Run an InfluxQL query that's expected to be rejected using the FlightSQL interface and
+    /// verify that the error message contains `expected_message`, whatever the error code.
+    ///
+    /// Substring matching keeps negative tests of InfluxQL validation robust against changes to
+    /// the surrounding error context. The actual error is reported on mismatch.
+    InfluxQLExpectError {
+        query: String,
+        expected_message: String,
+    },
+
     /// Run an InfluxQL query using the FlightSQL interface including an
     /// authorization header. Verify that the results match the expected
     /// results using the `assert_batches_eq!` macro.
@@ -305,6 +827,12 @@ pub enum Step {
         expected: Vec<&'static str>,
     },
 
+    /// Verify the catalog holds exactly `count` partitions for `table` in the mini cluster's
+    /// namespace, listing the partition keys on failure.
+    ///
+    /// Persists the ingesters first to ensure the catalog partition records exist.
+    ExpectPartitionCount { table: String, count: usize },
+
     /// Attempt to gracefully shutdown all running ingester instances.
     ///
     /// This step blocks until all ingesters have gracefully stopped, or at
@@ -326,6 +854,144 @@ pub enum Step {
     Custom(FCustom),
 }
 
+/// Expand `template` into `count` lines of line protocol, as described by
+/// [`Step::WriteLineProtocolRepeated`].
+fn expand_line_protocol_template(
+    template: &str,
+    count: usize,
+    timestamp_start: i64,
+    timestamp_step: i64,
+) -> String {
+    (0..count)
+        .map(|i| {
+            let timestamp = timestamp_start + i as i64 * timestamp_step;
+            template
+                .replace("{i}", &i.to_string())
+                .replace("{timestamp}", &timestamp.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Assert each of `lines` matches the whole of the corresponding regex in
+/// `patterns`, as described by [`Step::QueryMatches`].
+fn assert_lines_match(patterns: &[String], lines: &[String]) {
+    let output = lines.join("\n");
+    assert_eq!(
+        patterns.len(),
+        lines.len(),
+        "expected {} lines but got {}:\n\n{output}\n",
+        patterns.len(),
+        lines.len(),
+    );
+
+    for (i, (pattern, line)) in patterns.iter().zip(lines).enumerate() {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))
+            .unwrap_or_else(|e| panic!("invalid pattern for line {}: {e}", i + 1));
+        assert!(
+            regex.is_match(line),
+            "line {} does not match pattern\n\npattern: {pattern}\nline:    {line}\n\n{output}\n",
+            i + 1,
+        );
+    }
+}
+
+/// Compare the `schema` and formatted, sorted result `lines` of `sql` to what
+/// [`Step::QueryWithSchema`] expects, reporting schema and value mismatches separately.
+fn assert_schema_and_values(
+    sql: &str,
+    expected_schema: &[(&'static str, DataType)],
+    expected: &[&str],
+    schema: &Schema,
+    lines: &[String],
+) {
+    let mut failures = vec![];
+
+    let actual_schema: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|f| (f.name().as_str(), f.data_type().clone()))
+        .collect();
+    let expected_schema: Vec<_> = expected_schema
+        .iter()
+        .map(|(name, data_type)| (*name, data_type.clone()))
+        .collect();
+    if actual_schema != expected_schema {
+        let format = |fields: &[(&str, DataType)]| {
+            fields
+                .iter()
+                .map(|(name, data_type)| format!("    {name}: {data_type:?}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        failures.push(format!(
+            "schema mismatch\n\nexpected:\n{}\n\nactual:\n{}",
+            format(&expected_schema),
+            format(&actual_schema)
+        ));
+    }
+
+    let expected = sort_lines(expected.iter().map(|&s| s.into()).collect());
+    if lines != expected {
+        failures.push(format!(
+            "value mismatch\n\nexpected:\n{}\n\nactual:\n{}",
+            expected.join("\n"),
+            lines.join("\n")
+        ));
+    }
+
+    assert!(
+        failures.is_empty(),
+        "unexpected results for query: {sql}\n\n{}\n",
+        failures.join("\n\n")
+    );
+}
+
+/// Environment variable that, when set to `1`, makes [`Step::QueryExpectFile`] update its golden
+/// file instead of failing on a mismatch.
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+/// Strip trailing whitespace from each line of `s`, and any trailing empty lines.
+fn normalize_golden(s: &str) -> String {
+    let lines: Vec<_> = s.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+/// Compare `actual` to the contents of the golden file at `expected_path`, as described by
+/// [`Step::QueryExpectFile`].
+fn assert_golden_file(sql: &str, expected_path: &Path, actual: &[String]) {
+    let actual = normalize_golden(&actual.join("\n"));
+    let expected = match fs::read_to_string(expected_path) {
+        Ok(contents) => Some(normalize_golden(&contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => panic!("could not read golden file {expected_path:?}: {e}"),
+    };
+
+    if expected.as_ref() == Some(&actual) {
+        return;
+    }
+
+    if std::env::var(UPDATE_GOLDEN_ENV).map_or(false, |v| v == "1") {
+        info!("====Updating golden file {expected_path:?}");
+        fs::write(expected_path, format!("{actual}\n"))
+            .unwrap_or_else(|e| panic!("could not write golden file {expected_path:?}: {e}"));
+        return;
+    }
+
+    match expected {
+        Some(expected) => panic!(
+            "\n\nquery: {sql}\n\nresults do not match golden file {expected_path:?}\n\n\
+             expected:\n\n{expected}\n\nactual:\n\n{actual}\n\n\
+             Run with {UPDATE_GOLDEN_ENV}=1 to update the golden file.\n"
+        ),
+        None => panic!(
+            "\n\nquery: {sql}\n\ngolden file {expected_path:?} does not exist\n\n\
+             actual:\n\n{actual}\n\n\
+             Run with {UPDATE_GOLDEN_ENV}=1 to create the golden file.\n"
+        ),
+    }
+}
+
 impl AsRef<Step> for Step {
     fn as_ref(&self) -> &Step {
         self
@@ -356,6 +1022,8 @@ where
         let mut state = StepTestState {
             cluster,
             num_parquet_files: Default::default(),
+            num_parquet_files_by_table: Default::default(),
+            captures: Default::default(),
         };
 
         for (i, step) in steps.enumerate() {
@@ -366,21 +1034,38 @@ where
                         "====Begin writing line protocol to v2 HTTP API:\n{}",
                         line_protocol
                     );
-                    let response = state.cluster.write_to_router(line_protocol, None).await;
-                    let status = response.status();
-                    let body = hyper::body::to_bytes(response.into_body())
-                        .await
-                        .expect("reading response body");
-                    assert!(
-                        status == StatusCode::NO_CONTENT,
-                        "Invalid response code while writing line protocol:\n\nLine Protocol:\n{}\n\nExpected Status: {}\nActual Status: {}\n\nBody:\n{:?}",
-                        line_protocol,
-                        StatusCode::NO_CONTENT,
-                        status,
-                        body,
-                    );
+                    state.write_line_protocol(line_protocol).await;
                     info!("====Done writing line protocol");
                 }
+                Step::WriteThenRestartIngester(line_protocol) => {
+                    info!(
+                        "====Begin writing line protocol to v2 HTTP API:\n{}",
+                        line_protocol
+                    );
+                    state.write_line_protocol(line_protocol).await;
+                    info!("====Done writing line protocol, restarting ingesters");
+                    state.cluster_mut().restart_ingesters().await;
+                    info!("====Done restarting ingesters");
+                }
+                Step::WriteLineProtocolRepeated {
+                    template,
+                    count,
+                    timestamp_start,
+                    timestamp_step,
+                } => {
+                    info!(
+                        "====Begin writing {count} lines of repeated line protocol to v2 HTTP API:\n{}",
+                        template
+                    );
+                    let line_protocol = expand_line_protocol_template(
+                        template,
+                        *count,
+                        *timestamp_start,
+                        *timestamp_step,
+                    );
+                    state.write_line_protocol(&line_protocol).await;
+                    info!("====Done writing repeated line protocol");
+                }
                 Step::WriteLineProtocolExpectingError {
                     line_protocol,
                     expected_error_code,
@@ -429,6 +1114,70 @@ where
 
                     info!("====Done writing line protocol expecting error");
                 }
+                Step::WriteLineProtocolExpectPartialAccept {
+                    line_protocol,
+                    expected_lines_accepted,
+                    expected_error_line,
+                } => {
+                    info!(
+                        "====Begin writing line protocol expecting partial accept to v2 HTTP API:\n{}",
+                        line_protocol
+                    );
+                    let response = state.cluster.write_to_router(line_protocol, None).await;
+                    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+                    let body: serde_json::Value = serde_json::from_slice(
+                        &hyper::body::to_bytes(response.into_body())
+                            .await
+                            .expect("should be able to read response body"),
+                    )
+                    .expect("response body should be valid json");
+
+                    assert_eq!(
+                        body["lines_accepted"],
+                        serde_json::Value::from(*expected_lines_accepted),
+                        "unexpected number of accepted lines in response: {body}"
+                    );
+                    assert_eq!(
+                        body["line"],
+                        serde_json::Value::from(*expected_error_line),
+                        "unexpected error line in response: {body}"
+                    );
+
+                    info!("====Done writing line protocol expecting partial accept");
+                }
+                Step::WriteLineProtocolGzip(line_protocol) => {
+                    info!(
+                        "====Begin writing gzipped line protocol to v2 HTTP API:\n{}",
+                        line_protocol
+                    );
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    encoder
+                        .write_all(line_protocol.as_bytes())
+                        .expect("gzip compression should succeed");
+                    let body = encoder.finish().expect("gzip compression should succeed");
+
+                    let response = state.cluster.write_gzip_to_router(body).await;
+                    let status = response.status();
+                    let body = hyper::body::to_bytes(response.into_body())
+                        .await
+                        .expect("reading response body");
+                    assert_eq!(
+                        status,
+                        StatusCode::NO_CONTENT,
+                        "Invalid response code while writing gzipped line protocol, body: {body:?}",
+                    );
+                    info!("====Done writing gzipped line protocol");
+                }
+                Step::WriteGzipExpectingError {
+                    body,
+                    expected_error_code,
+                } => {
+                    info!("====Begin writing gzip body expecting error to v2 HTTP API");
+                    let response = state.cluster.write_gzip_to_router(body.clone()).await;
+                    assert_eq!(response.status(), *expected_error_code);
+                    info!("====Done writing gzip body expecting error");
+                }
                 Step::WriteLineProtocolWithAuthorization {
                     line_protocol,
                     authorization,
@@ -444,8 +1193,53 @@ where
                     assert_eq!(response.status(), StatusCode::NO_CONTENT);
                     info!("====Done writing line protocol");
                 }
+                Step::WriteLineProtocolWithPrecision { lp, precision } => {
+                    info!(
+                        "====Begin writing line protocol with precision {precision} to v2 HTTP API:\n{}",
+                        lp
+                    );
+                    let response = state
+                        .cluster
+                        .write_to_router_with_precision(lp, precision)
+                        .await;
+                    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+                    info!("====Done writing line protocol");
+                }
+                Step::WriteLineProtocolWithTimestampColumn { csv, mapping } => {
+                    let line_protocol = csv_to_line_protocol(csv, mapping)
+                        .unwrap_or_else(|e| panic!("invalid CSV fixture: {e}"));
+                    info!(
+                        "====Begin writing line protocol from CSV to v2 HTTP API:\n{}",
+                        line_protocol
+                    );
+                    let response = state.cluster.write_to_router(line_protocol, None).await;
+                    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+                    info!("====Done writing line protocol");
+                }
                 // Get the current number of Parquet files in the cluster's namespace before
                 // starting a new write so we can observe a change when waiting for persistence.
+                Step::WriteMultipleMeasurementsExpectingNFiles(line_protocol) => {
+                    let expected_increase = mutable_batch_lp::lines_to_batches(line_protocol, 0)
+                        .expect("invalid line protocol")
+                        .len();
+                    info!(
+                        "====Begin writing {expected_increase} measurements to v2 HTTP API:\n{}",
+                        line_protocol
+                    );
+                    state.record_num_parquet_files().await;
+                    let num_parquet_files = state.num_parquet_files.unwrap();
+                    state.write_line_protocol(line_protocol).await;
+                    state.cluster().persist_ingesters().await;
+                    state
+                        .wait_for_num_parquet_file_change(expected_increase)
+                        .await;
+                    assert_eq!(
+                        state.get_num_parquet_files().await,
+                        num_parquet_files + expected_increase,
+                        "expected one Parquet file per measurement"
+                    );
+                    info!("====Done writing and persisting measurements");
+                }
                 Step::RecordNumParquetFiles => {
                     state.record_num_parquet_files().await;
                 }
@@ -453,6 +1247,119 @@ where
                     let have_files = state.get_num_parquet_files().await;
                     assert_eq!(have_files, *expected);
                 }
+                Step::AssertMinNumParquetFiles { min } => {
+                    let have_files = state.get_num_parquet_files().await;
+                    assert!(
+                        have_files >= *min,
+                        "expected at least {min} Parquet files, found {have_files}"
+                    );
+                }
+                Step::AssertNoParquetFiles { table } => {
+                    let files = state.get_parquet_files(table.as_deref()).await;
+                    assert!(
+                        files.is_empty(),
+                        "expected no Parquet files for {}, found {}:\n{}",
+                        table.as_deref().map_or_else(
+                            || format!("namespace {}", state.cluster.namespace()),
+                            |table| format!("table {table}"),
+                        ),
+                        files.len(),
+                        files
+                            .iter()
+                            .map(|f| format!(
+                                "  id={} table_id={} object_store_id={} level={} rows={} \
+                                 min_time={} max_time={}",
+                                f.id,
+                                f.table_id,
+                                f.object_store_id,
+                                f.compaction_level,
+                                f.row_count,
+                                f.min_time,
+                                f.max_time,
+                            ))
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    );
+                }
+                Step::AssertCatalogConsistency => {
+                    info!("====Begin checking catalog consistency");
+                    let namespace_id = state.cluster.namespace_id().await;
+                    let problems = check_catalog_consistency(
+                        state.cluster.ingester().test_config(),
+                        namespace_id,
+                    )
+                    .await;
+                    assert!(
+                        problems.is_empty(),
+                        "catalog of namespace {} is inconsistent with the object store:\n{}",
+                        state.cluster.namespace(),
+                        problems.join("\n"),
+                    );
+                    info!("====Done checking catalog consistency");
+                }
+                Step::ExpectColumnType {
+                    table,
+                    column,
+                    expected_type,
+                } => {
+                    info!("====Begin checking the type of column {column} in table {table}");
+                    let actual = state.get_column_type(table, column).await;
+                    assert_eq!(
+                        actual,
+                        Some(*expected_type),
+                        "unexpected type for column {column} in table {table}: expected \
+                         {expected_type}, found {}",
+                        actual.map_or_else(|| "no such column".to_string(), |t| t.to_string()),
+                    );
+                    info!("====Done checking column type");
+                }
+                Step::ExpectColumnNullFraction {
+                    table,
+                    column,
+                    max_null_fraction,
+                } => {
+                    info!(
+                        "====Begin checking the NULL fraction of column {column} in table {table}"
+                    );
+                    let (total, non_null) = state.count_non_null(table, column).await;
+                    assert!(total > 0, "table {table} has no rows");
+
+                    let null_fraction = (total - non_null) as f64 / total as f64;
+                    assert!(
+                        null_fraction <= *max_null_fraction,
+                        "NULL fraction of column {column} in table {table} is {null_fraction} \
+                         ({} of {total} rows), expected at most {max_null_fraction}",
+                        total - non_null,
+                    );
+                    info!("====Done checking NULL fraction");
+                }
+                Step::WriteLineProtocolAndExpectSchemaChange {
+                    line_protocol,
+                    table,
+                    column,
+                    expected_type,
+                } => {
+                    info!(
+                        "====Begin writing line protocol adding column {column} to table {table}"
+                    );
+                    let before = state.get_column_type(table, column).await;
+                    assert_eq!(
+                        before, None,
+                        "column {column} already exists in table {table} before the write",
+                    );
+
+                    state.write_line_protocol(line_protocol).await;
+
+                    let after = state.get_column_type(table, column).await;
+                    assert_eq!(
+                        after,
+                        Some(*expected_type),
+                        "unexpected type for new column {column} in table {table}: expected \
+                         {expected_type}, found {}",
+                        after.map_or_else(|| "no such column".to_string(), |t| t.to_string()),
+                    );
+                    info!("====Done checking schema change");
+                }
                 // Ask the ingesters to persist immediately through the persist service gRPC API
                 Step::Persist => {
                     state.cluster().persist_ingesters().await;
@@ -469,6 +1376,14 @@ where
                     state.cluster.run_compaction().unwrap();
                     info!("====Done running compaction");
                 }
+                Step::CompactWithConfig(overrides) => {
+                    info!("====Begin running compaction with config {:?}", overrides);
+                    state
+                        .cluster
+                        .run_compaction_with_env(overrides.env())
+                        .unwrap();
+                    info!("====Done running compaction");
+                }
                 Step::CompactExpectingError { expected_message } => {
                     info!("====Begin running compaction expected to error");
                     let err = state.cluster.run_compaction().unwrap_err();
@@ -478,6 +1393,18 @@ where
                     info!("====Done running");
                 }
 
+                Step::WaitForPersistedTable {
+                    table,
+                    expected_increase,
+                } => {
+                    info!(
+                        "====Begin waiting for a change in the number of Parquet files of table {table}"
+                    );
+                    state
+                        .wait_for_num_parquet_file_change_in_table(table, *expected_increase)
+                        .await;
+                    info!("====Done waiting for a change in the number of Parquet files of table {table}");
+                }
                 Step::SetRetention(retention_period_ns) => {
                     info!("====Begin setting retention period to {retention_period_ns:?}");
                     let namespace = state.cluster().namespace();
@@ -490,8 +1417,80 @@ where
                     info!("====Done setting retention period");
                 }
                 Step::Query { sql, expected } => {
+                    let sql = &state.substitute_captures(sql);
                     info!("====Begin running SQL query: {}", sql);
-                    // run query
+                    state
+                        .assert_sql_query(state.cluster.namespace(), sql, expected)
+                        .await;
+                    info!("====Done running");
+                }
+                Step::QueryWithSchema {
+                    sql,
+                    expected_schema,
+                    expected,
+                } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!("====Begin running SQL query (checking schema): {}", sql);
+                    let (mut batches, schema) = run_sql(
+                        sql,
+                        state.cluster.namespace(),
+                        state.cluster.querier().querier_grpc_connection(),
+                        None,
+                        false,
+                    )
+                    .await;
+                    batches.push(RecordBatch::new_empty(Arc::clone(&schema)));
+                    assert_schema_and_values(
+                        sql,
+                        expected_schema,
+                        expected,
+                        &schema,
+                        &batches_to_sorted_lines(&batches),
+                    );
+                    info!("====Done running");
+                }
+                Step::QueryStable { sql, expected } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!(
+                        "====Begin running SQL query before and after persisting: {}",
+                        sql
+                    );
+                    let expected = sort_lines(expected.iter().map(|&s| s.into()).collect());
+
+                    let mut failures = vec![];
+                    for stage in ["ingester", "parquet"] {
+                        if stage == "parquet" {
+                            state.record_num_parquet_files().await;
+                            state.cluster().persist_ingesters().await;
+                            state.wait_for_num_parquet_file_change(1).await;
+                        }
+
+                        let (mut batches, schema) = run_sql(
+                            sql,
+                            state.cluster.namespace(),
+                            state.cluster.querier().querier_grpc_connection(),
+                            None,
+                            false,
+                        )
+                        .await;
+                        batches.push(RecordBatch::new_empty(schema));
+                        let actual = batches_to_sorted_lines(&batches);
+                        if actual != expected {
+                            failures.push(format!(
+                                "{stage} stage diverged\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}"
+                            ));
+                        }
+                    }
+                    assert!(
+                        failures.is_empty(),
+                        "\n\nquery: {sql}\n\n{}\n",
+                        failures.join("\n\n")
+                    );
+                    info!("====Done running");
+                }
+                Step::QueryMatches { sql, pattern } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!("====Begin running SQL query (matching patterns): {}", sql);
                     let (mut batches, schema) = run_sql(
                         sql,
                         state.cluster.namespace(),
@@ -501,7 +1500,74 @@ where
                     )
                     .await;
                     batches.push(RecordBatch::new_empty(schema));
-                    assert_batches_sorted_eq!(expected, &batches);
+                    assert_lines_match(pattern, &batches_to_lines(&batches));
+                    info!("====Done running");
+                }
+                Step::QueryExpectFile { sql, expected_path } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!(
+                        "====Begin running SQL query (expecting file {:?}): {}",
+                        expected_path, sql
+                    );
+                    let (mut batches, schema) = run_sql(
+                        sql,
+                        state.cluster.namespace(),
+                        state.cluster.querier().querier_grpc_connection(),
+                        None,
+                        false,
+                    )
+                    .await;
+                    batches.push(RecordBatch::new_empty(schema));
+                    assert_golden_file(sql, expected_path, &batches_to_sorted_lines(&batches));
+                    info!("====Done running");
+                }
+                Step::ConcurrentQueries { queries } => {
+                    info!(
+                        "====Begin running {} SQL queries concurrently",
+                        queries.len()
+                    );
+                    let queries: Vec<_> = queries
+                        .iter()
+                        .map(|(sql, expected)| (state.substitute_captures(sql), expected))
+                        .collect();
+                    let namespace = state.cluster.namespace();
+                    let results = futures::future::join_all(queries.iter().map(|(sql, _)| {
+                        run_sql(
+                            sql,
+                            namespace,
+                            state.cluster.querier().querier_grpc_connection(),
+                            None,
+                            false,
+                        )
+                    }))
+                    .await;
+
+                    let failures: Vec<_> = queries
+                        .iter()
+                        .zip(results)
+                        .enumerate()
+                        .filter_map(|(i, ((sql, expected), (mut batches, schema)))| {
+                            batches.push(RecordBatch::new_empty(schema));
+                            let expected = sort_lines(expected.iter().map(|&s| s.into()).collect());
+                            let actual = batches_to_sorted_lines(&batches);
+                            (expected != actual).then(|| {
+                                format!(
+                                    "query {i} diverged: {sql}\n\nexpected:\n\n{expected:#?}\nactual:\n\n{actual:#?}"
+                                )
+                            })
+                        })
+                        .collect();
+                    assert!(failures.is_empty(), "\n\n{}\n", failures.join("\n\n"));
+                    info!("====Done running");
+                }
+                Step::QueryNamespace {
+                    namespace,
+                    sql,
+                    expected,
+                } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!("====Begin running SQL query against namespace {namespace}: {sql}");
+                    state.assert_sql_query(namespace, sql, expected).await;
                     info!("====Done running");
                 }
                 Step::QueryAndCompare {
@@ -529,6 +1595,7 @@ where
                     expected_error_code,
                     expected_message,
                 } => {
+                    let sql = &state.substitute_captures(sql);
                     info!("====Begin running SQL query expected to error: {}", sql);
 
                     let err = try_run_sql(
@@ -550,6 +1617,7 @@ where
                     authorization,
                     expected,
                 } => {
+                    let sql = &state.substitute_captures(sql);
                     info!("====Begin running SQL query (authenticated): {}", sql);
                     // run query
                     let (mut batches, schema) = run_sql(
@@ -565,6 +1633,7 @@ where
                     info!("====Done running");
                 }
                 Step::QueryWithDebug { sql, expected } => {
+                    let sql = &state.substitute_captures(sql);
                     info!("====Begin running SQL query (w/ iox-debug): {}", sql);
                     // run query
                     let (mut batches, schema) = run_sql(
@@ -579,7 +1648,49 @@ where
                     assert_batches_sorted_eq!(expected, &batches);
                     info!("====Done running");
                 }
+                Step::MeasureQueryLatency { sql, max_ms } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!("====Begin measuring SQL query latency: {}", sql);
+                    let namespace = state.cluster.namespace();
+
+                    // The first run populates the querier caches, which is not what is measured.
+                    run_sql(
+                        sql,
+                        namespace,
+                        state.cluster.querier().querier_grpc_connection(),
+                        None,
+                        false,
+                    )
+                    .await;
+
+                    let mut latencies = Vec::with_capacity(QUERY_LATENCY_SAMPLES);
+                    for _ in 0..QUERY_LATENCY_SAMPLES {
+                        let start = Instant::now();
+                        run_sql(
+                            sql,
+                            namespace,
+                            state.cluster.querier().querier_grpc_connection(),
+                            None,
+                            false,
+                        )
+                        .await;
+                        latencies.push(start.elapsed());
+                    }
+                    latencies.sort();
+                    let median = latencies[QUERY_LATENCY_SAMPLES / 2];
+
+                    info!(
+                        "====Measured median SQL query latency of {median:?} (max {max_ms}ms), \
+                         samples: {latencies:?}"
+                    );
+                    assert!(
+                        median <= Duration::from_millis(*max_ms),
+                        "median latency of {median:?} exceeds {max_ms}ms for query: {sql}\n\n\
+                         samples: {latencies:?}"
+                    );
+                }
                 Step::VerifiedQuery { sql, verify } => {
+                    let sql = &state.substitute_captures(sql);
                     info!("====Begin running SQL verified query: {}", sql);
                     // run query
                     let (batches, _schema) = run_sql(
@@ -593,6 +1704,26 @@ where
                     verify(batches);
                     info!("====Done running");
                 }
+                Step::QueryCapture { sql, name } => {
+                    let sql = &state.substitute_captures(sql);
+                    info!("====Begin running SQL query to capture `{}`: {}", name, sql);
+                    let (batches, _schema) = run_sql(
+                        sql,
+                        state.cluster.namespace(),
+                        state.cluster.querier().querier_grpc_connection(),
+                        None,
+                        false,
+                    )
+                    .await;
+                    let batch = batches
+                        .iter()
+                        .find(|batch| batch.num_rows() > 0)
+                        .unwrap_or_else(|| panic!("no rows to capture `{name}` from: {sql}"));
+                    let value = array_value_to_string(batch.column(0), 0)
+                        .expect("formatting captured value");
+                    info!("====Captured `{}` = {}", name, value);
+                    state.captures.insert(name.clone(), value);
+                }
                 Step::InfluxQLQuery { query, expected } => {
                     info!("====Begin running InfluxQL query: {}", query);
                     // run query
@@ -650,6 +1781,41 @@ where
 
                     info!("====Done running");
                 }
+                Step::InfluxQLExpectError {
+                    query,
+                    expected_message,
+                } => {
+                    info!(
+                        "====Begin running InfluxQL query expected to be rejected: {}",
+                        query
+                    );
+
+                    let err = match try_run_influxql(
+                        query,
+                        state.cluster().namespace(),
+                        state.cluster().querier().querier_grpc_connection(),
+                        None,
+                    )
+                    .await
+                    {
+                        Ok((batches, _schema)) => panic!(
+                            "InfluxQL query was expected to be rejected but succeeded:\n{}",
+                            batches_to_lines(&batches).join("\n")
+                        ),
+                        Err(e) => e,
+                    };
+
+                    let actual = err
+                        .tonic_status()
+                        .map(|status| status.message().to_string())
+                        .unwrap_or_else(|| err.to_string());
+                    assert!(
+                        actual.contains(expected_message.as_str()),
+                        "\nActual error message:\n{actual}\nExpected to contain:\n{expected_message}"
+                    );
+
+                    info!("====Done running");
+                }
                 Step::InfluxQLQueryWithAuthorization {
                     query,
                     authorization,
@@ -685,6 +1851,14 @@ where
                         .await;
                     info!("====Done reading partition keys");
                 }
+                Step::ExpectPartitionCount { table, count } => {
+                    info!("====Persist ingesters to ensure catalog partition records exist");
+                    state.cluster().persist_ingesters().await;
+
+                    info!("====Begin counting partitions for table: {}", table);
+                    state.wait_for_partition_count(table, *count).await;
+                    info!("====Done counting partitions");
+                }
                 Step::GracefulStopIngesters => {
                     info!("====Gracefully stop all ingesters");
 
@@ -713,3 +1887,40 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_line_protocol_template() {
+        assert_eq!(
+            expand_line_protocol_template("m,tag=t{i} f={i} {timestamp}", 3, 100, 10),
+            "m,tag=t0 f=0 100\nm,tag=t1 f=1 110\nm,tag=t2 f=2 120",
+        );
+    }
+
+    #[test]
+    fn test_expand_line_protocol_template_negative_step() {
+        assert_eq!(
+            expand_line_protocol_template("m f=1 {timestamp}", 2, 1000, -1),
+            "m f=1 1000\nm f=1 999",
+        );
+    }
+
+    #[test]
+    fn test_expand_line_protocol_template_without_placeholders() {
+        assert_eq!(
+            expand_line_protocol_template("m f=3 2001", 2, 0, 1),
+            "m f=3 2001\nm f=3 2001",
+        );
+    }
+
+    #[test]
+    fn test_expand_line_protocol_template_zero_count() {
+        assert_eq!(
+            expand_line_protocol_template("m f={i} {timestamp}", 0, 0, 1),
+            ""
+        );
+    }
+}